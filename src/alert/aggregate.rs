@@ -0,0 +1,119 @@
+// ============================================================
+//  alert/aggregate.rs - Agregarea alertelor SIEM într-o fereastră
+// ============================================================
+//
+//  În modul `[siem] aggregate = true`, alertele cu aceeași semnătură
+//  (sig ID + tip de scan) dintr-o fereastră se pliază într-un singur
+//  mesaj (CEF sau LEEF, după `[siem] format`) cu:
+//    cnt=<N>                     - numărul de alerte pliate (convenție CEF)
+//    start=/end=                 - prima/ultima alertă (epoch ms)
+//    cs5Label=SourceIPs cs5=...  - IP-urile sursă afectate
+//    cs6Label=SourceTag cs6=...  - tag-urile lor din `[[labels]]`, dacă există
+//
+//  Concepte Rust demonstrate:
+//  - `std::sync::Mutex` : lock sincron, suficient când secțiunea critică
+//    nu conține `.await`
+//  - `std::mem::take` : golirea unei colecții fără realocare/clonare
+// ============================================================
+
+use super::AlertPayload;
+use crate::alert_fields::{alert_name, join_ips, AlertFields, Field};
+use crate::config::SiemFormat;
+use chrono::{DateTime, Utc};
+use std::collections::HashMap;
+use std::net::IpAddr;
+use std::sync::Mutex;
+
+/// Câte IP-uri sursă distincte se listează într-un mesaj agregat
+const MAX_AGGREGATE_SOURCES: usize = 100;
+
+/// Cheia de agregare: (sig ID, tip de scan)
+type AggregateKey = (String, String);
+
+/// Alertele pliate pentru o semnătură în fereastra curentă
+struct AggregateEntry {
+    name:     String,
+    severity: u8,
+    count:    u64,
+    sources:  Vec<IpAddr>,
+    labels:   Vec<String>,
+    first:    DateTime<Utc>,
+    last:     DateTime<Utc>,
+}
+
+/// Acumulatorul de alerte SIEM pentru fereastra curentă
+pub struct SiemAggregator {
+    pending:        Mutex<HashMap<AggregateKey, AggregateEntry>>,
+    format:         SiemFormat,
+    leef_delimiter: char,
+}
+
+impl SiemAggregator {
+    pub fn new(format: SiemFormat, leef_delimiter: char) -> Self {
+        SiemAggregator {
+            pending: Mutex::new(HashMap::new()),
+            format,
+            leef_delimiter,
+        }
+    }
+
+    /// Pliază o alertă (cu severitatea ei finală, după mapare) în intrarea semnăturii ei
+    pub fn add(&self, payload: &AlertPayload<'_>, severity: u8) {
+        let key = (
+            payload.result.sig_id().to_string(),
+            payload.result.scan_type_label(),
+        );
+        let now = Utc::now();
+
+        let mut pending = self.pending.lock().unwrap_or_else(|e| e.into_inner());
+        let entry = pending.entry(key).or_insert_with(|| AggregateEntry {
+            name:     alert_name(payload.result).unwrap_or_default(),
+            severity: 0,
+            count:    0,
+            sources:  Vec::new(),
+            labels:   Vec::new(),
+            first:    now,
+            last:     now,
+        });
+
+        entry.count += 1;
+        entry.last = now;
+        // Severitatea mesajului agregat = cea mai mare severitate pliată
+        entry.severity = entry.severity.max(severity);
+        if entry.sources.len() < MAX_AGGREGATE_SOURCES && !entry.sources.contains(payload.ip) {
+            entry.sources.push(*payload.ip);
+        }
+        for label in payload.labels {
+            if !entry.labels.contains(label) {
+                entry.labels.push(label.clone());
+            }
+        }
+    }
+
+    // -----------------------------------------------------------------------
+    // Golește fereastra curentă și returnează câte un mesaj per semnătură.
+    //
+    // Lock-ul este eliberat înainte de construirea mesajelor, astfel încât
+    // alertele noi să nu aștepte după formatare.
+    // -----------------------------------------------------------------------
+    pub fn drain(&self) -> Vec<String> {
+        let pending = {
+            let mut guard = self.pending.lock().unwrap_or_else(|e| e.into_inner());
+            std::mem::take(&mut *guard)
+        };
+
+        pending
+            .into_iter()
+            .map(|((sig_id, scan_type), entry)| {
+                let mut fields = AlertFields::new(sig_id, entry.name, entry.severity);
+                fields.push(Field::standard("cnt", entry.count));
+                fields.push(Field::standard("start", entry.first.timestamp_millis()));
+                fields.push(Field::standard("end", entry.last.timestamp_millis()));
+                fields.push(Field::custom("cs1", "ScanType", scan_type).leef_key("cat"));
+                fields.push(Field::custom("cs5", "SourceIPs", join_ips(&entry.sources)));
+                fields.push_labels(&entry.labels);
+                fields.to_syslog(self.format, self.leef_delimiter)
+            })
+            .collect()
+    }
+}
@@ -0,0 +1,535 @@
+// ============================================================
+//  alert/mod.rs - Trimiterea alertelor (SIEM UDP + Email)
+// ============================================================
+//
+//  Tot aici pleacă, pe același transport SMTP, raportul periodic din
+//  `[report] schedule` (vezi report.rs în bibliotecă).
+//
+//  Concepte Rust demonstrate:
+//  - Funcții `async` și `await`: programare asincronă non-blocantă
+//  - `anyhow::Result` pentru gestionarea erorilor din funcții async
+//  - `tokio::net::UdpSocket` pentru comunicare UDP asincronă
+//  - Crate-ul `lettre` pentru trimiterea email-urilor
+// ============================================================
+
+pub mod aggregate;
+pub mod resolver;
+#[cfg(unix)]
+pub mod uds;
+
+use crate::alert_fields::{detection_fields, join_ips, syslog_line, AlertFields, Field};
+use crate::alert_log::{AlertLog, AlertRecord};
+use crate::alert_template::{self, TemplateValues};
+use crate::config::{AlertConfig, Config, EmailConfig, ReportConfig, SiemConfig};
+use crate::context::ContextLine;
+use crate::detector::{DetectionEvidence, DetectionResult};
+use crate::diagnostics::ParseWarning;
+use crate::display;
+use crate::report::{Report, ReportFormat};
+use crate::routing::{Channel, RoutingPolicy};
+use crate::state::{RuleSummary, TargetSummary};
+use aggregate::SiemAggregator;
+use resolver::ResolvedTarget;
+use anyhow::{Context, Result};
+use chrono::{Local, Utc};
+use std::collections::BTreeMap;
+use std::net::{IpAddr, SocketAddr};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+use tokio::net::UdpSocket;
+
+// ---------------------------------------------------------------------------
+// Payload-ul unei alerte: toate informațiile necesare pentru notificare
+// ---------------------------------------------------------------------------
+pub struct AlertPayload<'a> {
+    pub ip:       &'a IpAddr,
+    pub result:   &'a DetectionResult,
+    /// Destinațiile cele mai atinse de `ip` (gol dacă log-urile nu au IP destinație)
+    pub targets:  &'a [TargetSummary],
+    /// Regulile firewall-ului care au blocat `ip` (gol dacă log-urile nu le raportează)
+    pub rules:    &'a [RuleSummary],
+    /// Ultimele linii brute ale lui `ip` (gol dacă buffer-ul de context e dezactivat)
+    pub context:  &'a [ContextLine],
+    /// Tag-urile lui `ip` din `[[labels]]` (gol dacă nicio rețea nu îl conține)
+    pub labels:   &'a [String],
+    /// De ce a declanșat (doar scanările de porturi per sursă)
+    pub evidence: Option<&'a DetectionEvidence>,
+}
+
+// ---------------------------------------------------------------------------
+// Dispatcher-ul de alerte: deține configurația și starea canalelor de
+// alertare (ex: agregatorul SIEM), partajat între task-uri prin `Arc`.
+// ---------------------------------------------------------------------------
+pub struct AlertDispatcher {
+    config:     Arc<Config>,
+    /// Adresa SIEM rezolvată (cache DNS cu re-rezolvare periodică)
+    siem:       ResolvedTarget,
+    /// Prezent doar în modul `[siem] aggregate = true`
+    aggregator: Option<SiemAggregator>,
+    /// Prezent doar dacă `[uds] enabled = true`
+    #[cfg(unix)]
+    uds:        Option<uds::UdsSink>,
+    /// Ce canal primește ce alertă (`[email] min_severity` / `quiet_hours`)
+    routing:    RoutingPolicy,
+    /// Prezent doar dacă `[alert_log] enabled = true`
+    alert_log:  Option<AlertLog>,
+    /// Sfârșitul perioadei de încălzire (`None` = fără warmup)
+    warmup_until: Option<Instant>,
+    /// Alertele suprimate în warmup: tip scan -> număr
+    suppressed:   Mutex<BTreeMap<String, u64>>,
+    /// Alertele trimise de la ultimul heartbeat
+    alerts_fired: AtomicU64,
+}
+
+impl AlertDispatcher {
+    // -----------------------------------------------------------------------
+    // Creează dispatcher-ul; rezolvă destinațiile de rețea.
+    //
+    // Un hostname SIEM/SMTP care nu se rezolvă la pornire este o eroare de
+    // configurare - mai bine oprim IDS-ul acum decât să pierdem alertele.
+    // -----------------------------------------------------------------------
+    pub async fn new(config: Arc<Config>) -> Result<Self> {
+        let siem = ResolvedTarget::resolve(
+            &config.siem.address,
+            config.siem.port,
+            config.siem.ip_preference,
+            config.siem.dns_refresh_secs,
+        )
+        .await
+        .context("Adresa [siem] nu poate fi rezolvată")?;
+
+        // SMTP: lettre rezolvă hostname-ul la fiecare conexiune (și are nevoie
+        // de el pentru TLS), deci aici doar validăm că se rezolvă
+        if config.email.enabled {
+            resolver::lookup(&config.email.smtp_server, config.email.smtp_port, config.siem.ip_preference)
+                .await
+                .context("Serverul [email] smtp_server nu poate fi rezolvat")?;
+        }
+
+        let aggregator = config.siem.aggregate.then(|| SiemAggregator::new(config.siem.format, config.siem.leef_delimiter));
+        #[cfg(unix)]
+        let uds = config.uds.enabled.then(|| uds::UdsSink::new(&config.uds.path));
+
+        let routing = RoutingPolicy::new(&config.email);
+        let alert_log = if config.alert_log.enabled { Some(AlertLog::open(&config.alert_log)?) } else { None };
+        let warmup_secs = config.detection.warmup_secs;
+        let warmup_until = (warmup_secs > 0).then(|| Instant::now() + Duration::from_secs(warmup_secs));
+
+        Ok(AlertDispatcher {
+            config,
+            siem,
+            aggregator,
+            #[cfg(unix)]
+            uds,
+            routing,
+            alert_log,
+            warmup_until,
+            suppressed: Mutex::new(BTreeMap::new()),
+            alerts_fired: AtomicU64::new(0),
+        })
+    }
+
+    /// Trimite un mesaj la SIEM pe adresa din cache și raportează rezultatul
+    /// resolver-ului (eșecurile repetate declanșează re-rezolvarea)
+    async fn send_siem(&self, message: &str) -> Result<()> {
+        let result = send_siem_alert(message, self.siem.addr().await).await;
+        self.siem.record_send(result.is_ok());
+        result
+    }
+
+    /// Secundele rămase din perioada de încălzire (`None` dacă s-a terminat)
+    pub fn warmup_remaining(&self) -> Option<Duration> {
+        self.warmup_until
+            .and_then(|until| until.checked_duration_since(Instant::now()))
+            .filter(|remaining| !remaining.is_zero())
+    }
+
+    // -----------------------------------------------------------------------
+    // Încheie perioada de încălzire: afișează sumarul alertelor suprimate.
+    // Apelată o singură dată, de task-ul programat la pornire.
+    // -----------------------------------------------------------------------
+    pub fn finish_warmup(&self) {
+        let suppressed = std::mem::take(&mut *self.suppressed.lock().unwrap_or_else(|e| e.into_inner()));
+        let total: u64 = suppressed.values().sum();
+
+        let detail: Vec<String> = suppressed
+            .iter()
+            .map(|(scan_type, count)| format!("{}={}", scan_type, count))
+            .collect();
+        display::log_info(&format!(
+            "Perioada de încălzire s-a încheiat: {} alerte suprimate{}. Alertele se trimit de acum.",
+            total,
+            if detail.is_empty() { String::new() } else { format!(" ({})", detail.join(", ")) }
+        ));
+    }
+
+    // -----------------------------------------------------------------------
+    // Funcția principală de alertare - orchestrează SIEM + Email
+    //
+    // `async fn` = funcție asincronă. Când apelăm `.await`, cedăm controlul
+    // executor-ului (tokio), care poate rula alt task între timp.
+    // Aceasta permite scalabilitate masivă fără thread-uri separate per conexiune.
+    //
+    // Returnează `false` dacă alerta a fost suprimată (perioada de încălzire),
+    // caz în care nici răspunsul activ nu trebuie declanșat.
+    // -----------------------------------------------------------------------
+    pub async fn send_alerts(&self, payload: &AlertPayload<'_>) -> bool {
+        if let Some(remaining) = self.warmup_remaining() {
+            let scan_type = payload.result.scan_type_label();
+            display::log_alert_suppressed(payload.ip, &scan_type, remaining.as_secs());
+            *self
+                .suppressed
+                .lock()
+                .unwrap_or_else(|e| e.into_inner())
+                .entry(scan_type)
+                .or_default() += 1;
+            return false;
+        }
+
+        let config = &self.config;
+        self.alerts_fired.fetch_add(1, Ordering::Relaxed);
+
+        // Construim mesajul de alertă o singură dată și îl refolosim
+        let severity = config.alert.alert_severity(payload.result);
+        let alert_msg = build_alert_message(payload, severity, &config.alert, &config.siem);
+
+        // Istoricul pentru rapoarte, înaintea canalelor de rețea (care pot eșua)
+        if let Some(alert_log) = &self.alert_log {
+            let record = AlertRecord::new(*payload.ip, payload.result, severity, payload.labels, Utc::now());
+            if let Err(e) = alert_log.record(&record) {
+                display::log_warn(&format!("Alerta nu a putut fi scrisă în [alert_log]: {:#}", e));
+            }
+        }
+
+        match &self.aggregator {
+            // Mod agregat: alerta se pliază în mesajul ferestrei curente,
+            // trimis la SIEM de `flush_siem()`
+            Some(aggregator) => aggregator.add(payload, severity),
+
+            // Trimitem alert la SIEM via UDP (nu blocăm dacă SIEM-ul nu răspunde)
+            None => {
+                if let Err(e) = self.send_siem(&alert_msg).await {
+                    display::log_warn(&format!("Nu s-a putut trimite alerta SIEM: {}", e));
+                } else {
+                    display::log_alert_sent(&config.siem_addr(), "SIEM UDP");
+                }
+            }
+        }
+
+        // Canal local: Unix domain socket (independent de SIEM și email)
+        #[cfg(unix)]
+        if let Some(uds) = &self.uds {
+            if let Err(e) = uds.send(&alert_msg).await {
+                display::log_warn(&format!("Nu s-a putut trimite alerta pe UDS: {:#}", e));
+            } else {
+                display::log_alert_sent(&uds.path().display().to_string(), "UDS");
+            }
+        }
+
+        // Trimitem email dacă este activat în configurație și dacă politica de
+        // rutare îl permite (severitate minimă, ore de liniște)
+        let email_allowed = self.routing.allows(Channel::Email, severity, Local::now().time());
+        if config.email.enabled && !email_allowed {
+            tracing::debug!(severity, "email omis de [email] min_severity / quiet_hours");
+        }
+        if config.email.enabled && email_allowed {
+            let template = config.alert.email_template.as_deref();
+            if let Err(e) = send_email_alert(&alert_msg, payload, template, &config.email).await {
+                display::log_warn(&format!("Nu s-a putut trimite email-ul de alertă: {}", e));
+            } else {
+                display::log_alert_sent(&config.email.to, "Email");
+            }
+        }
+
+        true
+    }
+
+    /// Trimite la SIEM un mesaj "IDS self-health" despre eșecurile de parsare
+    /// ale unui exporter (doar dacă `[siem] self_health = true`)
+    pub async fn send_parse_health(&self, warning: &ParseWarning) {
+        if !self.config.siem.self_health {
+            return;
+        }
+
+        let mut fields = AlertFields::new(SELF_HEALTH_SIG_ID, "IDS self-health", 3);
+        fields.push(Field::standard("src", warning.source));
+        fields.push(Field::custom("cs1", "Issue", "ParseFailures"));
+        fields.push(Field::custom("cn1", "FailurePercent", format!("{:.0}", warning.failure_percent)));
+        fields.push(Field::standard("msg", &warning.sample));
+        let message = fields.to_syslog(self.config.siem.format, self.config.siem.leef_delimiter);
+
+        if let Err(e) = self.send_siem(&message).await {
+            display::log_warn(&format!("Nu s-a putut trimite mesajul self-health la SIEM: {}", e));
+        }
+    }
+
+    // -----------------------------------------------------------------------
+    // Heartbeat-ul periodic (IDS000): dovada pentru SIEM că IDS-ul trăiește
+    // și primește log-uri. Contoarele sunt cele de la heartbeat-ul anterior;
+    // `input_silent` semnalează depășirea `[listener] input_silence_warn_secs`.
+    // -----------------------------------------------------------------------
+    pub async fn send_heartbeat(&self, packets: u64, input_silent: bool) {
+        let alerts = self.alerts_fired.swap(0, Ordering::Relaxed);
+        let mut fields = AlertFields::new(HEARTBEAT_SIG_ID, "IDS heartbeat", HEARTBEAT_SEVERITY);
+        fields.push(Field::custom("cn1", "PacketsReceived", packets));
+        fields.push(Field::custom("cn2", "AlertsFired", alerts));
+        fields.push(Field::custom("cn3", "IntervalSecs", self.config.siem.heartbeat_interval_secs));
+        fields.push(Field::custom("cs1", "InputSilent", input_silent));
+        let message = fields.to_syslog(self.config.siem.format, self.config.siem.leef_delimiter);
+
+        if let Err(e) = self.send_siem(&message).await {
+            display::log_warn(&format!("Nu s-a putut trimite heartbeat-ul la SIEM: {}", e));
+        }
+    }
+
+    /// Trimite la SIEM alertele agregate acumulate (no-op fără agregare).
+    /// Apelat la finalul fiecărei ferestre și la oprirea IDS-ului.
+    pub async fn flush_siem(&self) {
+        let Some(aggregator) = &self.aggregator else {
+            return;
+        };
+
+        for message in aggregator.drain() {
+            if let Err(e) = self.send_siem(&message).await {
+                display::log_warn(&format!("Nu s-a putut trimite alerta SIEM agregată: {}", e));
+            } else {
+                display::log_alert_sent(&self.config.siem_addr(), "SIEM UDP (agregat)");
+            }
+        }
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Construiește mesajul de alertă pentru SIEM, în formatul din `[siem] format`
+//
+// Valorile vin din `alert_fields::detection_fields` (comune CEF și LEEF);
+// aici se alege doar renderer-ul. Cu `[alert] siem_template` setat,
+// șablonul operatorului înlocuiește payload-ul (header-ul Syslog rămâne).
+// Modul agregat are propriul format și nu folosește șablonul.
+// ---------------------------------------------------------------------------
+fn build_alert_message(payload: &AlertPayload<'_>, severity: u8, alert: &AlertConfig, siem: &SiemConfig) -> String {
+    if let Some(template) = &alert.siem_template {
+        return syslog_line(&alert_template::render(template, &TemplateValues::new(payload.ip, payload.result)));
+    }
+    let Some(mut fields) = detection_fields(payload.ip, payload.result, payload.targets, payload.rules, severity)
+    else {
+        unreachable!("Nu se trimite alertă pentru Clean");
+    };
+    fields.push_labels(payload.labels);
+    fields.push_evidence(payload.evidence);
+    fields.to_syslog(siem.format, siem.leef_delimiter)
+}
+
+/// Tabelul text al țintelor principale pentru email (gol dacă nu există ținte)
+fn format_targets_table(targets: &[TargetSummary]) -> String {
+    if targets.is_empty() {
+        return String::new();
+    }
+
+    let mut table = format!("\nȚinte principale:\n  {:<40} {:>8} {:>8}\n", "Destinație", "Porturi", "Hits");
+    for target in targets {
+        table.push_str(&format!(
+            "  {:<40} {:>8} {:>8}\n",
+            target.dest.to_string(),
+            target.unique_ports,
+            target.hits
+        ));
+    }
+    table
+}
+
+/// Tabelul text al regulilor care au blocat sursa (gol dacă nu există reguli)
+fn format_rules_table(rules: &[RuleSummary]) -> String {
+    if rules.is_empty() {
+        return String::new();
+    }
+
+    let mut table = format!("\nReguli firewall:\n  {:<40} {:>8}\n", "Regulă", "Hits");
+    for rule in rules {
+        table.push_str(&format!("  {:<40} {:>8}\n", rule.rule, rule.hits));
+    }
+    table
+}
+
+// ---------------------------------------------------------------------------
+// Secțiunea de context a email-ului: ultimele linii brute ale sursei,
+// ca analistul să vadă ce a declanșat alerta fără să caute în arhivă.
+// Liniile sunt deja sanitizate la intrarea în buffer.
+// ---------------------------------------------------------------------------
+fn format_context_lines(context: &[ContextLine]) -> String {
+    if context.is_empty() {
+        return String::new();
+    }
+
+    let mut section = format!("\nUltimele {} linii brute de la sursă:\n", context.len());
+    for entry in context {
+        section.push_str(&format!(
+            "  [{}] {}\n",
+            entry.received_at.format("%Y-%m-%d %H:%M:%S"),
+            entry.line
+        ));
+    }
+    section
+}
+
+/// Signature ID-ul CEF al mesajelor de auto-diagnostic
+const SELF_HEALTH_SIG_ID: &str = "IDS900";
+
+/// Signature ID-ul și severitatea heartbeat-ului (informativ, nu alertă)
+const HEARTBEAT_SIG_ID: &str = "IDS000";
+const HEARTBEAT_SEVERITY: u8 = 1;
+
+// ---------------------------------------------------------------------------
+// Trimite alerta la SIEM via UDP
+//
+// UDP este ales deliberat pentru SIEM-uri: este lightweight, non-blocking,
+// și SIEM-urile sunt proiectate să primească fluxuri mari de mesaje UDP.
+// Pierderea ocazională a unui pachet este acceptabilă în acest context.
+// ---------------------------------------------------------------------------
+async fn send_siem_alert(message: &str, siem_addr: SocketAddr) -> Result<()> {
+    // Cream un socket UDP etalon. "0.0.0.0:0" = orice interfață, port aleatoriu
+    // (din aceeași familie cu destinația - un socket IPv4 nu poate trimite la IPv6)
+    let bind_addr = if siem_addr.is_ipv6() { "[::]:0" } else { "0.0.0.0:0" };
+    let socket = UdpSocket::bind(bind_addr)
+        .await
+        .context("Nu s-a putut crea socket UDP pentru SIEM")?;
+
+    socket
+        .send_to(message.as_bytes(), &siem_addr)
+        .await
+        .with_context(|| format!("Nu s-a putut trimite la SIEM {}", siem_addr))?;
+
+    Ok(())
+}
+
+// ---------------------------------------------------------------------------
+// Trimite email de alertă folosind lettre (SMTP async)
+//
+// Lettre este crate-ul standard Rust pentru email.
+// Versiunea 0.11 suportă async/tokio nativ.
+// ---------------------------------------------------------------------------
+async fn send_email_alert(
+    alert_msg: &str,
+    payload:   &AlertPayload<'_>,
+    template:  Option<&str>,
+    email_cfg: &EmailConfig,
+) -> Result<()> {
+    use lettre::{message::header::ContentType, AsyncTransport, Message};
+
+    let scan_type = payload.result.scan_type_label();
+
+    // Pentru scanările distribuite "sursa" este un grup de IP-uri
+    let (mut source_line, subject_source) = match payload.result {
+        DetectionResult::DistributedScan { dest, sources, sample, .. } => {
+            let target = dest.map(|d| d.to_string()).unwrap_or_else(|| "(global)".to_string());
+            (
+                format!(
+                    "Destinație: {}\nSurse:      {} (eșantion: {})",
+                    target,
+                    sources,
+                    join_ips(sample)
+                ),
+                format!("{} surse -> {}", sources, target),
+            )
+        }
+        _ => (format!("IP Sursă:   {}", payload.ip), payload.ip.to_string()),
+    };
+    if !payload.labels.is_empty() {
+        source_line.push_str(&format!("\nEtichete:   {}", payload.labels.join(", ")));
+    }
+    if let Some(evidence) = payload.evidence {
+        source_line.push_str(&format!("\nDovezi:     {}", evidence.compact()));
+    }
+
+    // `[alert] email_template` înlocuiește corpul încorporat de mai jos
+    let email_body = match template {
+        Some(template) => alert_template::render(template, &TemplateValues::new(payload.ip, payload.result)),
+        None => format!(
+            "RUST IDS ALERT\n\
+            ========================\n\
+            Timestamp:  {}\n\
+            {}\n\
+            Tip Scan:   {}\n\
+            {}\
+            {}\
+            {}\
+            \n\
+            Mesaj CEF:\n\
+            {}\n\
+            \n\
+            Acțiune recomandată: Investigați imediat IP-ul sursă.",
+            Utc::now().format("%Y-%m-%d %H:%M:%S UTC"),
+            source_line,
+            scan_type,
+            format_targets_table(payload.targets),
+            format_rules_table(payload.rules),
+            format_context_lines(payload.context),
+            alert_msg
+        ),
+    };
+
+    // Construim mesajul email
+    // `.parse()` pe adrese email returnează Result - folosim `?` pentru propagare
+    let email = Message::builder()
+        .from(email_cfg.from.parse().context("Adresă 'from' invalidă")?)
+        .to(email_cfg.to.parse().context("Adresă 'to' invalidă")?)
+        .subject(format!("[IDS ALERT] {} detectat de la {}", scan_type, subject_source))
+        .header(ContentType::TEXT_PLAIN)
+        .body(email_body)
+        .context("Nu s-a putut construi email-ul")?;
+
+    smtp_transport(email_cfg)?
+        .send(email)
+        .await
+        .context("Trimiterea email-ului SMTP a eșuat")?;
+
+    Ok(())
+}
+
+/// Transportul SMTP cu autentificare, comun alertelor și raportului
+fn smtp_transport(email_cfg: &EmailConfig) -> Result<lettre::AsyncSmtpTransport<lettre::Tokio1Executor>> {
+    use lettre::{transport::smtp::authentication::Credentials, AsyncSmtpTransport, Tokio1Executor};
+
+    let creds = Credentials::new(
+        email_cfg.username.clone(),
+        email_cfg.password.clone(),
+    );
+
+    Ok(AsyncSmtpTransport::<Tokio1Executor>::relay(&email_cfg.smtp_server)
+        .context("SMTP relay configuration failed")?
+        .credentials(creds)
+        .port(email_cfg.smtp_port)
+        .build())
+}
+
+// ---------------------------------------------------------------------------
+// Trimite raportul programat către `[report] to`, prin serverul SMTP și
+// expeditorul din `[email]`. Varianta HTML este un singur fișier autonom,
+// deci merge direct ca corp al email-ului.
+// ---------------------------------------------------------------------------
+pub async fn send_report_email(report: &Report, report_cfg: &ReportConfig, email_cfg: &EmailConfig) -> Result<()> {
+    use lettre::{message::header::ContentType, AsyncTransport, Message};
+
+    let mut builder = Message::builder()
+        .from(email_cfg.from.parse().context("Adresă 'from' invalidă")?)
+        .subject(report.subject());
+    for to in &report_cfg.to {
+        builder = builder.to(to.parse().with_context(|| format!("Adresă [report] to '{}' invalidă", to))?);
+    }
+    let content_type = match report_cfg.format {
+        ReportFormat::Html => ContentType::TEXT_HTML,
+        ReportFormat::Text => ContentType::TEXT_PLAIN,
+    };
+    let email = builder
+        .header(content_type)
+        .body(report.render(report_cfg.format))
+        .context("Nu s-a putut construi email-ul raportului")?;
+
+    smtp_transport(email_cfg)?
+        .send(email)
+        .await
+        .context("Trimiterea raportului prin SMTP a eșuat")?;
+
+    Ok(())
+}
@@ -0,0 +1,141 @@
+// ============================================================
+//  alert/resolver.rs - Rezolvarea DNS a destinației SIEM, cu cache
+// ============================================================
+//
+//  `[siem] address` poate fi un hostname (ex: VIP-ul SIEM-ului cu
+//  failover DNS). Rezolvarea:
+//    - la pornire: eșecul este eroare fatală de configurare
+//    - apoi adresa se ține în cache și se re-rezolvă:
+//        * după `dns_refresh_secs` secunde (TTL-ul nostru), sau
+//        * după `FAILURES_BEFORE_REFRESH` trimiteri eșuate consecutive
+//    - un eșec ulterior doar avertizează și păstrează ultima adresă bună
+//
+//  Concepte Rust demonstrate:
+//  - `tokio::net::lookup_host` : rezolvare DNS async (getaddrinfo pe un
+//    thread de blocking, fără a bloca executor-ul)
+//  - `std::sync::Mutex` ținut doar pentru citiri/scrieri scurte, niciodată
+//    peste `.await`
+// ============================================================
+
+use crate::config::IpPreference;
+use crate::display;
+use anyhow::{Context, Result};
+use std::net::SocketAddr;
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+/// După câte trimiteri eșuate consecutive forțăm o re-rezolvare
+const FAILURES_BEFORE_REFRESH: u32 = 3;
+
+/// Starea cache-ului: ultima adresă bună și momentul rezolvării
+struct CachedAddr {
+    addr:        SocketAddr,
+    resolved_at: Instant,
+    failures:    u32,
+}
+
+/// O destinație `host:port` rezolvată, re-rezolvată periodic
+pub struct ResolvedTarget {
+    host:       String,
+    port:       u16,
+    preference: IpPreference,
+    refresh:    Duration,
+    cached:     Mutex<CachedAddr>,
+}
+
+impl ResolvedTarget {
+    /// Rezolvarea inițială - eșecul se propagă (eroare de configurare)
+    pub async fn resolve(host: &str, port: u16, preference: IpPreference, refresh_secs: u64) -> Result<Self> {
+        let addr = lookup(host, port, preference).await?;
+
+        Ok(ResolvedTarget {
+            host: host.to_string(),
+            port,
+            preference,
+            refresh: Duration::from_secs(refresh_secs),
+            cached: Mutex::new(CachedAddr {
+                addr,
+                resolved_at: Instant::now(),
+                failures: 0,
+            }),
+        })
+    }
+
+    // -----------------------------------------------------------------------
+    // Adresa de folosit pentru următoarea trimitere.
+    //
+    // Dacă intrarea a expirat (sau au fost prea multe eșecuri), re-rezolvăm.
+    // Task-ul care observă primul expirarea "revendică" refresh-ul mutând
+    // `resolved_at` la acum - alertele concurente folosesc între timp
+    // adresa veche în loc să lanseze fiecare propriul lookup.
+    // -----------------------------------------------------------------------
+    pub async fn addr(&self) -> SocketAddr {
+        let (current, needs_refresh) = {
+            let mut cached = self.cached.lock().unwrap_or_else(|e| e.into_inner());
+            let stale = cached.resolved_at.elapsed() >= self.refresh
+                || cached.failures >= FAILURES_BEFORE_REFRESH;
+            if stale {
+                cached.resolved_at = Instant::now();
+                cached.failures = 0;
+            }
+            (cached.addr, stale)
+        };
+
+        if !needs_refresh {
+            return current;
+        }
+
+        match lookup(&self.host, self.port, self.preference).await {
+            Ok(addr) => {
+                if addr != current {
+                    display::log_info(&format!(
+                        "Destinația '{}' rezolvată acum la {} (anterior {})",
+                        self.host, addr, current
+                    ));
+                }
+                self.cached.lock().unwrap_or_else(|e| e.into_inner()).addr = addr;
+                addr
+            }
+            Err(e) => {
+                display::log_warn(&format!(
+                    "Re-rezolvarea '{}' a eșuat, se păstrează {}: {:#}",
+                    self.host, current, e
+                ));
+                current
+            }
+        }
+    }
+
+    /// Raportează rezultatul unei trimiteri (eșecurile repetate forțează re-rezolvarea)
+    pub fn record_send(&self, ok: bool) {
+        let mut cached = self.cached.lock().unwrap_or_else(|e| e.into_inner());
+        if ok {
+            cached.failures = 0;
+        } else {
+            cached.failures += 1;
+        }
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Rezolvă `host:port` și alege o adresă după familia preferată.
+//
+// Dacă nu există nicio adresă din familia preferată, folosim prima adresă
+// returnată (un SIEM doar-IPv6 rămâne utilizabil și cu preferința implicită).
+// ---------------------------------------------------------------------------
+pub async fn lookup(host: &str, port: u16, preference: IpPreference) -> Result<SocketAddr> {
+    let addrs: Vec<SocketAddr> = tokio::net::lookup_host((host, port))
+        .await
+        .with_context(|| format!("Nu s-a putut rezolva '{}'", host))?
+        .collect();
+
+    let preferred = addrs.iter().find(|a| match preference {
+        IpPreference::Ipv4 => a.is_ipv4(),
+        IpPreference::Ipv6 => a.is_ipv6(),
+    });
+
+    preferred
+        .or(addrs.first())
+        .copied()
+        .with_context(|| format!("'{}' nu are nicio adresă", host))
+}
@@ -0,0 +1,70 @@
+// ============================================================
+//  alert/uds.rs - Canal de alertă prin Unix domain socket (datagram)
+// ============================================================
+//
+//  Pe instalările single-host, un agent local de corelare poate citi
+//  alertele direct dintr-un socket Unix - fără stiva de rețea (latență
+//  mai mică, fără expunere pe loopback).
+//
+//  Peer-ul (agentul) poate porni DUPĂ IDS sau poate fi repornit:
+//  - dacă socket-ul nu există încă, trimiterea eșuează cu avertisment
+//  - după orice eșec, conexiunea se aruncă și se recreează la alerta următoare
+//
+//  Concepte Rust demonstrate:
+//  - `tokio::net::UnixDatagram` : socket Unix async, orientat pe mesaje
+//  - `#[cfg(unix)]` : cod disponibil doar pe sisteme Unix
+// ============================================================
+
+use anyhow::{Context, Result};
+use std::path::PathBuf;
+use tokio::net::UnixDatagram;
+use tokio::sync::Mutex;
+
+/// Canalul UDS: socket-ul "conectat" la calea peer-ului, creat la cerere
+pub struct UdsSink {
+    path:   PathBuf,
+    socket: Mutex<Option<UnixDatagram>>,
+}
+
+impl UdsSink {
+    pub fn new(path: impl Into<PathBuf>) -> Self {
+        UdsSink {
+            path:   path.into(),
+            socket: Mutex::new(None),
+        }
+    }
+
+    pub fn path(&self) -> &std::path::Path {
+        &self.path
+    }
+
+    // -----------------------------------------------------------------------
+    // Trimite un mesaj; (re)conectează socket-ul dacă e nevoie.
+    //
+    // La eroare socket-ul este aruncat (`*guard = None`), astfel încât un
+    // peer repornit (socket nou, același path) este preluat automat.
+    // -----------------------------------------------------------------------
+    pub async fn send(&self, message: &str) -> Result<()> {
+        let mut guard = self.socket.lock().await;
+
+        if guard.is_none() {
+            let socket = UnixDatagram::unbound().context("Nu s-a putut crea socket-ul Unix")?;
+            socket
+                .connect(&self.path)
+                .with_context(|| format!("Peer-ul UDS '{}' nu este disponibil", self.path.display()))?;
+            *guard = Some(socket);
+        }
+
+        let result = match guard.as_ref() {
+            Some(socket) => socket.send(message.as_bytes()).await,
+            None => unreachable!("socket-ul tocmai a fost creat"),
+        };
+
+        if let Err(e) = result {
+            *guard = None;
+            return Err(e).with_context(|| format!("Trimiterea pe UDS '{}' a eșuat", self.path.display()));
+        }
+
+        Ok(())
+    }
+}
@@ -0,0 +1,363 @@
+// ============================================================
+//  alert_fields.rs - Câmpurile unei alerte, independent de format
+// ============================================================
+//
+//  Același eveniment pleacă spre SIEM în mai multe formate:
+//    CEF  (ArcSight):  CEF:0|RustIDS|NetworkScanner|0.1.0|IDS001|Fast Port Scan Detected|8|src=... cs2Label=UniquePorts cs2=23
+//    LEEF (QRadar):    LEEF:2.0|RustIDS|NetworkScanner|0.1.0|IDS001|cat=FastScan<TAB>src=...<TAB>uniquePorts=23<TAB>sev=8
+//
+//  Valorile (severitate, porturi, ferestre, ținte) se calculează O SINGURĂ
+//  DATĂ în `AlertFields`; fiecare format are doar un renderer care decide
+//  numele câmpurilor și escaparea. Astfel cele două formate nu pot
+//  ajunge să raporteze valori diferite pentru aceeași alertă.
+//
+//  Concepte Rust demonstrate:
+//  - Enum cu date per variantă (`CefKey`) pentru a modela "câmp standard"
+//    vs. "câmp custom cu etichetă" (csN/cnN)
+//  - `Option<&'static str>` pentru suprascrieri opționale, fără alocare
+// ============================================================
+
+use crate::config::SiemFormat;
+use crate::detector::{DetectionEvidence, DetectionResult};
+use crate::state::{RuleSummary, TargetSummary};
+use chrono::Utc;
+use std::net::IpAddr;
+
+/// Vendor / produs / versiune, identice în header-ele CEF și LEEF
+const VENDOR: &str = "RustIDS";
+const PRODUCT: &str = "NetworkScanner";
+const VERSION: &str = "0.1.0";
+
+/// Hostname-ul din header-ul Syslog
+pub const SYSLOG_HOSTNAME: &str = "rust-ids";
+
+/// Delimitatorul implicit al atributelor LEEF 2.0 (nedeclarat în header)
+pub const LEEF_DEFAULT_DELIMITER: char = '\t';
+
+/// Cum se numește un câmp în CEF
+#[derive(Debug, Clone, PartialEq)]
+pub enum CefKey {
+    /// Cheie din dicționarul CEF (ex: `src`, `dst`, `proto`, `cnt`, `msg`)
+    Standard(&'static str),
+    /// Câmp custom: `cs1Label=ScanType cs1=FastScan`
+    Custom { slot: &'static str, label: &'static str },
+}
+
+/// Un câmp al alertei: valoarea plus numele ei în fiecare format
+#[derive(Debug, Clone, PartialEq)]
+pub struct Field {
+    pub cef:   CefKey,
+    /// Cheia LEEF; implicit cheia standard CEF, respectiv eticheta custom
+    /// în camelCase (`UniquePorts` -> `uniquePorts`)
+    pub leef:  Option<&'static str>,
+    pub value: String,
+}
+
+impl Field {
+    pub fn standard(key: &'static str, value: impl ToString) -> Self {
+        Field { cef: CefKey::Standard(key), leef: None, value: value.to_string() }
+    }
+
+    pub fn custom(slot: &'static str, label: &'static str, value: impl ToString) -> Self {
+        Field { cef: CefKey::Custom { slot, label }, leef: None, value: value.to_string() }
+    }
+
+    /// Suprascrie cheia LEEF (ex: tipul de scan devine atributul predefinit `cat`)
+    pub fn leef_key(mut self, key: &'static str) -> Self {
+        self.leef = Some(key);
+        self
+    }
+
+    fn leef_name(&self) -> String {
+        if let Some(key) = self.leef {
+            return key.to_string();
+        }
+        match &self.cef {
+            CefKey::Standard(key) => key.to_string(),
+            CefKey::Custom { label, .. } => lower_camel(label),
+        }
+    }
+}
+
+/// O alertă gata de trimis, în afara oricărui format
+#[derive(Debug, Clone, PartialEq)]
+pub struct AlertFields {
+    pub sig_id:   String,
+    /// Numele evenimentului (header-ul CEF; LEEF nu are un câmp pentru el)
+    pub name:     String,
+    /// Severitatea finală (după mapare și clamp), 0-10
+    pub severity: u8,
+    pub fields:   Vec<Field>,
+}
+
+impl AlertFields {
+    pub fn new(sig_id: impl ToString, name: impl ToString, severity: u8) -> Self {
+        AlertFields {
+            sig_id: sig_id.to_string(),
+            name: name.to_string(),
+            severity,
+            fields: Vec::new(),
+        }
+    }
+
+    pub fn push(&mut self, field: Field) {
+        self.fields.push(field);
+    }
+
+    /// Tag-urile sursei din `[[labels]]`: `cs6Label=SourceTag cs6=partner-acme,vpn`
+    /// (niciun câmp dacă lista e goală)
+    pub fn push_labels(&mut self, labels: &[String]) {
+        if !labels.is_empty() {
+            self.push(Field::custom("cs6", "SourceTag", labels.join(",")));
+        }
+    }
+
+    /// Dovezile scanării de porturi: pragul depășit în `cn3`, viteza în
+    /// `cfp1` și forma compactă în câmpul standard `reason` (toate sloturile
+    /// `csN` sunt ocupate)
+    pub fn push_evidence(&mut self, evidence: Option<&DetectionEvidence>) {
+        if let Some(evidence) = evidence {
+            self.push(Field::custom("cn3", "Threshold", evidence.threshold));
+            self.push(Field::custom("cfp1", "PortsPerSec", format!("{:.2}", evidence.ports_per_sec)));
+            self.push(Field::standard("reason", evidence.compact()));
+        }
+    }
+
+    /// Mesajul complet pentru SIEM: header Syslog + payload în formatul cerut
+    pub fn to_syslog(&self, format: SiemFormat, leef_delimiter: char) -> String {
+        let payload = match format {
+            SiemFormat::Cef => self.to_cef(),
+            SiemFormat::Leef => self.to_leef(leef_delimiter),
+        };
+        syslog_line(&payload)
+    }
+
+    // -----------------------------------------------------------------------
+    // CEF:Version|Vendor|Product|Version|SigID|Name|Severity|Extension
+    //
+    // Extensia este "cheie=valoare" separate prin spațiu; câmpurile custom
+    // au și eticheta lor (`cs2Label=UniquePorts cs2=23`).
+    // -----------------------------------------------------------------------
+    pub fn to_cef(&self) -> String {
+        let extension: Vec<String> = self
+            .fields
+            .iter()
+            .map(|field| {
+                let value = escape_cef_value(&field.value);
+                match &field.cef {
+                    CefKey::Standard(key) => format!("{}={}", key, value),
+                    CefKey::Custom { slot, label } => format!("{}Label={} {}={}", slot, label, slot, value),
+                }
+            })
+            .collect();
+
+        format!(
+            "CEF:0|{}|{}|{}|{}|{}|{}|{}",
+            VENDOR,
+            PRODUCT,
+            VERSION,
+            escape_cef_header(&self.sig_id),
+            escape_cef_header(&self.name),
+            self.severity,
+            extension.join(" ")
+        )
+    }
+
+    // -----------------------------------------------------------------------
+    // LEEF:2.0|Vendor|Product|Version|EventID|[Delimiter|]Attributes
+    //
+    // Conform specificației LEEF 2.0, delimitatorul implicit al atributelor
+    // este TAB și nu se declară; orice alt delimitator apare în header
+    // (caracterul însuși sau forma hexazecimală `xHH`). Categoria (`cat`)
+    // este primul atribut, iar severitatea ajunge în atributul `sev`.
+    // -----------------------------------------------------------------------
+    pub fn to_leef(&self, delimiter: char) -> String {
+        let mut attributes: Vec<String> = Vec::with_capacity(self.fields.len() + 1);
+        let (category, rest): (Vec<&Field>, Vec<&Field>) =
+            self.fields.iter().partition(|field| field.leef_name() == "cat");
+        for field in category.into_iter().chain(rest) {
+            attributes.push(format!("{}={}", field.leef_name(), clean_leef_value(&field.value, delimiter)));
+        }
+        attributes.push(format!("sev={}", self.severity));
+
+        let declared = if delimiter == LEEF_DEFAULT_DELIMITER {
+            String::new()
+        } else if delimiter.is_ascii_graphic() {
+            format!("{}|", delimiter)
+        } else {
+            format!("x{:02X}|", u32::from(delimiter))
+        };
+
+        format!(
+            "LEEF:2.0|{}|{}|{}|{}|{}{}",
+            VENDOR,
+            PRODUCT,
+            VERSION,
+            clean_leef_value(&self.sig_id, '|'),
+            declared,
+            attributes.join(&delimiter.to_string())
+        )
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Câmpurile unei alerte de detecție (`None` pentru `Clean`).
+//
+// `severity` este cea finală, deja mapată de dispatcher; `targets` sunt
+// țintele principale ale sursei (gol dacă log-urile nu au IP destinație),
+// iar `rules` regulile firewall-ului care au blocat-o (gol fără câmp de regulă).
+// ---------------------------------------------------------------------------
+pub fn detection_fields(
+    ip: &IpAddr,
+    result: &DetectionResult,
+    targets: &[TargetSummary],
+    rules: &[RuleSummary],
+    severity: u8,
+) -> Option<AlertFields> {
+    let mut alert = AlertFields::new(result.sig_id(), alert_name(result)?, severity);
+    let scan_type = |value: String| Field::custom("cs1", "ScanType", value).leef_key("cat");
+
+    match result {
+        DetectionResult::Scan(hit) => {
+            alert.push(Field::standard("src", ip));
+            alert.push(scan_type(format!("{}Scan", capitalize(&hit.tier))));
+            alert.push(Field::custom("cs2", "UniquePorts", hit.ports));
+            // Tier-ul "slow" păstrează câmpul dinaintea tier-urilor configurabile
+            // (minute), pe care se bazează regulile SIEM existente
+            if hit.tier == "slow" {
+                alert.push(Field::custom("cs3", "WindowMins", hit.window_secs / 60));
+            } else {
+                alert.push(Field::custom("cs3", "WindowSecs", hit.window_secs));
+            }
+        }
+        DetectionResult::MultiScan(hits) => {
+            let tiers: Vec<String> = hits.iter().map(|h| capitalize(&h.tier)).collect();
+            // Detaliu per tier: "fast=23/10s,slow=41/3600s"
+            let detail: Vec<String> = hits
+                .iter()
+                .map(|h| format!("{}={}/{}s", h.tier, h.ports, h.window_secs))
+                .collect();
+            let max_ports = hits.iter().map(|h| h.ports).max().unwrap_or(0);
+            alert.push(Field::standard("src", ip));
+            alert.push(scan_type(format!("{}Scan", tiers.join("And"))));
+            alert.push(Field::custom("cs2", "UniquePorts", max_ports));
+            alert.push(Field::custom("cs3", "Tiers", detail.join(",")));
+        }
+        DetectionResult::DistributedScan { dest, sources, total_unique_ports, window_secs, sample } => {
+            // Fără destinație cunoscută nu emitem `dst=` (bucket-ul global)
+            if let Some(dest) = dest {
+                alert.push(Field::standard("dst", dest));
+            }
+            alert.push(scan_type("DistributedScan".to_string()));
+            alert.push(Field::custom("cs2", "UniquePorts", total_unique_ports));
+            alert.push(Field::custom("cs3", "WindowSecs", window_secs));
+            alert.push(Field::custom("cn1", "SourceCount", sources));
+            alert.push(Field::custom("cs4", "SampleSources", join_ips(sample)));
+        }
+        DetectionResult::IcmpSweep { events, destinations, window_secs } => {
+            alert.push(Field::standard("src", ip));
+            alert.push(Field::standard("proto", "ICMP"));
+            alert.push(scan_type("IcmpSweep".to_string()));
+            alert.push(Field::custom("cn1", "IcmpEvents", events));
+            alert.push(Field::custom("cn2", "Destinations", destinations));
+            alert.push(Field::custom("cs3", "WindowSecs", window_secs));
+        }
+        DetectionResult::PostScanAccess { port } => {
+            alert.push(Field::standard("src", ip));
+            alert.push(Field::standard("dpt", port).leef_key("dstPort"));
+            alert.push(scan_type("PostScanAccess".to_string()));
+        }
+        DetectionResult::KnownOffenderReturned { alerts, since_alert_secs } => {
+            alert.push(Field::standard("src", ip));
+            alert.push(scan_type("KnownOffenderReturned".to_string()));
+            alert.push(Field::custom("cn1", "PriorAlerts", alerts));
+            alert.push(Field::custom("cn2", "SecsSinceAlert", since_alert_secs));
+        }
+        DetectionResult::Clean => return None,
+    }
+
+    // Țintele principale: "10.0.0.5[23p/40h],10.0.0.9[4p/4h]" (porturi unice / evenimente)
+    if !targets.is_empty() {
+        let targets: Vec<String> = targets
+            .iter()
+            .map(|t| format!("{}[{}p/{}h]", t.dest, t.unique_ports, t.hits))
+            .collect();
+        alert.push(Field::custom("cs4", "TopTargets", targets.join(",")));
+    }
+
+    // Regulile care au blocat sursa: "Block_Inbound[40h],42[3h]"
+    if !rules.is_empty() {
+        let rules: Vec<String> = rules.iter().map(|r| format!("{}[{}h]", r.rule, r.hits)).collect();
+        alert.push(Field::custom("cs5", "TopRules", rules.join(",")));
+    }
+
+    Some(alert)
+}
+
+/// Numele evenimentului pentru un rezultat de detecție (`None` pentru `Clean`)
+pub fn alert_name(result: &DetectionResult) -> Option<String> {
+    let name = match result {
+        DetectionResult::Scan(hit) => format!("{} Port Scan Detected", capitalize(&hit.tier)),
+        DetectionResult::MultiScan(hits) => {
+            let tiers: Vec<String> = hits.iter().map(|h| capitalize(&h.tier)).collect();
+            format!("Combined {} Port Scan Detected", tiers.join("+"))
+        }
+        DetectionResult::DistributedScan { .. } => "Distributed Port Scan Detected".to_string(),
+        DetectionResult::IcmpSweep { .. } => "ICMP Sweep Detected".to_string(),
+        DetectionResult::PostScanAccess { .. } => "Access After Port Scan Detected".to_string(),
+        DetectionResult::KnownOffenderReturned { .. } => "Known Offender Returned".to_string(),
+        DetectionResult::Clean => return None,
+    };
+    Some(name)
+}
+
+/// Header-ul Syslog (timestamp + hostname) urmat de `payload`
+pub fn syslog_line(payload: &str) -> String {
+    format!("{} {} {}", Utc::now().format("%b %d %H:%M:%S"), SYSLOG_HOSTNAME, payload)
+}
+
+/// Lista de IP-uri separate prin virgulă
+pub fn join_ips(ips: &[IpAddr]) -> String {
+    ips.iter().map(|ip| ip.to_string()).collect::<Vec<_>>().join(",")
+}
+
+/// Escapare valoare de extensie CEF: `\`, `=` și newline au semnificație specială
+fn escape_cef_value(value: &str) -> String {
+    value
+        .replace('\\', "\\\\")
+        .replace('=', "\\=")
+        .replace('\n', "\\n")
+        .replace('\r', "\\r")
+}
+
+/// Escapare câmp de header CEF: `\` și `|` (separatorul header-ului)
+fn escape_cef_header(value: &str) -> String {
+    value.replace('\\', "\\\\").replace('|', "\\|")
+}
+
+/// LEEF nu definește escapare: delimitatorul și sfârșiturile de linie
+/// dintr-o valoare devin spații, ca atributele să rămână separabile
+fn clean_leef_value(value: &str, delimiter: char) -> String {
+    value
+        .chars()
+        .map(|c| if c == delimiter || c == '\n' || c == '\r' { ' ' } else { c })
+        .collect()
+}
+
+/// "medium" -> "Medium" (pentru câmpurile în stil CamelCase)
+fn capitalize(s: &str) -> String {
+    let mut chars = s.chars();
+    match chars.next() {
+        Some(first) => first.to_uppercase().chain(chars).collect(),
+        None => String::new(),
+    }
+}
+
+/// "UniquePorts" -> "uniquePorts" (cheile LEEF custom)
+fn lower_camel(s: &str) -> String {
+    let mut chars = s.chars();
+    match chars.next() {
+        Some(first) => first.to_lowercase().chain(chars).collect(),
+        None => String::new(),
+    }
+}
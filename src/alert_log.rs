@@ -0,0 +1,85 @@
+// ============================================================
+//  alert_log.rs - Istoricul alertelor (`[alert_log]`)
+// ============================================================
+//
+//  Alertele pleacă spre SIEM / email / UDS și nu rămân nicăieri în IDS.
+//  Cu `[alert_log] enabled = true`, fiecare alertă trimisă (nu și cele
+//  suprimate în warmup) devine și o linie JSON într-un fișier:
+//
+//    {"timestamp":"2026-10-18T07:00:12Z","ip":"10.0.0.5","scan_type":"FAST_SCAN",...}
+//
+//  Este sursa de date a `rust-ids report` (vezi report.rs). Fișierul se
+//  rotește după dimensiune exact ca `[event_log]` - același `EventLogWriter`.
+//
+//  Alertele sunt rare (cooldown per IP), deci linia se scrie și se golește
+//  pe disc imediat, fără thread dedicat: o alertă scrisă nu se pierde la
+//  o oprire bruscă.
+//
+//  Concepte Rust demonstrate:
+//  - `#[derive(Serialize, Deserialize)]` : același tip scris de daemon și citit de raport
+//  - `Mutex<T>` : un singur writer partajat de task-urile de alertare
+// ============================================================
+
+use crate::config::AlertLogConfig;
+use crate::detector::DetectionResult;
+use crate::event_log::EventLogWriter;
+use anyhow::Result;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::net::IpAddr;
+use std::sync::Mutex;
+
+/// O alertă trimisă, așa cum apare în fișierul `[alert_log]`
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct AlertRecord {
+    pub timestamp:    DateTime<Utc>,
+    /// IP-ul sursă (la scanările distribuite: sursa cu cele mai multe porturi)
+    pub ip:           IpAddr,
+    /// Eticheta tipului de detecție (ex: "FAST_SCAN", "ICMP_SWEEP")
+    pub scan_type:    String,
+    pub severity:     u8,
+    /// Porturile unice ale detecției (0 la ICMP sweep / recidivist)
+    pub unique_ports: usize,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub labels:       Vec<String>,
+}
+
+impl AlertRecord {
+    pub fn new(ip: IpAddr, result: &DetectionResult, severity: u8, labels: &[String], timestamp: DateTime<Utc>) -> Self {
+        let unique_ports = match result {
+            DetectionResult::DistributedScan { total_unique_ports, .. } => *total_unique_ports,
+            DetectionResult::PostScanAccess { .. } => 1,
+            _ => result.hits().iter().map(|hit| hit.ports).max().unwrap_or(0),
+        };
+        AlertRecord {
+            timestamp,
+            ip,
+            scan_type: result.scan_type_label(),
+            severity,
+            unique_ports,
+            labels: labels.to_vec(),
+        }
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Fișierul `[alert_log]`, deschis la pornire (o cale greșită oprește IDS-ul
+// acum, nu la prima alertă)
+// ---------------------------------------------------------------------------
+pub struct AlertLog {
+    writer: Mutex<EventLogWriter>,
+}
+
+impl AlertLog {
+    pub fn open(config: &AlertLogConfig) -> Result<Self> {
+        let writer = EventLogWriter::open_as("[alert_log]", &config.path, config.rotate_size_bytes)?;
+        Ok(AlertLog { writer: Mutex::new(writer) })
+    }
+
+    /// Adaugă alerta și golește fișierul pe disc
+    pub fn record(&self, record: &AlertRecord) -> Result<()> {
+        let mut writer = self.writer.lock().unwrap_or_else(|e| e.into_inner());
+        writer.append(record)?;
+        writer.flush()
+    }
+}
@@ -0,0 +1,172 @@
+// ============================================================
+//  alert_template.rs - Șabloanele de alertă din `[alert]`
+// ============================================================
+//
+//  `[alert] email_template` și `siem_template` înlocuiesc corpul email-ului,
+//  respectiv payload-ul CEF/LEEF, cu un text al operatorului:
+//
+//    siem_template = "IDS {scan_type} de la {ip}: {fast_ports} porturi în {window}s"
+//
+//  Substituirea este deliberat minimală: `{nume}` devine valoarea câmpului,
+//  `{{` și `}}` sunt acolade literale. Numele necunoscute sunt respinse la
+//  încărcarea configurației - o greșeală de tipar nu trebuie descoperită
+//  abia la prima alertă reală.
+//
+//  Concepte Rust demonstrate:
+//  - `Peekable<CharIndices>` : parcurgere cu "privire înainte" a unui caracter
+//  - același parser pentru validare și randare (`segments`), deci cele două
+//    nu pot ajunge să interpreteze diferit un șablon
+// ============================================================
+
+use crate::alert_fields::SYSLOG_HOSTNAME;
+use crate::detector::DetectionResult;
+use anyhow::{bail, Result};
+use chrono::Utc;
+use std::net::IpAddr;
+
+/// Câmpurile disponibile într-un șablon
+pub const PLACEHOLDERS: &[&str] = &["ip", "scan_type", "fast_ports", "slow_ports", "window", "timestamp", "hostname"];
+
+/// O bucată de șablon: text literal sau câmp de substituit
+#[derive(Debug, PartialEq)]
+enum Segment<'a> {
+    Text(&'a str),
+    /// `{{` / `}}`: o acoladă literală
+    Brace(char),
+    Placeholder(&'a str),
+}
+
+// ---------------------------------------------------------------------------
+// Împarte șablonul în segmente. Erorile (acoladă neînchisă, `}` singur)
+// indică poziția în caractere, ca operatorul să găsească greșeala.
+// ---------------------------------------------------------------------------
+fn segments(template: &str) -> Result<Vec<Segment<'_>>> {
+    let mut out = Vec::new();
+    let mut chars = template.char_indices().peekable();
+    let mut text_start = 0;
+
+    while let Some((i, c)) = chars.next() {
+        if c != '{' && c != '}' {
+            continue;
+        }
+        if text_start < i {
+            out.push(Segment::Text(&template[text_start..i]));
+        }
+        if chars.peek().map(|&(_, next)| next) == Some(c) {
+            chars.next();
+            out.push(Segment::Brace(c));
+            text_start = i + 2;
+            continue;
+        }
+        if c == '}' {
+            bail!("'}}' fără pereche la poziția {} (folosiți '}}}}' pentru o acoladă literală)", i);
+        }
+        let Some(end) = template[i + 1..].find('}') else {
+            bail!("'{{' neînchis la poziția {} (folosiți '{{{{' pentru o acoladă literală)", i);
+        };
+        let name = &template[i + 1..i + 1 + end];
+        out.push(Segment::Placeholder(name));
+        // Sărim peste numele câmpului și acolada de închidere
+        while chars.next_if(|&(j, _)| j <= i + 1 + end).is_some() {}
+        text_start = i + 2 + end;
+    }
+    if text_start < template.len() {
+        out.push(Segment::Text(&template[text_start..]));
+    }
+    Ok(out)
+}
+
+/// Verifică sintaxa șablonului și că folosește doar câmpuri cunoscute
+pub fn validate(template: &str) -> Result<()> {
+    for segment in segments(template)? {
+        if let Segment::Placeholder(name) = segment {
+            if !PLACEHOLDERS.contains(&name) {
+                bail!("câmp necunoscut {{{}}} (disponibile: {})", name, PLACEHOLDERS.join(", "));
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Valorile câmpurilor pentru o alertă
+#[derive(Debug, Clone, PartialEq)]
+pub struct TemplateValues {
+    pub ip:         String,
+    pub scan_type:  String,
+    /// Porturile unice ale tier-ului "fast" (0 dacă nu a declanșat)
+    pub fast_ports: usize,
+    /// Porturile unice ale tier-ului "slow" (0 dacă nu a declanșat)
+    pub slow_ports: usize,
+    /// Fereastra detecției principale, în secunde (0 fără fereastră)
+    pub window:     u64,
+    pub timestamp:  String,
+    pub hostname:   String,
+}
+
+impl TemplateValues {
+    pub fn new(ip: &IpAddr, result: &DetectionResult) -> Self {
+        let hits = match result {
+            DetectionResult::Scan(hit) => std::slice::from_ref(hit),
+            DetectionResult::MultiScan(hits) => hits.as_slice(),
+            _ => &[],
+        };
+        let tier_ports = |tier: &str| {
+            hits.iter()
+                .find(|hit| hit.tier.eq_ignore_ascii_case(tier))
+                .map_or(0, |hit| hit.ports)
+        };
+        let window = match result {
+            DetectionResult::DistributedScan { window_secs, .. }
+            | DetectionResult::IcmpSweep { window_secs, .. } => *window_secs,
+            _ => result.primary().map_or(0, |hit| hit.window_secs),
+        };
+
+        TemplateValues {
+            ip:         ip.to_string(),
+            scan_type:  result.scan_type_label(),
+            fast_ports: tier_ports("fast"),
+            slow_ports: tier_ports("slow"),
+            window,
+            timestamp:  Utc::now().format("%Y-%m-%d %H:%M:%S UTC").to_string(),
+            hostname:   SYSLOG_HOSTNAME.to_string(),
+        }
+    }
+
+    fn get(&self, name: &str) -> Option<String> {
+        let value = match name {
+            "ip" => self.ip.clone(),
+            "scan_type" => self.scan_type.clone(),
+            "fast_ports" => self.fast_ports.to_string(),
+            "slow_ports" => self.slow_ports.to_string(),
+            "window" => self.window.to_string(),
+            "timestamp" => self.timestamp.clone(),
+            "hostname" => self.hostname.clone(),
+            _ => return None,
+        };
+        Some(value)
+    }
+}
+
+/// Randează un șablon validat; un câmp necunoscut (imposibil după
+/// `validate`) rămâne în text neschimbat
+pub fn render(template: &str, values: &TemplateValues) -> String {
+    let Ok(segments) = segments(template) else {
+        return template.to_string();
+    };
+    let mut out = String::with_capacity(template.len() + 32);
+    for segment in segments {
+        match segment {
+            Segment::Text(text) => out.push_str(text),
+            Segment::Brace(c) => out.push(c),
+            Segment::Placeholder(name) => match values.get(name) {
+                Some(value) => out.push_str(&value),
+                None => {
+                    out.push('{');
+                    out.push_str(name);
+                    out.push('}');
+                }
+            },
+        }
+    }
+    out
+}
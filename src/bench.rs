@@ -0,0 +1,361 @@
+// ============================================================
+//  bench.rs - Capacitatea end-to-end a motorului (`rust-ids bench`)
+// ============================================================
+//
+//  Înainte de un mediu cu trafic mare, operatorul vrea un număr: câte
+//  evenimente pe secundă susține această mașină? `bench` generează o
+//  încărcare sintetică - N surse, M evenimente fiecare, porturi aleatoare -
+//  și o trimite direct în `Engine::ingest_throttled`, calea per-eveniment a
+//  lui `process_packet` de după parser, de pe mai multe thread-uri simultan
+//  (contention pe DashMap exact ca în producție). Alertele ajung într-un
+//  sink no-op: doar se numără.
+//
+//    rust-ids bench --sources 5000 --events 200 --duration 30 --workers 8
+//
+//  Raportul: evenimente/s, latența per eveniment (p50 / p99 / max), vârful
+//  IP-urilor urmărite și memoria procesului (VmRSS / VmHWM din procfs).
+//
+//  Concepte Rust demonstrate:
+//  - `std::thread::scope` : thread-uri care împrumută date de pe stivă
+//  - Histogramă log-liniară: percentile în memorie constantă
+//  - Generator xorshift64: pseudo-aleator determinist, fără dependențe
+// ============================================================
+
+use crate::config::Config;
+use crate::engine::Engine;
+use crate::parser::{EventKind, LogEntry};
+use anyhow::{ensure, Result};
+use chrono::Utc;
+use std::net::{IpAddr, Ipv4Addr};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::time::{Duration, Instant};
+
+/// Cât de des eșantionează thread-ul de monitorizare starea și memoria
+const SAMPLE_INTERVAL: Duration = Duration::from_millis(50);
+
+/// Destinațiile sintetice (192.168.100.1 - .16): profilul de ținte se umple
+const BENCH_TARGETS: u32 = 16;
+
+/// Cât de des un worker rulează evaluările amânate ajunse la termen
+const DEFERRED_CHECK_EVERY: u64 = 1024;
+
+// ---------------------------------------------------------------------------
+// Parametrii încărcării
+// ---------------------------------------------------------------------------
+#[derive(Debug, Clone, PartialEq)]
+pub struct BenchOptions {
+    /// IP-uri sursă distincte (10.0.0.1, 10.0.0.2, ...)
+    pub sources:           u32,
+    /// Evenimente per sursă într-o trecere; trecerile se repetă până la `duration`
+    pub events_per_source: u32,
+    /// Durata fixă a măsurătorii
+    pub duration:          Duration,
+    /// Thread-uri care apelează motorul în paralel (plafonat la `sources`)
+    pub workers:           usize,
+}
+
+impl Default for BenchOptions {
+    fn default() -> Self {
+        BenchOptions {
+            sources:           1_000,
+            events_per_source: 100,
+            duration:          Duration::from_secs(10),
+            workers:           std::thread::available_parallelism().map_or(4, |n| n.get()),
+        }
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Rezultatul măsurătorii
+// ---------------------------------------------------------------------------
+#[derive(Debug, Clone, PartialEq)]
+pub struct BenchReport {
+    pub workers:          usize,
+    /// Evenimente procesate de motor
+    pub events:           u64,
+    /// Alerte decise (trimise în sink-ul no-op), inclusiv din evaluările amânate
+    pub alerts:           u64,
+    /// Evenimente a căror evaluare a fost amânată de throttling
+    pub deferred:         u64,
+    pub elapsed:          Duration,
+    pub latency_p50:      Duration,
+    pub latency_p99:      Duration,
+    pub latency_max:      Duration,
+    /// Vârful IP-urilor cu istoric de porturi (`StateStats::tracked_ips`)
+    pub peak_tracked_ips: usize,
+    /// Memoria rezidentă la pornirea încărcării (`None` în afara Linux)
+    pub rss_start_bytes:  Option<u64>,
+    /// Vârful memoriei rezidente a procesului (VmHWM)
+    pub rss_peak_bytes:   Option<u64>,
+}
+
+impl BenchReport {
+    /// Throughput-ul susținut
+    pub fn events_per_sec(&self) -> f64 {
+        self.events as f64 / self.elapsed.as_secs_f64().max(f64::EPSILON)
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Rulează încărcarea pe un motor nou, construit din `config` (pragurile,
+// cooldown-urile și throttling-ul sunt cele din producție).
+// ---------------------------------------------------------------------------
+pub fn run(config: &Config, options: &BenchOptions) -> Result<BenchReport> {
+    ensure!(options.sources > 0, "bench: --sources trebuie să fie > 0");
+    ensure!(options.sources < 1 << 24, "bench: --sources trebuie să încapă în 10.0.0.0/8");
+    ensure!(options.events_per_source > 0, "bench: --events trebuie să fie > 0");
+    ensure!(!options.duration.is_zero(), "bench: --duration trebuie să fie > 0");
+    ensure!(options.workers > 0, "bench: --workers trebuie să fie > 0");
+
+    let engine = Engine::new(config);
+    let workers = options.workers.min(options.sources as usize);
+    let rss_start_bytes = read_proc_status_kb("VmRSS:").map(|kb| kb * 1024);
+    let done = AtomicBool::new(false);
+
+    let start = Instant::now();
+    let deadline = start + options.duration;
+    let (totals, peak_tracked_ips) = std::thread::scope(|scope| {
+        let handles: Vec<_> = (0..workers)
+            .map(|worker| {
+                let engine = &engine;
+                scope.spawn(move || run_worker(engine, options, worker, workers, deadline))
+            })
+            .collect();
+
+        // Monitorizarea: vârful stării, cât timp lucrează worker-ii
+        let monitor = scope.spawn(|| {
+            let mut peak = 0;
+            while !done.load(Ordering::Relaxed) {
+                peak = peak.max(engine.state().stats().tracked_ips);
+                std::thread::sleep(SAMPLE_INTERVAL);
+            }
+            peak.max(engine.state().stats().tracked_ips)
+        });
+
+        let mut totals = WorkerTotals::default();
+        for handle in handles {
+            totals.merge(handle.join().expect("worker bench"));
+        }
+        done.store(true, Ordering::Relaxed);
+        (totals, monitor.join().expect("monitor bench"))
+    });
+    let elapsed = start.elapsed();
+
+    Ok(BenchReport {
+        workers,
+        events: totals.events,
+        alerts: totals.alerts,
+        deferred: totals.deferred,
+        elapsed,
+        latency_p50: totals.latency.percentile(50.0),
+        latency_p99: totals.latency.percentile(99.0),
+        latency_max: totals.latency.max(),
+        peak_tracked_ips,
+        rss_start_bytes,
+        rss_peak_bytes: read_proc_status_kb("VmHWM:").map(|kb| kb * 1024),
+    })
+}
+
+#[derive(Default)]
+struct WorkerTotals {
+    events:   u64,
+    alerts:   u64,
+    deferred: u64,
+    latency:  LatencyHistogram,
+}
+
+impl WorkerTotals {
+    fn merge(&mut self, other: WorkerTotals) {
+        self.events += other.events;
+        self.alerts += other.alerts;
+        self.deferred += other.deferred;
+        self.latency.merge(&other.latency);
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Un worker: sursele cu `index % workers == worker`, intercalate (câte un
+// eveniment per sursă pe rând, ca într-un flux real), trecere după trecere
+// până la `deadline`.
+//
+// Evaluările amânate de throttling rulează pe același thread când ajung la
+// termen (în producție: un task tokio) - intră în throughput, nu în latența
+// evenimentului care le-a amânat.
+// ---------------------------------------------------------------------------
+fn run_worker(engine: &Engine, options: &BenchOptions, worker: usize, workers: usize, deadline: Instant) -> WorkerTotals {
+    let sources: Vec<IpAddr> = (0..options.sources)
+        .filter(|index| *index as usize % workers == worker)
+        .map(source_ip)
+        .collect();
+    let mut rng = XorShift64::new(0x9E37_79B9_7F4A_7C15 ^ (worker as u64 + 1));
+    let timestamp = Utc::now();
+    let mut totals = WorkerTotals::default();
+    let mut pending: Vec<(Instant, IpAddr)> = Vec::new();
+
+    'load: loop {
+        for _ in 0..options.events_per_source {
+            for &ip in &sources {
+                let entry = LogEntry {
+                    source_ip: ip,
+                    dest_ip:   Some(target_ip(rng.next() as u32 % BENCH_TARGETS)),
+                    kind:      EventKind::Port((rng.next() % 65_535) as u16 + 1),
+                    protocol:  Some("tcp".to_string()),
+                    action:    "drop".to_string(),
+                    accepted:  false,
+                    rule:      None,
+                    timestamp,
+                };
+
+                let started = Instant::now();
+                let ingested = engine.ingest_throttled(entry);
+                let finished = Instant::now();
+                totals.latency.record(finished - started);
+                totals.events += 1;
+                totals.alerts += u64::from(ingested.alert.is_some());
+                if let Some(delay) = ingested.deferred {
+                    totals.deferred += 1;
+                    pending.push((finished + delay, ip));
+                }
+
+                if totals.events % DEFERRED_CHECK_EVERY == 0 {
+                    pending.retain(|(due, ip)| {
+                        if *due > finished {
+                            return true;
+                        }
+                        totals.alerts += u64::from(engine.evaluate_deferred(ip).is_some());
+                        false
+                    });
+                }
+                if finished >= deadline {
+                    break 'load;
+                }
+            }
+        }
+    }
+    totals
+}
+
+/// Sursa cu indexul dat: 10.0.0.1, 10.0.0.2, ...
+fn source_ip(index: u32) -> IpAddr {
+    IpAddr::V4(Ipv4Addr::from(0x0A00_0001 + index))
+}
+
+fn target_ip(index: u32) -> IpAddr {
+    IpAddr::V4(Ipv4Addr::new(192, 168, 100, 1 + index as u8))
+}
+
+// ---------------------------------------------------------------------------
+// xorshift64 (Marsaglia): suficient pentru porturi aleatoare, determinist
+// per worker (aceeași încărcare la fiecare rulare)
+// ---------------------------------------------------------------------------
+struct XorShift64(u64);
+
+impl XorShift64 {
+    fn new(seed: u64) -> Self {
+        XorShift64(seed.max(1))
+    }
+
+    fn next(&mut self) -> u64 {
+        let mut x = self.0;
+        x ^= x << 13;
+        x ^= x >> 7;
+        x ^= x << 17;
+        self.0 = x;
+        x
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Histograma latențelor, în nanosecunde: valorile sub 64 ns au câte un
+// bucket; peste, fiecare putere a lui 2 e împărțită în 32 de bucket-uri
+// egale (eroare relativă sub 3.2%). ~2000 de contoare, oricâte evenimente.
+// ---------------------------------------------------------------------------
+const LINEAR_BUCKETS: usize = 64;
+const SUB_BUCKET_BITS: u32 = 5;
+const SUB_BUCKETS: usize = 1 << SUB_BUCKET_BITS;
+const HISTOGRAM_BUCKETS: usize = LINEAR_BUCKETS + (64 - 6) * SUB_BUCKETS;
+
+#[derive(Debug, Clone)]
+pub struct LatencyHistogram {
+    counts: Vec<u64>,
+    total:  u64,
+    max_ns: u64,
+}
+
+impl Default for LatencyHistogram {
+    fn default() -> Self {
+        LatencyHistogram { counts: vec![0; HISTOGRAM_BUCKETS], total: 0, max_ns: 0 }
+    }
+}
+
+impl LatencyHistogram {
+    pub fn record(&mut self, latency: Duration) {
+        let ns = u64::try_from(latency.as_nanos()).unwrap_or(u64::MAX);
+        self.counts[bucket_of(ns)] += 1;
+        self.total += 1;
+        self.max_ns = self.max_ns.max(ns);
+    }
+
+    pub fn merge(&mut self, other: &LatencyHistogram) {
+        for (count, other) in self.counts.iter_mut().zip(&other.counts) {
+            *count += other;
+        }
+        self.total += other.total;
+        self.max_ns = self.max_ns.max(other.max_ns);
+    }
+
+    pub fn count(&self) -> u64 {
+        self.total
+    }
+
+    /// Percentila `p` (0-100): limita inferioară a bucket-ului care o conține
+    pub fn percentile(&self, p: f64) -> Duration {
+        if self.total == 0 {
+            return Duration::ZERO;
+        }
+        let rank = ((p / 100.0 * self.total as f64).ceil() as u64).clamp(1, self.total);
+        let mut seen = 0;
+        for (bucket, count) in self.counts.iter().enumerate() {
+            seen += count;
+            if seen >= rank {
+                return Duration::from_nanos(bucket_floor(bucket).min(self.max_ns));
+            }
+        }
+        self.max()
+    }
+
+    pub fn max(&self) -> Duration {
+        Duration::from_nanos(self.max_ns)
+    }
+}
+
+fn bucket_of(ns: u64) -> usize {
+    if ns < LINEAR_BUCKETS as u64 {
+        return ns as usize;
+    }
+    let exponent = 63 - ns.leading_zeros();
+    let sub = (ns >> (exponent - SUB_BUCKET_BITS)) as usize & (SUB_BUCKETS - 1);
+    LINEAR_BUCKETS + (exponent as usize - 6) * SUB_BUCKETS + sub
+}
+
+fn bucket_floor(bucket: usize) -> u64 {
+    if bucket < LINEAR_BUCKETS {
+        return bucket as u64;
+    }
+    let exponent = ((bucket - LINEAR_BUCKETS) / SUB_BUCKETS + 6) as u32;
+    let sub = ((bucket - LINEAR_BUCKETS) % SUB_BUCKETS) as u64;
+    (SUB_BUCKETS as u64 + sub) << (exponent - SUB_BUCKET_BITS)
+}
+
+// ---------------------------------------------------------------------------
+// Un câmp din /proc/self/status, în kB (ex: "VmRSS:   123456 kB");
+// `None` în afara Linux
+// ---------------------------------------------------------------------------
+fn read_proc_status_kb(field: &str) -> Option<u64> {
+    let status = std::fs::read_to_string("/proc/self/status").ok()?;
+    status
+        .lines()
+        .find_map(|line| line.strip_prefix(field))
+        .and_then(|value| value.split_whitespace().next())
+        .and_then(|kb| kb.parse().ok())
+}
@@ -0,0 +1,85 @@
+// ============================================================
+//  clock.rs - Sursa de timp a stării și a detecției
+// ============================================================
+//
+//  Ferestrele de scan, cooldown-urile și cleanup-ul compară momente
+//  `Instant`. Dacă "acum" vine direct din `Instant::now()`, un test pentru
+//  expirarea unui cooldown de 5 minute trebuie să aștepte 5 minute.
+//
+//  `SharedState` își ia momentul curent dintr-un `Arc<dyn Clock>`:
+//  `SystemClock` în producție (comportament identic cu `Instant::now()`),
+//  `MockClock` în teste - avansat manual, la nanosecundă:
+//
+//    let clock = Arc::new(MockClock::new());
+//    let state = SharedState::new().with_clock(clock.clone());
+//    clock.advance(Duration::from_secs(300));   // cooldown-ul a expirat
+//
+//  Concepte Rust demonstrate:
+//  - Trait object `Arc<dyn Clock>` : implementarea aleasă la runtime
+//  - Supertrait-uri `Send + Sync` : ceasul e partajat între thread-uri
+// ============================================================
+
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::time::{Duration, Instant};
+
+/// Sursa momentului curent (monoton)
+pub trait Clock: Send + Sync {
+    fn now(&self) -> Instant;
+}
+
+/// Ceasul sistemului: `Instant::now()`
+#[derive(Debug, Clone, Copy, Default)]
+pub struct SystemClock;
+
+impl Clock for SystemClock {
+    fn now(&self) -> Instant {
+        Instant::now()
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Ceas de test: stă pe loc până e avansat explicit. Momentul este `origin`
+// plus un offset atomic, deci un `Arc<MockClock>` se avansează din test în
+// timp ce starea îl citește.
+// ---------------------------------------------------------------------------
+#[derive(Debug)]
+pub struct MockClock {
+    origin:    Instant,
+    offset_ns: AtomicU64,
+}
+
+impl MockClock {
+    /// Ceas oprit la momentul creării
+    pub fn new() -> Self {
+        Self::starting_at(Instant::now())
+    }
+
+    pub fn starting_at(origin: Instant) -> Self {
+        MockClock { origin, offset_ns: AtomicU64::new(0) }
+    }
+
+    /// Avansează ceasul cu `by`
+    pub fn advance(&self, by: Duration) {
+        let by = u64::try_from(by.as_nanos()).unwrap_or(u64::MAX);
+        self.offset_ns.fetch_add(by, Ordering::Relaxed);
+    }
+
+    /// Mută ceasul la `at`; un moment dinaintea `origin` sau dinaintea
+    /// momentului curent este ignorat (ceasul nu dă înapoi)
+    pub fn set(&self, at: Instant) {
+        let target = u64::try_from(at.saturating_duration_since(self.origin).as_nanos()).unwrap_or(u64::MAX);
+        self.offset_ns.fetch_max(target, Ordering::Relaxed);
+    }
+}
+
+impl Default for MockClock {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Clock for MockClock {
+    fn now(&self) -> Instant {
+        self.origin + Duration::from_nanos(self.offset_ns.load(Ordering::Relaxed))
+    }
+}
@@ -9,9 +9,19 @@
 //  - anyhow::Result : un Result cu tipul de eroare dinamic (Box<dyn Error>)
 // ============================================================
 
-use anyhow::{Context, Result};
-use serde::Deserialize;
+use anyhow::{bail, ensure, Context, Result};
+use ipnet::IpNet;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
 use std::fs;
+use std::io::ErrorKind;
+use std::time::Duration;
+
+use crate::alert_template;
+use crate::detector::DetectionResult;
+use crate::report::{ReportFormat, ReportSchedule};
+use crate::routing::QuietHours;
+use crate::state::{CooldownPolicy, MAX_ICMP_EVENTS_PER_SOURCE};
 
 // ---------------------------------------------------------------------------
 // Structura principală de configurare
@@ -22,15 +32,89 @@ use std::fs;
 //
 // `Debug` permite afișarea cu {:?} și `Clone` permite copierea structurii.
 // ---------------------------------------------------------------------------
-#[derive(Deserialize, Debug, Clone)]
+#[derive(Serialize, Deserialize, Debug, Clone)]
 pub struct Config {
     pub listener:  ListenerConfig,
     pub detection: DetectionConfig,
     pub siem:      SiemConfig,
     pub email:     EmailConfig,
+
+    /// Canal de alertă local prin Unix domain socket (opțional)
+    #[serde(default)]
+    pub uds:       UdsConfig,
+
+    /// Maparea severităților CEF (opțional)
+    #[serde(default)]
+    pub alert:     AlertConfig,
+
+    /// Output-ul de consolă (opțional)
+    #[serde(default)]
+    pub display:   DisplayConfig,
+
+    /// Răspuns activ (opțional): blocarea automată a scanner-elor pe firewall
+    #[serde(default)]
+    pub response:  ResponseConfig,
+
+    /// Endpoint-ul de control pentru teste (opțional, dezactivat implicit)
+    #[serde(default)]
+    pub control:   ControlConfig,
+
+    /// Intrare HTTP pentru log-uri (opțional, dezactivată implicit)
+    #[serde(default)]
+    pub http_ingest: HttpIngestConfig,
+
+    /// `GET /healthz` + watchdog systemd (opțional, dezactivat implicit)
+    #[serde(default)]
+    pub health:    HealthConfig,
+
+    /// Jurnalul evenimentelor normalizate, JSONL cu rotație (opțional)
+    #[serde(default)]
+    pub event_log: EventLogConfig,
+
+    /// Istoricul alertelor trimise, JSONL - sursa rapoartelor (opțional)
+    #[serde(default)]
+    pub alert_log: AlertLogConfig,
+
+    /// Raportul periodic trimis pe email (opțional)
+    #[serde(default)]
+    pub report:    ReportConfig,
+
+    /// Etichetele surselor (`[[labels]]`): rețea CIDR -> tag atașat alertelor
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub labels:    Vec<SourceLabel>,
 }
 
-#[derive(Deserialize, Debug, Clone)]
+// ---------------------------------------------------------------------------
+// Configurația implicită - aceleași valori ca `config.toml` din repo.
+//
+// Stă la baza `rust-ids init-config` (vezi config_template.rs): valorile se
+// serializează de aici, comentariile vin din `config.toml`.
+// ---------------------------------------------------------------------------
+impl Default for Config {
+    fn default() -> Self {
+        let mut detection = DetectionConfig::default();
+        detection.resolve_tiers();
+        Config {
+            listener: ListenerConfig::default(),
+            detection,
+            siem:     SiemConfig::default(),
+            email:    EmailConfig::default(),
+            uds:      UdsConfig::default(),
+            alert:    AlertConfig::default(),
+            display:  DisplayConfig::default(),
+            response: ResponseConfig::default(),
+            control:  ControlConfig::default(),
+            http_ingest: HttpIngestConfig::default(),
+            health:   HealthConfig::default(),
+            event_log: EventLogConfig::default(),
+            alert_log: AlertLogConfig::default(),
+            report:   ReportConfig::default(),
+            labels:   Vec::new(),
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
 pub struct ListenerConfig {
     /// Adresa IP pe care IDS-ul ascultă (ex: "0.0.0.0" pentru toate interfețele)
     pub bind_address: String,
@@ -38,11 +122,134 @@ pub struct ListenerConfig {
     /// Portul UDP pe care sosesc log-urile de firewall
     pub port: u16,
 
-    /// Tipul de parser: "gaia" sau "cef"
+    /// Tipul de parser: "gaia", "cef", "leef", "windows" sau un format
+    /// înregistrat cu `parser::register_parser`
     pub parser: String,
+
+    /// Dimensiunea dorită a buffer-ului de recepție al socket-ului (SO_RCVBUF), în bytes.
+    /// Dacă lipsește, se păstrează valoarea implicită a kernel-ului.
+    #[serde(default)]
+    pub recv_buffer_bytes: Option<usize>,
+
+    /// Cât de des (secunde) se verifică drop-urile UDP raportate de kernel (0 = dezactivat)
+    #[serde(default = "default_drop_check_interval_secs")]
+    pub drop_check_interval_secs: u64,
+
+    /// Portul TCP pentru syslog peste TCP (o linie per mesaj). Lipsă = dezactivat.
+    #[serde(default)]
+    pub tcp_port: Option<u16>,
+
+    /// TCP: conexiunile fără date în acest interval (secunde) sunt închise
+    #[serde(default = "default_read_timeout_secs")]
+    pub read_timeout_secs: u64,
+
+    /// TCP: numărul maxim de conexiuni simultane; peste el se resping
+    #[serde(default = "default_max_connections")]
+    pub max_connections: usize,
+
+    /// Avertizare locală dacă nu sosește niciun pachet atâtea secunde (0 = dezactivat)
+    #[serde(default)]
+    pub input_silence_warn_secs: u64,
+
+    /// Nume de serviciu suplimentare -> port (ex: `service: myapp`),
+    /// pe lângă tabelul încorporat al serviciilor uzuale
+    #[serde(default)]
+    pub services: HashMap<String, u16>,
+
+    /// Separatorul înregistrărilor dintr-un pachet UDP, ca text cu escape-uri
+    /// (`\n`, `\r`, `\t`, `\0`, `\\`): ex. `'\0'` pentru expeditorii cu NUL
+    #[serde(default = "default_record_delimiter")]
+    pub record_delimiter: String,
+
+    /// Lungimea maximă a unei linii (bytes); liniile mai lungi sunt sărite
+    /// înainte de parsare (protecție contra regex-urilor scumpe). 0 = fără limită
+    #[serde(default = "default_max_line_bytes")]
+    pub max_line_bytes: usize,
+
+    /// `record_delimiter` cu escape-urile rezolvate (calculat la încărcare)
+    #[serde(skip)]
+    record_separator: String,
+}
+
+impl Default for ListenerConfig {
+    fn default() -> Self {
+        ListenerConfig {
+            bind_address:             "0.0.0.0".to_string(),
+            port:                     5555,
+            parser:                   "gaia".to_string(),
+            recv_buffer_bytes:        None,
+            drop_check_interval_secs: default_drop_check_interval_secs(),
+            tcp_port:                 None,
+            read_timeout_secs:        default_read_timeout_secs(),
+            max_connections:          default_max_connections(),
+            input_silence_warn_secs:  0,
+            services:                 HashMap::new(),
+            record_delimiter:         default_record_delimiter(),
+            max_line_bytes:           default_max_line_bytes(),
+            record_separator:         "\n".to_string(),
+        }
+    }
+}
+
+impl ListenerConfig {
+    /// Separatorul efectiv al înregistrărilor (ex: "\n", "\0")
+    pub fn record_separator(&self) -> &str {
+        &self.record_separator
+    }
+
+    // -----------------------------------------------------------------------
+    // Rezolvă escape-urile din `record_delimiter`. TOML nu are escape pentru
+    // NUL în afară de `"\u0000"`, deci acceptăm și forma scurtă `'\0'`
+    // (șir literal TOML, backslash-ul ajunge neinterpretat până aici).
+    // -----------------------------------------------------------------------
+    fn resolve_record_separator(&mut self) -> Result<()> {
+        let mut separator = String::new();
+        let mut chars = self.record_delimiter.chars();
+        while let Some(c) = chars.next() {
+            if c != '\\' {
+                separator.push(c);
+                continue;
+            }
+            match chars.next() {
+                Some('n') => separator.push('\n'),
+                Some('r') => separator.push('\r'),
+                Some('t') => separator.push('\t'),
+                Some('0') => separator.push('\0'),
+                Some('\\') => separator.push('\\'),
+                other => bail!(
+                    "[listener] record_delimiter = {:?}: escape necunoscut '\\{}' (acceptate: \\n \\r \\t \\0 \\\\)",
+                    self.record_delimiter,
+                    other.map(String::from).unwrap_or_default()
+                ),
+            }
+        }
+        ensure!(!separator.is_empty(), "[listener] record_delimiter nu poate fi gol");
+        self.record_separator = separator;
+        Ok(())
+    }
+}
+
+fn default_drop_check_interval_secs() -> u64 {
+    30
+}
+
+fn default_read_timeout_secs() -> u64 {
+    300
+}
+
+fn default_max_connections() -> usize {
+    256
+}
+
+fn default_record_delimiter() -> String {
+    "\\n".to_string()
 }
 
-#[derive(Deserialize, Debug, Clone)]
+fn default_max_line_bytes() -> usize {
+    4096
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
 pub struct DetectionConfig {
     /// Fast Scan: câte porturi unice trebuie accesate ca să se declanșeze alerta
     pub fast_scan_ports: usize,
@@ -59,20 +266,473 @@ pub struct DetectionConfig {
     /// Cât de des (în secunde) rulează task-ul de curățare a stării interne
     pub cleanup_interval_secs: u64,
 
-    /// Cooldown în secunde între alerte pentru același IP (previne spam)
+    /// Cooldown în secunde între alerte pentru același IP (previne spam).
+    /// Este valoarea de bază: la fiecare re-alertare crește (vezi mai jos).
     pub alert_cooldown_secs: u64,
+
+    /// Factorul cu care crește cooldown-ul la fiecare re-alertare a aceluiași
+    /// IP (1.0 = cooldown fix)
+    #[serde(default = "default_alert_cooldown_multiplier")]
+    pub alert_cooldown_multiplier: f64,
+
+    /// Plafonul cooldown-ului crescut, în secunde
+    #[serde(default = "default_alert_cooldown_max_secs")]
+    pub alert_cooldown_max_secs: u64,
+
+    /// Un IP fără nicio alertă atâtea secunde după finalul cooldown-ului
+    /// revine la cooldown-ul de bază
+    #[serde(default = "default_alert_cooldown_reset_secs")]
+    pub alert_cooldown_reset_secs: u64,
+
+    /// O alertă cu severitate strict mai mare decât ultima trimisă pentru
+    /// același IP (ex: slow -> fast) trece de cooldown și îl repornește
+    #[serde(default = "default_true")]
+    pub escalate_through_cooldown: bool,
+
+    /// Histerezis: o sursă intrată într-o stare de amenințare rămâne acolo
+    /// până când porturile tier-urilor declanșate scad la `prag * clear_ratio`
+    /// (0, 1]. Alerta pleacă doar la intrare (și la escaladare), nu la
+    /// fiecare ieșire din cooldown a aceleiași activități.
+    #[serde(default = "default_clear_ratio")]
+    pub clear_ratio: f64,
+
+    /// Afișează o notificare "activity ceased" când o sursă revine la Clean
+    #[serde(default)]
+    pub notify_clear: bool,
+
+    /// Perioada de încălzire după pornire (secunde): detecția rulează, dar
+    /// alertele sunt doar afișate ca "SUPPRESSED (warmup)", nu trimise. 0 = fără.
+    #[serde(default)]
+    pub warmup_secs: u64,
+
+    /// O sursă evaluată "curată" nu se re-evaluează mai des de atât (ms);
+    /// un prag depășit între timp este prins cel târziu la final. 0 = fără limită.
+    #[serde(default = "default_evaluation_min_interval_ms")]
+    pub evaluation_min_interval_ms: u64,
+
+    /// Distributed Scan: câte porturi unice (cumulat pe toate sursele) către
+    /// aceeași destinație declanșează alerta. 0 = detector dezactivat.
+    #[serde(default)]
+    pub distributed_scan_ports: usize,
+
+    /// Distributed Scan: numărul minim de surse distincte implicate
+    #[serde(default = "default_distributed_scan_min_sources", alias = "distributed_scan_sources")]
+    pub distributed_scan_min_sources: usize,
+
+    /// Distributed Scan: fereastra de timp în secunde
+    #[serde(default = "default_distributed_scan_window_secs")]
+    pub distributed_scan_window_secs: u64,
+
+    /// Distributed Scan: o sursă participă la cluster doar dacă atinge cel
+    /// mult atâtea porturi. Implicit: cel mai mic prag dintre tier-uri
+    /// (sursele peste el sunt deja prinse de detecția per-IP).
+    #[serde(default)]
+    pub distributed_scan_max_ports_per_source: Option<usize>,
+
+    /// Distributed Scan: la câte secunde rulează pasul de agregare între surse
+    #[serde(default = "default_distributed_scan_interval_secs")]
+    pub distributed_scan_interval_secs: u64,
+
+    /// ICMP Sweep: câte drop-uri ICMP de la aceeași sursă declanșează alerta
+    /// (împreună cu `icmp_sweep_destinations`). 0 = detector dezactivat.
+    #[serde(default)]
+    pub icmp_sweep_events: usize,
+
+    /// ICMP Sweep: numărul minim de destinații distincte atinse
+    #[serde(default = "default_icmp_sweep_destinations")]
+    pub icmp_sweep_destinations: usize,
+
+    /// ICMP Sweep: fereastra de timp în secunde
+    #[serde(default = "default_icmp_sweep_window_secs")]
+    pub icmp_sweep_window_secs: u64,
+
+    /// Acces după scan: cât timp (secunde) după ultima detecție ca scanner
+    /// un `accept` pe un port sondat anterior declanșează alerta.
+    /// 0 = dezactivat; altfel parserele emit și evenimentele accept/allow.
+    #[serde(default)]
+    pub post_scan_access_window_secs: u64,
+
+    /// Recidiviști: cât timp (ore) după ultima alertă un IP rămâne ținut
+    /// minte, peste cleanup-ul periodic. 0 = dezactivat.
+    #[serde(default)]
+    pub repeat_offender_window_hours: u64,
+
+    /// Ce se întâmplă când un recidivist revine: notificare imediată
+    /// (IDS007, severitate mică) sau praguri reduse
+    #[serde(default)]
+    pub repeat_offender_action: RepeatOffenderAction,
+
+    /// Factorul (0, 1] aplicat pragurilor tier-urilor pentru un recidivist
+    /// (doar cu `repeat_offender_action = "lower_thresholds"`)
+    #[serde(default = "default_repeat_offender_threshold_factor")]
+    pub repeat_offender_threshold_factor: f64,
+
+    /// Porturi destinație excluse din numărătoarea porturilor unice (ex:
+    /// porturile efemere ale load balancer-elor). Evenimentele rămân în
+    /// istoric (context, ținte, acces-după-scan), dar nu declanșează tier-uri.
+    #[serde(default)]
+    pub ignore_ports: Vec<u16>,
+
+    /// Doar porturile din acest interval (inclusiv) contează la tier-uri;
+    /// lipsă = toate porturile
+    #[serde(default)]
+    pub port_range: Option<PortRange>,
+
+    /// Tier-urile de detecție (`[[detection.tier]]`), ordonate după fereastră.
+    ///
+    /// După `Config::load`, lista conține ÎNTOTDEAUNA și tier-urile "fast" și
+    /// "slow" derivate din câmpurile `fast_scan_*` / `slow_scan_*` (dacă nu
+    /// au fost redefinite explicit cu același nume). Nu se serializează:
+    /// tier-urile derivate ar deveni explicite la următoarea încărcare.
+    #[serde(default, rename = "tier", skip_serializing)]
+    pub tiers: Vec<ScanTier>,
+}
+
+impl Default for DetectionConfig {
+    fn default() -> Self {
+        DetectionConfig {
+            fast_scan_ports:                       15,
+            fast_scan_window_secs:                 10,
+            slow_scan_ports:                       30,
+            slow_scan_window_mins:                 60,
+            cleanup_interval_secs:                 300,
+            alert_cooldown_secs:                   60,
+            alert_cooldown_multiplier:             default_alert_cooldown_multiplier(),
+            alert_cooldown_max_secs:               default_alert_cooldown_max_secs(),
+            alert_cooldown_reset_secs:             default_alert_cooldown_reset_secs(),
+            escalate_through_cooldown:             true,
+            clear_ratio:                           default_clear_ratio(),
+            notify_clear:                          false,
+            warmup_secs:                           0,
+            evaluation_min_interval_ms:            default_evaluation_min_interval_ms(),
+            distributed_scan_ports:                0,
+            distributed_scan_min_sources:          default_distributed_scan_min_sources(),
+            distributed_scan_window_secs:          default_distributed_scan_window_secs(),
+            distributed_scan_max_ports_per_source: None,
+            distributed_scan_interval_secs:        default_distributed_scan_interval_secs(),
+            icmp_sweep_events:                     0,
+            icmp_sweep_destinations:               default_icmp_sweep_destinations(),
+            icmp_sweep_window_secs:                default_icmp_sweep_window_secs(),
+            post_scan_access_window_secs:          0,
+            repeat_offender_window_hours:          0,
+            repeat_offender_action:                RepeatOffenderAction::default(),
+            repeat_offender_threshold_factor:      default_repeat_offender_threshold_factor(),
+            ignore_ports:                          Vec::new(),
+            port_range:                            None,
+            tiers:                                 Vec::new(),
+        }
+    }
+}
+
+/// Intervalul de porturi numărate de detecție (`port_range = { min, max }`)
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq)]
+pub struct PortRange {
+    #[serde(default)]
+    pub min: u16,
+
+    #[serde(default = "default_port_range_max")]
+    pub max: u16,
+}
+
+impl PortRange {
+    pub fn contains(&self, port: u16) -> bool {
+        (self.min..=self.max).contains(&port)
+    }
+}
+
+fn default_port_range_max() -> u16 {
+    u16::MAX
+}
+
+// ---------------------------------------------------------------------------
+// Un tier de detecție: "mai mult de `ports` porturi unice în `window_secs`"
+//
+// Fast Scan și Slow Scan sunt doar două tier-uri predefinite; operatorul
+// poate adăuga oricâte (ex: un "medium" de 60s între cele două).
+// ---------------------------------------------------------------------------
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct ScanTier {
+    /// Numele tier-ului (apare în alerte: "medium" -> MEDIUM_SCAN)
+    pub name: String,
+
+    /// Pragul: alertă la strict mai mult de `ports` porturi unice
+    pub ports: usize,
+
+    /// Fereastra de timp în secunde
+    pub window_secs: u64,
+
+    /// Severitatea CEF (0-10) a alertei generate de acest tier
+    #[serde(default = "default_tier_severity")]
+    pub severity: u8,
+
+    /// Signature ID-ul CEF; implicit derivat din nume (ex: "IDS-MEDIUM")
+    #[serde(default)]
+    pub sig_id: Option<String>,
+}
+
+/// O etichetă de sursă (`[[labels]]`): IP-urile din `network` primesc `tag`
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct SourceLabel {
+    /// Rețeaua CIDR (ex: "203.0.113.0/24", "2001:db8::/32", "10.0.0.5/32")
+    pub network: IpNet,
+
+    /// Tag-ul atașat alertelor (ex: "partner-acme", "vpn-pool")
+    pub tag:     String,
+}
+
+fn default_alert_cooldown_multiplier() -> f64 {
+    2.0
+}
+
+fn default_alert_cooldown_max_secs() -> u64 {
+    3600
+}
+
+fn default_alert_cooldown_reset_secs() -> u64 {
+    3600
+}
+
+fn default_clear_ratio() -> f64 {
+    0.5
+}
+
+fn default_evaluation_min_interval_ms() -> u64 {
+    100
+}
+
+fn default_distributed_scan_min_sources() -> usize {
+    5
+}
+
+fn default_distributed_scan_window_secs() -> u64 {
+    300
+}
+
+fn default_distributed_scan_interval_secs() -> u64 {
+    10
+}
+
+fn default_icmp_sweep_destinations() -> usize {
+    10
+}
+
+fn default_icmp_sweep_window_secs() -> u64 {
+    60
+}
+
+fn default_repeat_offender_threshold_factor() -> f64 {
+    0.5
+}
+
+/// Reacția la revenirea unui recidivist (`[detection] repeat_offender_action`)
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, Default, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum RepeatOffenderAction {
+    /// Primul drop după o perioadă de liniște trimite imediat "known offender returned"
+    #[default]
+    Notify,
+    /// Pragurile tier-urilor se înmulțesc cu `repeat_offender_threshold_factor`
+    LowerThresholds,
+}
+
+fn default_tier_severity() -> u8 {
+    7
+}
+
+impl ScanTier {
+    /// Signature ID-ul CEF efectiv al tier-ului
+    pub fn sig_id(&self) -> String {
+        self.sig_id
+            .clone()
+            .unwrap_or_else(|| format!("IDS-{}", self.name.to_uppercase()))
+    }
+}
+
+impl DetectionConfig {
+    // -----------------------------------------------------------------------
+    // "Desugaring": câmpurile clasice fast_scan_* / slow_scan_* devin două
+    // tier-uri obișnuite, astfel încât detectorul lucrează cu o singură listă.
+    //
+    // Un tier explicit numit "fast" sau "slow" are prioritate față de cel derivat.
+    // -----------------------------------------------------------------------
+    fn resolve_tiers(&mut self) {
+        let builtin = [
+            ScanTier {
+                name:        "fast".to_string(),
+                ports:       self.fast_scan_ports,
+                window_secs: self.fast_scan_window_secs,
+                severity:    8,
+                sig_id:      Some("IDS001".to_string()),
+            },
+            ScanTier {
+                name:        "slow".to_string(),
+                ports:       self.slow_scan_ports,
+                window_secs: self.slow_scan_window_mins * 60,
+                severity:    6,
+                sig_id:      Some("IDS002".to_string()),
+            },
+        ];
+
+        for tier in builtin {
+            if !self.tiers.iter().any(|t| t.name.eq_ignore_ascii_case(&tier.name)) {
+                self.tiers.push(tier);
+            }
+        }
+
+        // Ordonăm după fereastră (cea mai scurtă primă) pentru afișare consistentă
+        self.tiers.sort_by_key(|t| t.window_secs);
+    }
+
+    /// Politica de cooldown a alertelor per IP, din câmpurile `alert_cooldown_*`
+    pub fn cooldown_policy(&self) -> CooldownPolicy {
+        CooldownPolicy {
+            base:        Duration::from_secs(self.alert_cooldown_secs),
+            multiplier:  self.alert_cooldown_multiplier,
+            max:         Duration::from_secs(self.alert_cooldown_max_secs),
+            reset_after: Duration::from_secs(self.alert_cooldown_reset_secs),
+        }
+    }
+
+    /// Cea mai lungă fereastră de detecție (tier-uri + distributed + ICMP), în secunde
+    pub fn max_window_secs(&self) -> u64 {
+        let mut max = self.tiers.iter().map(|t| t.window_secs).max().unwrap_or(0);
+        if self.distributed_enabled() {
+            max = max.max(self.distributed_scan_window_secs);
+        }
+        if self.icmp_sweep_enabled() {
+            max = max.max(self.icmp_sweep_window_secs);
+        }
+        max
+    }
+
+    /// Detectorul de ICMP sweep este activ doar dacă are un prag setat
+    pub fn icmp_sweep_enabled(&self) -> bool {
+        self.icmp_sweep_events > 0
+    }
+
+    /// Corelarea accept-după-scan este activă doar dacă are o fereastră setată
+    pub fn post_scan_access_enabled(&self) -> bool {
+        self.post_scan_access_window_secs > 0
+    }
+
+    /// Memoria recidiviștilor este activă doar dacă are o fereastră setată
+    pub fn repeat_offender_enabled(&self) -> bool {
+        self.repeat_offender_window_hours > 0
+    }
+
+    /// Cât timp după ultima alertă un IP este considerat recidivist
+    pub fn repeat_offender_window(&self) -> Duration {
+        Duration::from_secs(self.repeat_offender_window_hours.saturating_mul(3600))
+    }
+
+    /// Portul contează la numărătoarea porturilor unice? (`ignore_ports`,
+    /// `port_range`)
+    pub fn counts_port(&self, port: u16) -> bool {
+        self.port_range.is_none_or(|range| range.contains(port)) && !self.ignore_ports.contains(&port)
+    }
+
+    /// Detectorul de scanări distribuite este activ doar dacă are un prag setat
+    pub fn distributed_enabled(&self) -> bool {
+        self.distributed_scan_ports > 0
+    }
+
+    /// Pragul de porturi per sursă pentru apartenența la un cluster distribuit
+    pub fn distributed_max_ports_per_source(&self) -> usize {
+        self.distributed_scan_max_ports_per_source
+            .unwrap_or_else(|| self.tiers.iter().map(|t| t.ports).min().unwrap_or(usize::MAX))
+    }
 }
 
-#[derive(Deserialize, Debug, Clone)]
+#[derive(Serialize, Deserialize, Debug, Clone)]
 pub struct SiemConfig {
     /// IP-ul sau hostname-ul SIEM-ului ArcSight
     pub address: String,
 
     /// Portul UDP al SIEM-ului
     pub port: u16,
+
+    /// Dacă true, alertele identice (sig ID + tip scan) dintr-o fereastră
+    /// se pliază într-un singur mesaj cu câmpul `cnt=`
+    #[serde(default)]
+    pub aggregate: bool,
+
+    /// Durata ferestrei de agregare, în secunde
+    #[serde(default = "default_aggregate_window_secs")]
+    pub aggregate_window_secs: u64,
+
+    /// Trimite la SIEM și mesaje "IDS self-health" (ex: exporter cu format greșit)
+    #[serde(default)]
+    pub self_health: bool,
+
+    /// Familia de adrese preferată când `address` este un hostname
+    #[serde(default)]
+    pub ip_preference: IpPreference,
+
+    /// La câte secunde se re-rezolvă hostname-ul SIEM (failover DNS)
+    #[serde(default = "default_dns_refresh_secs")]
+    pub dns_refresh_secs: u64,
+
+    /// La câte secunde se trimite heartbeat-ul IDS000 (0 = dezactivat)
+    #[serde(default)]
+    pub heartbeat_interval_secs: u64,
+
+    /// Formatul mesajelor: "cef" (ArcSight, implicit) sau "leef" (QRadar)
+    #[serde(default)]
+    pub format: SiemFormat,
+
+    /// Delimitatorul atributelor LEEF; TAB (implicit) nu se declară în header
+    #[serde(default = "default_leef_delimiter")]
+    pub leef_delimiter: char,
+}
+
+impl Default for SiemConfig {
+    fn default() -> Self {
+        SiemConfig {
+            address:                 "127.0.0.1".to_string(),
+            port:                    514,
+            aggregate:               false,
+            aggregate_window_secs:   default_aggregate_window_secs(),
+            self_health:             false,
+            ip_preference:           IpPreference::default(),
+            dns_refresh_secs:        default_dns_refresh_secs(),
+            heartbeat_interval_secs: 0,
+            format:                  SiemFormat::default(),
+            leef_delimiter:          default_leef_delimiter(),
+        }
+    }
+}
+
+/// Formatul mesajelor trimise la SIEM (și pe UDS)
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, Default, PartialEq)]
+#[serde(rename_all = "lowercase")]
+pub enum SiemFormat {
+    #[default]
+    Cef,
+    Leef,
+}
+
+fn default_leef_delimiter() -> char {
+    '\t'
+}
+
+fn default_aggregate_window_secs() -> u64 {
+    60
+}
+
+fn default_dns_refresh_secs() -> u64 {
+    60
+}
+
+/// Familia de adrese aleasă dintre rezultatele DNS
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, Default, PartialEq)]
+#[serde(rename_all = "lowercase")]
+pub enum IpPreference {
+    #[default]
+    Ipv4,
+    Ipv6,
 }
 
-#[derive(Deserialize, Debug, Clone)]
+#[derive(Serialize, Deserialize, Debug, Clone)]
 pub struct EmailConfig {
     pub smtp_server: String,
     pub smtp_port:   u16,
@@ -82,6 +742,474 @@ pub struct EmailConfig {
     pub to:          String,
     /// Dacă false, email-urile NU se trimit (util pentru development/testare)
     pub enabled:     bool,
+
+    /// Severitatea minimă (după mapare) pentru email; sub ea alerta
+    /// ajunge doar în SIEM / UDS
+    #[serde(default)]
+    pub min_severity: u8,
+
+    /// Intervalul orar local "HH:MM-HH:MM" în care email-ul cere
+    /// `quiet_hours_min_severity` (ex: "22:00-07:00")
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub quiet_hours: Option<QuietHours>,
+
+    #[serde(default = "default_quiet_hours_min_severity")]
+    pub quiet_hours_min_severity: u8,
+}
+
+/// Valori-exemplu: email-ul rămâne dezactivat până la completarea lor
+impl Default for EmailConfig {
+    fn default() -> Self {
+        EmailConfig {
+            smtp_server:              "smtp.company.internal".to_string(),
+            smtp_port:                587,
+            username:                 "ids-noreply@company.com".to_string(),
+            password:                 "CHANGE_ME_IN_PRODUCTION".to_string(),
+            from:                     "IDS Alerts <ids-noreply@company.com>".to_string(),
+            to:                       "security-team@company.com".to_string(),
+            enabled:                  false,
+            min_severity:             0,
+            quiet_hours:              None,
+            quiet_hours_min_severity: default_quiet_hours_min_severity(),
+        }
+    }
+}
+
+fn default_quiet_hours_min_severity() -> u8 {
+    9
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct UdsConfig {
+    /// Activează trimiterea alertelor (în formatul din `[siem] format`) pe socket-ul Unix
+    #[serde(default)]
+    pub enabled: bool,
+
+    /// Calea socket-ului datagram al peer-ului (creat de agentul local)
+    #[serde(default = "default_uds_path")]
+    pub path: String,
+}
+
+impl Default for UdsConfig {
+    fn default() -> Self {
+        UdsConfig {
+            enabled: false,
+            path:    default_uds_path(),
+        }
+    }
+}
+
+fn default_uds_path() -> String {
+    "/run/rust-ids/alerts.sock".to_string()
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct ControlConfig {
+    /// Activează `POST /inject` (NU în producție: declanșează alerte reale)
+    #[serde(default)]
+    pub enabled: bool,
+
+    /// Adresa de bind - implicit doar local
+    #[serde(default = "default_control_bind")]
+    pub bind_address: String,
+
+    #[serde(default = "default_control_port")]
+    pub port: u16,
+
+    /// Token cerut în antetul `Authorization: Bearer <token>` (opțional)
+    #[serde(default)]
+    pub token: Option<String>,
+}
+
+impl Default for ControlConfig {
+    fn default() -> Self {
+        ControlConfig {
+            enabled:      false,
+            bind_address: default_control_bind(),
+            port:         default_control_port(),
+            token:        None,
+        }
+    }
+}
+
+fn default_control_bind() -> String {
+    "127.0.0.1".to_string()
+}
+
+fn default_control_port() -> u16 {
+    5580
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct HttpIngestConfig {
+    /// Activează `POST /ingest` (agenți care pot trimite doar prin HTTP)
+    #[serde(default)]
+    pub enabled: bool,
+
+    #[serde(default = "default_http_ingest_bind")]
+    pub bind_address: String,
+
+    #[serde(default = "default_http_ingest_port")]
+    pub port: u16,
+
+    /// Token cerut în antetul `Authorization: Bearer <token>` (opțional)
+    #[serde(default)]
+    pub token: Option<String>,
+
+    /// Cereri procesate simultan; peste plafon conexiunile noi sunt închise
+    #[serde(default = "default_http_ingest_max_connections")]
+    pub max_connections: usize,
+}
+
+impl Default for HttpIngestConfig {
+    fn default() -> Self {
+        HttpIngestConfig {
+            enabled:         false,
+            bind_address:    default_http_ingest_bind(),
+            port:            default_http_ingest_port(),
+            token:           None,
+            max_connections: default_http_ingest_max_connections(),
+        }
+    }
+}
+
+fn default_http_ingest_bind() -> String {
+    "0.0.0.0".to_string()
+}
+
+fn default_http_ingest_port() -> u16 {
+    5581
+}
+
+fn default_http_ingest_max_connections() -> usize {
+    64
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct HealthConfig {
+    /// Activează `GET /healthz` (200 = bucla de recepție procesează, 503 = blocată)
+    #[serde(default)]
+    pub enabled: bool,
+
+    #[serde(default = "default_health_bind")]
+    pub bind_address: String,
+
+    #[serde(default = "default_health_port")]
+    pub port: u16,
+
+    /// După câte secunde fără activitate a buclei de recepție IDS-ul e
+    /// raportat nesănătos (pachete SAU tic-uri de inactivitate)
+    #[serde(default = "default_liveness_timeout_secs")]
+    pub liveness_timeout_secs: u64,
+
+    /// Notifică systemd (`READY=1`, `WATCHDOG=1`) dacă rulează sub un
+    /// serviciu cu `WatchdogSec=`; fără `NOTIFY_SOCKET` nu face nimic
+    #[serde(default)]
+    pub systemd_watchdog: bool,
+}
+
+impl Default for HealthConfig {
+    fn default() -> Self {
+        HealthConfig {
+            enabled:               false,
+            bind_address:          default_health_bind(),
+            port:                  default_health_port(),
+            liveness_timeout_secs: default_liveness_timeout_secs(),
+            systemd_watchdog:      false,
+        }
+    }
+}
+
+fn default_health_bind() -> String {
+    "127.0.0.1".to_string()
+}
+
+fn default_health_port() -> u16 {
+    5582
+}
+
+fn default_liveness_timeout_secs() -> u64 {
+    30
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct EventLogConfig {
+    /// Scrie fiecare `LogEntry` parsat ca linie JSON (audit al fluxului normalizat)
+    #[serde(default)]
+    pub enabled: bool,
+
+    #[serde(default = "default_event_log_path")]
+    pub path: String,
+
+    /// Peste această dimensiune fișierul se rotește (sufix cu data). 0 = fără rotație
+    #[serde(default = "default_event_log_rotate_size_bytes")]
+    pub rotate_size_bytes: u64,
+}
+
+impl Default for EventLogConfig {
+    fn default() -> Self {
+        EventLogConfig {
+            enabled:           false,
+            path:              default_event_log_path(),
+            rotate_size_bytes: default_event_log_rotate_size_bytes(),
+        }
+    }
+}
+
+fn default_event_log_path() -> String {
+    "events.jsonl".to_string()
+}
+
+fn default_event_log_rotate_size_bytes() -> u64 {
+    100 * 1024 * 1024
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct AlertLogConfig {
+    /// Scrie fiecare alertă trimisă ca linie JSON (citită de `rust-ids report`)
+    #[serde(default)]
+    pub enabled: bool,
+
+    #[serde(default = "default_alert_log_path")]
+    pub path: String,
+
+    /// Peste această dimensiune fișierul se rotește (sufix cu data). 0 = fără rotație
+    #[serde(default = "default_event_log_rotate_size_bytes")]
+    pub rotate_size_bytes: u64,
+}
+
+impl Default for AlertLogConfig {
+    fn default() -> Self {
+        AlertLogConfig {
+            enabled:           false,
+            path:              default_alert_log_path(),
+            rotate_size_bytes: default_event_log_rotate_size_bytes(),
+        }
+    }
+}
+
+fn default_alert_log_path() -> String {
+    "alerts.jsonl".to_string()
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Default)]
+pub struct ReportConfig {
+    /// "daily@HH:MM" sau "weekly@mon@HH:MM" (ora locală); lipsă = fără raport programat
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub schedule: Option<ReportSchedule>,
+
+    /// Destinatarii raportului (separați de `[email] to`, care primește alertele)
+    #[serde(default)]
+    pub to: Vec<String>,
+
+    /// Formatul email-ului: "html" sau "text"
+    #[serde(default)]
+    pub format: ReportFormat,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct DisplayConfig {
+    /// Afișarea liniilor [DROP]: "all", "aggregated" sau "off"
+    #[serde(default)]
+    pub drop_events: DropEventsMode,
+
+    /// Fereastra de agregare a liniilor [DROP], în secunde
+    #[serde(default = "default_drop_aggregate_window_secs")]
+    pub drop_aggregate_window_secs: u64,
+
+    /// Formatul log-urilor: "console" (colorat) sau "json" (structurat)
+    #[serde(default)]
+    pub log_format: LogFormat,
+}
+
+impl Default for DisplayConfig {
+    fn default() -> Self {
+        DisplayConfig {
+            drop_events:                DropEventsMode::default(),
+            drop_aggregate_window_secs: default_drop_aggregate_window_secs(),
+            log_format:                 LogFormat::default(),
+        }
+    }
+}
+
+fn default_drop_aggregate_window_secs() -> u64 {
+    2
+}
+
+/// Cum se afișează evenimentele de drop în consolă
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, Default, PartialEq)]
+#[serde(rename_all = "lowercase")]
+pub enum DropEventsMode {
+    /// O linie per eveniment
+    All,
+    /// O linie per sursă per fereastră
+    #[default]
+    Aggregated,
+    /// Fără linii [DROP]
+    Off,
+}
+
+/// Formatul output-ului: consolă pentru operator sau JSON pentru Loki/Elastic
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, Default, PartialEq)]
+#[serde(rename_all = "lowercase")]
+pub enum LogFormat {
+    /// Output colorat (display.rs), evenimentele structurate sunt ascunse
+    #[default]
+    Console,
+    /// Un obiect JSON per linie: evenimentele tracing cu câmpurile lor
+    Json,
+}
+
+// ---------------------------------------------------------------------------
+// Maparea severităților CEF pe taxonomia SOC-ului
+//
+// `severity_fast` / `severity_slow` înlocuiesc severitățile tier-urilor
+// predefinite, `severity_both` pe cea a alertelor multi-tier. Clamp-ul
+// global [severity_min, severity_max] se aplică ULTIMUL, pe orice alertă.
+// ---------------------------------------------------------------------------
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct AlertConfig {
+    /// Severitatea alertelor Fast Scan (implicit 8)
+    #[serde(default)]
+    pub severity_fast: Option<u8>,
+
+    /// Severitatea alertelor Slow Scan (implicit 6)
+    #[serde(default)]
+    pub severity_slow: Option<u8>,
+
+    /// Severitatea alertelor multi-tier (implicit: maximul tier-urilor + 1)
+    #[serde(default)]
+    pub severity_both: Option<u8>,
+
+    /// Pragul inferior al severității trimise
+    #[serde(default)]
+    pub severity_min: u8,
+
+    /// Plafonul severității trimise (ex: coborât temporar în perioade zgomotoase)
+    #[serde(default = "default_severity_max")]
+    pub severity_max: u8,
+
+    /// Câte linii brute recente (toate sursele) se păstrează în memorie
+    /// pentru contextul alertelor; 0 = dezactivat
+    #[serde(default = "default_context_buffer_size")]
+    pub context_buffer_size: usize,
+
+    /// Câte dintre acestea (ale IP-ului alertat) intră în email-ul de alertă
+    #[serde(default = "default_context_lines")]
+    pub context_lines: usize,
+
+    /// Corpul email-ului de alertă (implicit: formatul încorporat)
+    #[serde(default)]
+    pub email_template: Option<String>,
+
+    /// Payload-ul mesajului SIEM, după header-ul Syslog (implicit: CEF/LEEF)
+    #[serde(default)]
+    pub siem_template: Option<String>,
+}
+
+impl Default for AlertConfig {
+    fn default() -> Self {
+        AlertConfig {
+            severity_fast: None,
+            severity_slow: None,
+            severity_both: None,
+            severity_min:  0,
+            severity_max:  default_severity_max(),
+            context_buffer_size: default_context_buffer_size(),
+            context_lines: default_context_lines(),
+            email_template: None,
+            siem_template:  None,
+        }
+    }
+}
+
+impl AlertConfig {
+    /// Aplică clamp-ul global unei severități
+    pub fn clamp_severity(&self, severity: u8) -> u8 {
+        severity.clamp(self.severity_min, self.severity_max)
+    }
+
+    // -----------------------------------------------------------------------
+    // Severitatea CEF trimisă pentru o alertă: `severity_both` pentru
+    // alertele multi-tier (dacă e setat), apoi clamp-ul global.
+    // Severitățile fast/slow sunt deja aplicate pe tier-uri la încărcare.
+    // -----------------------------------------------------------------------
+    pub fn alert_severity(&self, result: &DetectionResult) -> u8 {
+        let severity = match (result, self.severity_both) {
+            (DetectionResult::MultiScan(_), Some(both)) => both,
+            _ => result.severity(),
+        };
+        self.clamp_severity(severity)
+    }
+}
+
+fn default_severity_max() -> u8 {
+    10
+}
+
+fn default_context_buffer_size() -> usize {
+    500
+}
+
+fn default_context_lines() -> usize {
+    20
+}
+
+/// Severitatea maximă pe scala CEF
+const CEF_MAX_SEVERITY: u8 = 10;
+
+#[derive(Serialize, Deserialize, Debug, Clone, Default)]
+pub struct ResponseConfig {
+    /// Blocare SAM prin Check Point Management API (secțiunea `[response.checkpoint]`)
+    pub checkpoint: Option<CheckpointConfig>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct CheckpointConfig {
+    /// URL-ul serverului de management (ex: "https://mgmt.company.internal")
+    pub management_url: String,
+
+    /// Cheie API - alternativă la username/password
+    #[serde(default)]
+    pub api_key: Option<String>,
+
+    #[serde(default)]
+    pub username: Option<String>,
+
+    #[serde(default)]
+    pub password: Option<String>,
+
+    /// Policy package pe care se aplică regula (opțional)
+    #[serde(default)]
+    pub package: Option<String>,
+
+    /// Gateway-urile țintă; listă goală = toate gateway-urile gestionate
+    #[serde(default)]
+    pub targets: Vec<String>,
+
+    /// Durata blocării în secunde (după care regula SAM expiră automat)
+    #[serde(default = "default_block_duration_secs")]
+    pub block_duration_secs: u64,
+
+    /// Severitatea minimă a alertei (0-10) pentru care se blochează IP-ul
+    #[serde(default)]
+    pub min_severity: u8,
+
+    /// Dacă true, doar se loghează cererea care ar fi fost trimisă
+    #[serde(default)]
+    pub dry_run: bool,
+
+    /// Verificarea certificatului TLS (dezactivați doar pentru certificate self-signed)
+    #[serde(default = "default_true")]
+    pub verify_tls: bool,
+}
+
+fn default_block_duration_secs() -> u64 {
+    3600
+}
+
+fn default_true() -> bool {
+    true
 }
 
 impl Config {
@@ -99,13 +1227,293 @@ impl Config {
         let content = fs::read_to_string(path)
             .with_context(|| format!("Nu s-a putut citi fișierul de configurare: '{}'", path))?;
 
+        Self::from_toml_str(&content).with_context(|| format!("Configurație invalidă în '{}'", path))
+    }
+
+    // ---------------------------------------------------------------------------
+    // Ca `load`, dar un fișier care NU EXISTĂ nu este o eroare: `Ok(None)`,
+    // iar apelantul decide (ex: valorile implicite, cu un avertisment).
+    //
+    // Doar `ErrorKind::NotFound` este tolerat: un fișier existent dar
+    // ilizibil (permisiuni) sau un TOML invalid rămân erori - o greșeală de
+    // tipar în producție nu trebuie să pornească IDS-ul cu alte praguri.
+    // ---------------------------------------------------------------------------
+    pub fn load_optional(path: &str) -> Result<Option<Self>> {
+        match fs::read_to_string(path) {
+            Ok(content) => Self::from_toml_str(&content)
+                .with_context(|| format!("Configurație invalidă în '{}'", path))
+                .map(Some),
+            Err(e) if e.kind() == ErrorKind::NotFound => Ok(None),
+            Err(e) => Err(e).with_context(|| format!("Nu s-a putut citi fișierul de configurare: '{}'", path)),
+        }
+    }
+
+    /// Ca `load`, dintr-un text TOML deja citit (ex: configurație încorporată)
+    pub fn from_toml_str(content: &str) -> Result<Self> {
         // `toml::from_str` returnează Result<Config, toml::de::Error>
-        let config: Config = toml::from_str(&content)
-            .with_context(|| format!("Eroare la parsarea TOML din '{}'", path))?;
+        let mut config: Config = toml::from_str(content).context("Eroare la parsarea TOML")?;
+
+        config.detection.resolve_tiers();
+        config.listener.resolve_record_separator()?;
+        config.validate_tcp_limits()?;
+        config.apply_severity_overrides();
+        config.validate_severities()?;
+        config.validate_cooldown()?;
+        config.validate_clear_ratio()?;
+        config.validate_repeat_offender()?;
+        config.validate_leef_delimiter()?;
+        config.validate_port_filter()?;
+        config.validate_icmp_sweep()?;
+        config.validate_alert_templates()?;
+        config.validate_labels()?;
+        config.validate_health()?;
+        config.validate_email_routing()?;
+        config.validate_siem()?;
+        config.validate_report()?;
 
         Ok(config)
     }
 
+    /// Severitățile din `[alert]` înlocuiesc pe cele ale tier-urilor fast/slow
+    fn apply_severity_overrides(&mut self) {
+        for tier in &mut self.detection.tiers {
+            let override_severity = match tier.name.to_lowercase().as_str() {
+                "fast" => self.alert.severity_fast,
+                "slow" => self.alert.severity_slow,
+                _ => None,
+            };
+            if let Some(severity) = override_severity {
+                tier.severity = severity;
+            }
+        }
+    }
+
+    // -----------------------------------------------------------------------
+    // Toate severitățile trebuie să fie pe scala CEF 0-10, iar clamp-ul
+    // trebuie să fie un interval valid - altfel SIEM-ul ar primi valori
+    // pe care regulile de corelare nu le recunosc.
+    // -----------------------------------------------------------------------
+    fn validate_severities(&self) -> Result<()> {
+        let alert = &self.alert;
+        let named = [
+            ("[alert] severity_fast", alert.severity_fast),
+            ("[alert] severity_slow", alert.severity_slow),
+            ("[alert] severity_both", alert.severity_both),
+            ("[alert] severity_min", Some(alert.severity_min)),
+            ("[alert] severity_max", Some(alert.severity_max)),
+        ];
+        for (name, value) in named {
+            if let Some(value) = value {
+                ensure!(value <= CEF_MAX_SEVERITY, "{} = {} în afara intervalului 0-10", name, value);
+            }
+        }
+        for tier in &self.detection.tiers {
+            ensure!(
+                tier.severity <= CEF_MAX_SEVERITY,
+                "Severitatea tier-ului '{}' = {} în afara intervalului 0-10",
+                tier.name,
+                tier.severity
+            );
+        }
+        ensure!(
+            alert.severity_min <= alert.severity_max,
+            "[alert] severity_min ({}) este mai mare decât severity_max ({})",
+            alert.severity_min,
+            alert.severity_max
+        );
+        Ok(())
+    }
+
+    /// Un cooldown care ar scădea la re-alertare sau un plafon sub bază
+    /// ar anula escaladarea (sau ar inversa-o)
+    fn validate_cooldown(&self) -> Result<()> {
+        let detection = &self.detection;
+        ensure!(
+            detection.alert_cooldown_multiplier.is_finite() && detection.alert_cooldown_multiplier >= 1.0,
+            "[detection] alert_cooldown_multiplier = {} trebuie să fie cel puțin 1.0",
+            detection.alert_cooldown_multiplier
+        );
+        ensure!(
+            detection.alert_cooldown_max_secs >= detection.alert_cooldown_secs,
+            "[detection] alert_cooldown_max_secs ({}) este mai mic decât alert_cooldown_secs ({})",
+            detection.alert_cooldown_max_secs,
+            detection.alert_cooldown_secs
+        );
+        Ok(())
+    }
+
+    /// Un raport peste 1 ar elibera sursa înainte de a scădea sub prag, iar
+    /// 0 nu ar elibera-o niciodată cât timp are istoric
+    fn validate_clear_ratio(&self) -> Result<()> {
+        let ratio = self.detection.clear_ratio;
+        ensure!(
+            ratio.is_finite() && ratio > 0.0 && ratio <= 1.0,
+            "[detection] clear_ratio = {} trebuie să fie în intervalul (0, 1]",
+            ratio
+        );
+        Ok(())
+    }
+
+    /// Un factor peste 1 ar ridica pragurile recidiviștilor, iar 0 ar
+    /// alerta la primul port
+    fn validate_repeat_offender(&self) -> Result<()> {
+        let factor = self.detection.repeat_offender_threshold_factor;
+        ensure!(
+            factor.is_finite() && factor > 0.0 && factor <= 1.0,
+            "[detection] repeat_offender_threshold_factor = {} trebuie să fie în intervalul (0, 1]",
+            factor
+        );
+        Ok(())
+    }
+
+    /// Un tag gol ar produce `cs6=` fără valoare; virgula este separatorul
+    /// tag-urilor concatenate
+    fn validate_labels(&self) -> Result<()> {
+        for label in &self.labels {
+            ensure!(
+                !label.tag.trim().is_empty() && !label.tag.contains(','),
+                "[[labels]] network = \"{}\": tag-ul \"{}\" trebuie să fie nevid și fără virgule",
+                label.network,
+                label.tag
+            );
+        }
+        Ok(())
+    }
+
+    fn validate_email_routing(&self) -> Result<()> {
+        for (name, value) in [
+            ("min_severity", self.email.min_severity),
+            ("quiet_hours_min_severity", self.email.quiet_hours_min_severity),
+        ] {
+            ensure!(value <= 10, "[email] {} = {} invalid: severitatea este 0-10", name, value);
+        }
+        Ok(())
+    }
+
+    /// Zero ar închide orice conexiune TCP imediat, respectiv le-ar respinge pe toate
+    /// (și pe cele HTTP ale `[http_ingest]`)
+    fn validate_tcp_limits(&self) -> Result<()> {
+        ensure!(self.listener.read_timeout_secs > 0, "[listener] read_timeout_secs trebuie să fie > 0");
+        ensure!(self.listener.max_connections > 0, "[listener] max_connections trebuie să fie > 0");
+        ensure!(self.http_ingest.max_connections > 0, "[http_ingest] max_connections trebuie să fie > 0");
+        Ok(())
+    }
+
+    /// Fereastra de agregare devine perioada unui `tokio::time::interval`,
+    /// care nu acceptă zero
+    fn validate_siem(&self) -> Result<()> {
+        ensure!(
+            self.siem.aggregate_window_secs > 0,
+            "[siem] aggregate_window_secs trebuie să fie > 0"
+        );
+        Ok(())
+    }
+
+    /// Raportul programat are nevoie de istoricul alertelor și de destinatari valizi
+    fn validate_report(&self) -> Result<()> {
+        if self.report.schedule.is_none() {
+            return Ok(());
+        }
+        ensure!(
+            self.alert_log.enabled,
+            "[report] schedule cere [alert_log] enabled = true: raportul se construiește din istoricul alertelor"
+        );
+        ensure!(!self.report.to.is_empty(), "[report] schedule cere cel puțin un destinatar în [report] to");
+        for to in &self.report.to {
+            ensure!(
+                to.parse::<lettre::message::Mailbox>().is_ok(),
+                "[report] to: adresa \"{}\" este invalidă",
+                to
+            );
+        }
+        Ok(())
+    }
+
+    fn validate_health(&self) -> Result<()> {
+        ensure!(
+            self.health.liveness_timeout_secs > 0,
+            "[health] liveness_timeout_secs trebuie să fie > 0"
+        );
+        Ok(())
+    }
+
+    /// Șabloanele de alertă pot folosi doar câmpurile din `alert_template::PLACEHOLDERS`
+    fn validate_alert_templates(&self) -> Result<()> {
+        let templates = [
+            ("[alert] email_template", &self.alert.email_template),
+            ("[alert] siem_template", &self.alert.siem_template),
+        ];
+        for (name, template) in templates {
+            if let Some(template) = template {
+                alert_template::validate(template).with_context(|| format!("{} invalid", name))?;
+            }
+        }
+        Ok(())
+    }
+
+    /// `=` separă cheia de valoare, `|` câmpurile header-ului, iar un
+    /// sfârșit de linie ar rupe mesajul Syslog
+    fn validate_leef_delimiter(&self) -> Result<()> {
+        let delimiter = self.siem.leef_delimiter;
+        ensure!(
+            !matches!(delimiter, '=' | '|' | '\n' | '\r') && delimiter.is_ascii(),
+            "[siem] leef_delimiter = {:?} nu poate separa atributele LEEF",
+            delimiter
+        );
+        Ok(())
+    }
+
+    // -----------------------------------------------------------------------
+    // Filtrul de porturi trebuie să lase ceva de numărat și să nu se
+    // contrazică: un port ignorat din afara intervalului indică, de obicei,
+    // un interval greșit, nu o excludere intenționată.
+    // -----------------------------------------------------------------------
+    fn validate_port_filter(&self) -> Result<()> {
+        let detection = &self.detection;
+        let Some(range) = detection.port_range else {
+            return Ok(());
+        };
+        ensure!(
+            range.min <= range.max,
+            "[detection] port_range: min ({}) este mai mare decât max ({})",
+            range.min,
+            range.max
+        );
+        if let Some(port) = detection.ignore_ports.iter().find(|port| !range.contains(**port)) {
+            bail!(
+                "[detection] ignore_ports conține {}, în afara port_range {}-{} (deja exclus)",
+                port,
+                range.min,
+                range.max
+            );
+        }
+        ensure!(
+            (range.min..=range.max).any(|port| detection.counts_port(port)),
+            "[detection] ignore_ports exclude tot port_range {}-{}: detecția nu ar alerta niciodată",
+            range.min,
+            range.max
+        );
+        Ok(())
+    }
+
+    /// Istoricul ICMP per sursă e plafonat: un prag mai mare nu ar fi atins niciodată
+    fn validate_icmp_sweep(&self) -> Result<()> {
+        let detection = &self.detection;
+        ensure!(
+            detection.icmp_sweep_events <= MAX_ICMP_EVENTS_PER_SOURCE,
+            "[detection] icmp_sweep_events = {} depășește istoricul ICMP reținut per sursă ({})",
+            detection.icmp_sweep_events,
+            MAX_ICMP_EVENTS_PER_SOURCE
+        );
+        ensure!(
+            detection.icmp_sweep_destinations <= MAX_ICMP_EVENTS_PER_SOURCE,
+            "[detection] icmp_sweep_destinations = {} depășește istoricul ICMP reținut per sursă ({})",
+            detection.icmp_sweep_destinations,
+            MAX_ICMP_EVENTS_PER_SOURCE
+        );
+        Ok(())
+    }
+
     /// Returnează adresa completă a listener-ului UDP (ex: "0.0.0.0:5555")
     pub fn listener_addr(&self) -> String {
         format!("{}:{}", self.listener.bind_address, self.listener.port)
@@ -115,9 +1523,4 @@ impl Config {
     pub fn siem_addr(&self) -> String {
         format!("{}:{}", self.siem.address, self.siem.port)
     }
-
-    /// Returnează fereastra slow scan convertită în secunde
-    pub fn slow_scan_window_secs(&self) -> u64 {
-        self.detection.slow_scan_window_mins * 60
-    }
 }
@@ -0,0 +1,231 @@
+// ============================================================
+//  config_template.rs - `rust-ids init-config`: config.toml implicit comentat
+// ============================================================
+//
+//  Valorile vin din `Config::default()` serializat cu `toml`; comentariile
+//  vin din `config.toml`-ul repo-ului, încorporat la compilare. Astfel
+//  documentația câmpurilor există într-un singur loc, iar valorile scrise
+//  sunt exact cele pe care codul le consideră implicite.
+//
+//  Fiecare cheie primește comentariile de deasupra ei din `config.toml` (cu
+//  tot cu exemplele comentate, ex: `# recv_buffer_bytes = ...`) și
+//  comentariul de pe aceeași linie. O cheie nouă, nedocumentată încă în
+//  `config.toml`, apare la finalul secțiunii ei, fără comentariu.
+//
+//  Concepte Rust demonstrate:
+//  - `include_str!` : fișier text încorporat în binar la compilare
+//  - `toml::Value::try_from` : orice `Serialize` -> arbore TOML generic
+//  - `OpenOptions::create_new` : creare atomică, fără suprascriere accidentală
+// ============================================================
+
+use crate::config::Config;
+use anyhow::{bail, Context, Result};
+use std::fs::OpenOptions;
+use std::io::{ErrorKind, Write};
+use std::path::Path;
+use toml::{Table, Value};
+
+/// `config.toml` din repo: sursa comentariilor
+const COMMENTED_CONFIG: &str = include_str!("../config.toml");
+
+/// Antetul fișierului generat
+const HEADER: &str = "\
+# ============================================================
+#  Rust IDS - Fișier de Configurare (generat de `rust-ids init-config`)
+#  Modificați valorile de mai jos fără a recompila proiectul.
+# ============================================================";
+
+/// O cheie din `config.toml`: comentariile de deasupra și cel de pe linie
+struct DocumentedKey {
+    name:    String,
+    /// `cheie =` cu alinierea originală (ex: `port         =`)
+    prefix:  String,
+    leading: Vec<String>,
+    /// Comentariul de pe linie și coloana la care începea
+    inline:  Option<(usize, String)>,
+}
+
+/// O secțiune `[nume]` din `config.toml`, cu cheile ei în ordine
+struct DocumentedSection {
+    name:    String,
+    leading: Vec<String>,
+    keys:    Vec<DocumentedKey>,
+}
+
+/// Structura comentată a `config.toml` + comentariile de după ultima cheie
+struct Layout {
+    sections: Vec<DocumentedSection>,
+    trailing: Vec<String>,
+}
+
+// ---------------------------------------------------------------------------
+// Parcurgerea linie cu linie a `config.toml`: tot ce nu este cheie sau
+// antet de secțiune (comentarii, linii goale) se acumulează și se atașează
+// următorului element. Ordinea și spațierea originală se păstrează.
+// ---------------------------------------------------------------------------
+fn layout() -> Layout {
+    let mut sections: Vec<DocumentedSection> = Vec::new();
+    let mut pending: Vec<String> = Vec::new();
+
+    for line in COMMENTED_CONFIG.lines() {
+        let trimmed = line.trim();
+        if trimmed.is_empty() || trimmed.starts_with('#') {
+            pending.push(line.to_string());
+        } else if let Some(name) = trimmed.strip_prefix('[').and_then(|rest| rest.strip_suffix(']')) {
+            sections.push(DocumentedSection {
+                name:    name.trim().to_string(),
+                leading: std::mem::take(&mut pending),
+                keys:    Vec::new(),
+            });
+        } else if let (Some((prefix, rest)), Some(section)) = (line.split_once('='), sections.last_mut()) {
+            let inline = comment_start(rest).map(|at| (prefix.len() + 1 + at, rest[at..].to_string()));
+            section.keys.push(DocumentedKey {
+                name:    prefix.trim().to_string(),
+                prefix:  format!("{}=", prefix),
+                leading: std::mem::take(&mut pending),
+                inline,
+            });
+        }
+    }
+
+    Layout { sections, trailing: pending }
+}
+
+/// Poziția unui `#` de comentariu în valoarea unei chei (ignorând șirurile)
+fn comment_start(value: &str) -> Option<usize> {
+    let mut in_string = false;
+    let mut escaped = false;
+    for (i, c) in value.char_indices() {
+        match c {
+            _ if escaped => escaped = false,
+            '\\' if in_string => escaped = true,
+            '"' => in_string = !in_string,
+            '#' if !in_string => return Some(i),
+            _ => {}
+        }
+    }
+    None
+}
+
+/// Linia `cheie = valoare  # comentariu`, cu comentariul la coloana originală
+fn key_line(key: &DocumentedKey, value: &Value) -> String {
+    let mut line = format!("{} {}", key.prefix, value);
+    if let Some((column, comment)) = &key.inline {
+        let padding = column.saturating_sub(line.len()).max(1);
+        line.push_str(&" ".repeat(padding));
+        line.push_str(comment);
+    }
+    line
+}
+
+// ---------------------------------------------------------------------------
+// Generarea textului: secțiunile în ordinea din `config.toml`, fiecare cu
+// valorile din `Config::default()`. Sub-tabelele nevide (ex: servicii
+// adăugate în cod) devin `[secțiune.sub]`; cele goale rămân doar ca
+// exemplele comentate din `config.toml`.
+// ---------------------------------------------------------------------------
+pub fn render_default() -> Result<String> {
+    let defaults = match Value::try_from(Config::default()).context("Serializarea configurației implicite")? {
+        Value::Table(table) => table,
+        other => bail!("Configurația implicită nu este un tabel TOML: {}", other.type_str()),
+    };
+
+    let layout = layout();
+    let mut out = vec![HEADER.to_string()];
+    let mut rendered = Vec::new();
+
+    for section in &layout.sections {
+        let Some(Value::Table(values)) = defaults.get(&section.name) else {
+            continue;
+        };
+        rendered.push(section.name.as_str());
+        // Antetul din `config.toml` este înlocuit de HEADER
+        let leading = if out.len() == 1 { skip_file_header(&section.leading) } else { &section.leading[..] };
+        out.extend(leading.iter().cloned());
+        out.push(format!("[{}]", section.name));
+        render_section(&mut out, &section.name, section, values);
+    }
+
+    // Secțiuni fără niciun exemplu în `config.toml` (ex: adăugate ulterior)
+    for (name, value) in &defaults {
+        if let (false, Value::Table(values)) = (rendered.contains(&name.as_str()), value) {
+            if !values.is_empty() {
+                out.push(String::new());
+                out.push(format!("[{}]", name));
+                let empty = DocumentedSection { name: name.clone(), leading: Vec::new(), keys: Vec::new() };
+                render_section(&mut out, name, &empty, values);
+            }
+        }
+    }
+
+    out.extend(layout.trailing.iter().cloned());
+    let mut text = out.join("\n");
+    text.push('\n');
+    Ok(text)
+}
+
+fn render_section(out: &mut Vec<String>, path: &str, section: &DocumentedSection, values: &Table) {
+    // Comentariile unei chei fără valoare implicită (`None` nu se
+    // serializează) trec la următoarea cheie, nu se pierd
+    let mut carried = Vec::new();
+    for key in &section.keys {
+        carried.extend(key.leading.iter().cloned());
+        if let Some(value) = values.get(&key.name).filter(|v| !v.is_table()) {
+            out.append(&mut carried);
+            out.push(key_line(key, value));
+        }
+    }
+    out.append(&mut carried);
+
+    // Chei nedocumentate încă în `config.toml`
+    let documented = |name: &str| section.keys.iter().any(|key| key.name == name);
+    for (name, value) in values {
+        if !value.is_table() && !documented(name) {
+            out.push(format!("{} = {}", name, value));
+        }
+    }
+
+    for (name, value) in values {
+        if let Value::Table(sub) = value {
+            if !sub.is_empty() {
+                out.push(String::new());
+                out.push(format!("[{}.{}]", path, name));
+                for (key, value) in sub {
+                    out.push(format!("{} = {}", key, value));
+                }
+            }
+        }
+    }
+}
+
+/// Sare peste banner-ul `# ===` de la începutul `config.toml`
+fn skip_file_header(lines: &[String]) -> &[String] {
+    let banner_end = lines.iter().rposition(|line| line.starts_with("# ===")).map_or(0, |i| i + 1);
+    &lines[banner_end..]
+}
+
+// ---------------------------------------------------------------------------
+// Scrierea fișierului: fără `force`, un fișier existent NU se atinge
+// (`create_new` eșuează atomic dacă fișierul există deja).
+// ---------------------------------------------------------------------------
+pub fn write_default(path: &Path, force: bool) -> Result<()> {
+    let text = render_default()?;
+
+    let mut options = OpenOptions::new();
+    options.write(true);
+    if force {
+        options.create(true).truncate(true);
+    } else {
+        options.create_new(true);
+    }
+
+    let mut file = match options.open(path) {
+        Ok(file) => file,
+        Err(e) if e.kind() == ErrorKind::AlreadyExists => {
+            bail!("'{}' există deja; folosiți --force pentru a-l suprascrie", path.display())
+        }
+        Err(e) => return Err(e).with_context(|| format!("Nu s-a putut crea '{}'", path.display())),
+    };
+    file.write_all(text.as_bytes())
+        .with_context(|| format!("Nu s-a putut scrie '{}'", path.display()))
+}
@@ -0,0 +1,91 @@
+// ============================================================
+//  context.rs - Buffer circular cu liniile brute recente
+// ============================================================
+//
+//  La o alertă, analistul vrea liniile de log care au dus la ea, nu
+//  doar numărătorile agregate. Păstrăm ultimele `capacity` linii
+//  potrivite (de la toate sursele) într-un buffer circular global;
+//  la alertă, extragem ultimele K linii ale IP-ului sursă.
+//
+//  Un buffer global (nu unul per IP) are memorie fixă: un flood de la
+//  mii de surse falsificate nu poate crea mii de buffere. Liniile sunt
+//  stocate deja sanitizate și trunchiate (conținut controlat de atacator).
+//
+//  Concepte Rust demonstrate:
+//  - `VecDeque` : coadă cu două capete - `push_back` + `pop_front` în O(1),
+//    exact ce trebuie pentru un ring buffer
+//  - `std::sync::Mutex` : secțiune critică scurtă, fără `.await` în interior
+// ============================================================
+
+use crate::diagnostics::sanitize_sample;
+use chrono::{DateTime, Local};
+use std::collections::VecDeque;
+use std::net::IpAddr;
+use std::sync::Mutex;
+
+/// O linie brută primită, cu sursa și momentul recepției
+#[derive(Debug, Clone)]
+pub struct ContextLine {
+    pub source_ip:   IpAddr,
+    pub received_at: DateTime<Local>,
+    /// Linia sanitizată (caractere de control escapate, trunchiată)
+    pub line:        String,
+}
+
+/// Buffer-ul circular; capacitate 0 = dezactivat (nu se stochează nimic)
+#[derive(Debug, Default)]
+pub struct ContextBuffer {
+    capacity: usize,
+    lines:    Mutex<VecDeque<ContextLine>>,
+}
+
+impl ContextBuffer {
+    pub fn new(capacity: usize) -> Self {
+        ContextBuffer {
+            capacity,
+            lines: Mutex::new(VecDeque::with_capacity(capacity)),
+        }
+    }
+
+    pub fn is_enabled(&self) -> bool {
+        self.capacity > 0
+    }
+
+    /// Adaugă o linie; cea mai veche iese dacă buffer-ul e plin
+    pub fn push(&self, source_ip: IpAddr, line: &str) {
+        if !self.is_enabled() {
+            return;
+        }
+
+        // Sanitizarea se face în afara lock-ului
+        let entry = ContextLine {
+            source_ip,
+            received_at: Local::now(),
+            line: sanitize_sample(line),
+        };
+
+        let mut lines = self.lines.lock().unwrap_or_else(|e| e.into_inner());
+        if lines.len() >= self.capacity {
+            lines.pop_front();
+        }
+        lines.push_back(entry);
+    }
+
+    /// Ultimele `limit` linii ale lui `ip`, în ordine cronologică
+    pub fn recent_for(&self, ip: &IpAddr, limit: usize) -> Vec<ContextLine> {
+        if !self.is_enabled() || limit == 0 {
+            return Vec::new();
+        }
+
+        let lines = self.lines.lock().unwrap_or_else(|e| e.into_inner());
+        let mut recent: Vec<ContextLine> = lines
+            .iter()
+            .rev()
+            .filter(|l| l.source_ip == *ip)
+            .take(limit)
+            .cloned()
+            .collect();
+        recent.reverse();
+        recent
+    }
+}
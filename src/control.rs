@@ -0,0 +1,110 @@
+// ============================================================
+//  control.rs - Endpoint de control: injectare de evenimente sintetice
+// ============================================================
+//
+//  Pentru teste automate și demo-uri: `POST /inject` cu un array JSON
+//
+//    [{"src_ip": "10.0.0.5", "dst_port": 1000, "count": 40, "spread_secs": 3000}]
+//
+//  generează `count` evenimente pe porturi consecutive începând cu
+//  `dst_port`, datate uniform în ultimele `spread_secs` secunde, și le
+//  trece prin ACELAȘI flux ca log-urile reale (stare, detecție, cooldown,
+//  alerte). `spread_secs` comprimă timpul: un slow scan de o oră se
+//  testează instantaneu, fără a fabrica log-uri de firewall.
+//
+//  Dezactivat implicit (`[control] enabled = false`): endpoint-ul
+//  manipulează direct starea și poate declanșa alerte reale.
+// ============================================================
+
+use crate::http::{self, Request, Response};
+use crate::parser::{EventKind, LogEntry};
+use chrono::Utc;
+use serde::Deserialize;
+use std::net::IpAddr;
+use std::time::{Duration, Instant};
+
+/// Plafonul evenimentelor generate de o singură cerere
+pub const MAX_INJECT_EVENTS: u64 = 100_000;
+
+/// Dimensiunea maximă a corpului unei cereri de injectare
+pub const MAX_BODY_BYTES: usize = 1024 * 1024;
+
+/// Un șablon de scanare din corpul cererii
+#[derive(Deserialize, Debug, Clone)]
+pub struct InjectSpec {
+    pub src_ip:      IpAddr,
+    /// Primul port; următoarele sunt consecutive (`dst_port + i`)
+    pub dst_port:    u16,
+    #[serde(default)]
+    pub dst_ip:      Option<IpAddr>,
+    #[serde(default = "default_count")]
+    pub count:       u64,
+    /// Intervalul (în trecut) peste care sunt distribuite evenimentele
+    #[serde(default)]
+    pub spread_secs: u64,
+}
+
+fn default_count() -> u64 {
+    1
+}
+
+/// Un eveniment sintetic gata de procesare, cu momentul lui (în trecut)
+pub struct InjectedEvent {
+    pub entry:   LogEntry,
+    pub seen_at: Instant,
+}
+
+// ---------------------------------------------------------------------------
+// Expandează un șablon în evenimente, cele mai vechi primele.
+//
+// Evenimentul i este datat la `spread * (count-1-i) / (count-1)` în urmă:
+// primul la `spread_secs`, ultimul "acum". Porturile peste 65535 sunt omise.
+// ---------------------------------------------------------------------------
+pub fn expand(spec: &InjectSpec, now: Instant) -> Vec<InjectedEvent> {
+    let last = spec.count.saturating_sub(1).max(1);
+    let spread = Duration::from_secs(spec.spread_secs);
+
+    (0..spec.count)
+        .map_while(|i| {
+            let port = u16::try_from(u64::from(spec.dst_port) + i).ok()?;
+            let age = spread.mul_f64((spec.count - 1 - i) as f64 / last as f64);
+            Some(InjectedEvent {
+                entry: LogEntry {
+                    source_ip: spec.src_ip,
+                    dest_ip:   spec.dst_ip,
+                    kind:      EventKind::Port(port),
+                    protocol:  None,
+                    action:    "drop".to_string(),
+                    accepted:  false,
+                    rule:      None,
+                    timestamp: Utc::now(),
+                },
+                seen_at: now.checked_sub(age).unwrap_or(now),
+            })
+        })
+        .collect()
+}
+
+/// Rezultatul validării unei cereri: șabloanele, sau răspunsul de eroare
+pub fn parse_inject(request: &Request, token: Option<&str>) -> Result<Vec<InjectSpec>, Response> {
+    if let Err(e) = http::check_bearer(request, token) {
+        return Err(Response::text(401, &e.to_string()));
+    }
+    match (request.method.as_str(), request.path.as_str()) {
+        ("POST", "/inject") => {}
+        (_, "/inject") => return Err(Response::text(405, "use POST")),
+        _ => return Err(Response::text(404, "not found")),
+    }
+
+    let specs: Vec<InjectSpec> = serde_json::from_slice(&request.body)
+        .map_err(|e| Response::text(400, &format!("JSON invalid: {}", e)))?;
+
+    let total = specs.iter().fold(0u64, |acc, s| acc.saturating_add(s.count));
+    if total > MAX_INJECT_EVENTS {
+        return Err(Response::text(
+            413,
+            &format!("{} evenimente cerute, maximum {}", total, MAX_INJECT_EVENTS),
+        ));
+    }
+    Ok(specs)
+}
@@ -0,0 +1,156 @@
+// ============================================================
+//  datagram.rs - Decodarea datagramelor UDP (UTF-8 invalid, trunchiere)
+// ============================================================
+//
+//  Două situații care altfel trec neobservate:
+//    - octeți UTF-8 invalizi: `from_utf8_lossy` îi înlocuiește cu U+FFFD,
+//      deci linia se schimbă înainte de parsare
+//    - datagrame mai mari decât buffer-ul (relay-uri cu jumbo frames, un
+//      expeditor defect): kernel-ul le taie în tăcere. Semnul este
+//      `len == capacitatea buffer-ului`. Ultima linie este atunci tăiată
+//      la mijloc ("...dpt=44" în loc de "dpt=4433") și s-ar parsa cu
+//      portul greșit - o eliminăm și păstrăm doar liniile complete.
+//
+//  Înregistrările dintr-un pachet sunt separate de `[listener]
+//  record_delimiter`: implicit newline, dar unii expeditori folosesc NUL
+//  sau un caracter propriu de încadrare.
+//
+//  Ambele situații se raportează în `DecodeReport`; numărarea și
+//  avertizarea per sursă sunt în `diagnostics::DatagramStats`.
+//
+//  Concepte Rust demonstrate:
+//  - `str::from_utf8` vs `String::from_utf8_lossy` : validare fără copiere
+//  - `windows` + `rposition` : căutarea de la coadă a ultimului separator
+//  - Pool de buffere (`Mutex<Vec<String>>`) : alocările se refolosesc
+// ============================================================
+
+use std::sync::Mutex;
+
+/// Dimensiunea buffer-ului de recepție (maximul unui pachet UDP)
+pub const MAX_DATAGRAM_BYTES: usize = 65535;
+
+/// Câte buffere text libere păstrează pool-ul (≈ pachete procesate simultan)
+pub const MAX_POOLED_BUFFERS: usize = 64;
+
+/// Capacitatea inițială a unui buffer nou din pool
+const INITIAL_BUFFER_BYTES: usize = 4096;
+
+/// Ce s-a întâmplat la decodarea unei datagrame
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct DecodeReport {
+    /// Octeți UTF-8 invalizi, înlocuiți cu U+FFFD
+    pub lossy:     bool,
+    /// Datagrama a umplut buffer-ul; ultima linie (parțială) a fost eliminată
+    pub truncated: bool,
+}
+
+// ---------------------------------------------------------------------------
+// Decodează `bytes` în `out` (golit înainte; capacitatea se păstrează).
+//
+// `capacity` este dimensiunea buffer-ului de recepție: o datagramă care îl
+// umple complet este considerată trunchiată, iar tot ce urmează după
+// ultimul `delimiter` se elimină. Tăierea se face pe octeți, înainte de
+// decodare, astfel încât un caracter multi-byte tăiat la final nu apare
+// și ca UTF-8 invalid.
+// ---------------------------------------------------------------------------
+pub fn decode_into(bytes: &[u8], capacity: usize, delimiter: &str, out: &mut String) -> DecodeReport {
+    out.clear();
+
+    let truncated = bytes.len() >= capacity;
+    let complete = if truncated {
+        let delimiter = delimiter.as_bytes();
+        match bytes.windows(delimiter.len()).rposition(|window| window == delimiter) {
+            Some(start) => &bytes[..start + delimiter.len()],
+            None => &[], // O singură înregistrare, tăiată: nimic de parsat
+        }
+    } else {
+        bytes
+    };
+
+    let lossy = match std::str::from_utf8(complete) {
+        Ok(text) => {
+            out.push_str(text);
+            false
+        }
+        Err(_) => {
+            out.push_str(&String::from_utf8_lossy(complete));
+            true
+        }
+    };
+
+    DecodeReport { lossy, truncated }
+}
+
+// ---------------------------------------------------------------------------
+// Înregistrările unui pachet, separate de `delimiter`. Segmentele goale
+// (separator la început/sfârșit sau dublat) se sar, iar spațiile de la
+// capete (inclusiv `\r` din CRLF) se elimină - ca la `.lines()` + `trim`.
+// ---------------------------------------------------------------------------
+pub fn records<'a>(text: &'a str, delimiter: &'a str) -> impl Iterator<Item = &'a str> + 'a {
+    text.split(delimiter).map(str::trim).filter(|record| !record.is_empty())
+}
+
+// ---------------------------------------------------------------------------
+// Ca `records`, fără înregistrările mai lungi de `max_line_bytes` (0 = fără
+// limită), care sunt date lui `on_oversized` în loc să ajungă la parser.
+//
+// O datagramă de 64KB cu o singură "linie" uriașă (ex: `service:` repetat)
+// costă mult timp de regex per pachet; log-urile reale au câteva sute de
+// bytes, deci limita nu atinge traficul legitim.
+// ---------------------------------------------------------------------------
+pub fn records_within<'a>(
+    text: &'a str,
+    delimiter: &'a str,
+    max_line_bytes: usize,
+    mut on_oversized: impl FnMut(&'a str) + 'a,
+) -> impl Iterator<Item = &'a str> + 'a {
+    records(text, delimiter).filter(move |record| {
+        let fits = max_line_bytes == 0 || record.len() <= max_line_bytes;
+        if !fits {
+            on_oversized(record);
+        }
+        fits
+    })
+}
+
+// ---------------------------------------------------------------------------
+// Pool de buffere text pentru task-urile care procesează pachetele
+//
+// Fiecare pachet primea un `String` alocat nou; acum task-ul ia un buffer
+// din pool și îl returnează după procesare, cu capacitatea deja crescută.
+// Pool-ul păstrează cel mult `max_pooled` buffere (restul se eliberează),
+// deci memoria rămâne plafonată după un vârf de trafic.
+// ---------------------------------------------------------------------------
+pub struct BufferPool {
+    buffers:    Mutex<Vec<String>>,
+    max_pooled: usize,
+}
+
+impl BufferPool {
+    pub fn new(max_pooled: usize) -> Self {
+        BufferPool { buffers: Mutex::new(Vec::new()), max_pooled }
+    }
+
+    /// Un buffer gol (refolosit, dacă pool-ul are unul)
+    pub fn take(&self) -> String {
+        self.buffers
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .pop()
+            .unwrap_or_else(|| String::with_capacity(INITIAL_BUFFER_BYTES))
+    }
+
+    /// Returnează un buffer în pool (golit); peste limită se eliberează
+    pub fn give(&self, mut buffer: String) {
+        buffer.clear();
+        let mut buffers = self.buffers.lock().unwrap_or_else(|e| e.into_inner());
+        if buffers.len() < self.max_pooled {
+            buffers.push(buffer);
+        }
+    }
+
+    /// Câte buffere libere sunt în pool
+    pub fn available(&self) -> usize {
+        self.buffers.lock().unwrap_or_else(|e| e.into_inner()).len()
+    }
+}
@@ -1,17 +1,112 @@
 // ============================================================
-//  detector.rs - Logica de detecție Fast Scan și Slow Scan
+//  detector.rs - Logica de detecție (tier-uri de scan, distribuit, ICMP)
 // ============================================================
 //
 //  Concepte Rust demonstrate:
 //  - Enum-uri cu date asociate: `DetectionResult` transportă informații
-//    despre tipul de scan detectat
+//    despre tier-ul (tier-urile) de scan care au declanșat
 //  - Pattern matching exhaustiv cu `match`
 //  - Funcții pure (fără side-effects) - ușor de testat
 // ============================================================
 
-use crate::config::DetectionConfig;
-use crate::state::SharedState;
+use crate::config::{DetectionConfig, RepeatOffenderAction, ScanTier};
+use crate::state::{ProtocolBreakdown, SharedState, ThrottleCheck};
+use serde::Serialize;
 use std::net::IpAddr;
+use std::time::Duration;
+
+// ---------------------------------------------------------------------------
+// Un tier de detecție al cărui prag a fost depășit
+//
+// Copiem datele relevante din `ScanTier` (nume, severitate, sig ID) pentru
+// ca rezultatul să fie auto-suficient - alertele nu mai consultă configurația.
+// ---------------------------------------------------------------------------
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct TierHit {
+    /// Numele tier-ului care a declanșat (ex: "fast", "medium", "slow")
+    pub tier:        String,
+
+    /// Numărul de porturi unice observate în fereastra tier-ului
+    pub ports:       usize,
+
+    /// Fereastra de timp a tier-ului, în secunde
+    pub window_secs: u64,
+
+    /// Severitatea CEF (0-10) configurată pentru tier
+    pub severity:    u8,
+
+    /// Signature ID-ul CEF al tier-ului
+    pub sig_id:      String,
+}
+
+// ---------------------------------------------------------------------------
+// De ce a declanșat o scanare de porturi: numărătoarea vs. pragul, fereastra,
+// un eșantion din porturile sondate și împărțirea lor pe protocoale.
+//
+// `TierHit` spune doar CÂT; dovezile spun și CE, ca analistul (sau
+// automatizarea din aval) să nu mai interogheze starea IDS-ului.
+// ---------------------------------------------------------------------------
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct DetectionEvidence {
+    /// Tier-ul principal al detecției
+    pub tier:          String,
+    /// Porturile unice numărate de detecție
+    pub unique_ports:  usize,
+    /// Pragul depășit (strict mai mult de atâtea porturi), după reducerea
+    /// pentru recidiviști
+    pub threshold:     usize,
+    pub window_secs:   u64,
+    /// Primele porturi din fereastră, sortate (max `EVIDENCE_SAMPLE_SIZE`)
+    pub sample_ports:  Vec<u16>,
+    /// Porturile unice din fereastră per protocol
+    pub protocols:     ProtocolBreakdown,
+    /// Drop-urile ICMP ale sursei în aceeași fereastră
+    pub icmp_events:   usize,
+    /// Intervalul dintre prima și ultima apariție din fereastră, în secunde
+    pub span_secs:     f64,
+    /// Viteza scanării: porturi unice / max(`span_secs`, 1s). 50 de porturi
+    /// în 2s (25/s) sunt o unealtă automată; aceleași 50 în 2 minute, nu.
+    pub ports_per_sec: f64,
+}
+
+/// Câte porturi sondate se includ în dovezile unei alerte
+pub const EVIDENCE_SAMPLE_SIZE: usize = 20;
+
+/// Intervalul minim luat în calcul pentru viteză: o rafală sosită în
+/// același milisecund nu trebuie să dea o viteză "infinită"
+pub const MIN_VELOCITY_SPAN: Duration = Duration::from_secs(1);
+
+/// Porturi pe secundă pentru `ports` unice văzute de-a lungul lui `span`
+pub fn ports_per_sec(ports: usize, span: Duration) -> f64 {
+    ports as f64 / span.max(MIN_VELOCITY_SPAN).as_secs_f64()
+}
+
+impl DetectionEvidence {
+    /// Forma compactă, pe o linie (câmpul `reason` din CEF, email):
+    /// `fast: 23 > 15 ports in 10s; sample: 21,22,23,...(+3); tcp: 20, udp: 3, other: 0; rate: 4.60 ports/s over 5.0s`
+    /// (fără `=`, ca valoarea să nu fie escapată în CEF / LEEF)
+    pub fn compact(&self) -> String {
+        let sample: Vec<String> = self.sample_ports.iter().map(u16::to_string).collect();
+        let more = self.unique_ports.saturating_sub(self.sample_ports.len());
+        let mut text = format!(
+            "{}: {} > {} ports in {}s; sample: {}{}; tcp: {}, udp: {}, other: {}",
+            self.tier,
+            self.unique_ports,
+            self.threshold,
+            self.window_secs,
+            sample.join(","),
+            if more > 0 { format!(",...(+{})", more) } else { String::new() },
+            self.protocols.tcp,
+            self.protocols.udp,
+            self.protocols.other
+        );
+        if self.icmp_events > 0 {
+            text.push_str(&format!(", icmp: {}", self.icmp_events));
+        }
+        text.push_str(&format!("; rate: {:.2} ports/s over {:.1}s", self.ports_per_sec, self.span_secs));
+        text
+    }
+}
 
 // ---------------------------------------------------------------------------
 // Rezultatul unei evaluări de detecție
@@ -20,26 +115,90 @@ use std::net::IpAddr;
 // fiecare variantă poate transporta date diferite.
 // Aceasta se numește "Algebraic Data Type" sau "Sum Type".
 // ---------------------------------------------------------------------------
-#[derive(Debug, Clone, PartialEq)]
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[non_exhaustive]
 pub enum DetectionResult {
     /// Nicio activitate suspicioasă detectată
     Clean,
 
-    /// Fast Scan detectat
-    /// Câmpuri: ports (număr porturi unice), window_secs (fereastra de timp)
-    FastScan { ports: usize, window_secs: u64 },
+    /// Un singur tier depășit (ex: doar Fast Scan)
+    Scan(TierHit),
+
+    /// Mai multe tier-uri depășite simultan (posibil în faza de tranziție).
+    /// Ordonate descrescător după severitate: primul element este tier-ul principal.
+    MultiScan(Vec<TierHit>),
 
-    /// Slow Scan detectat
-    /// Câmpuri: ports (număr porturi unice), window_mins (fereastra în minute)
-    SlowScan { ports: usize, window_mins: u64 },
+    /// Scan distribuit: multe surse, fiecare sub pragurile individuale,
+    /// acoperă împreună multe porturi ale aceleiași destinații
+    DistributedScan {
+        /// Destinația țintă (`None` = bucket-ul global, fără IP destinație)
+        dest:               Option<IpAddr>,
+        /// Numărul de surse participante (fiecare sub pragul per sursă)
+        sources:            usize,
+        /// Porturi unice cumulate pe sursele participante
+        total_unique_ports: usize,
+        window_secs:        u64,
+        /// Eșantion de surse participante (plafonat la `DISTRIBUTED_SAMPLE_SIZE`)
+        sample:             Vec<IpAddr>,
+    },
+
+    /// ICMP sweep: o sursă trimite ICMP către multe destinații distincte
+    /// (ping sweep - recunoașterea de rețea dinaintea unui port scan)
+    IcmpSweep {
+        /// Drop-uri ICMP în fereastră
+        events:       usize,
+        /// Destinații distincte atinse
+        destinations: usize,
+        window_secs:  u64,
+    },
 
-    /// Ambele tipuri de scan detectate simultan (posibil în faza de tranziție)
-    BothScans {
-        fast_ports: usize,
-        slow_ports:  usize,
+    /// Acces după scan: o sursă deja semnalată ca scanner primește `accept`
+    /// pe un port pe care fusese anterior blocată - posibilă intruziune reușită
+    PostScanAccess {
+        /// Portul destinație al conexiunii permise
+        port: u16,
+    },
+
+    /// Recidivist: un IP alertat în ultimele `repeat_offender_window_hours`
+    /// revine (primul drop după ce istoricul lui a expirat)
+    KnownOffenderReturned {
+        /// Alertele anterioare ținute minte
+        alerts:           u32,
+        /// Secunde de la ultima alertă
+        since_alert_secs: u64,
     },
 }
 
+/// Signature ID-ul CEF pentru alertele în care mai multe tier-uri declanșează simultan
+pub const MULTI_SCAN_SIG_ID: &str = "IDS003";
+
+/// Signature ID-ul CEF pentru scanările distribuite
+pub const DISTRIBUTED_SCAN_SIG_ID: &str = "IDS004";
+
+/// Severitatea CEF a scanărilor distribuite
+const DISTRIBUTED_SCAN_SEVERITY: u8 = 8;
+
+/// Câte surse participante se includ în alertă (restul doar se numără)
+pub const DISTRIBUTED_SAMPLE_SIZE: usize = 10;
+
+/// Signature ID-ul CEF pentru ICMP sweep
+pub const ICMP_SWEEP_SIG_ID: &str = "IDS005";
+
+/// Severitatea CEF a unui ICMP sweep - recunoaștere, nu încă un atac
+const ICMP_SWEEP_SEVERITY: u8 = 5;
+
+/// Signature ID-ul CEF pentru accesul după scan
+pub const POST_SCAN_ACCESS_SIG_ID: &str = "IDS006";
+
+/// Severitatea CEF a accesului după scan - maximă: recunoașterea a reușit
+const POST_SCAN_ACCESS_SEVERITY: u8 = 10;
+
+/// Signature ID-ul CEF pentru revenirea unui recidivist
+pub const KNOWN_OFFENDER_SIG_ID: &str = "IDS007";
+
+/// Severitatea CEF a revenirii unui recidivist - informativă: încă nu e un scan
+const KNOWN_OFFENDER_SEVERITY: u8 = 3;
+
 /// Evaluează dacă un IP a depășit pragurile de detecție.
 ///
 /// Aceasta este o funcție pură: primește starea și configurația,
@@ -49,55 +208,312 @@ pub enum DetectionResult {
 /// * `ip`     - IP-ul de evaluat
 /// * `state`  - Starea shared (read-only în acest context)
 /// * `config` - Pragurile de detecție din configurație
+///
+/// Cu `repeat_offender_action = "lower_thresholds"`, pragurile unui
+/// recidivist sunt înmulțite cu `repeat_offender_threshold_factor`.
 pub fn evaluate(ip: &IpAddr, state: &SharedState, config: &DetectionConfig) -> DetectionResult {
-    // Calculăm numărul de porturi unice în fereastra Fast Scan
-    let fast_ports = state.unique_ports_in_window(ip, config.fast_scan_window_secs);
-
-    // Calculăm numărul de porturi unice în fereastra Slow Scan
-    // (slow_scan_window_mins * 60 = secunde)
-    let slow_window_secs = config.slow_scan_window_mins * 60;
-    let slow_ports = state.unique_ports_in_window(ip, slow_window_secs);
-
-    // Determinăm dacă pragurile sunt depășite
-    let is_fast_scan = fast_ports > config.fast_scan_ports;
-    let is_slow_scan = slow_ports > config.slow_scan_ports;
-
-    // Pattern matching exhaustiv - compilatorul ne forțează să acoperim
-    // TOATE combinațiile posibile (în cazul tuplelor bool, sunt 4)
-    match (is_fast_scan, is_slow_scan) {
-        (false, false) => DetectionResult::Clean,
-
-        (true, false) => DetectionResult::FastScan {
-            ports:       fast_ports,
-            window_secs: config.fast_scan_window_secs,
-        },
+    let factor = offender_factor(ip, state, config);
+    evaluate_scaled(ip, state, config, |_tier| factor)
+}
 
-        (false, true) => DetectionResult::SlowScan {
-            ports:       slow_ports,
-            window_mins: config.slow_scan_window_mins,
-        },
+/// Sursa activă (vezi `SharedState::enter_scan`) își menține starea de
+/// amenințare?
+///
+/// Pragurile tier-urilor deja declanșate în activitate sunt înmulțite cu
+/// `clear_ratio` (histerezis); celelalte rămân neschimbate. `false` =
+/// tranziția Threat -> Clean.
+pub fn holds_threat(ip: &IpAddr, state: &SharedState, config: &DetectionConfig, triggered: &[String]) -> bool {
+    let factor = offender_factor(ip, state, config);
+    evaluate_scaled(ip, state, config, |tier| {
+        if triggered.contains(&tier.name) {
+            factor * config.clear_ratio
+        } else {
+            factor
+        }
+    })
+    .is_threat()
+}
+
+/// Factorul pragurilor unei surse: redus pentru un recidivist (doar cu
+/// `repeat_offender_action = "lower_thresholds"`)
+fn offender_factor(ip: &IpAddr, state: &SharedState, config: &DetectionConfig) -> f64 {
+    let lowered = config.repeat_offender_action == RepeatOffenderAction::LowerThresholds
+        && state.repeat_offender(ip).is_some();
+    if lowered { config.repeat_offender_threshold_factor } else { 1.0 }
+}
+
+/// Evaluarea propriu-zisă, cu factorul pragului ales per tier
+fn evaluate_scaled(
+    ip: &IpAddr,
+    state: &SharedState,
+    config: &DetectionConfig,
+    factor: impl Fn(&ScanTier) -> f64,
+) -> DetectionResult {
+    // Evaluăm fiecare tier independent: porturi unice în fereastra lui
+    // vs. pragul lui. `filter_map` păstrează doar tier-urile depășite.
+    let mut hits: Vec<TierHit> = config
+        .tiers
+        .iter()
+        .filter_map(|tier| {
+            let ports = state.unique_ports_in_window_where(ip, tier.window_secs, |port| config.counts_port(port));
+            let threshold = (tier.ports as f64 * factor(tier)).ceil() as usize;
+            (ports > threshold).then(|| TierHit {
+                tier:        tier.name.clone(),
+                ports,
+                window_secs: tier.window_secs,
+                severity:    tier.severity,
+                sig_id:      tier.sig_id(),
+            })
+        })
+        .collect();
+
+    // Tier-ul cel mai sever primul; la egalitate, fereastra mai scurtă
+    // (scan mai agresiv) are prioritate
+    hits.sort_by(|a, b| {
+        b.severity
+            .cmp(&a.severity)
+            .then(a.window_secs.cmp(&b.window_secs))
+    });
+
+    match hits.len() {
+        0 => DetectionResult::Clean,
+        1 => DetectionResult::Scan(hits.remove(0)),
+        _ => DetectionResult::MultiScan(hits),
+    }
+}
 
-        // Ambele praguri depășite simultan
-        (true, true) => DetectionResult::BothScans {
-            fast_ports,
-            slow_ports,
+/// Rezultatul unei evaluări supuse throttling-ului (vezi `evaluate_throttled`)
+#[derive(Debug, Clone, PartialEq)]
+pub enum Throttled {
+    /// Evaluarea a rulat acum
+    Evaluated(DetectionResult),
+    /// Sursa a fost evaluată curată recent; o evaluare amânată este deja programată
+    Skipped,
+    /// Sursa a fost evaluată curată recent: apelantul programează
+    /// `evaluate_and_record` după durata dată
+    Deferred(Duration),
+}
+
+/// `evaluate` + înregistrarea rezultatului pentru throttling.
+///
+/// Cu `post_scan_access_window_secs` setat, o sursă detectată ca scanner
+/// este și semnalată pentru corelarea cu `accept`-urile ulterioare
+/// (vezi `evaluate_post_scan_access`).
+pub fn evaluate_and_record(ip: &IpAddr, state: &SharedState, config: &DetectionConfig) -> DetectionResult {
+    let result = evaluate(ip, state, config);
+    state.record_evaluation(*ip, result.is_threat());
+    if result.is_threat() && config.post_scan_access_enabled() {
+        state.flag_scanner(*ip, Duration::from_secs(config.post_scan_access_window_secs));
+    }
+    result
+}
+
+/// Evaluează o sursă, dar nu mai des de `evaluation_min_interval_ms` după o
+/// evaluare curată.
+///
+/// Pentru fluxul per-pachet în timpul unui flood. Un `Deferred` trebuie
+/// onorat de apelant (ex: `tokio::time::sleep` + `evaluate_and_record`):
+/// acolo este prins pragul depășit între timp.
+pub fn evaluate_throttled(ip: &IpAddr, state: &SharedState, config: &DetectionConfig) -> Throttled {
+    let min_interval = Duration::from_millis(config.evaluation_min_interval_ms);
+    match state.throttle_check(ip, min_interval) {
+        ThrottleCheck::Due => Throttled::Evaluated(evaluate_and_record(ip, state, config)),
+        ThrottleCheck::Skip => Throttled::Skipped,
+        ThrottleCheck::Defer(delay) => Throttled::Deferred(delay),
+    }
+}
+
+/// Evaluează scanările distribuite către o destinație.
+///
+/// Complementar cu `evaluate`: nu privește o sursă, ci clusterul surselor
+/// discrete (fiecare sub pragul per sursă) care au atins destinația `dest`
+/// în fereastra configurată. Apelată din pasul periodic de agregare, nu
+/// per pachet - costul este O(perechi) per destinație.
+pub fn evaluate_distributed(dest: &Option<IpAddr>, state: &SharedState, config: &DetectionConfig) -> DetectionResult {
+    if !config.distributed_enabled() {
+        return DetectionResult::Clean;
+    }
+
+    let stats = state.distributed_stats(
+        dest,
+        config.distributed_scan_window_secs,
+        config.distributed_max_ports_per_source(),
+    );
+
+    if stats.ports > config.distributed_scan_ports
+        && stats.sources.len() >= config.distributed_scan_min_sources
+    {
+        DetectionResult::DistributedScan {
+            dest:               *dest,
+            sources:            stats.sources.len(),
+            total_unique_ports: stats.ports,
+            window_secs:        config.distributed_scan_window_secs,
+            sample:             stats.sources.into_iter().take(DISTRIBUTED_SAMPLE_SIZE).collect(),
+        }
+    } else {
+        DetectionResult::Clean
+    }
+}
+
+/// Evaluează dacă un IP a făcut un ICMP sweep.
+///
+/// Folosește doar evenimentele ICMP ale sursei - separat de `evaluate`,
+/// care numără exclusiv porturi.
+pub fn evaluate_icmp(ip: &IpAddr, state: &SharedState, config: &DetectionConfig) -> DetectionResult {
+    if !config.icmp_sweep_enabled() {
+        return DetectionResult::Clean;
+    }
+
+    let activity = state.icmp_activity_in_window(ip, config.icmp_sweep_window_secs);
+
+    if activity.events >= config.icmp_sweep_events
+        && activity.destinations >= config.icmp_sweep_destinations
+    {
+        DetectionResult::IcmpSweep {
+            events:       activity.events,
+            destinations: activity.destinations,
+            window_secs:  config.icmp_sweep_window_secs,
+        }
+    } else {
+        DetectionResult::Clean
+    }
+}
+
+/// Evaluează un `accept` de la `ip` către `port`.
+///
+/// Alertă doar dacă `ip` a fost semnalat recent ca scanner de porturi
+/// ȘI fusese blocat pe exact acest port: un scanner care ajunge la un
+/// serviciu pe care îl sondase înseamnă că recunoașterea a găsit o cale.
+pub fn evaluate_post_scan_access(ip: &IpAddr, port: u16, state: &SharedState, config: &DetectionConfig) -> DetectionResult {
+    if config.post_scan_access_enabled() && state.was_probed_by_scanner(ip, port) {
+        DetectionResult::PostScanAccess { port }
+    } else {
+        DetectionResult::Clean
+    }
+}
+
+/// Evaluează revenirea unui recidivist, ÎNAINTE de înregistrarea drop-ului.
+///
+/// Doar cu `repeat_offender_action = "notify"`: primul drop al unui IP
+/// alertat în fereastra recidiviștilor, după ce istoricul lui a fost
+/// șters de cleanup, produce imediat o notificare de severitate mică.
+pub fn evaluate_returning_offender(ip: &IpAddr, state: &SharedState, config: &DetectionConfig) -> DetectionResult {
+    if config.repeat_offender_action != RepeatOffenderAction::Notify {
+        return DetectionResult::Clean;
+    }
+    match state.returning_offender(ip) {
+        Some(offender) => DetectionResult::KnownOffenderReturned {
+            alerts:           offender.alerts,
+            since_alert_secs: offender.since_last_alert.as_secs(),
         },
+        None => DetectionResult::Clean,
     }
 }
 
+/// Dovezile tier-ului principal al unei scanări de porturi (`None` pentru
+/// celelalte detecții, care își poartă deja detaliile în `DetectionResult`).
+///
+/// Se apelează la alertare, nu la fiecare evaluare: eșantionul sortat și
+/// împărțirea pe protocoale costă mai mult decât numărătoarea.
+pub fn evidence(ip: &IpAddr, state: &SharedState, config: &DetectionConfig, result: &DetectionResult) -> Option<DetectionEvidence> {
+    let hit = result.primary()?;
+    let tier = config.tiers.iter().find(|tier| tier.name == hit.tier)?;
+    let threshold = (tier.ports as f64 * offender_factor(ip, state, config)).ceil() as usize;
+    let window = state.port_window_where(ip, hit.window_secs, EVIDENCE_SAMPLE_SIZE, |port| config.counts_port(port));
+    let span = match (window.first_seen, window.last_seen) {
+        (Some(first), Some(last)) => last.saturating_duration_since(first),
+        _ => Duration::ZERO,
+    };
+
+    Some(DetectionEvidence {
+        tier:          hit.tier.clone(),
+        unique_ports:  hit.ports,
+        threshold,
+        window_secs:   hit.window_secs,
+        sample_ports:  window.sample,
+        protocols:     window.protocols,
+        icmp_events:   state.icmp_activity_in_window(ip, hit.window_secs).events,
+        span_secs:     span.as_secs_f64(),
+        ports_per_sec: ports_per_sec(hit.ports, span),
+    })
+}
+
 impl DetectionResult {
     /// Returnează `true` dacă s-a detectat un scan (oricare tip)
     pub fn is_threat(&self) -> bool {
         !matches!(self, DetectionResult::Clean)
     }
 
-    /// Returnează tipul de scan ca string (pentru logging)
-    pub fn scan_type_label(&self) -> &str {
+    /// Tier-ul principal (cel mai sever) care a declanșat, dacă există
+    pub fn primary(&self) -> Option<&TierHit> {
+        match self {
+            DetectionResult::Clean            => None,
+            DetectionResult::Scan(hit)        => Some(hit),
+            DetectionResult::MultiScan(hits)  => hits.first(),
+            DetectionResult::DistributedScan { .. }
+            | DetectionResult::IcmpSweep { .. }
+            | DetectionResult::PostScanAccess { .. }
+            | DetectionResult::KnownOffenderReturned { .. } => None,
+        }
+    }
+
+    /// Toate tier-urile care au declanșat (gol pentru `Clean`)
+    pub fn hits(&self) -> &[TierHit] {
+        match self {
+            DetectionResult::Clean
+            | DetectionResult::DistributedScan { .. }
+            | DetectionResult::IcmpSweep { .. }
+            | DetectionResult::PostScanAccess { .. }
+            | DetectionResult::KnownOffenderReturned { .. } => &[],
+            DetectionResult::Scan(hit)        => std::slice::from_ref(hit),
+            DetectionResult::MultiScan(hits)  => hits,
+        }
+    }
+
+    /// Severitatea alertei pe scala CEF (0-10).
+    ///
+    /// Pentru mai multe tier-uri simultan: severitatea maximă + 1
+    /// (plafonată la 10) - activitatea pe mai multe scale de timp agravează.
+    pub fn severity(&self) -> u8 {
+        match self {
+            DetectionResult::Clean            => 0,
+            DetectionResult::Scan(hit)        => hit.severity,
+            DetectionResult::MultiScan(hits)  => {
+                let max = hits.iter().map(|h| h.severity).max().unwrap_or(0);
+                (max + 1).min(10)
+            }
+            DetectionResult::DistributedScan { .. } => DISTRIBUTED_SCAN_SEVERITY,
+            DetectionResult::IcmpSweep { .. } => ICMP_SWEEP_SEVERITY,
+            DetectionResult::PostScanAccess { .. } => POST_SCAN_ACCESS_SEVERITY,
+            DetectionResult::KnownOffenderReturned { .. } => KNOWN_OFFENDER_SEVERITY,
+        }
+    }
+
+    /// Signature ID-ul CEF al alertei
+    pub fn sig_id(&self) -> &str {
+        match self {
+            DetectionResult::Clean            => "IDS000",
+            DetectionResult::Scan(hit)        => &hit.sig_id,
+            DetectionResult::MultiScan(_)     => MULTI_SCAN_SIG_ID,
+            DetectionResult::DistributedScan { .. } => DISTRIBUTED_SCAN_SIG_ID,
+            DetectionResult::IcmpSweep { .. } => ICMP_SWEEP_SIG_ID,
+            DetectionResult::PostScanAccess { .. } => POST_SCAN_ACCESS_SIG_ID,
+            DetectionResult::KnownOffenderReturned { .. } => KNOWN_OFFENDER_SIG_ID,
+        }
+    }
+
+    /// Returnează tipul de scan ca string (pentru logging), ex: "FAST+SLOW_SCAN"
+    pub fn scan_type_label(&self) -> String {
         match self {
-            DetectionResult::Clean        => "CLEAN",
-            DetectionResult::FastScan { .. } => "FAST_SCAN",
-            DetectionResult::SlowScan { .. } => "SLOW_SCAN",
-            DetectionResult::BothScans { .. } => "FAST+SLOW_SCAN",
+            DetectionResult::Clean => "CLEAN".to_string(),
+            DetectionResult::DistributedScan { .. } => "DISTRIBUTED_SCAN".to_string(),
+            DetectionResult::IcmpSweep { .. } => "ICMP_SWEEP".to_string(),
+            DetectionResult::PostScanAccess { .. } => "POST_SCAN_ACCESS".to_string(),
+            DetectionResult::KnownOffenderReturned { .. } => "KNOWN_OFFENDER_RETURNED".to_string(),
+            _ => {
+                let tiers: Vec<String> = self.hits().iter().map(|h| h.tier.to_uppercase()).collect();
+                format!("{}_SCAN", tiers.join("+"))
+            }
         }
     }
 }
@@ -0,0 +1,520 @@
+// ============================================================
+//  diagnostics.rs - Diagnosticarea eșecurilor de parsare per exporter
+// ============================================================
+//
+//  Un exporter configurat greșit (format greșit selectat, firmware nou
+//  cu alt layout) face ca `parser.parse()` să returneze `None` pentru
+//  FIECARE linie - iar IDS-ul raportează liniștit "nimic suspect".
+//
+//  Numărăm liniile și eșecurile per adresă sursă a pachetelor UDP
+//  (exporter-ul, NU IP-ul atacatorului din log). La fiecare bloc de
+//  `BLOCK_LINES` linii, dacă peste `FAILURE_RATIO` au eșuat, emitem un
+//  avertisment cu o linie eșantion - cel mult o dată pe oră per sursă.
+//
+//  Linia eșantion este conținut controlat de atacator: o trunchiem și
+//  escapăm caracterele de control înainte de a o afișa. La fel adresa
+//  sursă (UDP, ușor de falsificat): se urmăresc cel mult
+//  `MAX_PARSE_SOURCES` surse, cele inactive dispar la cleanup, iar sumarul
+//  periodic afișează doar primele `SUMMARY_TOP_SOURCES`.
+//
+//  Separat, `ParserStats` numără rezultatele parserului activ pe categorii
+//  (potrivite / acțiune ignorată / nerecunoscute) - răspunsul la întrebarea
+//  "de ce log-urile mele nu produc evenimente?" la integrarea unui firewall nou.
+//
+//  `DatagramStats` numără datagramele cu UTF-8 invalid sau trunchiate și
+//  semnalează fiecare situație o singură dată per sursă.
+//
+//  `IngestStats` numără, per listener (udp/tcp) și per parser, datagramele
+//  și liniile primite - ce intrare produce efectiv evenimente și care tace.
+// ============================================================
+
+use crate::datagram::DecodeReport;
+use crate::parser::ParseOutcome;
+use chrono::{DateTime, TimeZone, Utc};
+use dashmap::DashMap;
+use std::net::IpAddr;
+use std::sync::atomic::{AtomicBool, AtomicI64, AtomicU64, Ordering};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+/// Numărul de linii dintr-un bloc de evaluare
+pub const BLOCK_LINES: u64 = 500;
+
+/// Pragul de eșec peste care se emite avertismentul (fracțiune din bloc)
+pub const FAILURE_RATIO: f64 = 0.95;
+
+/// Intervalul minim între două avertismente pentru aceeași sursă
+const WARN_INTERVAL: Duration = Duration::from_secs(3600);
+
+/// Câte perechi (sursă, problemă) avertizate se rețin; peste limită,
+/// sursele noi nu mai primesc avertismentul (contoarele cresc în continuare)
+const MAX_DATAGRAM_WARNED: usize = 1024;
+
+/// Câte surse (exportere) se urmăresc; peste limită, liniile surselor noi
+/// se numără doar global (`untracked_lines`), fără diagnostic per sursă
+pub const MAX_PARSE_SOURCES: usize = 1024;
+
+/// Câte surse apar în sumarul periodic (cele cu cele mai multe eșecuri)
+pub const SUMMARY_TOP_SOURCES: usize = 10;
+
+/// Lungimea maximă (în caractere) a liniei eșantion
+const SAMPLE_MAX_CHARS: usize = 200;
+
+/// Câte linii nerecunoscute se afișează în modul `--debug-parse`
+pub const DEBUG_PARSE_SAMPLES: u64 = 10;
+
+/// Câte acțiuni ignorate distincte se numără (acțiunea vine din log -
+/// un atacator nu trebuie să poată umple memoria cu acțiuni inventate)
+const MAX_IGNORED_ACTIONS: usize = 32;
+
+// ---------------------------------------------------------------------------
+// Contoarele parserului activ
+//
+// `AtomicU64` permite incrementarea din multe task-uri simultan fără lock:
+// `fetch_add` este o singură instrucțiune atomică a procesorului.
+// `Ordering::Relaxed` ajunge - contoarele sunt independente între ele.
+// ---------------------------------------------------------------------------
+#[derive(Debug, Default)]
+pub struct ParserStats {
+    /// Linii ne-goale primite
+    pub lines_seen:     AtomicU64,
+    /// Linii transformate în `LogEntry`
+    pub matched:        AtomicU64,
+    /// Format recunoscut, acțiune irelevantă (ex: accept)
+    pub ignored_action: AtomicU64,
+    /// Linii care nu corespund formatului
+    pub unrecognized:   AtomicU64,
+    /// Distribuția acțiunilor ignorate (ex: "accept" -> 1200)
+    pub ignored_by_action: DashMap<String, u64>,
+}
+
+/// Instantaneu (valori simple) al `ParserStats`, pentru afișare
+#[derive(Debug, Clone, Default)]
+pub struct ParserStatsSnapshot {
+    pub lines_seen:        u64,
+    pub matched:           u64,
+    pub ignored_action:    u64,
+    pub unrecognized:      u64,
+    pub ignored_by_action: Vec<(String, u64)>,
+}
+
+impl ParserStats {
+    /// Înregistrează rezultatul parsării unei linii
+    pub fn record(&self, outcome: &ParseOutcome) {
+        self.lines_seen.fetch_add(1, Ordering::Relaxed);
+        match outcome {
+            ParseOutcome::Matched(_) => {
+                self.matched.fetch_add(1, Ordering::Relaxed);
+            }
+            ParseOutcome::IgnoredAction(action) => {
+                self.ignored_action.fetch_add(1, Ordering::Relaxed);
+                if let Some(mut count) = self.ignored_by_action.get_mut(action) {
+                    *count += 1;
+                } else if self.ignored_by_action.len() < MAX_IGNORED_ACTIONS {
+                    *self.ignored_by_action.entry(action.clone()).or_default() += 1;
+                }
+            }
+            ParseOutcome::Unrecognized => {
+                self.unrecognized.fetch_add(1, Ordering::Relaxed);
+            }
+        }
+    }
+
+    pub fn snapshot(&self) -> ParserStatsSnapshot {
+        let mut ignored_by_action: Vec<(String, u64)> = self
+            .ignored_by_action
+            .iter()
+            .map(|e| (e.key().clone(), *e.value()))
+            .collect();
+        ignored_by_action.sort_by_key(|(_, count)| std::cmp::Reverse(*count));
+
+        ParserStatsSnapshot {
+            lines_seen:     self.lines_seen.load(Ordering::Relaxed),
+            matched:        self.matched.load(Ordering::Relaxed),
+            ignored_action: self.ignored_action.load(Ordering::Relaxed),
+            unrecognized:   self.unrecognized.load(Ordering::Relaxed),
+            ignored_by_action,
+        }
+    }
+}
+
+/// Contoarele de parsare pentru o sursă (exporter)
+#[derive(Debug, Default)]
+pub struct SourceParseStats {
+    /// Total linii primite de la pornire
+    pub total_lines:    u64,
+    /// Total linii al căror format parser-ul nu l-a recunoscut
+    pub total_failures: u64,
+
+    block_lines:    u64,
+    block_failures: u64,
+    /// Prima linie eșuată din blocul curent (deja sanitizată)
+    block_sample:   Option<String>,
+    last_warned:    Option<Instant>,
+    /// Ultima linie primită (pentru eliminarea surselor inactive)
+    last_seen:      Option<Instant>,
+}
+
+/// Avertismentul emis când o sursă depășește pragul de eșec
+#[derive(Debug, Clone)]
+pub struct ParseWarning {
+    pub source:          IpAddr,
+    pub failure_percent: f64,
+    /// Linie eșantion, trunchiată și cu caracterele de control escapate
+    pub sample:          String,
+}
+
+/// O problemă de decodare a unei datagrame
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum DatagramIssue {
+    /// Octeți UTF-8 invalizi înlocuiți
+    Lossy,
+    /// Datagramă trunchiată (ultima linie eliminată)
+    Truncated,
+    /// Linie peste `[listener] max_line_bytes` (sărită, neparsată)
+    Oversized,
+}
+
+// ---------------------------------------------------------------------------
+// Contoarele problemelor de decodare, pentru toate sursele
+//
+// Avertismentul se emite doar prima dată când o sursă are o anumită
+// problemă: un expeditor cu encoding greșit ar umple altfel consola.
+// ---------------------------------------------------------------------------
+#[derive(Debug, Default)]
+pub struct DatagramStats {
+    /// Datagrame cu octeți UTF-8 invalizi
+    pub lossy:     AtomicU64,
+    /// Datagrame care au umplut buffer-ul de recepție
+    pub truncated: AtomicU64,
+    /// Linii sărite pentru că depășeau `max_line_bytes`
+    pub oversized: AtomicU64,
+    warned:        DashMap<(IpAddr, DatagramIssue), ()>,
+}
+
+impl DatagramStats {
+    /// Numără problemele unei datagrame de la `source`; returnează cele
+    /// întâlnite acum prima dată pentru această sursă (de avertizat)
+    pub fn record(&self, source: IpAddr, report: DecodeReport) -> Vec<DatagramIssue> {
+        let mut first_seen = Vec::new();
+        for (happened, issue, counter) in [
+            (report.lossy, DatagramIssue::Lossy, &self.lossy),
+            (report.truncated, DatagramIssue::Truncated, &self.truncated),
+        ] {
+            if !happened {
+                continue;
+            }
+            counter.fetch_add(1, Ordering::Relaxed);
+            if self.first_warning(source, issue) {
+                first_seen.push(issue);
+            }
+        }
+        first_seen
+    }
+
+    /// Numără o linie prea lungă de la `source`; `true` = prima pentru
+    /// această sursă (de avertizat)
+    pub fn record_oversized(&self, source: IpAddr) -> bool {
+        self.oversized.fetch_add(1, Ordering::Relaxed);
+        self.first_warning(source, DatagramIssue::Oversized)
+    }
+
+    /// Liniile prea lungi sărite de la pornire
+    pub fn oversized(&self) -> u64 {
+        self.oversized.load(Ordering::Relaxed)
+    }
+
+    fn first_warning(&self, source: IpAddr, issue: DatagramIssue) -> bool {
+        // `insert` întoarce valoarea veche: atomic, un singur avertisment
+        self.warned.len() < MAX_DATAGRAM_WARNED && self.warned.insert((source, issue), ()).is_none()
+    }
+
+    /// Instantaneu: (datagrame cu UTF-8 invalid, datagrame trunchiate)
+    pub fn snapshot(&self) -> (u64, u64) {
+        (self.lossy.load(Ordering::Relaxed), self.truncated.load(Ordering::Relaxed))
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Contoarele de ingestie ale unei intrări (un listener sau un parser)
+//
+// "Parsată" = format recunoscut (eveniment sau acțiune ignorată), "respinsă"
+// = nerecunoscută - deci `lines == parsed + rejected`. Momentul ultimului
+// eveniment se ține ca milisecunde Unix într-un `AtomicI64` (0 = niciodată).
+// ---------------------------------------------------------------------------
+#[derive(Debug, Default)]
+pub struct IngestCounters {
+    /// Datagrame UDP / linii TCP primite (un apel `process_packet`)
+    pub datagrams: AtomicU64,
+    /// Linii ne-goale văzute
+    pub lines:     AtomicU64,
+    /// Linii cu format recunoscut
+    pub parsed:    AtomicU64,
+    /// Linii nerecunoscute
+    pub rejected:  AtomicU64,
+    last_event_ms: AtomicI64,
+}
+
+/// Instantaneu al `IngestCounters`, pentru afișare
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct IngestSnapshot {
+    /// Numele intrării: "udp"/"tcp" pentru listener, numele parser-ului
+    pub name:       String,
+    pub datagrams:  u64,
+    pub lines:      u64,
+    pub parsed:     u64,
+    pub rejected:   u64,
+    /// Ultima linie care a produs un eveniment (`None` = niciodată)
+    pub last_event: Option<DateTime<Utc>>,
+}
+
+impl IngestCounters {
+    fn record_datagram(&self) {
+        self.datagrams.fetch_add(1, Ordering::Relaxed);
+    }
+
+    fn record_line(&self, outcome: &ParseOutcome, now_ms: i64) {
+        self.lines.fetch_add(1, Ordering::Relaxed);
+        match outcome {
+            ParseOutcome::Unrecognized => {
+                self.rejected.fetch_add(1, Ordering::Relaxed);
+            }
+            ParseOutcome::Matched(_) => {
+                self.parsed.fetch_add(1, Ordering::Relaxed);
+                self.last_event_ms.store(now_ms, Ordering::Relaxed);
+            }
+            ParseOutcome::IgnoredAction(_) => {
+                self.parsed.fetch_add(1, Ordering::Relaxed);
+            }
+        }
+    }
+
+    fn snapshot(&self, name: &str) -> IngestSnapshot {
+        let last_event_ms = self.last_event_ms.load(Ordering::Relaxed);
+        IngestSnapshot {
+            name:       name.to_string(),
+            datagrams:  self.datagrams.load(Ordering::Relaxed),
+            lines:      self.lines.load(Ordering::Relaxed),
+            parsed:     self.parsed.load(Ordering::Relaxed),
+            rejected:   self.rejected.load(Ordering::Relaxed),
+            last_event: (last_event_ms > 0)
+                .then(|| Utc.timestamp_millis_opt(last_event_ms).single())
+                .flatten(),
+        }
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Contoarele de ingestie pentru toate intrările
+//
+// Fiecare listener primește un `IngestHandle` la pornire (cheile sunt fixe,
+// din configurație - nu din trafic, deci map-urile nu cresc nelimitat).
+// Numărarea se face în apelant: trait-ul `LogParser` rămâne pur.
+// ---------------------------------------------------------------------------
+#[derive(Debug, Default)]
+pub struct IngestStats {
+    listeners: DashMap<String, Arc<IngestCounters>>,
+    parsers:   DashMap<String, Arc<IngestCounters>>,
+}
+
+/// Contoarele unui listener și ale parser-ului său, actualizate împreună
+#[derive(Debug, Clone)]
+pub struct IngestHandle {
+    listener: Arc<IngestCounters>,
+    parser:   Arc<IngestCounters>,
+}
+
+impl IngestStats {
+    /// Handle-ul pentru perechea (listener, parser); aceleași contoare
+    /// sunt returnate la fiecare apel cu aceleași nume
+    pub fn handle(&self, listener: &str, parser: &str) -> IngestHandle {
+        IngestHandle {
+            listener: Arc::clone(&self.listeners.entry(listener.to_string()).or_default()),
+            parser:   Arc::clone(&self.parsers.entry(parser.to_string()).or_default()),
+        }
+    }
+
+    /// Instantaneu per listener, sortat după nume
+    pub fn listeners(&self) -> Vec<IngestSnapshot> {
+        Self::snapshot(&self.listeners)
+    }
+
+    /// Instantaneu per parser, sortat după nume
+    pub fn parsers(&self) -> Vec<IngestSnapshot> {
+        Self::snapshot(&self.parsers)
+    }
+
+    fn snapshot(map: &DashMap<String, Arc<IngestCounters>>) -> Vec<IngestSnapshot> {
+        let mut rows: Vec<IngestSnapshot> = map.iter().map(|e| e.value().snapshot(e.key())).collect();
+        rows.sort_by(|a, b| a.name.cmp(&b.name));
+        rows
+    }
+}
+
+impl IngestHandle {
+    /// O datagramă UDP / linie TCP primită de listener
+    pub fn record_datagram(&self) {
+        self.listener.record_datagram();
+        self.parser.record_datagram();
+    }
+
+    /// Rezultatul parsării unei linii din datagrama curentă
+    pub fn record_line(&self, outcome: &ParseOutcome) {
+        let now_ms = Utc::now().timestamp_millis();
+        self.listener.record_line(outcome, now_ms);
+        self.parser.record_line(outcome, now_ms);
+    }
+}
+
+/// Diagnosticul de parsare pentru toate sursele
+#[derive(Default)]
+pub struct ParseDiagnostics {
+    sources: DashMap<IpAddr, SourceParseStats>,
+
+    /// Linii de la surse neurmărite (peste `MAX_PARSE_SOURCES`)
+    untracked_lines: AtomicU64,
+
+    /// Contoarele parserului activ, pe categorii
+    pub parser_stats: ParserStats,
+
+    /// Datagramele cu UTF-8 invalid / trunchiate
+    pub datagrams: DatagramStats,
+
+    /// Contoarele de ingestie per listener și per parser
+    pub ingest: IngestStats,
+
+    /// Modul `--debug-parse`: primele linii nerecunoscute se afișează integral
+    debug_parse: AtomicBool,
+    debug_samples_logged: AtomicU64,
+}
+
+impl ParseDiagnostics {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn set_debug_parse(&self, enabled: bool) {
+        self.debug_parse.store(enabled, Ordering::Relaxed);
+    }
+
+    /// În modul `--debug-parse`, returnează `true` pentru primele
+    /// `DEBUG_PARSE_SAMPLES` linii nerecunoscute (cele care trebuie afișate)
+    pub fn should_log_unrecognized(&self) -> bool {
+        self.debug_parse.load(Ordering::Relaxed)
+            && self.debug_samples_logged.fetch_add(1, Ordering::Relaxed) < DEBUG_PARSE_SAMPLES
+    }
+
+    // -----------------------------------------------------------------------
+    // Înregistrează rezultatul parsării unei linii de la `source`.
+    //
+    // Returnează `Some(ParseWarning)` doar la finalul unui bloc cu rată de
+    // eșec peste prag, și doar dacă sursa nu a fost avertizată în ultima oră.
+    // -----------------------------------------------------------------------
+    pub fn record(&self, source: IpAddr, recognized: bool, line: &str) -> Option<ParseWarning> {
+        // Verificarea plafonului și inserarea nu sunt atomice împreună: la
+        // concurență map-ul poate depăși limita cu câteva intrări, nu nelimitat
+        if self.sources.len() >= MAX_PARSE_SOURCES && !self.sources.contains_key(&source) {
+            self.untracked_lines.fetch_add(1, Ordering::Relaxed);
+            return None;
+        }
+        let mut stats = self.sources.entry(source).or_default();
+
+        stats.last_seen = Some(Instant::now());
+        stats.total_lines += 1;
+        stats.block_lines += 1;
+        if !recognized {
+            stats.total_failures += 1;
+            stats.block_failures += 1;
+            if stats.block_sample.is_none() {
+                stats.block_sample = Some(sanitize_sample(line));
+            }
+        }
+
+        if stats.block_lines < BLOCK_LINES {
+            return None;
+        }
+
+        // Sfârșit de bloc: evaluăm și resetăm contoarele blocului
+        let ratio = stats.block_failures as f64 / stats.block_lines as f64;
+        let sample = stats.block_sample.take();
+        stats.block_lines = 0;
+        stats.block_failures = 0;
+
+        let recently_warned = stats
+            .last_warned
+            .is_some_and(|t| t.elapsed() < WARN_INTERVAL);
+        if ratio <= FAILURE_RATIO || recently_warned {
+            return None;
+        }
+
+        stats.last_warned = Some(Instant::now());
+        Some(ParseWarning {
+            source,
+            failure_percent: ratio * 100.0,
+            sample: sample.unwrap_or_default(),
+        })
+    }
+
+    /// Instantaneu al contoarelor: (sursă, linii totale, eșecuri totale)
+    pub fn snapshot(&self) -> Vec<(IpAddr, u64, u64)> {
+        let mut rows: Vec<(IpAddr, u64, u64)> = self
+            .sources
+            .iter()
+            .map(|e| (*e.key(), e.total_lines, e.total_failures))
+            .collect();
+        rows.sort();
+        rows
+    }
+
+    /// Primele `n` surse după eșecuri, apoi după linii (pentru sumar)
+    pub fn top_sources(&self, n: usize) -> Vec<(IpAddr, u64, u64)> {
+        let mut rows = self.snapshot();
+        rows.sort_by_key(|&(source, lines, failures)| (std::cmp::Reverse(failures), std::cmp::Reverse(lines), source));
+        rows.truncate(n);
+        rows
+    }
+
+    /// Numărul de surse urmărite
+    pub fn source_count(&self) -> usize {
+        self.sources.len()
+    }
+
+    /// Liniile primite de la surse neurmărite (plafon atins)
+    pub fn untracked_lines(&self) -> u64 {
+        self.untracked_lines.load(Ordering::Relaxed)
+    }
+
+    // -----------------------------------------------------------------------
+    // Elimină sursele fără linii în ultimele `max_age` (apelat din
+    // `SharedState::cleanup_old_entries`). Returnează câte au fost eliminate.
+    // -----------------------------------------------------------------------
+    pub fn prune_idle(&self, now: Instant, max_age: Duration) -> usize {
+        let before = self.sources.len();
+        self.sources.retain(|_source, stats| {
+            stats.last_seen.is_some_and(|seen| now.saturating_duration_since(seen) <= max_age)
+        });
+        before.saturating_sub(self.sources.len())
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Pregătește o linie primită din rețea pentru afișare sigură:
+//   - trunchiere la SAMPLE_MAX_CHARS caractere (nu bytes - evităm tăierea
+//     în mijlocul unui caracter UTF-8)
+//   - caracterele de control (ESC, CR, NUL...) devin secvențe vizibile
+//     (`\u{1b}`), astfel încât nu pot injecta coduri ANSI în terminal
+// ---------------------------------------------------------------------------
+pub fn sanitize_sample(line: &str) -> String {
+    let mut out = String::with_capacity(line.len().min(SAMPLE_MAX_CHARS));
+    for c in line.chars().take(SAMPLE_MAX_CHARS) {
+        if c.is_control() {
+            out.extend(c.escape_default());
+        } else {
+            out.push(c);
+        }
+    }
+    if line.chars().count() > SAMPLE_MAX_CHARS {
+        out.push_str("...");
+    }
+    out
+}
@@ -9,12 +9,40 @@
 //  - Macro-ul `format!` pentru construire de String-uri
 // ============================================================
 
+use crate::config::DropEventsMode;
+use crate::parser::EventKind;
+use crate::state::{RuleSummary, StateStats};
 use chrono::Local;
 use colored::Colorize;
+use std::collections::HashMap;
+use std::net::IpAddr;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::OnceLock;
+use std::time::Duration;
+use tokio::sync::mpsc;
 
 // Lățimea separatorului orizontal (în caractere)
 const SEPARATOR_WIDTH: usize = 70;
 
+// ---------------------------------------------------------------------------
+// Modul structurat (`[display] log_format = "json"`)
+//
+// Consola colorată este pentru un operator; în producție stdout-ul merge la
+// un colector. În modul structurat mesajele log_* devin evenimente tracing,
+// iar bannerele de alertă și liniile [DROP] nu se mai afișează: apelantul
+// emite deja evenimentele `rust_ids::events` cu câmpurile lor.
+// ---------------------------------------------------------------------------
+static STRUCTURED: AtomicBool = AtomicBool::new(false);
+
+/// Comută între consola colorată și evenimentele tracing (la pornire)
+pub fn set_structured(structured: bool) {
+    STRUCTURED.store(structured, Ordering::Relaxed);
+}
+
+fn structured() -> bool {
+    STRUCTURED.load(Ordering::Relaxed)
+}
+
 // ---------------------------------------------------------------------------
 // Banner-ul de pornire al aplicației
 //
@@ -22,6 +50,9 @@ const SEPARATOR_WIDTH: usize = 70;
 // Caracterele box-drawing (╔, ═, etc.) sunt Unicode standard
 // ---------------------------------------------------------------------------
 pub fn print_banner() {
+    if structured() {
+        return;
+    }
     let border = "═".repeat(SEPARATOR_WIDTH - 2);
     println!();
     println!("{}", format!("╔{}╗", border).bold().cyan());
@@ -50,6 +81,9 @@ pub fn print_banner() {
 
 /// Linie separatoare orizontală pentru lizibilitate vizuală
 pub fn print_separator() {
+    if structured() {
+        return;
+    }
     let line = "─".repeat(SEPARATOR_WIDTH);
     println!("{}", line.dimmed());
 }
@@ -65,6 +99,10 @@ pub fn print_separator() {
 
 /// Mesaj informațional - verde, pentru operații normale
 pub fn log_info(msg: &str) {
+    if structured() {
+        tracing::info!("{}", msg);
+        return;
+    }
     let ts = timestamp();
     println!(
         "{} {} {}",
@@ -76,6 +114,10 @@ pub fn log_info(msg: &str) {
 
 /// Avertisment - galben, pentru situații care merită atenție
 pub fn log_warn(msg: &str) {
+    if structured() {
+        tracing::warn!("{}", msg);
+        return;
+    }
     let ts = timestamp();
     println!(
         "{} {} {}",
@@ -85,8 +127,30 @@ pub fn log_warn(msg: &str) {
     );
 }
 
+/// Fișierul de configurare lipsește: rulăm cu valorile implicite.
+/// Chenar galben, ca operatorul să nu creadă că rulează cu pragurile lui.
+pub fn log_default_config_warning(path: &str) {
+    let advice = format!(
+        "'{}' nu există - se folosesc valorile implicite (UDP 0.0.0.0:5555, parser gaia, \
+         SIEM 127.0.0.1:514, email dezactivat). Generați un fișier cu `rust-ids init-config`; \
+         în producție rulați cu --strict-config.",
+        path
+    );
+    if structured() {
+        tracing::warn!(path, "{}", advice);
+        return;
+    }
+    println!("{}", "═".repeat(SEPARATOR_WIDTH).yellow().bold());
+    log_warn(&format!("CONFIGURAȚIE IMPLICITĂ: {}", advice));
+    println!("{}", "═".repeat(SEPARATOR_WIDTH).yellow().bold());
+}
+
 /// Eroare - roșu aprins, pentru eșecuri non-fatale
 pub fn log_error(msg: &str) {
+    if structured() {
+        tracing::error!("{}", msg);
+        return;
+    }
     let ts = timestamp();
     eprintln!(
         "{} {} {}",
@@ -99,6 +163,10 @@ pub fn log_error(msg: &str) {
 /// Mesaj de debug - albastru deschis, afișat doar dacă RUST_LOG=debug
 /// În producție, aceste mesaje sunt suprimate de tracing subscriber
 pub fn log_debug(msg: &str) {
+    if structured() {
+        tracing::debug!("{}", msg);
+        return;
+    }
     let ts = timestamp();
     println!(
         "{} {} {}",
@@ -115,48 +183,187 @@ pub fn log_debug(msg: &str) {
 // pentru a forța tipizare corectă (nu orice string poate fi IP valid).
 // ---------------------------------------------------------------------------
 
-/// Alertă Fast Scan - fundal roșu intens, imposibil de ratat
-pub fn log_fast_scan_alert(ip: &std::net::IpAddr, ports: usize, window_secs: u64) {
+/// Alertă de scan pentru un tier.
+///
+/// Tier-urile severe (>= 8, ex: Fast Scan) au fundal roșu intens, imposibil
+/// de ratat; celelalte (ex: Slow Scan) sunt galbene - mai puțin urgente,
+/// dar la fel de periculoase.
+pub fn log_scan_alert(ip: &std::net::IpAddr, tier: &str, ports: usize, window_secs: u64, severity: u8) {
+    if structured() {
+        return; // evenimentul `alert` structurat îl emite apelantul
+    }
     let ts = timestamp();
     let separator = "▶".repeat(3);
+    let urgent = severity >= 8;
+
+    // Ferestrele exprimate în minute întregi se afișează în minute
+    let window = if window_secs >= 60 && window_secs.is_multiple_of(60) {
+        format!("{}min", window_secs / 60)
+    } else {
+        format!("{}s", window_secs)
+    };
+
+    let paint = |text: String| if urgent { text.red() } else { text.yellow() };
+    let badge = if urgent {
+        " ALERT ".on_red().white().bold()
+    } else {
+        " ALERT ".on_yellow().black().bold()
+    };
 
     println!();
-    println!("{}", "─".repeat(SEPARATOR_WIDTH).red());
+    println!("{}", paint("─".repeat(SEPARATOR_WIDTH)));
     println!(
-        "{} {} {} [FAST SCAN] {} | {} porturi unice in {}s",
+        "{} {} {} [{} SCAN] {} | {} porturi unice in {}",
         ts.bold().white(),
-        separator.red().bold(),
-        " ALERT ".on_red().white().bold(),
-        format!("[IP: {}]", ip).red().bold(),
-        format!("{}", ports).red().bold(),
-        window_secs
+        paint(separator).bold(),
+        badge,
+        tier.to_uppercase(),
+        paint(format!("[IP: {}]", ip)).bold(),
+        paint(format!("{}", ports)).bold(),
+        window
     );
-    println!("{}", "─".repeat(SEPARATOR_WIDTH).red());
+    println!("{}", paint("─".repeat(SEPARATOR_WIDTH)));
     println!();
 }
 
-/// Alertă Slow Scan - roșu, mai puțin urgent dar la fel de periculos
-pub fn log_slow_scan_alert(ip: &std::net::IpAddr, ports: usize, window_mins: u64) {
+/// Alertă de scan distribuit - magenta, listează sursele participante
+pub fn log_distributed_scan_alert(
+    dest: Option<&std::net::IpAddr>,
+    sources: usize,
+    ports: usize,
+    window_secs: u64,
+    sample: &[std::net::IpAddr],
+) {
+    if structured() {
+        return; // evenimentul `alert` structurat îl emite apelantul
+    }
     let ts = timestamp();
     let separator = "▶".repeat(3);
+    let target = dest.map(|d| d.to_string()).unwrap_or_else(|| "(global)".to_string());
 
     println!();
-    println!("{}", "─".repeat(SEPARATOR_WIDTH).yellow());
+    println!("{}", "─".repeat(SEPARATOR_WIDTH).magenta());
     println!(
-        "{} {} {} [SLOW SCAN] {} | {} porturi unice in {}min",
+        "{} {} {} [DISTRIBUTED SCAN] {} | {} surse, {} porturi unice in {}s",
         ts.bold().white(),
-        separator.yellow().bold(),
-        " ALERT ".on_yellow().black().bold(),
-        format!("[IP: {}]", ip).yellow().bold(),
-        format!("{}", ports).yellow().bold(),
-        window_mins
+        separator.magenta().bold(),
+        " ALERT ".on_magenta().white().bold(),
+        format!("[DST: {}]", target).magenta().bold(),
+        format!("{}", sources).magenta().bold(),
+        format!("{}", ports).magenta().bold(),
+        window_secs
     );
-    println!("{}", "─".repeat(SEPARATOR_WIDTH).yellow());
+    for ip in sample {
+        println!("{}   {} {}", " ".repeat(21), "↳".magenta(), ip);
+    }
+    if sources > sample.len() {
+        println!("{}   {} ... încă {} surse", " ".repeat(21), "↳".magenta(), sources - sample.len());
+    }
+    println!("{}", "─".repeat(SEPARATOR_WIDTH).magenta());
     println!();
 }
 
+/// Alertă ICMP sweep - cyan, o singură linie (recunoaștere, nu atac)
+pub fn log_icmp_sweep_alert(ip: &std::net::IpAddr, events: usize, destinations: usize, window_secs: u64) {
+    if structured() {
+        return; // evenimentul `alert` structurat îl emite apelantul
+    }
+    let ts = timestamp();
+    println!(
+        "{} {} {} [ICMP SWEEP] {} | {} drop-uri ICMP către {} destinații in {}s",
+        ts.bold().white(),
+        "▶".repeat(3).cyan().bold(),
+        " ALERT ".on_cyan().black().bold(),
+        format!("[IP: {}]", ip).cyan().bold(),
+        events,
+        format!("{}", destinations).cyan().bold(),
+        window_secs
+    );
+}
+
+/// Alertă acces-după-scan - fundal roșu, încadrată: posibilă intruziune reușită
+pub fn log_post_scan_access_alert(ip: &std::net::IpAddr, dest: Option<&std::net::IpAddr>, port: u16) {
+    if structured() {
+        return; // evenimentul `alert` structurat îl emite apelantul
+    }
+    let ts = timestamp();
+    let target = match dest {
+        Some(dest) => format!("{}:{}", dest, port),
+        None => format!("portul {}", port),
+    };
+
+    println!();
+    println!("{}", "═".repeat(SEPARATOR_WIDTH).red().bold());
+    println!(
+        "{} {} {} [ACCESS AFTER SCAN] {} | conexiune PERMISĂ către {} (port sondat anterior)",
+        ts.bold().white(),
+        "▶".repeat(3).red().bold(),
+        " ALERT ".on_red().white().bold(),
+        format!("[IP: {}]", ip).red().bold(),
+        target.red().bold()
+    );
+    println!("{}", "═".repeat(SEPARATOR_WIDTH).red().bold());
+    println!();
+}
+
+/// Revenirea unui recidivist - galben, o singură linie (informativă)
+pub fn log_known_offender_alert(ip: &std::net::IpAddr, alerts: u32, since_alert_secs: u64) {
+    if structured() {
+        return; // evenimentul `alert` structurat îl emite apelantul
+    }
+    let ts = timestamp();
+    println!(
+        "{} {} {} [KNOWN OFFENDER] {} | revine după o pauză: {} alerte anterioare, ultima acum {}min",
+        ts.bold().white(),
+        "▶".repeat(3).yellow().bold(),
+        " NOTICE ".on_yellow().black().bold(),
+        format!("[IP: {}]", ip).yellow().bold(),
+        alerts,
+        since_alert_secs / 60
+    );
+}
+
+/// Tranziția Threat -> Clean a unei surse (`[detection] notify_clear`)
+pub fn log_scan_cleared(ip: &std::net::IpAddr, active_secs: u64, peak_severity: u8) {
+    if structured() {
+        tracing::info!(%ip, active_secs, peak_severity, "Activitate încetată");
+        return;
+    }
+    let ts = timestamp();
+    println!(
+        "{} {} {} revenit la Clean - activitate încetată după {}s (severitate maximă {})",
+        ts.dimmed(),
+        "[CLEAR]".bold().green(),
+        format!("[IP: {}]", ip).bold(),
+        active_secs,
+        peak_severity
+    );
+}
+
+/// Alertă suprimată în perioada de încălzire (afișată, dar netrimisă)
+pub fn log_alert_suppressed(ip: &std::net::IpAddr, scan_type: &str, remaining_secs: u64) {
+    if structured() {
+        tracing::info!(%ip, scan_type, remaining_secs, "Alertă suprimată (warmup)");
+        return;
+    }
+    let ts = timestamp();
+    println!(
+        "{} {} {} {} pentru {} - warmup încă {}s, alerta nu este trimisă",
+        ts.dimmed(),
+        "[SUPPRESSED (warmup)]".bold().bright_black(),
+        scan_type.bright_black(),
+        "ALERT".bright_black(),
+        format!("{}", ip).bold(),
+        remaining_secs
+    );
+}
+
 /// Confirmă că o alertă a fost trimisă cu succes (verde subtil)
 pub fn log_alert_sent(destination: &str, alert_type: &str) {
+    if structured() {
+        tracing::info!(destination, alert_type, "Alertă transmisă");
+        return;
+    }
     let ts = timestamp();
     println!(
         "{} {} Alert '{}' transmis -> {}",
@@ -167,20 +374,168 @@ pub fn log_alert_sent(destination: &str, alert_type: &str) {
     );
 }
 
+// ---------------------------------------------------------------------------
+// Liniile [DROP] - singurul output per pachet
+//
+// În timpul unui scan real sosesc mii de drop-uri pe secundă: afișate
+// individual, ele împing bannerele de alertă din ecran și, pentru că
+// fiecare `println!` ia lock-ul stdout, încetinesc procesarea pachetelor.
+//
+// Modul `[display] drop_events`:
+//   - "all"        : o linie per eveniment (comportamentul istoric)
+//   - "aggregated" : evenimentele merg printr-un canal către un task care,
+//                    la fiecare fereastră, afișează O linie per sursă
+//   - "off"        : fără linii [DROP]
+//
+// `OnceLock` ține modul global, setat o singură dată la pornire de
+// `init_drop_events`. Până atunci (sau fără init) se afișează tot.
+// ---------------------------------------------------------------------------
+static DROP_SINK: OnceLock<DropSink> = OnceLock::new();
+
+enum DropSink {
+    Off,
+    Aggregated(mpsc::Sender<(IpAddr, EventKind)>),
+}
+
+/// Capacitatea canalului către task-ul de agregare. Plin = evenimentul
+/// nu mai apare în consolă (detecția nu este afectată).
+const DROP_CHANNEL_CAPACITY: usize = 10_000;
+
+/// Câte porturi distincte se listează într-o linie agregată
+const DROP_PORTS_SHOWN: usize = 10;
+
+/// Câte surse (cele mai active) se afișează per fereastră
+const DROP_SOURCES_SHOWN: usize = 20;
+
+/// Evenimentele unei surse în fereastra curentă de agregare
+#[derive(Default)]
+struct DropSummary {
+    events: u64,
+    ports:  Vec<u16>,
+    icmp:   u64,
+}
+
+/// Configurează afișarea liniilor [DROP]; în modul agregat pornește task-ul
+/// de buffering (trebuie apelată din runtime-ul tokio)
+pub fn init_drop_events(mode: DropEventsMode, window: Duration) {
+    let sink = match mode {
+        DropEventsMode::All => return,
+        DropEventsMode::Off => DropSink::Off,
+        DropEventsMode::Aggregated => {
+            let (tx, rx) = mpsc::channel(DROP_CHANNEL_CAPACITY);
+            tokio::spawn(aggregate_drop_events(rx, window));
+            DropSink::Aggregated(tx)
+        }
+    };
+    let _ = DROP_SINK.set(sink);
+}
+
 /// Logarea unui eveniment de pachet primit (drop firewall) - albastru subtil
-pub fn log_drop_event(ip: &std::net::IpAddr, port: u16) {
+pub fn log_drop_event(ip: &IpAddr, kind: &EventKind) {
+    if structured() {
+        return; // evenimentul `record` structurat îl emite apelantul
+    }
+    match DROP_SINK.get() {
+        None => print_drop_line(ip, kind),
+        Some(DropSink::Off) => {}
+        // `try_send` nu așteaptă: consola nu are voie să frâneze procesarea
+        Some(DropSink::Aggregated(tx)) => {
+            let _ = tx.try_send((*ip, *kind));
+        }
+    }
+}
+
+fn print_drop_line(ip: &IpAddr, kind: &EventKind) {
     let ts = timestamp();
+    let detail = match kind {
+        EventKind::Port(port) => format!("DstPort={}", port.to_string().bright_blue()),
+        EventKind::Icmp { icmp_type: Some(t) } => format!("ICMP type={}", t.to_string().bright_blue()),
+        EventKind::Icmp { icmp_type: None } => "ICMP".to_string(),
+        other => format!("{:?}", other),
+    };
     println!(
-        "{} {} Src={} DstPort={}",
+        "{} {} Src={} {}",
         ts.dimmed(),
         "[DROP]".blue(),
         format!("{}", ip).bright_blue(),
-        format!("{}", port).bright_blue()
+        detail
     );
 }
 
+// ---------------------------------------------------------------------------
+// Task-ul de agregare: colectează evenimentele unei ferestre, apoi afișează
+// o linie per sursă, ex: `[DROP] Src=1.2.3.4 ports=22,23,80 (+417 more events)`
+// ---------------------------------------------------------------------------
+async fn aggregate_drop_events(mut rx: mpsc::Receiver<(IpAddr, EventKind)>, window: Duration) {
+    let mut pending: HashMap<IpAddr, DropSummary> = HashMap::new();
+    let mut interval = tokio::time::interval(window);
+
+    loop {
+        tokio::select! {
+            event = rx.recv() => {
+                let Some((ip, kind)) = event else { return };
+                let summary = pending.entry(ip).or_default();
+                summary.events += 1;
+                match kind {
+                    EventKind::Port(port)
+                        if summary.ports.len() < DROP_PORTS_SHOWN && !summary.ports.contains(&port) =>
+                    {
+                        summary.ports.push(port);
+                    }
+                    EventKind::Icmp { .. } => summary.icmp += 1,
+                    // Porturi peste limită, tipuri noi: doar în total ("+N more events")
+                    _ => {}
+                }
+            }
+            _ = interval.tick() => flush_drop_summaries(std::mem::take(&mut pending)),
+        }
+    }
+}
+
+fn flush_drop_summaries(pending: HashMap<IpAddr, DropSummary>) {
+    if pending.is_empty() {
+        return;
+    }
+
+    // Sursele cele mai active primele; restul doar se numără
+    let mut rows: Vec<(IpAddr, DropSummary)> = pending.into_iter().collect();
+    rows.sort_by(|a, b| b.1.events.cmp(&a.1.events).then(a.0.cmp(&b.0)));
+    let hidden = rows.len().saturating_sub(DROP_SOURCES_SHOWN);
+
+    let ts = timestamp();
+    for (ip, summary) in rows.into_iter().take(DROP_SOURCES_SHOWN) {
+        let mut detail = Vec::new();
+        if !summary.ports.is_empty() {
+            let ports: Vec<String> = summary.ports.iter().map(u16::to_string).collect();
+            detail.push(format!("ports={}", ports.join(",").bright_blue()));
+        }
+        if summary.icmp > 0 {
+            detail.push(format!("icmp={}", summary.icmp.to_string().bright_blue()));
+        }
+
+        // Evenimentele care nu se văd deja în linie (porturi repetate sau peste limită)
+        let shown = summary.ports.len() as u64 + summary.icmp;
+        let more = summary.events.saturating_sub(shown);
+        println!(
+            "{} {} Src={} {}{}",
+            ts.dimmed(),
+            "[DROP]".blue(),
+            format!("{}", ip).bright_blue(),
+            detail.join(" "),
+            if more > 0 { format!(" (+{} more events)", more).dimmed().to_string() } else { String::new() }
+        );
+    }
+    if hidden > 0 {
+        println!("{} {} ... încă {} surse în această fereastră", ts.dimmed(), "[DROP]".blue(), hidden);
+    }
+}
+
 /// Logarea cleanup-ului periodic
 pub fn log_cleanup(removed_ips: usize) {
+    if structured() {
+        tracing::info!(removed_ips, "Cleanup periodic");
+        return;
+    }
     let ts = timestamp();
     println!(
         "{} {} {} intrari de IP vechi eliminate din memorie",
@@ -190,6 +545,202 @@ pub fn log_cleanup(removed_ips: usize) {
     );
 }
 
+/// Sumarul periodic: dimensiunile stării interne
+pub fn log_state_stats(stats: &StateStats) {
+    if structured() {
+        tracing::info!(
+            tracked_ips = stats.tracked_ips,
+            icmp_ips = stats.icmp_ips,
+            cooldowns = stats.cooldowns,
+            active_scans = stats.active_scans,
+            repeat_offenders = stats.repeat_offenders,
+            "Statistici stare"
+        );
+        return;
+    }
+    let ts = timestamp();
+    println!(
+        "{} {} Stare: {} IP-uri urmărite | {} ICMP | {} în cooldown | {} active | {} recidiviști",
+        ts.dimmed(),
+        "[STATE]".cyan(),
+        stats.tracked_ips,
+        stats.icmp_ips,
+        stats.cooldowns,
+        stats.active_scans,
+        stats.repeat_offenders
+    );
+}
+
+/// Rezultatul subcomenzii `bench` (consolă, indiferent de `log_format`)
+pub fn print_bench_report(report: &crate::bench::BenchReport) {
+    let mib = |bytes: Option<u64>| bytes.map_or("n/a".to_string(), |b| format!("{:.1} MiB", b as f64 / 1_048_576.0));
+    print_separator();
+    println!("  {} {:.0} evenimente/s", "Throughput:".bold(), report.events_per_sec());
+    println!(
+        "  {} {} evenimente în {:.1}s, {} worker-i ({} alerte, {} evaluări amânate)",
+        "Încărcare: ".bold(),
+        report.events,
+        report.elapsed.as_secs_f64(),
+        report.workers,
+        report.alerts,
+        report.deferred
+    );
+    println!(
+        "  {} p50 {:?} | p99 {:?} | max {:?}",
+        "Latență:   ".bold(),
+        report.latency_p50,
+        report.latency_p99,
+        report.latency_max
+    );
+    println!("  {} {} IP-uri urmărite (vârf)", "Stare:     ".bold(), report.peak_tracked_ips);
+    println!(
+        "  {} {} la pornire, {} vârf (VmHWM)",
+        "Memorie:   ".bold(),
+        mib(report.rss_start_bytes),
+        mib(report.rss_peak_bytes)
+    );
+    print_separator();
+}
+
+/// Sumarul periodic al contoarelor parserului activ
+pub fn log_parser_stats(parser_name: &str, stats: &crate::diagnostics::ParserStatsSnapshot) {
+    if structured() {
+        tracing::info!(
+            parser = parser_name,
+            lines = stats.lines_seen,
+            matched = stats.matched,
+            ignored_action = stats.ignored_action,
+            unrecognized = stats.unrecognized,
+            "Statistici parser"
+        );
+        return;
+    }
+    let ts = timestamp();
+    let ignored: Vec<String> = stats
+        .ignored_by_action
+        .iter()
+        .map(|(action, count)| format!("{}={}", action, count))
+        .collect();
+
+    println!(
+        "{} {} Parser '{}' : {} linii | {} potrivite | {} acțiune ignorată{} | {} nerecunoscute",
+        ts.dimmed(),
+        "[PARSE]".cyan(),
+        parser_name.bold().cyan(),
+        stats.lines_seen,
+        format!("{}", stats.matched).green(),
+        stats.ignored_action,
+        if ignored.is_empty() { String::new() } else { format!(" ({})", ignored.join(", ")) },
+        format!("{}", stats.unrecognized).yellow()
+    );
+}
+
+/// Sumarul periodic al parsării pentru un exporter
+pub fn log_parse_summary(source: &std::net::IpAddr, lines: u64, failures: u64) {
+    if structured() {
+        tracing::info!(%source, lines, failures, "Sumar parsare exporter");
+        return;
+    }
+    let ts = timestamp();
+    let percent = if lines > 0 { failures as f64 * 100.0 / lines as f64 } else { 0.0 };
+    println!(
+        "{} {} Sursa {} : {} linii, {} nerecunoscute ({:.1}%)",
+        ts.dimmed(),
+        "[PARSE]".cyan(),
+        format!("{}", source).bold().cyan(),
+        lines,
+        failures,
+        percent
+    );
+}
+
+/// Sumarul periodic: datagramele cu UTF-8 invalid / trunchiate și liniile
+/// prea lungi (de la pornire)
+pub fn log_datagram_stats(lossy: u64, truncated: u64, oversized: u64) {
+    if structured() {
+        tracing::info!(lossy, truncated, oversized, "Sumar datagrame");
+        return;
+    }
+    let ts = timestamp();
+    println!(
+        "{} {} Datagrame: {} cu UTF-8 invalid, {} trunchiate; {} linii prea lungi sărite",
+        ts.dimmed(),
+        "[PARSE]".cyan(),
+        lossy,
+        truncated,
+        oversized
+    );
+}
+
+/// Sumarul periodic: evenimentele scrise / pierdute de `[event_log]` (de la pornire)
+pub fn log_event_log_stats(written: u64, dropped: u64) {
+    if structured() {
+        tracing::info!(written, dropped, "Sumar event_log");
+        return;
+    }
+    let ts = timestamp();
+    let dropped_text = format!("{} pierdute", dropped);
+    println!(
+        "{} {} Jurnal evenimente: {} scrise, {}",
+        ts.dimmed(),
+        "[EVENTS]".cyan(),
+        written,
+        if dropped > 0 { dropped_text.yellow() } else { dropped_text.normal() }
+    );
+}
+
+/// Sumarul periodic: contoarele de ingestie ale unui listener sau parser
+pub fn log_ingest_stats(kind: &str, stats: &crate::diagnostics::IngestSnapshot) {
+    if structured() {
+        tracing::info!(
+            kind,
+            name = %stats.name,
+            datagrams = stats.datagrams,
+            lines = stats.lines,
+            parsed = stats.parsed,
+            rejected = stats.rejected,
+            last_event = stats.last_event.map(|t| t.to_rfc3339()),
+            "Sumar ingestie"
+        );
+        return;
+    }
+    let ts = timestamp();
+    let last_event = match stats.last_event {
+        Some(t) => t.with_timezone(&Local).format("%H:%M:%S").to_string(),
+        None => "niciodată".to_string(),
+    };
+    println!(
+        "{} {} {} '{}': {} datagrame | {} linii | {} parsate | {} respinse | ultimul eveniment: {}",
+        ts.dimmed(),
+        "[INGEST]".cyan(),
+        kind,
+        stats.name.bold().cyan(),
+        stats.datagrams,
+        stats.lines,
+        format!("{}", stats.parsed).green(),
+        if stats.rejected > 0 { format!("{}", stats.rejected).yellow() } else { format!("{}", stats.rejected).normal() },
+        last_event
+    );
+}
+
+/// Sumarul periodic: regulile firewall-ului cu cele mai multe drop-uri
+pub fn log_top_rules(rules: &[RuleSummary]) {
+    if structured() {
+        for (rank, rule) in rules.iter().enumerate() {
+            tracing::info!(rank = rank + 1, rule = %rule.rule, hits = rule.hits, "Top reguli firewall");
+        }
+        return;
+    }
+    let ts = timestamp();
+    let list: Vec<String> = rules.iter().map(|r| format!("{} ({})", r.rule, r.hits)).collect();
+    println!(
+        "{} {} Top reguli cu drop-uri: {}",
+        ts.dimmed(),
+        "[RULES]".cyan(),
+        list.join(", ")
+    );
+}
+
 // ---------------------------------------------------------------------------
 // Funcție helper privată: returnează timestamp-ul curent formatat
 //
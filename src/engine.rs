@@ -0,0 +1,499 @@
+// ============================================================
+//  engine.rs - Motorul de detecție: eveniment -> decizie de alertă
+// ============================================================
+//
+//  Fluxul complet al unui eveniment parsat - înregistrarea în stare,
+//  evaluarea, gating-ul de cooldown - fără niciun side-effect extern.
+//  `Engine::ingest` returnează decizia (`Option<Alert>`); ce se face cu ea
+//  (consolă, SIEM, email, blocare pe firewall) decide apelantul.
+//
+//  Binarul `rust-ids` este un astfel de apelant: leagă socket-urile de
+//  `Engine` și trimite alertele returnate către sink-uri. Un colector
+//  propriu poate face același lucru cu sursa și reacțiile lui.
+//
+//  Concepte Rust demonstrate:
+//  - Separarea deciziei de efect: funcții care returnează date, nu acționează
+//  - `Option<T>` ca rezultat al unei decizii ("alertă sau nu")
+//  - `let ... else` : ieșire timpurie din pattern matching
+// ============================================================
+
+use crate::clock::{Clock, SystemClock};
+use crate::config::{AlertConfig, Config, DetectionConfig};
+use crate::detector::{
+    evaluate_and_record, evaluate_distributed, evaluate_icmp, evaluate_post_scan_access, evaluate_returning_offender,
+    evaluate_throttled, evidence, holds_threat, DetectionEvidence, DetectionResult, Throttled,
+};
+use crate::labels::LabelResolver;
+use crate::logging::EVENTS_TARGET;
+use crate::parser::LogEntry;
+use crate::state::{RuleSummary, SharedState, TargetSummary, TOP_RULES, TOP_TARGETS};
+use std::net::IpAddr;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+// ---------------------------------------------------------------------------
+// O alertă decisă de motor: trecută de cooldown, gata de trimis.
+//
+// Conține tot ce au nevoie sink-urile; contextul (liniile brute) se ia la
+// trimitere din `state().context()`, cu numărul de linii al apelantului.
+// ---------------------------------------------------------------------------
+#[derive(Debug, Clone, PartialEq)]
+pub struct Alert {
+    /// IP-ul sursă (la scanările distribuite: sursa cu cele mai multe porturi)
+    pub ip:        IpAddr,
+
+    /// Destinația evenimentului care a declanșat alerta, dacă log-ul o are
+    pub dest:      Option<IpAddr>,
+
+    /// Rezultatul detecției
+    pub result:    DetectionResult,
+
+    /// Severitatea finală (după maparea `[alert]`), cea care ajunge în SIEM
+    pub severity:  u8,
+
+    /// Țintele principale ale sursei (doar la scanările de porturi)
+    pub targets:   Vec<TargetSummary>,
+
+    /// Regulile firewall-ului care au blocat sursa
+    pub rules:     Vec<RuleSummary>,
+
+    /// Cooldown-ul per IP pornit de alertă (doar la scanările de porturi)
+    pub cooldown:  Option<Duration>,
+
+    /// Alerta a trecut de cooldown fiind mai severă decât precedenta
+    pub escalated: bool,
+
+    /// Tag-urile sursei din `[[labels]]` (gol dacă nicio rețea nu o conține)
+    pub labels:    Vec<String>,
+
+    /// De ce a declanșat (doar la scanările de porturi per sursă)
+    pub evidence:  Option<DetectionEvidence>,
+}
+
+impl Alert {
+    /// Răspunsul activ (blocarea pe firewall) se aplică acestei alerte?
+    ///
+    /// Doar scanărilor de porturi și accesului după scan: un ping sweep
+    /// sau revenirea unui recidivist sunt informative, iar la o scanare
+    /// distribuită sursa "reprezentativă" nu este singurul atacator.
+    pub fn blocks(&self) -> bool {
+        matches!(
+            self.result,
+            DetectionResult::Scan(_) | DetectionResult::MultiScan(_) | DetectionResult::PostScanAccess { .. }
+        )
+    }
+}
+
+/// O sursă revenită la Clean (tranziția Threat -> Clean, vezi `Engine::clear_pass`)
+#[derive(Debug, Clone, PartialEq)]
+pub struct ScanCleared {
+    pub ip:            IpAddr,
+    /// Cât a durat starea de amenințare
+    pub active_for:    Duration,
+    /// Cea mai mare severitate evaluată în activitate
+    pub peak_severity: u8,
+}
+
+/// Rezultatul unui `ingest_throttled`
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Ingested {
+    /// Alerta decisă acum, dacă există
+    pub alert:    Option<Alert>,
+
+    /// Evaluarea sursei a fost amânată: apelantul cheamă
+    /// `evaluate_deferred` după durata dată (altfel pragul poate fi ratat)
+    pub deferred: Option<Duration>,
+}
+
+// ---------------------------------------------------------------------------
+// Motorul: starea shared + configurația detecției
+//
+// `Engine` este `Send + Sync` (DashMap + date imutabile): un `Arc<Engine>`
+// se partajează între task-uri exact ca `SharedState`.
+// ---------------------------------------------------------------------------
+pub struct Engine {
+    state:     SharedState,
+    detection: DetectionConfig,
+    /// Maparea severităților: cooldown-ul compară severitatea trimisă efectiv
+    alert:     AlertConfig,
+    /// Etichetele surselor, rezolvate la alertare
+    labels:    LabelResolver,
+}
+
+impl Engine {
+    /// Motor cu stare nouă, dimensionată conform configurației
+    pub fn new(config: &Config) -> Self {
+        Self::with_clock(config, Arc::new(SystemClock))
+    }
+
+    /// Ca `new`, cu sursa de timp dată (ex: `MockClock` în teste)
+    pub fn with_clock(config: &Config, clock: Arc<dyn Clock>) -> Self {
+        let state = SharedState::new()
+            .with_clock(clock)
+            .with_context_buffer(config.alert.context_buffer_size)
+            .with_repeat_offender_window(config.detection.repeat_offender_window());
+        Self::with_state(state, config)
+    }
+
+    /// Motor peste o stare existentă (ex: partajată cu alte componente)
+    pub fn with_state(state: SharedState, config: &Config) -> Self {
+        Engine {
+            state,
+            detection: config.detection.clone(),
+            alert:     config.alert.clone(),
+            labels:    LabelResolver::new(&config.labels),
+        }
+    }
+
+    /// Starea shared (context, diagnostice, statistici)
+    pub fn state(&self) -> &SharedState {
+        &self.state
+    }
+
+    /// Configurația detecției
+    pub fn detection(&self) -> &DetectionConfig {
+        &self.detection
+    }
+
+    // -----------------------------------------------------------------------
+    // Procesează un eveniment parsat, văzut acum, și returnează alerta
+    // decisă (dacă există). Fiecare eveniment este evaluat - pentru fluxuri
+    // mari, `ingest_throttled` evită re-evaluarea la fiecare pachet.
+    // -----------------------------------------------------------------------
+    pub fn ingest(&self, entry: LogEntry) -> Option<Alert> {
+        self.ingest_at(entry, self.state.now())
+    }
+
+    /// Ca `ingest`, cu momentul dat (evenimente sintetice, datate în trecut)
+    pub fn ingest_at(&self, entry: LogEntry, seen_at: Instant) -> Option<Alert> {
+        self.process(&entry, seen_at, false).alert
+    }
+
+    /// Ca `ingest`, cu throttling-ul evaluării (`evaluation_min_interval_ms`)
+    /// - pentru fluxul per-pachet în timpul unui flood
+    pub fn ingest_throttled(&self, entry: LogEntry) -> Ingested {
+        self.process(&entry, self.state.now(), true)
+    }
+
+    /// Evaluarea amânată de `ingest_throttled` (vezi `Ingested::deferred`)
+    pub fn evaluate_deferred(&self, ip: &IpAddr) -> Option<Alert> {
+        let detection = evaluate_and_record(ip, &self.state, &self.detection);
+        self.port_alert(ip, None, detection)
+    }
+
+    // -----------------------------------------------------------------------
+    // Fluxul comun: accept -> corelarea acces-după-scan; drop -> revenirea
+    // recidivistului, regula, apoi ICMP sau port.
+    //
+    // Un singur drop poate produce și notificarea de recidivist, și o
+    // alertă de scan (doar cu praguri de 0 porturi); alerta de scan câștigă.
+    // -----------------------------------------------------------------------
+    fn process(&self, entry: &LogEntry, seen_at: Instant, throttle: bool) -> Ingested {
+        if entry.accepted {
+            return Ingested { alert: self.accept(entry), deferred: None };
+        }
+
+        // Verificat înainte ca drop-ul să refacă istoricul sursei
+        let returned = self.returning_offender(entry);
+
+        if let Some(rule) = &entry.rule {
+            self.state.record_rule(entry.source_ip, rule);
+        }
+
+        // ICMP nu are port: flux separat, care nu atinge numărătoarea porturilor
+        let mut ingested = match entry.dest_port() {
+            Some(port) => self.port_event(entry, port, seen_at, throttle),
+            None => Ingested { alert: self.icmp(entry), deferred: None },
+        };
+        ingested.alert = ingested.alert.or(returned);
+        ingested
+    }
+
+    // -----------------------------------------------------------------------
+    // Un eveniment cu port: stare, detecție, cooldown.
+    //
+    // Porturile excluse (`ignore_ports` / `port_range`) rămân în istoric,
+    // dar nu contează la pragurile de porturi unice și la detecția distribuită.
+    // -----------------------------------------------------------------------
+    fn port_event(&self, entry: &LogEntry, port: u16, seen_at: Instant, throttle: bool) -> Ingested {
+        let ip = entry.source_ip;
+        self.state.record_port_event_at(ip, port, entry.protocol.as_deref(), seen_at);
+        let counted = self.detection.counts_port(port);
+        tracing::debug!(target: EVENTS_TARGET, ip = %ip, port, counted, "record");
+        if let Some(dest_ip) = entry.dest_ip {
+            self.state.record_target(ip, dest_ip, port);
+        }
+
+        let mut deferred = None;
+        let detection = if throttle {
+            match evaluate_throttled(&ip, &self.state, &self.detection) {
+                Throttled::Evaluated(detection) => detection,
+                Throttled::Skipped => DetectionResult::Clean,
+                Throttled::Deferred(delay) => {
+                    deferred = Some(delay);
+                    DetectionResult::Clean
+                }
+            }
+        } else {
+            evaluate_and_record(&ip, &self.state, &self.detection)
+        };
+        let alert = self.port_alert(&ip, entry.dest_ip, detection);
+
+        // Detecția distribuită: doar înregistrăm perechea (sursă, port);
+        // agregarea între surse rulează periodic în `distributed_pass`
+        if self.detection.distributed_enabled() && counted {
+            self.state.record_distributed_at(
+                entry.dest_ip,
+                ip,
+                port,
+                self.detection.distributed_scan_window_secs,
+                seen_at,
+            );
+        }
+
+        Ingested { alert, deferred }
+    }
+
+    // -----------------------------------------------------------------------
+    // Gating-ul unei scanări de porturi: histerezis, apoi cooldown.
+    //
+    // Alerta pleacă la tranziția Clean -> Threat (dacă IP-ul nu e în
+    // cooldown). Cât timp sursa rămâne activă (până la `clear_pass`), doar
+    // o severitate mai mare decât maximul activității mai trece: un atacator
+    // care oscilează în jurul pragului nu re-alertează la fiecare expirare
+    // a cooldown-ului. Comparăm severitatea finală (după mapare), cea care
+    // ajunge în SIEM.
+    // -----------------------------------------------------------------------
+    fn port_alert(&self, ip: &IpAddr, dest: Option<IpAddr>, detection: DetectionResult) -> Option<Alert> {
+        if !detection.is_threat() {
+            return None;
+        }
+        trace_detection(ip, &detection, "detect", &[]);
+
+        let policy = self.detection.cooldown_policy();
+        let severity = self.alert.alert_severity(&detection);
+        let tiers: Vec<&str> = detection.hits().iter().map(|hit| hit.tier.as_str()).collect();
+        let escalate = self.detection.escalate_through_cooldown;
+
+        let (cooldown, escalated) = match self.state.enter_scan(*ip, severity, &tiers) {
+            // Tranziția Clean -> Threat
+            None if !self.state.is_in_cooldown(ip) => (self.state.mark_alerted(*ip, &policy, severity), false),
+            None if escalate && self.state.is_escalation(ip, severity) => {
+                (self.state.mark_escalated(*ip, &policy, severity), true)
+            }
+            // Aceeași activitate, mai severă decât tot ce a produs până acum
+            Some(peak) if escalate && severity > peak => {
+                let cooldown = if self.state.is_in_cooldown(ip) {
+                    self.state.mark_escalated(*ip, &policy, severity)
+                } else {
+                    self.state.mark_alerted(*ip, &policy, severity)
+                };
+                (cooldown, true)
+            }
+            _ => return None,
+        };
+        let labels = self.labels.tags(ip);
+        trace_detection(ip, &detection, "alert", &labels);
+        self.state.remember_offender(*ip);
+        let evidence = evidence(ip, &self.state, &self.detection, &detection);
+
+        Some(Alert {
+            ip: *ip,
+            dest,
+            result: detection,
+            severity,
+            targets: self.state.targets_for(ip).into_iter().take(TOP_TARGETS).collect(),
+            rules: self.state.rules_for(ip).into_iter().take(TOP_RULES).collect(),
+            cooldown: Some(cooldown),
+            escalated,
+            labels,
+            evidence,
+        })
+    }
+
+    /// Un drop ICMP: istoric separat per sursă + detecția ICMP sweep
+    fn icmp(&self, entry: &LogEntry) -> Option<Alert> {
+        let ip = entry.source_ip;
+        self.state.record_icmp(ip, entry.dest_ip);
+        tracing::debug!(target: EVENTS_TARGET, ip = %ip, proto = "icmp", "record");
+
+        let detection = evaluate_icmp(&ip, &self.state, &self.detection);
+        if !detection.is_threat() || self.state.is_icmp_in_cooldown(&ip, self.detection.alert_cooldown_secs) {
+            return None;
+        }
+        self.state.mark_icmp_alerted(ip);
+        Some(self.alert(entry, detection))
+    }
+
+    // -----------------------------------------------------------------------
+    // Un accept: un scanner semnalat ajunge pe un port sondat?
+    //
+    // Cooldown-ul este per (sursă, port), separat de cel al scanărilor: o
+    // intruziune reușită nu trebuie ascunsă de alerta de scan de acum un minut.
+    // -----------------------------------------------------------------------
+    fn accept(&self, entry: &LogEntry) -> Option<Alert> {
+        let ip = entry.source_ip;
+        let port = entry.dest_port()?;
+
+        let detection = evaluate_post_scan_access(&ip, port, &self.state, &self.detection);
+        if !detection.is_threat()
+            || self.state.is_post_scan_in_cooldown(&ip, port, self.detection.alert_cooldown_secs)
+        {
+            return None;
+        }
+        self.state.mark_post_scan_alerted(ip, port);
+        Some(self.alert(entry, detection))
+    }
+
+    // -----------------------------------------------------------------------
+    // Revenirea unui recidivist (`repeat_offender_action = "notify"`).
+    //
+    // Fără cooldown propriu: notificarea pleacă doar la primul drop de după
+    // pauză (apoi sursa are din nou istoric) și nu marchează cooldown-ul
+    // scanărilor - un scan reluat trebuie alertat normal.
+    // -----------------------------------------------------------------------
+    fn returning_offender(&self, entry: &LogEntry) -> Option<Alert> {
+        let detection = evaluate_returning_offender(&entry.source_ip, &self.state, &self.detection);
+        detection.is_threat().then(|| self.alert(entry, detection))
+    }
+
+    /// Alerta unei detecții per sursă fără cooldown escaladat (ICMP, accept,
+    /// recidivist): doar regulile sursei, fără ținte
+    fn alert(&self, entry: &LogEntry, detection: DetectionResult) -> Alert {
+        let ip = entry.source_ip;
+        let labels = self.labels.tags(&ip);
+        trace_detection(&ip, &detection, "alert", &labels);
+        // Notificarea de recidivist nu reîmprospătează memoria: doar alertele reale o fac
+        if !matches!(detection, DetectionResult::KnownOffenderReturned { .. }) {
+            self.state.remember_offender(ip);
+        }
+        Alert {
+            ip,
+            dest: entry.dest_ip,
+            severity: self.alert.alert_severity(&detection),
+            result: detection,
+            targets: Vec::new(),
+            rules: self.state.rules_for(&ip).into_iter().take(TOP_RULES).collect(),
+            cooldown: None,
+            escalated: false,
+            labels,
+            evidence: None,
+        }
+    }
+
+    // -----------------------------------------------------------------------
+    // Pasul periodic de agregare între surse (scanări distribuite)
+    //
+    // Evaluarea fiecărei destinații costă O(perechi din bucket) - prea mult
+    // pentru fiecare pachet în timpul unui flood, dar neglijabil o dată la
+    // câteva secunde. Cooldown-ul este per destinație, astfel încât fiecare
+    // sursă nouă din botnet să nu re-declanșeze aceeași alertă.
+    // -----------------------------------------------------------------------
+    pub fn distributed_pass(&self) -> Vec<Alert> {
+        let mut alerts = Vec::new();
+        for dest in self.state.distributed_keys() {
+            if self.state.is_distributed_in_cooldown(&dest, self.detection.alert_cooldown_secs) {
+                continue;
+            }
+
+            let detection = evaluate_distributed(&dest, &self.state, &self.detection);
+            let DetectionResult::DistributedScan { sample, .. } = &detection else {
+                continue;
+            };
+            // Sursa "reprezentativă" a alertei: cea care a atins cele mai multe porturi.
+            // Fără ea nu pleacă nicio alertă, deci nici cooldown-ul nu pornește.
+            let Some(lead_source) = sample.first().copied() else {
+                continue;
+            };
+            self.state.mark_distributed_alerted(dest);
+
+            let labels = self.labels.tags(&lead_source);
+            trace_detection(&lead_source, &detection, "alert", &labels);
+            alerts.push(Alert {
+                ip:        lead_source,
+                dest,
+                severity:  self.alert.alert_severity(&detection),
+                result:    detection,
+                targets:   Vec::new(),
+                rules:     Vec::new(),
+                cooldown:  None,
+                escalated: false,
+                labels,
+                evidence:  None,
+            });
+        }
+        alerts
+    }
+
+    // -----------------------------------------------------------------------
+    // Pasul periodic al histerezisului: sursele active ale căror porturi au
+    // scăzut sub pragul de eliberare (`clear_ratio`) revin la Clean.
+    //
+    // Rulează periodic, nu la fiecare eveniment: o sursă care s-a oprit nu
+    // mai produce evenimente, dar tot trebuie eliberată (și notificată).
+    // -----------------------------------------------------------------------
+    pub fn clear_pass(&self) -> Vec<ScanCleared> {
+        let mut cleared = Vec::new();
+        for ip in self.state.active_scan_ips() {
+            let Some(scan) = self.state.active_scan(&ip) else {
+                continue;
+            };
+            if holds_threat(&ip, &self.state, &self.detection, &scan.tiers) {
+                continue;
+            }
+            let Some(scan) = self.state.leave_scan(&ip) else {
+                continue;
+            };
+            tracing::debug!(target: EVENTS_TARGET, ip = %ip, peak_severity = scan.peak_severity, "clear");
+            cleared.push(ScanCleared {
+                ip,
+                active_for:    self.state.now().saturating_duration_since(scan.since),
+                peak_severity: scan.peak_severity,
+            });
+        }
+        cleared
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Evenimentul structurat al unei detecții (`detect` la fiecare evaluare
+// amenințătoare, `alert` când alerta chiar pleacă). Câmpurile sunt aceleași
+// pentru toate tipurile, ca interogările din Loki/Elastic să fie uniforme;
+// `labels` (tag-urile sursei) apar doar pe evenimentul `alert`.
+// ---------------------------------------------------------------------------
+fn trace_detection(ip: &IpAddr, detection: &DetectionResult, event: &str, labels: &[String]) {
+    let unique_ports = match detection {
+        DetectionResult::DistributedScan { total_unique_ports, .. } => Some(*total_unique_ports),
+        _ => detection.primary().map(|hit| hit.ports),
+    };
+    let port = match detection {
+        DetectionResult::PostScanAccess { port } => Some(*port),
+        _ => None,
+    };
+
+    let tags = (!labels.is_empty()).then(|| labels.join(","));
+
+    if event == "alert" {
+        tracing::info!(
+            target: EVENTS_TARGET,
+            ip = %ip,
+            port,
+            scan_type = %detection.scan_type_label(),
+            unique_ports,
+            severity = detection.severity(),
+            sig_id = detection.sig_id(),
+            labels = tags.as_deref(),
+            "alert"
+        );
+    } else {
+        tracing::debug!(
+            target: EVENTS_TARGET,
+            ip = %ip,
+            scan_type = %detection.scan_type_label(),
+            unique_ports,
+            severity = detection.severity(),
+            "{}",
+            event
+        );
+    }
+}
@@ -0,0 +1,248 @@
+// ============================================================
+//  event_log.rs - Jurnalul evenimentelor normalizate (`[event_log]`)
+// ============================================================
+//
+//  Unele regimuri de conformitate cer păstrarea fluxului NORMALIZAT de
+//  evenimente - fiecare `LogEntry` parsat, nu doar log-urile brute sau
+//  alertele. Fiecare eveniment devine o linie JSON într-un fișier:
+//
+//    {"source_ip":"10.0.0.5","dest_ip":null,"kind":{"Port":22},"protocol":"tcp",...}
+//
+//  Scrierea pe disc nu are voie să încetinească parsarea: `process_packet`
+//  pune evenimentul într-un canal MĂRGINIT (`try_send`), iar un thread
+//  dedicat scrie. Un disc lent umple canalul; evenimentele care nu mai
+//  încap sunt numărate ca pierdute, memoria rămâne plafonată.
+//
+//  Peste `rotate_size_bytes` fișierul curent se redenumește cu un sufix de
+//  timp (`events.jsonl.20261018-142501`) și se deschide unul nou.
+//
+//  Același writer (`EventLogWriter`, orice `Serialize`) ține și istoricul
+//  alertelor din `[alert_log]` - vezi alert_log.rs.
+//
+//  La oprire, `close` pune un marcaj la coada canalului: writer-ul scrie tot
+//  ce era în coadă, golește buffer-ul pe disc și se termină - chiar dacă
+//  task-uri încă în viață mai țin clone ale tap-ului.
+//
+//  Concepte Rust demonstrate:
+//  - `std::sync::mpsc::sync_channel` : canal mărginit, `try_send` fără blocare
+//  - `BufWriter` golit doar când canalul e gol (scrieri grupate)
+// ============================================================
+
+use crate::config::EventLogConfig;
+use crate::parser::LogEntry;
+use anyhow::{Context, Result};
+use chrono::Local;
+use serde::Serialize;
+use std::fs::{self, File, OpenOptions};
+use std::io::{BufWriter, Write};
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::mpsc::{self, Receiver, SyncSender, TryRecvError, TrySendError};
+use std::sync::Arc;
+use std::thread::JoinHandle;
+
+/// Evenimentele care pot aștepta scrierea pe disc
+pub const EVENT_LOG_CHANNEL_CAPACITY: usize = 10_000;
+
+// ---------------------------------------------------------------------------
+// Fișierul JSONL cu rotație după dimensiune (sincron - rulează pe thread-ul
+// writer-ului, sau direct în teste)
+// ---------------------------------------------------------------------------
+pub struct EventLogWriter {
+    /// Secțiunea din config.toml, pentru mesajele de eroare (`[event_log]`)
+    section:     &'static str,
+    path:        PathBuf,
+    rotate_size: u64,
+    file:        BufWriter<File>,
+    /// Dimensiunea fișierului curent, inclusiv ce e încă în buffer
+    size:        u64,
+}
+
+impl EventLogWriter {
+    /// Deschide (append) fișierul; `rotate_size` = 0 dezactivează rotația
+    pub fn open(path: impl Into<PathBuf>, rotate_size: u64) -> Result<Self> {
+        Self::open_as("[event_log]", path, rotate_size)
+    }
+
+    /// Ca `open`, pentru un alt jurnal JSONL (ex: `[alert_log]`)
+    pub fn open_as(section: &'static str, path: impl Into<PathBuf>, rotate_size: u64) -> Result<Self> {
+        let path = path.into();
+        let (file, size) = Self::open_file(section, &path)?;
+        Ok(EventLogWriter { section, path, rotate_size, file, size })
+    }
+
+    fn open_file(section: &str, path: &Path) -> Result<(BufWriter<File>, u64)> {
+        let file = OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(path)
+            .with_context(|| format!("Nu s-a putut deschide {} path = {}", section, path.display()))?;
+        let size = file.metadata().map(|m| m.len()).unwrap_or(0);
+        Ok((BufWriter::new(file), size))
+    }
+
+    /// Adaugă o linie JSON; rotește înainte dacă linia ar depăși limita
+    pub fn append(&mut self, record: &impl Serialize) -> Result<()> {
+        let mut line = serde_json::to_vec(record).context("Serializarea înregistrării a eșuat")?;
+        line.push(b'\n');
+        let len = line.len() as u64;
+
+        if self.rotate_size > 0 && self.size > 0 && self.size + len > self.rotate_size {
+            self.rotate()?;
+        }
+        self.file.write_all(&line).with_context(|| format!("Scrierea în {} a eșuat", self.section))?;
+        self.size += len;
+        Ok(())
+    }
+
+    pub fn flush(&mut self) -> Result<()> {
+        self.file.flush().with_context(|| format!("Golirea {} pe disc a eșuat", self.section))
+    }
+
+    // -----------------------------------------------------------------------
+    // Redenumește fișierul curent cu sufixul `.AAAALLZZ-HHMMSS` (plus `.N`
+    // dacă două rotații cad în aceeași secundă) și deschide unul nou.
+    // -----------------------------------------------------------------------
+    fn rotate(&mut self) -> Result<()> {
+        self.flush()?;
+        let stamp = Local::now().format("%Y%m%d-%H%M%S");
+        let mut rotated = PathBuf::from(format!("{}.{}", self.path.display(), stamp));
+        let mut n = 1;
+        while rotated.exists() {
+            rotated = PathBuf::from(format!("{}.{}.{}", self.path.display(), stamp, n));
+            n += 1;
+        }
+        fs::rename(&self.path, &rotated)
+            .with_context(|| format!("Rotația {} spre {} a eșuat", self.section, rotated.display()))?;
+        (self.file, self.size) = Self::open_file(self.section, &self.path)?;
+        Ok(())
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Capătul din fluxul de procesare: trimite evenimentele spre writer fără
+// să aștepte discul
+// ---------------------------------------------------------------------------
+pub struct EventTap {
+    sender:   SyncSender<Queued>,
+    counters: Arc<TapCounters>,
+}
+
+/// Ce circulă pe canal: evenimente, apoi (o dată) marcajul de închidere
+enum Queued {
+    Entry(LogEntry),
+    Close,
+}
+
+/// Contoarele comune tap-ului și writer-ului (writer-ul nu ține `sender`-ul,
+/// altfel canalul nu s-ar închide niciodată)
+#[derive(Debug, Default)]
+struct TapCounters {
+    /// Evenimente pierdute: canal plin (disc lent), writer oprit sau eroare de scriere
+    dropped: AtomicU64,
+    written: AtomicU64,
+}
+
+impl EventTap {
+    // -----------------------------------------------------------------------
+    // Deschide fișierul și pornește thread-ul writer-ului. Eroarea de
+    // deschidere apare aici, la pornire, nu la primul eveniment.
+    //
+    // Thread-ul se termină după `close` (sau după ce toate clonele tap-ului
+    // sunt eliberate), cu coada scrisă pe disc.
+    // -----------------------------------------------------------------------
+    pub fn start(config: &EventLogConfig) -> Result<(Self, JoinHandle<()>)> {
+        Self::start_with_capacity(config, EVENT_LOG_CHANNEL_CAPACITY)
+    }
+
+    pub fn start_with_capacity(config: &EventLogConfig, capacity: usize) -> Result<(Self, JoinHandle<()>)> {
+        let writer = EventLogWriter::open(&config.path, config.rotate_size_bytes)?;
+        let (sender, receiver) = mpsc::sync_channel(capacity);
+        let counters = Arc::new(TapCounters::default());
+
+        let writer_counters = Arc::clone(&counters);
+        let handle = std::thread::Builder::new()
+            .name("event-log".to_string())
+            .spawn(move || write_loop(writer, receiver, &writer_counters))
+            .context("Thread-ul [event_log] nu a putut porni")?;
+        Ok((EventTap { sender, counters }, handle))
+    }
+
+    /// Pune evenimentul în coadă; `false` = pierdut (coada plină)
+    pub fn record(&self, entry: &LogEntry) -> bool {
+        match self.sender.try_send(Queued::Entry(entry.clone())) {
+            Ok(()) => true,
+            Err(TrySendError::Full(_) | TrySendError::Disconnected(_)) => {
+                self.counters.dropped.fetch_add(1, Ordering::Relaxed);
+                false
+            }
+        }
+    }
+
+    // -----------------------------------------------------------------------
+    // Oprirea ordonată: marcajul intră după evenimentele deja în coadă
+    // (blochează cât timp coada e plină). Evenimentele înregistrate după
+    // închiderea writer-ului sunt numărate ca pierdute.
+    //
+    // Apelantul face apoi `join()` pe handle-ul returnat de `start`.
+    // -----------------------------------------------------------------------
+    pub fn close(&self) {
+        let _ = self.sender.send(Queued::Close);
+    }
+
+    /// Evenimente pierdute de la pornire
+    pub fn dropped(&self) -> u64 {
+        self.counters.dropped.load(Ordering::Relaxed)
+    }
+
+    /// Evenimente scrise de la pornire
+    pub fn written(&self) -> u64 {
+        self.counters.written.load(Ordering::Relaxed)
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Bucla writer-ului: scrie tot ce e în canal, golește buffer-ul pe disc
+// când canalul se golește, apoi așteaptă. Se termină la marcajul `Close`
+// sau când ultimul `EventTap` e eliberat (evenimentele din coadă sunt scrise).
+// ---------------------------------------------------------------------------
+fn write_loop(mut writer: EventLogWriter, receiver: Receiver<Queued>, counters: &TapCounters) {
+    let write = |writer: &mut EventLogWriter, entry: LogEntry| match writer.append(&entry) {
+        Ok(()) => {
+            counters.written.fetch_add(1, Ordering::Relaxed);
+        }
+        Err(e) => {
+            counters.dropped.fetch_add(1, Ordering::Relaxed);
+            tracing::warn!("[event_log]: {:#}", e);
+        }
+    };
+
+    loop {
+        let queued = match receiver.try_recv() {
+            Ok(queued) => queued,
+            Err(TryRecvError::Empty) => {
+                if let Err(e) = writer.flush() {
+                    tracing::warn!("[event_log]: {:#}", e);
+                }
+                match receiver.recv() {
+                    Ok(queued) => queued,
+                    Err(_) => break,
+                }
+            }
+            Err(TryRecvError::Disconnected) => break,
+        };
+        match queued {
+            Queued::Entry(entry) => write(&mut writer, entry),
+            Queued::Close => {
+                // Ce a mai intrat după marcaj se scrie și el
+                while let Ok(Queued::Entry(entry)) = receiver.try_recv() {
+                    write(&mut writer, entry);
+                }
+                break;
+            }
+        }
+    }
+    if let Err(e) = writer.flush() {
+        tracing::warn!("[event_log]: {:#}", e);
+    }
+}
@@ -0,0 +1,120 @@
+// ============================================================
+//  health.rs - `GET /healthz` și watchdog-ul systemd
+// ============================================================
+//
+//  Ambele răspund la aceeași întrebare: "bucla de recepție procesează?"
+//  (vezi `liveness.rs`), nu doar "procesul există?".
+//
+//    GET /healthz -> 200 {"live": true, ...}   activitate recentă
+//                 -> 503 {"live": false, ...}  bucla blocată
+//
+//  Watchdog-ul systemd (`[health] systemd_watchdog = true`, unit cu
+//  `Type=notify` și `WatchdogSec=`): după pornire se trimite `READY=1`,
+//  apoi `WATCHDOG=1` la jumătate din `WATCHDOG_USEC` - DOAR cât timp bucla
+//  e activă. O buclă blocată oprește notificările și systemd repornește
+//  serviciul.
+//
+//  Concepte Rust demonstrate:
+//  - `std::os::unix::net::UnixDatagram` : protocolul `sd_notify`, fără libsystemd
+//  - `#[cfg(target_os = "linux")]` : socket-uri în namespace-ul abstract (`@...`)
+// ============================================================
+
+use crate::display;
+use crate::http::{Request, Response};
+use rust_ids::liveness::Liveness;
+use std::sync::Arc;
+use std::time::Duration;
+
+/// Răspunsul pentru o cerere către endpoint-ul `[health]`
+pub fn handle_healthz(request: &Request, liveness: &Liveness, timeout: Duration) -> Response {
+    match (request.method.as_str(), request.path.as_str()) {
+        ("GET" | "HEAD", "/healthz") => {}
+        (_, "/healthz") => return Response::text(405, "use GET"),
+        _ => return Response::text(404, "not found"),
+    }
+    let report = liveness.report(timeout);
+    let status = if report.live { 200 } else { 503 };
+    Response::json(status, serde_json::to_string(&report).unwrap_or_default())
+}
+
+// ---------------------------------------------------------------------------
+// Trimite o stare `sd_notify` (ex: "READY=1") pe `$NOTIFY_SOCKET`.
+//
+// `Ok(false)` = nu rulăm sub systemd (variabila lipsește). Calea care
+// începe cu `@` este un socket din namespace-ul abstract (doar Linux).
+// ---------------------------------------------------------------------------
+#[cfg(unix)]
+pub fn sd_notify(state: &str) -> std::io::Result<bool> {
+    use std::os::unix::net::UnixDatagram;
+
+    let Some(path) = std::env::var_os("NOTIFY_SOCKET") else {
+        return Ok(false);
+    };
+    let socket = UnixDatagram::unbound()?;
+    match path.as_encoded_bytes().strip_prefix(b"@") {
+        #[cfg(target_os = "linux")]
+        Some(name) => {
+            use std::os::linux::net::SocketAddrExt;
+            let addr = std::os::unix::net::SocketAddr::from_abstract_name(name)?;
+            socket.send_to_addr(state.as_bytes(), &addr)?;
+        }
+        #[cfg(not(target_os = "linux"))]
+        Some(_) => {
+            return Err(std::io::Error::new(
+                std::io::ErrorKind::Unsupported,
+                "NOTIFY_SOCKET abstract (@) este suportat doar pe Linux",
+            ));
+        }
+        None => {
+            socket.send_to(state.as_bytes(), &path)?;
+        }
+    }
+    Ok(true)
+}
+
+#[cfg(not(unix))]
+pub fn sd_notify(_state: &str) -> std::io::Result<bool> {
+    Ok(false)
+}
+
+/// Intervalul cerut de systemd (`WATCHDOG_USEC`), dacă watchdog-ul e activ
+fn watchdog_interval() -> Option<Duration> {
+    let usec: u64 = std::env::var("WATCHDOG_USEC").ok()?.trim().parse().ok()?;
+    (usec > 0).then(|| Duration::from_micros(usec))
+}
+
+// ---------------------------------------------------------------------------
+// `READY=1` acum, apoi (dacă unit-ul are `WatchdogSec=`) `WATCHDOG=1`
+// periodic cât timp bucla de recepție e activă.
+// ---------------------------------------------------------------------------
+pub fn start_systemd_watchdog(liveness: Arc<Liveness>, timeout: Duration) {
+    match sd_notify("READY=1") {
+        Ok(true) => {}
+        Ok(false) => {
+            display::log_warn("[health] systemd_watchdog activ, dar NOTIFY_SOCKET lipsește (nu rulăm sub systemd)");
+            return;
+        }
+        Err(e) => {
+            display::log_warn(&format!("[health] sd_notify READY=1 a eșuat: {}", e));
+            return;
+        }
+    }
+    let Some(interval) = watchdog_interval() else {
+        return;
+    };
+    display::log_info(&format!("Watchdog systemd activ: WATCHDOG=1 la fiecare {:?}", interval / 2));
+
+    tokio::spawn(async move {
+        let mut ticker = tokio::time::interval(interval / 2);
+        loop {
+            ticker.tick().await;
+            if !liveness.report(timeout).live {
+                tracing::warn!("bucla de recepție inactivă - WATCHDOG=1 nu se mai trimite");
+                continue;
+            }
+            if let Err(e) = sd_notify("WATCHDOG=1") {
+                tracing::warn!("sd_notify WATCHDOG=1 a eșuat: {}", e);
+            }
+        }
+    });
+}
@@ -0,0 +1,266 @@
+// ============================================================
+//  http.rs - Server HTTP/1.1 minimal pentru endpoint-urile interne
+// ============================================================
+//
+//  Endpoint-urile IDS-ului (ex: `[control]`) sunt puține, locale și
+//  primesc corpuri mici - un framework web complet ar aduce zeci de
+//  dependențe pentru câteva rute. Aici implementăm strict subsetul
+//  necesar: o cerere per conexiune, corp delimitat de `Content-Length`,
+//  răspuns cu `Connection: close`.
+//
+//  Protecții:
+//  - timeout pentru citirea întregii cereri (client lent / conexiune moartă)
+//  - limite pentru antete și corp (memorie mărginită per conexiune); corpul
+//    crește doar cu ce sosește efectiv, nu se prealocă după `Content-Length`
+//  - `max_connections` conexiuni simultane; peste plafon, conexiunile noi
+//    sunt închise imediat (ca în `tcp::serve`)
+//
+//  Concepte Rust demonstrate:
+//  - Generice cu trait bounds: `F: Fn(Request) -> Fut`, `Fut: Future<Output = Response>`
+//  - `AsyncBufReadExt::read_line` / `AsyncReadExt::take` + `read_to_end`
+//  - `tokio::sync::Semaphore` : plafonul de conexiuni, permis eliberat la `drop`
+//
+//  Modulul e în bibliotecă (doar transport): limitele se testează din tests/.
+// ============================================================
+
+use anyhow::{bail, Context, Result};
+use std::future::Future;
+use std::net::SocketAddr;
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::io::{AsyncBufReadExt, AsyncReadExt, AsyncWriteExt, BufReader};
+use tokio::net::{TcpListener, TcpStream};
+use tokio::sync::Semaphore;
+
+/// Timpul maxim pentru primirea unei cereri complete
+const REQUEST_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// Dimensiunea maximă a liniei de cerere + antetelor
+const MAX_HEADER_BYTES: usize = 8 * 1024;
+
+/// Plafonul de conexiuni simultane pentru endpoint-urile locale
+/// (`[control]`, `[health]`); `[http_ingest]` îl are în configurație
+pub const DEFAULT_MAX_CONNECTIONS: usize = 64;
+
+/// O cerere HTTP primită
+#[derive(Debug)]
+pub struct Request {
+    pub method:  String,
+    pub path:    String,
+    /// Antetele, cu numele în litere mici
+    pub headers: Vec<(String, String)>,
+    pub body:    Vec<u8>,
+    pub peer:    SocketAddr,
+}
+
+impl Request {
+    /// Valoarea primului antet cu numele dat (case-insensitive)
+    pub fn header(&self, name: &str) -> Option<&str> {
+        self.headers
+            .iter()
+            .find(|(n, _)| n.eq_ignore_ascii_case(name))
+            .map(|(_, v)| v.as_str())
+    }
+}
+
+/// Răspunsul unui handler
+#[derive(Debug)]
+pub struct Response {
+    pub status:       u16,
+    pub content_type: &'static str,
+    pub body:         String,
+}
+
+impl Response {
+    pub fn json(status: u16, body: String) -> Self {
+        Response { status, content_type: "application/json", body }
+    }
+
+    pub fn text(status: u16, body: &str) -> Self {
+        Response { status, content_type: "text/plain; charset=utf-8", body: format!("{}\n", body) }
+    }
+}
+
+fn reason_phrase(status: u16) -> &'static str {
+    match status {
+        200 => "OK",
+        400 => "Bad Request",
+        401 => "Unauthorized",
+        404 => "Not Found",
+        405 => "Method Not Allowed",
+        408 => "Request Timeout",
+        413 => "Payload Too Large",
+        503 => "Service Unavailable",
+        _ => "Internal Server Error",
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Acceptă conexiuni la nesfârșit; fiecare conexiune rulează în task-ul ei.
+//
+// `handler` este partajat între task-uri prin `Arc` - de aceea cerem
+// `Send + Sync + 'static`, exact ca pentru callback-ul din `tcp::serve`.
+// Fiecare conexiune ține un permis din `max_connections` (cel mult
+// `max_body_bytes` în memorie per permis).
+// ---------------------------------------------------------------------------
+pub async fn serve<F, Fut>(listener: TcpListener, max_connections: usize, max_body_bytes: usize, handler: F)
+where
+    F: Fn(Request) -> Fut + Send + Sync + 'static,
+    Fut: Future<Output = Response> + Send + 'static,
+{
+    let slots = Arc::new(Semaphore::new(max_connections));
+    let handler = Arc::new(handler);
+
+    loop {
+        let (stream, peer) = match listener.accept().await {
+            Ok(conn) => conn,
+            Err(e) => {
+                tracing::warn!("HTTP: eroare la accept(): {}", e);
+                tokio::time::sleep(Duration::from_millis(100)).await;
+                continue;
+            }
+        };
+
+        // Fără slot liber, conexiunea se închide fără răspuns
+        let Ok(permit) = Arc::clone(&slots).try_acquire_owned() else {
+            tracing::warn!("HTTP: conexiune respinsă de la {}: limita de {} conexiuni atinsă", peer, max_connections);
+            continue;
+        };
+
+        let handler = Arc::clone(&handler);
+        tokio::spawn(async move {
+            if let Err(e) = handle_connection(stream, peer, max_body_bytes, handler.as_ref()).await {
+                tracing::debug!("HTTP {}: {:#}", peer, e);
+            }
+            drop(permit);
+        });
+    }
+}
+
+async fn handle_connection<F, Fut>(
+    mut stream: TcpStream,
+    peer: SocketAddr,
+    max_body_bytes: usize,
+    handler: &F,
+) -> Result<()>
+where
+    F: Fn(Request) -> Fut,
+    Fut: Future<Output = Response>,
+{
+    let response = match tokio::time::timeout(REQUEST_TIMEOUT, read_request(&mut stream, peer, max_body_bytes)).await {
+        Ok(Ok(request)) => handler(request).await,
+        Ok(Err(RequestError::TooLarge)) => Response::text(413, "request too large"),
+        Ok(Err(RequestError::Malformed(e))) => Response::text(400, &e),
+        Ok(Err(RequestError::Io(e))) => return Err(e),
+        Err(_) => Response::text(408, "request timeout"),
+    };
+
+    let head = format!(
+        "HTTP/1.1 {} {}\r\nContent-Type: {}\r\nContent-Length: {}\r\nConnection: close\r\n\r\n",
+        response.status,
+        reason_phrase(response.status),
+        response.content_type,
+        response.body.len()
+    );
+    stream.write_all(head.as_bytes()).await.context("scriere antete")?;
+    stream.write_all(response.body.as_bytes()).await.context("scriere corp")?;
+    stream.shutdown().await.ok();
+    Ok(())
+}
+
+/// De ce nu a putut fi citită o cerere (determină codul de răspuns)
+enum RequestError {
+    TooLarge,
+    Malformed(String),
+    Io(anyhow::Error),
+}
+
+impl From<std::io::Error> for RequestError {
+    fn from(e: std::io::Error) -> Self {
+        RequestError::Io(e.into())
+    }
+}
+
+async fn read_request(
+    stream: &mut TcpStream,
+    peer: SocketAddr,
+    max_body_bytes: usize,
+) -> std::result::Result<Request, RequestError> {
+    let mut reader = BufReader::new(stream);
+    let mut header_bytes = 0usize;
+
+    // Linia de cerere: "POST /inject HTTP/1.1"
+    let request_line = read_header_line(&mut reader, &mut header_bytes).await?;
+    let mut parts = request_line.split_whitespace();
+    let (Some(method), Some(path)) = (parts.next(), parts.next()) else {
+        return Err(RequestError::Malformed(format!("linie de cerere invalidă: {:?}", request_line)));
+    };
+    let (method, path) = (method.to_string(), path.to_string());
+
+    // Antetele, până la linia goală
+    let mut headers = Vec::new();
+    loop {
+        let line = read_header_line(&mut reader, &mut header_bytes).await?;
+        if line.is_empty() {
+            break;
+        }
+        let Some((name, value)) = line.split_once(':') else {
+            return Err(RequestError::Malformed(format!("antet invalid: {:?}", line)));
+        };
+        headers.push((name.trim().to_ascii_lowercase(), value.trim().to_string()));
+    }
+
+    let content_length = match headers.iter().find(|(n, _)| n == "content-length") {
+        Some((_, v)) => v
+            .parse::<usize>()
+            .map_err(|_| RequestError::Malformed(format!("Content-Length invalid: {:?}", v)))?,
+        None => 0,
+    };
+    if content_length > max_body_bytes {
+        return Err(RequestError::TooLarge);
+    }
+
+    // Buffer-ul crește cu datele primite: un `Content-Length` mare fără corp
+    // nu rezervă memorie
+    let mut body = Vec::new();
+    (&mut reader).take(content_length as u64).read_to_end(&mut body).await?;
+    if body.len() < content_length {
+        return Err(RequestError::Io(anyhow::anyhow!(
+            "conexiune închisă după {} din {} octeți de corp",
+            body.len(),
+            content_length
+        )));
+    }
+
+    Ok(Request { method, path, headers, body, peer })
+}
+
+/// O linie de antet fără CRLF; plafonul se aplică tuturor antetelor cumulat
+async fn read_header_line(
+    reader: &mut BufReader<&mut TcpStream>,
+    header_bytes: &mut usize,
+) -> std::result::Result<String, RequestError> {
+    let mut line = String::new();
+    let remaining = MAX_HEADER_BYTES.saturating_sub(*header_bytes) as u64;
+    let read = (&mut *reader).take(remaining + 1).read_line(&mut line).await?;
+    *header_bytes += read;
+
+    if *header_bytes > MAX_HEADER_BYTES {
+        return Err(RequestError::TooLarge);
+    }
+    if read == 0 {
+        return Err(RequestError::Io(anyhow::anyhow!("conexiune închisă înainte de finalul antetelor")));
+    }
+    Ok(line.trim_end_matches(['\r', '\n']).to_string())
+}
+
+/// Verifică antetul `Authorization: Bearer <token>` (fără token configurat = acceptat)
+pub fn check_bearer(request: &Request, token: Option<&str>) -> Result<()> {
+    let Some(expected) = token else {
+        return Ok(());
+    };
+    match request.header("authorization").and_then(|v| v.strip_prefix("Bearer ")) {
+        Some(given) if given == expected => Ok(()),
+        _ => bail!("token lipsă sau invalid"),
+    }
+}
@@ -0,0 +1,49 @@
+// ============================================================
+//  ingest.rs - Intrarea HTTP: `POST /ingest` cu linii de log
+// ============================================================
+//
+//  Unii agenți (ex: colectoare găzduite în cloud) pot ieși din rețea doar
+//  prin HTTP(S), nu prin UDP/TCP syslog. `[http_ingest]` le oferă o
+//  "ușă din față" HTTP către ACELAȘI flux ca pachetele UDP: parser,
+//  detector, cooldown, alertare. Corpul cererii poate fi:
+//
+//    - text: linii de log separate prin newline
+//    - JSON: un array de linii, `["Sep 3 15:12:20 ... drop ...", "..."]`
+//      (ales după `Content-Type: application/json` sau un corp care
+//      începe cu `[`)
+//
+//  TLS-ul nu este implementat aici: pentru HTTPS, endpoint-ul stă în
+//  spatele unui reverse proxy care termină TLS-ul.
+// ============================================================
+
+use crate::http::{self, Request, Response};
+
+/// Dimensiunea maximă a corpului unei cereri (un lot de linii)
+pub const MAX_BODY_BYTES: usize = 4 * 1024 * 1024;
+
+// ---------------------------------------------------------------------------
+// Validează cererea și extrage liniile de log, sau răspunsul de eroare.
+// Liniile goale sunt păstrate: `process_packet` le sare oricum.
+// ---------------------------------------------------------------------------
+pub fn parse_ingest(request: &Request, token: Option<&str>) -> Result<Vec<String>, Response> {
+    if let Err(e) = http::check_bearer(request, token) {
+        return Err(Response::text(401, &e.to_string()));
+    }
+    match (request.method.as_str(), request.path.as_str()) {
+        ("POST", "/ingest") => {}
+        (_, "/ingest") => return Err(Response::text(405, "use POST")),
+        _ => return Err(Response::text(404, "not found")),
+    }
+
+    let is_json = request
+        .header("content-type")
+        .is_some_and(|v| v.to_ascii_lowercase().starts_with("application/json"))
+        || request.body.trim_ascii_start().starts_with(b"[");
+    if is_json {
+        return serde_json::from_slice(&request.body)
+            .map_err(|e| Response::text(400, &format!("JSON invalid (se așteaptă un array de linii): {}", e)));
+    }
+
+    let text = String::from_utf8_lossy(&request.body);
+    Ok(text.lines().map(str::to_string).collect())
+}
@@ -0,0 +1,87 @@
+// ============================================================
+//  labels.rs - Etichetele surselor (`[[labels]]`): CIDR -> tag
+// ============================================================
+//
+//  Inventarul rețelei știe deja ce IP-uri sunt parteneri, pool-uri VPN sau
+//  host-uri DMZ. `[[labels]]` aduce acest context direct în alertă:
+//
+//    [[labels]]
+//    network = "203.0.113.0/24"
+//    tag     = "partner-acme"
+//
+//  Tag-urile tuturor rețelelor care conțin sursa se concatenează, în
+//  ordinea din config ("partner-acme,vpn-pool"). Rezolvarea se face la
+//  alertare; rezultatul se păstrează per IP `LABEL_CACHE_TTL`, ca un
+//  atacator re-alertat să nu reparcurgă lista de rețele de fiecare dată.
+//
+//  Concepte Rust demonstrate:
+//  - `ipnet::IpNet::contains` : apartenența la o rețea, IPv4 și IPv6
+//  - Cache cu expirare peste `DashMap` (fără lock global)
+// ============================================================
+
+use crate::config::SourceLabel;
+use dashmap::DashMap;
+use std::net::IpAddr;
+use std::time::{Duration, Instant};
+
+/// Cât timp rămâne valid rezultatul rezolvării unui IP
+pub const LABEL_CACHE_TTL: Duration = Duration::from_secs(60);
+
+/// Limita cache-ului: la depășire se elimină intrările expirate (sau toate)
+const MAX_CACHED_LABELS: usize = 10_000;
+
+/// Tag-urile unui IP, cu momentul rezolvării
+struct CachedTags {
+    tags:        Vec<String>,
+    resolved_at: Instant,
+}
+
+/// Rezolvarea tag-urilor per IP sursă, cu cache
+pub struct LabelResolver {
+    labels: Vec<SourceLabel>,
+    cache:  DashMap<IpAddr, CachedTags>,
+}
+
+impl LabelResolver {
+    pub fn new(labels: &[SourceLabel]) -> Self {
+        LabelResolver { labels: labels.to_vec(), cache: DashMap::new() }
+    }
+
+    /// Nicio etichetă configurată (rezolvarea nu face nimic)
+    pub fn is_empty(&self) -> bool {
+        self.labels.is_empty()
+    }
+
+    // -----------------------------------------------------------------------
+    // Tag-urile lui `ip`, în ordinea din config, fără duplicate (gol dacă
+    // nicio rețea nu îl conține). Un IPv4 mapat în IPv6 (`::ffff:a.b.c.d`)
+    // este comparat ca IPv4.
+    // -----------------------------------------------------------------------
+    pub fn tags(&self, ip: &IpAddr) -> Vec<String> {
+        if self.labels.is_empty() {
+            return Vec::new();
+        }
+        let ip = ip.to_canonical();
+        if let Some(cached) = self.cache.get(&ip) {
+            if cached.resolved_at.elapsed() < LABEL_CACHE_TTL {
+                return cached.tags.clone();
+            }
+        }
+
+        let mut tags: Vec<String> = Vec::new();
+        for label in self.labels.iter().filter(|label| label.network.contains(&ip)) {
+            if !tags.contains(&label.tag) {
+                tags.push(label.tag.clone());
+            }
+        }
+
+        if self.cache.len() >= MAX_CACHED_LABELS {
+            self.cache.retain(|_ip, cached| cached.resolved_at.elapsed() < LABEL_CACHE_TTL);
+            if self.cache.len() >= MAX_CACHED_LABELS {
+                self.cache.clear();
+            }
+        }
+        self.cache.insert(ip, CachedTags { tags: tags.clone(), resolved_at: Instant::now() });
+        tags
+    }
+}
@@ -0,0 +1,73 @@
+// ============================================================
+//  lib.rs - Biblioteca `rust_ids`: parsare, detecție, transport
+// ============================================================
+//
+//  Binarul `rust-ids` (src/main.rs) este doar un consumator al acestei
+//  biblioteci: leagă socket-urile, adaugă alertarea și consola. Modulele
+//  de transport (`tcp`, `http`, `ingest`) stau aici doar ca să poată fi
+//  testate din tests/. Un colector propriu poate încorpora aceeași logică
+//  direct, fără un proces separat.
+//
+//  Concepte Rust demonstrate:
+//  - Crate bibliotecă + crate binar în același pachet (src/lib.rs + src/main.rs)
+//  - `//!` : documentația modulului/crate-ului (apare în `cargo doc`)
+//  - Doc-tests: exemplele din documentație sunt compilate și rulate de `cargo test`
+// ============================================================
+
+//! Detecția scanărilor de porturi din log-uri de firewall.
+//!
+//! Fluxul tipic: parsează o linie, înregistrează evenimentul, evaluează.
+//! Fluxul complet, cu gating-ul de cooldown inclus, este `engine::Engine`:
+//! `ingest(entry)` returnează direct decizia de alertă.
+//!
+//! ```
+//! use rust_ids::config::Config;
+//! use rust_ids::detector::evaluate;
+//! use rust_ids::parser::create_parser;
+//! use rust_ids::state::SharedState;
+//!
+//! // Valorile implicite (fără fișier); în producție: `Config::load("config.toml")?`
+//! let config = Config::default();
+//! let parser = create_parser(&config.listener.parser);
+//! let state = SharedState::new();
+//!
+//! // 20 de porturi distincte de la aceeași sursă, în câteva milisecunde
+//! for port in 1000..1020 {
+//!     let line = format!(
+//!         "Sep 3 15:12:20 192.168.99.1 Checkpoint: drop 192.168.11.7 proto: tcp; service: {}; s_port: 1352",
+//!         port
+//!     );
+//!     let entry = parser.parse(&line).expect("linie Gaia validă");
+//!     state.record_event(entry.source_ip, entry.dest_port().unwrap());
+//!
+//!     let result = evaluate(&entry.source_ip, &state, &config.detection);
+//!     if result.is_threat() {
+//!         println!("{} -> {}", entry.source_ip, result.scan_type_label());
+//!     }
+//! }
+//! ```
+
+pub mod alert_fields;
+pub mod alert_log;
+pub mod alert_template;
+pub mod bench;
+pub mod clock;
+pub mod config;
+pub mod config_template;
+pub mod context;
+pub mod datagram;
+pub mod detector;
+pub mod diagnostics;
+pub mod engine;
+pub mod event_log;
+pub mod http;
+pub mod ingest;
+pub mod labels;
+pub mod liveness;
+pub mod logging;
+pub mod parser;
+pub mod report;
+pub mod response;
+pub mod routing;
+pub mod state;
+pub mod tcp;
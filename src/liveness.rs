@@ -0,0 +1,99 @@
+// ============================================================
+//  liveness.rs - "Bucla de recepție chiar procesează?"
+// ============================================================
+//
+//  Un PID în viață nu înseamnă un IDS care detectează: bucla `recv_from`
+//  poate rămâne blocată fără ca procesul să moară. Bucla principală
+//  atinge `Liveness` la fiecare datagramă primită și la fiecare tic de
+//  inactivitate (`tokio::select!` pe un `interval`), deci:
+//
+//    - trafic normal       -> ultima activitate = ultimul pachet
+//    - sistem fără trafic  -> ultima activitate = ultimul tic (tot sănătos)
+//    - buclă blocată       -> nici pachete, nici tic-uri -> nesănătos
+//
+//  `GET /healthz` (`[health]`) și watchdog-ul systemd citesc aceeași stare.
+//
+//  Concepte Rust demonstrate:
+//  - `AtomicU64` : timestamp partajat între task-uri, fără lock
+//  - Timp monoton (`Instant`) stocat ca milisecunde de la o origine fixă
+// ============================================================
+
+use serde::Serialize;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::time::{Duration, Instant};
+
+/// Ultima activitate a buclei de recepție, partajată prin `Arc`
+#[derive(Debug)]
+pub struct Liveness {
+    origin:           Instant,
+    /// Milisecunde de la `origin` până la ultima activitate
+    last_activity_ms: AtomicU64,
+    /// Datagramele primite de la pornire
+    packets:          AtomicU64,
+}
+
+/// Starea raportată de `GET /healthz`
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct LivenessReport {
+    /// Activitate în ultimele `timeout_secs`
+    pub live:         bool,
+    /// Secunde de la ultima activitate a buclei
+    pub idle_secs:    f64,
+    pub timeout_secs: u64,
+    pub packets:      u64,
+}
+
+impl Liveness {
+    /// Starea inițială: activă "acum" (pornirea contează ca activitate)
+    pub fn new() -> Self {
+        Self::starting_at(Instant::now())
+    }
+
+    pub fn starting_at(origin: Instant) -> Self {
+        Liveness { origin, last_activity_ms: AtomicU64::new(0), packets: AtomicU64::new(0) }
+    }
+
+    /// Bucla a iterat fără pachet (tic de inactivitate)
+    pub fn touch(&self) {
+        self.touch_at(Instant::now());
+    }
+
+    pub fn touch_at(&self, now: Instant) {
+        let ms = now.saturating_duration_since(self.origin).as_millis() as u64;
+        // `fetch_max`: un tic întârziat nu poate da ceasul înapoi
+        self.last_activity_ms.fetch_max(ms, Ordering::Relaxed);
+    }
+
+    /// O datagramă primită cu succes
+    pub fn record_packet(&self) {
+        self.packets.fetch_add(1, Ordering::Relaxed);
+        self.touch();
+    }
+
+    /// Timpul scurs de la ultima activitate, măsurat la `now`
+    pub fn idle_at(&self, now: Instant) -> Duration {
+        let last = self.origin + Duration::from_millis(self.last_activity_ms.load(Ordering::Relaxed));
+        now.saturating_duration_since(last)
+    }
+
+    /// Starea curentă față de pragul `timeout`
+    pub fn report(&self, timeout: Duration) -> LivenessReport {
+        self.report_at(Instant::now(), timeout)
+    }
+
+    pub fn report_at(&self, now: Instant, timeout: Duration) -> LivenessReport {
+        let idle = self.idle_at(now);
+        LivenessReport {
+            live:         idle <= timeout,
+            idle_secs:    idle.as_secs_f64(),
+            timeout_secs: timeout.as_secs(),
+            packets:      self.packets.load(Ordering::Relaxed),
+        }
+    }
+}
+
+impl Default for Liveness {
+    fn default() -> Self {
+        Self::new()
+    }
+}
@@ -0,0 +1,159 @@
+// ============================================================
+//  logging.rs - Subscriber-ul tracing: consolă umană sau JSON structurat
+// ============================================================
+//
+//  Două moduri (`[display] log_format`):
+//    - "console" : output-ul colorat din display.rs; evenimentele structurate
+//                  (target `rust_ids::events`) NU se mai afișează a doua oară
+//    - "json"    : un obiect JSON per linie pe stdout, cu câmpurile
+//                  evenimentului și ale span-urilor părinte (ex: `src_addr`),
+//                  direct consumabil de Loki / Elastic
+//
+//  Concepte Rust demonstrate:
+//  - `Layer<S>` : extinderea unui subscriber tracing cu un consumator propriu
+//  - `field::Visit` : vizitarea câmpurilor tipizate ale unui eveniment
+//  - Extensiile span-urilor (`extensions_mut`) : date atașate unui span activ
+//  - `MakeWriter` : destinația output-ului, înlocuibilă în teste
+// ============================================================
+
+use crate::config::LogFormat;
+use serde_json::{Map, Value};
+use std::io::Write;
+use tracing::field::{Field, Visit};
+use tracing::span::{Attributes, Id, Record};
+use tracing::{Event, Subscriber};
+use tracing_subscriber::filter::{filter_fn, EnvFilter};
+use tracing_subscriber::fmt::MakeWriter;
+use tracing_subscriber::layer::{Context, Layer, SubscriberExt};
+use tracing_subscriber::registry::LookupSpan;
+use tracing_subscriber::util::SubscriberInitExt;
+
+/// Target-ul evenimentelor structurate (parse, record, detect, alert)
+pub const EVENTS_TARGET: &str = "rust_ids::events";
+
+/// Filtrul implicit, dacă RUST_LOG lipsește
+const DEFAULT_FILTER: &str = "rust_ids=info";
+
+// ---------------------------------------------------------------------------
+// Inițializarea subscriber-ului global, o singură dată la pornire.
+//
+// Fără RUST_LOG se folosește nivelul info (o directivă adăugată peste
+// RUST_LOG ar suprascrie-o pe cea a utilizatorului, ex: rust_ids=debug).
+// ---------------------------------------------------------------------------
+pub fn init(format: LogFormat) {
+    let env_filter = EnvFilter::try_from_default_env().unwrap_or_else(|_| EnvFilter::new(DEFAULT_FILTER));
+    let registry = tracing_subscriber::registry().with(env_filter);
+
+    match format {
+        LogFormat::Console => {
+            // Evenimentele structurate dublează consola colorată: le filtrăm
+            let console = tracing_subscriber::fmt::layer()
+                .without_time() // Gestionăm manual timestamp-urile în display.rs
+                .compact()
+                .with_filter(filter_fn(|meta| meta.target() != EVENTS_TARGET));
+            registry.with(console).init();
+        }
+        LogFormat::Json => registry.with(JsonLayer::new(std::io::stdout)).init(),
+    }
+}
+
+/// Layer care scrie fiecare eveniment ca o linie JSON
+pub struct JsonLayer<W> {
+    make_writer: W,
+}
+
+impl<W> JsonLayer<W>
+where
+    W: for<'a> MakeWriter<'a> + 'static,
+{
+    pub fn new(make_writer: W) -> Self {
+        JsonLayer { make_writer }
+    }
+}
+
+/// Câmpurile unui span, păstrate în extensiile lui până la închidere
+struct SpanFields(Map<String, Value>);
+
+// ---------------------------------------------------------------------------
+// Vizitatorul de câmpuri: fiecare tip primește reprezentarea JSON nativă
+// (numerele rămân numere, pentru interogări de tip `unique_ports > 20`).
+// Valorile `%x` / `?x` ajung în `record_debug` ca text.
+// ---------------------------------------------------------------------------
+struct JsonVisitor<'a>(&'a mut Map<String, Value>);
+
+impl Visit for JsonVisitor<'_> {
+    fn record_f64(&mut self, field: &Field, value: f64) {
+        self.0.insert(field.name().to_string(), Value::from(value));
+    }
+
+    fn record_i64(&mut self, field: &Field, value: i64) {
+        self.0.insert(field.name().to_string(), Value::from(value));
+    }
+
+    fn record_u64(&mut self, field: &Field, value: u64) {
+        self.0.insert(field.name().to_string(), Value::from(value));
+    }
+
+    fn record_bool(&mut self, field: &Field, value: bool) {
+        self.0.insert(field.name().to_string(), Value::from(value));
+    }
+
+    fn record_str(&mut self, field: &Field, value: &str) {
+        self.0.insert(field.name().to_string(), Value::from(value));
+    }
+
+    fn record_debug(&mut self, field: &Field, value: &dyn std::fmt::Debug) {
+        self.0.insert(field.name().to_string(), Value::from(format!("{:?}", value)));
+    }
+}
+
+impl<S, W> Layer<S> for JsonLayer<W>
+where
+    S: Subscriber + for<'a> LookupSpan<'a>,
+    W: for<'a> MakeWriter<'a> + 'static,
+{
+    fn on_new_span(&self, attrs: &Attributes<'_>, id: &Id, ctx: Context<'_, S>) {
+        let Some(span) = ctx.span(id) else { return };
+        let mut fields = Map::new();
+        attrs.record(&mut JsonVisitor(&mut fields));
+        span.extensions_mut().insert(SpanFields(fields));
+    }
+
+    fn on_record(&self, id: &Id, values: &Record<'_>, ctx: Context<'_, S>) {
+        let Some(span) = ctx.span(id) else { return };
+        let mut extensions = span.extensions_mut();
+        if let Some(SpanFields(fields)) = extensions.get_mut::<SpanFields>() {
+            values.record(&mut JsonVisitor(fields));
+        }
+    }
+
+    fn on_event(&self, event: &Event<'_>, ctx: Context<'_, S>) {
+        let meta = event.metadata();
+        let mut object = Map::new();
+        object.insert("timestamp".to_string(), Value::from(chrono::Utc::now().to_rfc3339()));
+        object.insert("level".to_string(), Value::from(meta.level().as_str()));
+        object.insert("target".to_string(), Value::from(meta.target()));
+
+        // Câmpurile span-urilor, de la rădăcină spre frunză: un câmp
+        // al span-ului interior îl acoperă pe cel al span-ului exterior
+        if let Some(scope) = ctx.event_scope(event) {
+            let mut spans = Vec::new();
+            for span in scope.from_root() {
+                spans.push(Value::from(span.name()));
+                if let Some(SpanFields(fields)) = span.extensions().get::<SpanFields>() {
+                    object.extend(fields.clone());
+                }
+            }
+            if !spans.is_empty() {
+                object.insert("spans".to_string(), Value::Array(spans));
+            }
+        }
+
+        // Câmpurile evenimentului (inclusiv `message`) au ultimul cuvânt
+        event.record(&mut JsonVisitor(&mut object));
+
+        let mut line = Value::Object(object).to_string();
+        line.push('\n');
+        let _ = self.make_writer.make_writer().write_all(line.as_bytes());
+    }
+}
@@ -11,25 +11,42 @@
 //  - `loop` + `.recv_from().await` : bucla principală asincronă
 // ============================================================
 
-// Declarăm modulele proiectului.
-// Rustc va căuta fișierele: src/config.rs, src/display.rs, etc.
+// Logica de parsare și detecție vine din biblioteca `rust_ids` (src/lib.rs).
+// Re-exportul la rădăcina crate-ului păstrează căile `crate::config`,
+// `crate::state` etc. valide și în modulele binarului de mai jos.
+use rust_ids::{
+    alert_fields, alert_log, alert_template, bench, config, config_template, context, datagram, detector, diagnostics,
+    engine, event_log, http, ingest, liveness, logging, parser, report, routing, state, tcp,
+};
+#[cfg(feature = "checkpoint")]
+use rust_ids::response;
+
+// Modulele proprii binarului: rețea, alertare, consolă.
+// Rustc va căuta fișierele: src/alert/mod.rs, src/display.rs, etc.
 mod alert;
-mod config;
-mod detector;
+mod control;
 mod display;
-mod parser;
-mod state;
+mod health;
+mod netstat;
 
-use alert::{send_alerts, AlertPayload};
+use alert::{AlertDispatcher, AlertPayload};
 use config::Config;
-use detector::evaluate;
+use detector::DetectionResult;
+use engine::{Alert, Engine};
+use logging::EVENTS_TARGET;
 use parser::LogParser;
+use report::ReportSchedule;
 use state::SharedState;
 
 use anyhow::{Context, Result};
+use std::net::SocketAddr;
 use std::sync::Arc;
 use std::time::Duration;
 use tokio::net::UdpSocket;
+use tracing::Instrument;
+
+/// Fișierul de configurare, relativ la directorul curent
+const CONFIG_PATH: &str = "config.toml";
 
 // ---------------------------------------------------------------------------
 // `#[tokio::main]` este un macro procedural care:
@@ -41,41 +58,77 @@ use tokio::net::UdpSocket;
 // ---------------------------------------------------------------------------
 #[tokio::main]
 async fn main() -> Result<()> {
+    // `rust-ids init-config [cale] [--force]`: scrie config.toml-ul implicit
+    // comentat și iese - fără configurație existentă, fără rețea
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    if args.first().map(String::as_str) == Some("init-config") {
+        return init_config(&args[1..]);
+    }
+    if args.first().map(String::as_str) == Some("report") {
+        return report(&args[1..]);
+    }
+    if args.first().map(String::as_str) == Some("bench") {
+        return run_bench(&args[1..]);
+    }
+
     // -----------------------------------------------------------------------
-    // 1. Inițializare tracing subscriber
+    // 1. Încărcăm configurația - ea alege formatul log-urilor
     //
-    // `tracing-subscriber` configurează cum se afișează mesajele tracing.
-    // `RUST_LOG=debug cargo run` activează nivel debug.
-    // `EnvFilter` citește variabila de mediu RUST_LOG.
+    // Un config.toml lipsă pornește IDS-ul cu valorile implicite (prima
+    // rulare, demo); `--strict-config` păstrează eroarea fatală pentru
+    // producție. Un fișier existent dar invalid este mereu fatal.
     // -----------------------------------------------------------------------
-    tracing_subscriber::fmt()
-        .with_env_filter(
-            tracing_subscriber::EnvFilter::from_default_env()
-                .add_directive("rust_ids=info".parse().unwrap()),
-        )
-        .without_time() // Gestionăm manual timestamp-urile în display.rs
-        .compact()
-        .init();
+    let strict_config = args.iter().any(|arg| arg == "--strict-config");
+    let loaded = if strict_config {
+        Config::load(CONFIG_PATH).map(Some)
+    } else {
+        Config::load_optional(CONFIG_PATH)
+    };
+    let loaded = loaded.with_context(|| format!("Eroare fatală: nu s-a putut încărca {}", CONFIG_PATH))?;
+    let using_defaults = loaded.is_none();
+    let config = loaded.unwrap_or_default();
 
     // -----------------------------------------------------------------------
-    // 2. Afișăm banner-ul și încărcăm configurația
+    // 2. Inițializare tracing subscriber + banner
+    //
+    // `tracing-subscriber` configurează cum se afișează mesajele tracing.
+    // `RUST_LOG=debug cargo run` activează nivel debug.
+    // `EnvFilter` citește variabila de mediu RUST_LOG.
+    // În modul "json" consola colorată tace: stdout-ul conține doar
+    // evenimentele structurate (vezi logging.rs).
     // -----------------------------------------------------------------------
+    logging::init(config.display.log_format);
+    display::set_structured(config.display.log_format == config::LogFormat::Json);
     display::print_banner();
 
-    let config = Config::load("config.toml")
-        .context("Eroare fatală: nu s-a putut încărca config.toml")?;
-
+    if using_defaults {
+        display::log_default_config_warning(CONFIG_PATH);
+    }
     display::log_info(&format!(
         "Configurație încărcată. Parser activ: [{}]",
         config.listener.parser.to_uppercase()
     ));
-    display::log_info(&format!(
-        "Fast Scan: >{} porturi in {}s | Slow Scan: >{} porturi in {}min",
-        config.detection.fast_scan_ports,
-        config.detection.fast_scan_window_secs,
-        config.detection.slow_scan_ports,
-        config.detection.slow_scan_window_mins
-    ));
+    for tier in &config.detection.tiers {
+        display::log_info(&format!(
+            "Tier '{}': >{} porturi in {}s (severitate {}, {})",
+            tier.name,
+            tier.ports,
+            tier.window_secs,
+            tier.severity,
+            tier.sig_id()
+        ));
+    }
+
+    let detection_cfg = &config.detection;
+    if let Some(range) = detection_cfg.port_range {
+        display::log_info(&format!("Numărătoarea porturilor unice: doar porturile {}-{}", range.min, range.max));
+    }
+    if !detection_cfg.ignore_ports.is_empty() {
+        display::log_info(&format!(
+            "Porturi excluse din numărătoare: {:?}",
+            detection_cfg.ignore_ports
+        ));
+    }
 
     // -----------------------------------------------------------------------
     // 3. Creăm parser-ul și starea shared
@@ -86,15 +139,188 @@ async fn main() -> Result<()> {
     // De ce Arc și nu simplu clone? Parser-ul implementează `Box<dyn LogParser>`.
     // Clonarea box-ului ar duplica datele (scump). Arc numără referințele atomic.
     // -----------------------------------------------------------------------
-    let parser: Arc<Box<dyn LogParser>> = Arc::new(parser::create_parser(&config.listener.parser));
+    display::init_drop_events(
+        config.display.drop_events,
+        Duration::from_secs(config.display.drop_aggregate_window_secs.max(1)),
+    );
+
+    // Accept-urile se emit doar pentru detecția acces-după-scan (volum mai mare)
+    let parser_options = parser::ParserOptions {
+        ports:        parser::PortResolver::new(&config.listener.services),
+        emit_accepts: config.detection.post_scan_access_enabled(),
+    };
+    let parser: Arc<Box<dyn LogParser>> =
+        Arc::new(parser::create_parser_with_options(&config.listener.parser, parser_options));
     display::log_info(&format!("Parser '{}' inițializat", parser.name()));
+    if config.detection.post_scan_access_enabled() {
+        display::log_info(&format!(
+            "Acces după scan activ: accept pe un port sondat, până la {}s după detecție (IDS006)",
+            config.detection.post_scan_access_window_secs
+        ));
+    }
+
+    if config.detection.repeat_offender_enabled() {
+        display::log_info(&format!(
+            "Recidiviști ținuți minte {}h după ultima alertă (la revenire: {})",
+            config.detection.repeat_offender_window_hours,
+            match config.detection.repeat_offender_action {
+                config::RepeatOffenderAction::Notify => "notificare IDS007".to_string(),
+                config::RepeatOffenderAction::LowerThresholds => format!(
+                    "praguri x{}",
+                    config.detection.repeat_offender_threshold_factor
+                ),
+            }
+        ));
+    }
+
+    // Motorul de detecție deține starea shared; restul task-urilor o
+    // partajează prin `state` (clonă ieftină - doar Arc-uri)
+    let engine = Arc::new(Engine::new(&config));
+    let state = engine.state().clone();
+
+    // Jurnalul evenimentelor normalizate (opțional): thread propriu, coadă
+    // mărginită - un disc lent pierde evenimente din jurnal, nu încetinește detecția
+    let (event_tap, event_writer) = if config.event_log.enabled {
+        let (tap, writer) = event_log::EventTap::start(&config.event_log)?;
+        display::log_info(&format!("Evenimentele normalizate se scriu în {}", config.event_log.path));
+        (Some(Arc::new(tap)), Some(writer))
+    } else {
+        (None, None)
+    };
 
-    let state = SharedState::new();
+    // Fiecare listener are propriile contoare de ingestie (plus cele ale parser-ului)
+    let udp_input = Arc::new(Input::new("udp", &parser, &state, &event_tap));
+
+    // `--debug-parse`: afișează primele linii nerecunoscute, pentru
+    // integrarea unui exporter nou
+    if std::env::args().any(|arg| arg == "--debug-parse") {
+        state.parse_diagnostics().set_debug_parse(true);
+        display::log_info(&format!(
+            "Mod --debug-parse: primele {} linii nerecunoscute vor fi afișate",
+            diagnostics::DEBUG_PARSE_SAMPLES
+        ));
+    }
+
+    // Răspuns activ (opțional): blocare SAM pe Check Point
+    #[cfg(feature = "checkpoint")]
+    let responder = match &config.response.checkpoint {
+        Some(cp_config) => {
+            let responder = response::checkpoint::CheckpointResponder::new(cp_config.clone())
+                .context("Configurație [response.checkpoint] invalidă")?;
+            display::log_info(&format!(
+                "Blocare SAM Check Point activă -> {}{}",
+                cp_config.management_url,
+                if responder.dry_run() { " (dry-run)" } else { "" }
+            ));
+            Some(Arc::new(responder))
+        }
+        None => None,
+    };
+
+    #[cfg(not(feature = "checkpoint"))]
+    if config.response.checkpoint.is_some() {
+        display::log_warn("[response.checkpoint] ignorat: binarul a fost compilat fără feature-ul 'checkpoint'");
+    }
 
     // `Arc::new(config)` - configurația e immutabilă după inițializare,
     // deci o partajăm cu Arc (fără locks, accesul concurrent la date imutabile e safe)
     let config = Arc::new(config);
 
+    // Dispatcher-ul de alerte (SIEM + email), partajat de toate task-urile
+    let alerts = Arc::new(AlertDispatcher::new(Arc::clone(&config)).await?);
+    if config.alert_log.enabled {
+        display::log_info(&format!("Alertele trimise se scriu în {}", config.alert_log.path));
+    }
+
+    // Raportul programat: construit din jurnale la ora fixată, trimis pe email
+    if let Some(schedule) = config.report.schedule {
+        display::log_info(&format!("Raport programat {} -> {}", schedule, config.report.to.join(", ")));
+        let report_config = Arc::clone(&config);
+        tokio::spawn(async move {
+            loop {
+                let now = chrono::Local::now();
+                let next = schedule.next_after(&now);
+                tokio::time::sleep((next - now).to_std().unwrap_or_default()).await;
+                send_scheduled_report(&report_config, schedule).await;
+            }
+        });
+    }
+
+    // Perioada de încălzire: la final se afișează sumarul alertelor suprimate
+    if let Some(warmup) = alerts.warmup_remaining() {
+        display::log_warn(&format!(
+            "Warmup activ: alertele sunt suprimate (doar afișate) în primele {}s",
+            config.detection.warmup_secs
+        ));
+        let warmup_alerts = Arc::clone(&alerts);
+        tokio::spawn(async move {
+            tokio::time::sleep(warmup).await;
+            warmup_alerts.finish_warmup();
+        });
+    }
+
+    // În modul agregat, alertele SIEM se trimit la finalul fiecărei ferestre
+    if config.siem.aggregate {
+        display::log_info(&format!(
+            "Agregare SIEM activă: fereastră de {}s",
+            config.siem.aggregate_window_secs
+        ));
+        let flush_alerts = Arc::clone(&alerts);
+        let window = Duration::from_secs(config.siem.aggregate_window_secs);
+        tokio::spawn(async move {
+            let mut interval = tokio::time::interval(window);
+            interval.tick().await; // Primul tick e imediat - îl consumăm
+            loop {
+                interval.tick().await;
+                flush_alerts.flush_siem().await;
+            }
+        });
+    }
+
+    // Heartbeat SIEM și avertizarea de tăcere a fluxului de intrare
+    let heartbeat_secs = config.siem.heartbeat_interval_secs;
+    if heartbeat_secs > 0 {
+        display::log_info(&format!("Heartbeat SIEM (IDS000) la fiecare {}s", heartbeat_secs));
+        let hb_config = Arc::clone(&config);
+        let hb_state = state.clone();
+        let hb_alerts = Arc::clone(&alerts);
+        tokio::spawn(async move {
+            let mut interval = tokio::time::interval(Duration::from_secs(heartbeat_secs));
+            interval.tick().await; // Primul tick e imediat - îl consumăm
+            loop {
+                interval.tick().await;
+                let silent = input_is_silent(&hb_config, &hb_state);
+                hb_alerts.send_heartbeat(hb_state.activity().take_packets(), silent).await;
+            }
+        });
+    }
+
+    let silence_secs = config.listener.input_silence_warn_secs;
+    if silence_secs > 0 {
+        let silence_config = Arc::clone(&config);
+        let silence_state = state.clone();
+        tokio::spawn(async move {
+            // Verificăm de câteva ori per prag; avertizăm o singură dată
+            // per perioadă de tăcere și confirmăm reluarea fluxului
+            let period = Duration::from_secs((silence_secs / 4).max(1));
+            let mut interval = tokio::time::interval(period);
+            let mut warned = false;
+            loop {
+                interval.tick().await;
+                let silent = input_is_silent(&silence_config, &silence_state);
+                if silent && !warned {
+                    display::log_warn(&format!(
+                        "Niciun log primit de {}s - exporter oprit sau syslog blocat?",
+                        silence_state.activity().silent_for().as_secs()
+                    ));
+                } else if !silent && warned {
+                    display::log_info("Fluxul de log-uri a fost reluat");
+                }
+                warned = silent;
+            }
+        });
+    }
+
     // -----------------------------------------------------------------------
     // 4. Pornire task cleanup periodic
     //
@@ -107,8 +333,10 @@ async fn main() -> Result<()> {
     //   - `move` în `async move` transferă ownership-ul variabilelor capturate în task
     // -----------------------------------------------------------------------
     let cleanup_state = state.clone();
+    let summary_parser_name = parser.name().to_string();
+    let summary_event_tap = event_tap.clone();
     let cleanup_interval = config.detection.cleanup_interval_secs;
-    let max_age_secs = config.slow_scan_window_secs() + 120; // +2min grace period
+    let max_age_secs = config.detection.max_window_secs() + 120; // +2min grace period
 
     tokio::spawn(async move {
         let mut interval = tokio::time::interval(Duration::from_secs(cleanup_interval));
@@ -121,9 +349,103 @@ async fn main() -> Result<()> {
             if removed > 0 {
                 display::log_cleanup(removed);
             }
+            display::log_state_stats(&cleanup_state.stats());
+
+            // Sumar periodic: contoarele parserului și cele per exporter
+            let diagnostics = cleanup_state.parse_diagnostics();
+            let parser_stats = diagnostics.parser_stats.snapshot();
+            if parser_stats.lines_seen > 0 {
+                display::log_parser_stats(&summary_parser_name, &parser_stats);
+            }
+            for listener in diagnostics.ingest.listeners() {
+                display::log_ingest_stats("listener", &listener);
+            }
+            for parser in diagnostics.ingest.parsers() {
+                display::log_ingest_stats("parser", &parser);
+            }
+            // Doar sursele cu cele mai multe eșecuri: adresele UDP se pot falsifica
+            for (source, lines, failures) in diagnostics.top_sources(diagnostics::SUMMARY_TOP_SOURCES) {
+                display::log_parse_summary(&source, lines, failures);
+            }
+            let hidden = diagnostics.source_count().saturating_sub(diagnostics::SUMMARY_TOP_SOURCES);
+            if hidden > 0 || diagnostics.untracked_lines() > 0 {
+                display::log_info(&format!(
+                    "[PARSE] încă {} surse neafișate; {} linii de la surse neurmărite (limita {})",
+                    hidden,
+                    diagnostics.untracked_lines(),
+                    diagnostics::MAX_PARSE_SOURCES
+                ));
+            }
+            if let Some(events) = &summary_event_tap {
+                display::log_event_log_stats(events.written(), events.dropped());
+            }
+            let (lossy, truncated) = diagnostics.datagrams.snapshot();
+            let oversized = diagnostics.datagrams.oversized();
+            if lossy > 0 || truncated > 0 || oversized > 0 {
+                display::log_datagram_stats(lossy, truncated, oversized);
+            }
+
+            // Regulile cu cele mai multe drop-uri din interval (o politică
+            // greșită care inundă IDS-ul iese imediat în evidență)
+            let top_rules = cleanup_state.take_top_rules(state::TOP_RULES);
+            if !top_rules.is_empty() {
+                display::log_top_rules(&top_rules);
+            }
         }
     });
 
+    // Histerezisul: sursele active revin la Clean doar când porturile lor
+    // scad sub pragul de eliberare - verificat periodic, nu per eveniment,
+    // pentru că un atacator care s-a oprit nu mai trimite nimic
+    let clear_engine = Arc::clone(&engine);
+    let notify_clear = config.detection.notify_clear;
+    tokio::spawn(async move {
+        let mut interval = tokio::time::interval(state::SCAN_BUCKET);
+        loop {
+            interval.tick().await;
+            for cleared in clear_engine.clear_pass() {
+                if notify_clear {
+                    display::log_scan_cleared(&cleared.ip, cleared.active_for.as_secs(), cleared.peak_severity);
+                }
+            }
+        }
+    });
+
+    // Scanări distribuite: agregarea între surse rulează periodic
+    if config.detection.distributed_enabled() {
+        display::log_info(&format!(
+            "Distributed Scan activ: >{} porturi de la >={} surse (max {} porturi/sursă) in {}s, verificat la {}s",
+            config.detection.distributed_scan_ports,
+            config.detection.distributed_scan_min_sources,
+            config.detection.distributed_max_ports_per_source(),
+            config.detection.distributed_scan_window_secs,
+            config.detection.distributed_scan_interval_secs
+        ));
+        let pass_config = Arc::clone(&config);
+        let pass_engine = Arc::clone(&engine);
+        let pass_alerts = Arc::clone(&alerts);
+        #[cfg(feature = "checkpoint")]
+        let pass_responder = responder.clone();
+        tokio::spawn(async move {
+            let period = Duration::from_secs(pass_config.detection.distributed_scan_interval_secs.max(1));
+            let mut interval = tokio::time::interval(period);
+            loop {
+                interval.tick().await;
+                for alert in pass_engine.distributed_pass() {
+                    dispatch_alert(
+                        &alert,
+                        &pass_config,
+                        pass_engine.state(),
+                        &pass_alerts,
+                        #[cfg(feature = "checkpoint")]
+                        pass_responder.as_ref(),
+                    )
+                    .await;
+                }
+            }
+        });
+    }
+
     // -----------------------------------------------------------------------
     // 5. Legăm socket-ul UDP
     // -----------------------------------------------------------------------
@@ -132,11 +454,212 @@ async fn main() -> Result<()> {
         .await
         .with_context(|| format!("Nu s-a putut lega socket UDP pe {}", bind_addr))?;
 
+    // Buffer-ul de recepție implicit al kernel-ului (~200KB) se umple rapid
+    // în timpul unui burst; datagramele în plus sunt aruncate silențios.
+    if let Some(requested) = config.listener.recv_buffer_bytes {
+        apply_recv_buffer_size(&socket, requested);
+    }
+
+    // -----------------------------------------------------------------------
+    // 5b. Monitorizare drop-uri UDP la nivel de kernel
+    //
+    // Un log pierdut în kernel nu ajunge niciodată la detector, deci
+    // operatorul trebuie să afle că buffer-ul trebuie mărit.
+    // -----------------------------------------------------------------------
+    let drop_check_interval = config.listener.drop_check_interval_secs;
+    if drop_check_interval > 0 {
+        let local_port = socket
+            .local_addr()
+            .context("Nu s-a putut determina adresa locală a socket-ului UDP")?
+            .port();
+
+        tokio::spawn(async move {
+            // Valoarea de referință: contorul e cumulativ de la crearea socket-ului
+            let Some(mut last_drops) = netstat::read_udp_drops(local_port) else {
+                display::log_warn("Statisticile de drop UDP ale kernel-ului nu sunt disponibile (/proc/net/udp)");
+                return;
+            };
+
+            let mut interval = tokio::time::interval(Duration::from_secs(drop_check_interval));
+            loop {
+                interval.tick().await;
+
+                let Some(drops) = netstat::read_udp_drops(local_port) else {
+                    continue;
+                };
+                if drops > last_drops {
+                    display::log_warn(&format!(
+                        "Kernel-ul a aruncat {} datagrame UDP în ultimele {}s (total {}). \
+                         Măriți [listener] recv_buffer_bytes.",
+                        drops - last_drops,
+                        drop_check_interval,
+                        drops
+                    ));
+                }
+                last_drops = drops;
+            }
+        });
+    }
+
+    // -----------------------------------------------------------------------
+    // 5c. Listener TCP (opțional): fiecare linie intră în același flux
+    // de procesare ca un pachet UDP
+    // -----------------------------------------------------------------------
+    if let Some(tcp_port) = config.listener.tcp_port {
+        let tcp_addr = format!("{}:{}", config.listener.bind_address, tcp_port);
+        let listener = tokio::net::TcpListener::bind(&tcp_addr)
+            .await
+            .with_context(|| format!("Nu s-a putut lega listener-ul TCP pe {}", tcp_addr))?;
+        let limits = tcp::TcpLimits {
+            read_timeout:    Duration::from_secs(config.listener.read_timeout_secs),
+            max_connections: config.listener.max_connections,
+        };
+        display::log_info(&format!(
+            "Ascult pe TCP {} (max {} conexiuni, timeout {}s)",
+            tcp_addr, limits.max_connections, config.listener.read_timeout_secs
+        ));
+
+        let config = Arc::clone(&config);
+        let input  = Arc::new(Input::new("tcp", &parser, &state, &event_tap));
+        let engine = Arc::clone(&engine);
+        let alerts = Arc::clone(&alerts);
+        #[cfg(feature = "checkpoint")]
+        let responder = responder.clone();
+        tokio::spawn(tcp::serve(listener, limits, move |line, peer| {
+            let config = Arc::clone(&config);
+            let input  = Arc::clone(&input);
+            let engine = Arc::clone(&engine);
+            let alerts = Arc::clone(&alerts);
+            #[cfg(feature = "checkpoint")]
+            let responder = responder.clone();
+            tokio::spawn(async move {
+                process_packet(
+                    &line,
+                    peer,
+                    &config,
+                    &input,
+                    &engine,
+                    &alerts,
+                    #[cfg(feature = "checkpoint")]
+                    responder.as_ref(),
+                )
+                .await;
+            });
+        }));
+    }
+
+    // -----------------------------------------------------------------------
+    // 5d. Endpoint de control (opțional, doar pentru teste/demo):
+    // evenimente sintetice injectate direct în fluxul de detecție
+    // -----------------------------------------------------------------------
+    if config.control.enabled {
+        let control_addr = format!("{}:{}", config.control.bind_address, config.control.port);
+        let listener = tokio::net::TcpListener::bind(&control_addr)
+            .await
+            .with_context(|| format!("Nu s-a putut lega endpoint-ul [control] pe {}", control_addr))?;
+        display::log_warn(&format!(
+            "Endpoint de control ACTIV pe http://{}/inject - nu folosiți în producție",
+            control_addr
+        ));
+
+        let config = Arc::clone(&config);
+        let engine = Arc::clone(&engine);
+        let alerts = Arc::clone(&alerts);
+        #[cfg(feature = "checkpoint")]
+        let responder = responder.clone();
+        tokio::spawn(http::serve(listener, http::DEFAULT_MAX_CONNECTIONS, control::MAX_BODY_BYTES, move |request| {
+            let config = Arc::clone(&config);
+            let engine = Arc::clone(&engine);
+            let alerts = Arc::clone(&alerts);
+            #[cfg(feature = "checkpoint")]
+            let responder = responder.clone();
+            async move {
+                handle_inject(
+                    request,
+                    &config,
+                    &engine,
+                    &alerts,
+                    #[cfg(feature = "checkpoint")]
+                    responder.as_ref(),
+                )
+                .await
+            }
+        }));
+    }
+
+    // -----------------------------------------------------------------------
+    // 5e. Intrarea HTTP (opțională): `POST /ingest` cu linii de log, pentru
+    // agenții care nu pot trimite syslog - același flux ca pachetele UDP
+    // -----------------------------------------------------------------------
+    if config.http_ingest.enabled {
+        let ingest_addr = format!("{}:{}", config.http_ingest.bind_address, config.http_ingest.port);
+        let listener = tokio::net::TcpListener::bind(&ingest_addr)
+            .await
+            .with_context(|| format!("Nu s-a putut lega [http_ingest] pe {}", ingest_addr))?;
+        display::log_info(&format!("Ascult pe HTTP http://{}/ingest", ingest_addr));
+        if config.http_ingest.token.is_none() {
+            display::log_warn("[http_ingest] fără token: oricine ajunge la port poate trimite log-uri");
+        }
+
+        let config = Arc::clone(&config);
+        let input  = Arc::new(Input::new("http", &parser, &state, &event_tap));
+        let engine = Arc::clone(&engine);
+        let alerts = Arc::clone(&alerts);
+        #[cfg(feature = "checkpoint")]
+        let responder = responder.clone();
+        tokio::spawn(http::serve(listener, config.http_ingest.max_connections, ingest::MAX_BODY_BYTES, move |request| {
+            let config = Arc::clone(&config);
+            let input  = Arc::clone(&input);
+            let engine = Arc::clone(&engine);
+            let alerts = Arc::clone(&alerts);
+            #[cfg(feature = "checkpoint")]
+            let responder = responder.clone();
+            async move {
+                handle_ingest(
+                    request,
+                    &config,
+                    &input,
+                    &engine,
+                    &alerts,
+                    #[cfg(feature = "checkpoint")]
+                    responder.as_ref(),
+                )
+                .await
+            }
+        }));
+    }
+
+    // -----------------------------------------------------------------------
+    // 5f. Sănătate (opțional): `GET /healthz` și watchdog-ul systemd, ambele
+    // pe baza activității buclei de recepție de mai jos
+    // -----------------------------------------------------------------------
+    let liveness = Arc::new(liveness::Liveness::new());
+    let liveness_timeout = Duration::from_secs(config.health.liveness_timeout_secs);
+    if config.health.enabled {
+        let health_addr = format!("{}:{}", config.health.bind_address, config.health.port);
+        let listener = tokio::net::TcpListener::bind(&health_addr)
+            .await
+            .with_context(|| format!("Nu s-a putut lega [health] pe {}", health_addr))?;
+        display::log_info(&format!("Endpoint de sănătate pe http://{}/healthz", health_addr));
+
+        let liveness = Arc::clone(&liveness);
+        tokio::spawn(http::serve(listener, http::DEFAULT_MAX_CONNECTIONS, 0, move |request| {
+            let response = health::handle_healthz(&request, &liveness, liveness_timeout);
+            async move { response }
+        }));
+    }
+    if config.health.systemd_watchdog {
+        health::start_systemd_watchdog(Arc::clone(&liveness), liveness_timeout);
+    }
+
     display::log_info(&format!("Ascult pe UDP {} ...", bind_addr));
     display::print_separator();
 
     // Buffer pentru datele UDP (64KB - dimensiunea maximă a unui pachet UDP)
-    let mut buf = vec![0u8; 65535];
+    let mut buf = vec![0u8; datagram::MAX_DATAGRAM_BYTES];
+
+    // Buffere text refolosite de task-urile de procesare (fără alocare per pachet)
+    let text_buffers = Arc::new(datagram::BufferPool::new(datagram::MAX_POOLED_BUFFERS));
 
     // -----------------------------------------------------------------------
     // 6. Bucla principală de procesare
@@ -145,33 +668,328 @@ async fn main() -> Result<()> {
     //   - Suspendă task-ul curent dacă nu sunt date disponibile
     //   - Tokio procesează alte task-uri între timp
     //   - Când sosesc date, task-ul este reprogramat pentru execuție
+    //
+    // `tokio::select!` așteaptă simultan un pachet SAU semnalul de oprire
+    // (Ctrl+C / SIGTERM) - prima ramură gata câștigă.
     // -----------------------------------------------------------------------
+    // Tic-ul de inactivitate: fără trafic, bucla tot "bate" de câteva ori
+    // per `liveness_timeout_secs`, deci un sistem liniștit rămâne sănătos
+    // în `/healthz`; doar o buclă blocată nu mai atinge `liveness`.
+    let mut shutdown = std::pin::pin!(shutdown_signal());
+    let mut liveness_tick = tokio::time::interval(liveness_timeout / 3);
+    liveness_tick.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Delay);
     loop {
-        let (len, src_addr) = socket
-            .recv_from(&mut buf)
-            .await
-            .context("Eroare la recv_from UDP")?;
+        let (len, src_addr) = tokio::select! {
+            received = socket.recv_from(&mut buf) => received.context("Eroare la recv_from UDP")?,
+            _ = liveness_tick.tick() => {
+                liveness.touch();
+                continue;
+            }
+            _ = &mut shutdown => break,
+        };
+        liveness.record_packet();
 
-        // Convertim bytes-ii la String (lossy = înlocuiește caractere invalide cu '?')
-        // `to_string()` crează un String owned, necesar pentru task-ul spawn
-        let raw_data = String::from_utf8_lossy(&buf[..len]).to_string();
+        // Convertim bytes-ii la String într-un buffer din pool (owned, necesar
+        // pentru task-ul spawn). UTF-8 invalid și trunchierea se numără, iar
+        // linia parțială a unei datagrame trunchiate nu ajunge la parser.
+        let mut raw_data = text_buffers.take();
+        let report = datagram::decode_into(&buf[..len], buf.len(), config.listener.record_separator(), &mut raw_data);
+        if report != datagram::DecodeReport::default() {
+            warn_datagram_issues(&state, src_addr, len, report, &raw_data);
+        }
 
         // -----------------------------------------------------------------------
         // Clonăm Arc-urile pentru task-ul spawned
         //
         // De ce clonăm? `tokio::spawn(async move { ... })` preia ownership-ul
-        // variabilelor capturate. Dacă am muta `config` sau `state` în task,
+        // variabilelor capturate. Dacă am muta `config` sau `engine` în task,
         // nu le-am mai putea folosi în iterația următoare a buclei `loop`.
         // Arc::clone() este ieftin: O(1), incrementează atomic un contor.
         // -----------------------------------------------------------------------
         let config   = Arc::clone(&config);
-        let parser   = Arc::clone(&parser);
-        let state    = state.clone(); // SharedState::clone clonează Arc-urile interne
+        let input    = Arc::clone(&udp_input);
+        let engine   = Arc::clone(&engine); // motorul deține starea shared
+        let alerts   = Arc::clone(&alerts);
+        let text_buffers = Arc::clone(&text_buffers);
+        #[cfg(feature = "checkpoint")]
+        let responder = responder.clone();
 
         tokio::spawn(async move {
-            process_packet(&raw_data, &src_addr.to_string(), &config, &parser, &state).await;
+            process_packet(
+                &raw_data,
+                src_addr,
+                &config,
+                &input,
+                &engine,
+                &alerts,
+                #[cfg(feature = "checkpoint")]
+                responder.as_ref(),
+            )
+            .await;
+            text_buffers.give(raw_data);
         });
     }
+
+    // -----------------------------------------------------------------------
+    // 7. Oprire ordonată: alertele agregate încă netrimise pleacă la SIEM,
+    //    jurnalul de evenimente se golește pe disc
+    // -----------------------------------------------------------------------
+    display::log_info("Semnal de oprire primit - se trimit alertele în așteptare...");
+    alerts.flush_siem().await;
+
+    // Clonele tap-ului din task-urile încă pornite (listener-e, sumar) nu se
+    // eliberează singure: `close` oprește writer-ul după ce scrie coada,
+    // apoi așteptăm thread-ul (blocant - în afara runtime-ului async)
+    drop(udp_input);
+    if let (Some(events), Some(writer)) = (event_tap, event_writer) {
+        let closed = tokio::task::spawn_blocking(move || {
+            events.close();
+            let joined = writer.join().is_ok();
+            (joined, events.written(), events.dropped())
+        })
+        .await;
+        match closed {
+            Ok((true, written, dropped)) => display::log_info(&format!(
+                "Jurnalul de evenimente închis: {} scrise, {} pierdute",
+                written, dropped
+            )),
+            _ => display::log_warn("Thread-ul [event_log] s-a oprit cu eroare; ultimele evenimente pot lipsi"),
+        }
+    }
+    display::log_info("IDS oprit.");
+
+    Ok(())
+}
+
+// ---------------------------------------------------------------------------
+// O intrare de log-uri: parser-ul activ și contoarele de ingestie ale
+// listener-ului ("udp" / "tcp"). Parser-ul și jurnalul de evenimente sunt
+// partajate între listener-e, contoarele nu.
+// ---------------------------------------------------------------------------
+struct Input {
+    parser: Arc<Box<dyn LogParser>>,
+    stats:  diagnostics::IngestHandle,
+    /// `[event_log]`: fiecare eveniment parsat, spre jurnalul JSONL
+    events: Option<Arc<event_log::EventTap>>,
+}
+
+impl Input {
+    fn new(
+        listener: &str,
+        parser: &Arc<Box<dyn LogParser>>,
+        state: &SharedState,
+        events: &Option<Arc<event_log::EventTap>>,
+    ) -> Self {
+        Self {
+            parser: Arc::clone(parser),
+            stats:  state.parse_diagnostics().ingest.handle(listener, parser.name()),
+            events: events.clone(),
+        }
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Avertismentul (o singură dată per sursă și problemă) pentru o datagramă
+// cu UTF-8 invalid sau trunchiată. Eșantionul este textul deja decodat,
+// sanitizat - conținut controlat de expeditor.
+// ---------------------------------------------------------------------------
+fn warn_datagram_issues(state: &SharedState, src_addr: SocketAddr, len: usize, report: datagram::DecodeReport, text: &str) {
+    for issue in state.parse_diagnostics().datagrams.record(src_addr.ip(), report) {
+        match issue {
+            diagnostics::DatagramIssue::Lossy => display::log_warn(&format!(
+                "Datagramă cu UTF-8 invalid de la {} (caracterele invalide au fost înlocuite). \
+                 Verificați encoding-ul exporterului. Exemplu: \"{}\"",
+                src_addr.ip(),
+                diagnostics::sanitize_sample(text)
+            )),
+            diagnostics::DatagramIssue::Truncated => display::log_warn(&format!(
+                "Datagramă trunchiată de la {}: {} bytes au umplut buffer-ul; ultima linie \
+                 (incompletă) a fost ignorată",
+                src_addr.ip(),
+                len
+            )),
+            // Liniile prea lungi se raportează per linie (`warn_oversized_line`)
+            diagnostics::DatagramIssue::Oversized => {}
+        }
+    }
+}
+
+/// O linie peste `[listener] max_line_bytes`: numărată, avertisment o dată per sursă
+fn warn_oversized_line(state: &SharedState, src_addr: SocketAddr, line: &str, max_line_bytes: usize) {
+    if state.parse_diagnostics().datagrams.record_oversized(src_addr.ip()) {
+        display::log_warn(&format!(
+            "Linie de {} bytes de la {} sărită (peste [listener] max_line_bytes = {}). Început: \"{}\"",
+            line.len(),
+            src_addr.ip(),
+            max_line_bytes,
+            diagnostics::sanitize_sample(line)
+        ));
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Subcomanda `report --since 7d --format html|text`: sumarul perioadei din
+// jurnalele `[alert_log]` / `[event_log]` (vezi report.rs), scris la stdout.
+// Nu pornește daemon-ul și nu trimite nimic.
+// ---------------------------------------------------------------------------
+fn report(args: &[String]) -> Result<()> {
+    const USAGE: &str = "Utilizare: rust-ids report [--since 7d] [--format html|text]";
+    let mut span = chrono::Duration::days(7);
+    let mut format = report::ReportFormat::Text;
+    let mut options = args.iter();
+    while let Some(flag) = options.next() {
+        let value = options.next();
+        match (flag.as_str(), value.map(String::as_str)) {
+            ("--since", Some(since)) => {
+                span = report::parse_since(since).map_err(|e| anyhow::anyhow!("{}. {}", e, USAGE))?
+            }
+            ("--format", Some("html")) => format = report::ReportFormat::Html,
+            ("--format", Some("text")) => format = report::ReportFormat::Text,
+            ("--format", Some(other)) => anyhow::bail!("--format '{}' necunoscut. {}", other, USAGE),
+            _ => anyhow::bail!("Argument neașteptat '{}'. {}", flag, USAGE),
+        }
+    }
+
+    let config = Config::load_optional(CONFIG_PATH)
+        .with_context(|| format!("Eroare fatală: nu s-a putut încărca {}", CONFIG_PATH))?
+        .unwrap_or_default();
+    let report = report::build(&config, span, chrono::Utc::now())?;
+    print!("{}", report.render(format));
+    Ok(())
+}
+
+/// Construiește raportul programat (citirea jurnalelor blochează - thread
+/// separat) și îl trimite către `[report] to`
+async fn send_scheduled_report(config: &Arc<Config>, schedule: ReportSchedule) {
+    let build_config = Arc::clone(config);
+    let built = tokio::task::spawn_blocking(move || report::build(&build_config, schedule.period(), chrono::Utc::now()))
+        .await
+        .map_err(anyhow::Error::from)
+        .and_then(|built| built);
+
+    match built {
+        Ok(report) => match alert::send_report_email(&report, &config.report, &config.email).await {
+            Ok(()) => display::log_info(&format!("Raportul programat a fost trimis către {}", config.report.to.join(", "))),
+            Err(e) => display::log_warn(&format!("Raportul programat nu a putut fi trimis: {:#}", e)),
+        },
+        Err(e) => display::log_warn(&format!("Raportul programat nu a putut fi construit: {:#}", e)),
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Subcomanda `bench`: capacitatea end-to-end a acestei mașini, cu pragurile
+// din config.toml (sau cele implicite). Nu deschide socket-uri și nu trimite
+// alerte - vezi bench.rs.
+// ---------------------------------------------------------------------------
+fn run_bench(args: &[String]) -> Result<()> {
+    const USAGE: &str =
+        "Utilizare: rust-ids bench [--sources N] [--events M] [--duration SEC] [--workers W]";
+    let mut options = bench::BenchOptions::default();
+    let mut flags = args.iter();
+    while let Some(flag) = flags.next() {
+        let value = flags.next().map(String::as_str);
+        let number = |value: Option<&str>| -> Result<u64> {
+            value
+                .and_then(|v| v.parse::<u64>().ok())
+                .with_context(|| format!("{} așteaptă un număr întreg. {}", flag, USAGE))
+        };
+        match flag.as_str() {
+            "--sources" => options.sources = u32::try_from(number(value)?).unwrap_or(u32::MAX),
+            "--events" => options.events_per_source = u32::try_from(number(value)?).unwrap_or(u32::MAX),
+            "--duration" => options.duration = Duration::from_secs(number(value)?),
+            "--workers" => options.workers = usize::try_from(number(value)?).unwrap_or(usize::MAX),
+            _ => anyhow::bail!("Argument neașteptat '{}'. {}", flag, USAGE),
+        }
+    }
+
+    let config = Config::load_optional(CONFIG_PATH)
+        .with_context(|| format!("Eroare fatală: nu s-a putut încărca {}", CONFIG_PATH))?
+        .unwrap_or_default();
+    display::log_info(&format!(
+        "Bench: {} surse x {} evenimente, {} worker-i, {}s...",
+        options.sources,
+        options.events_per_source,
+        options.workers,
+        options.duration.as_secs()
+    ));
+    let report = bench::run(&config, &options)?;
+    display::print_bench_report(&report);
+    Ok(())
+}
+
+// ---------------------------------------------------------------------------
+// Subcomanda `init-config`: calea implicită este `config.toml` din directorul
+// curent (cea citită la pornire); un fișier existent se suprascrie doar cu
+// `--force`.
+// ---------------------------------------------------------------------------
+fn init_config(args: &[String]) -> Result<()> {
+    let force = args.iter().any(|arg| arg == "--force");
+    let mut paths = args.iter().filter(|arg| !arg.starts_with("--"));
+    let path = paths.next().map_or(CONFIG_PATH, String::as_str);
+    if let Some(extra) = paths.next() {
+        anyhow::bail!("Argument neașteptat '{}'. Utilizare: rust-ids init-config [cale] [--force]", extra);
+    }
+    if let Some(flag) = args.iter().find(|arg| arg.starts_with("--") && *arg != "--force") {
+        anyhow::bail!("Opțiune necunoscută '{}'. Utilizare: rust-ids init-config [cale] [--force]", flag);
+    }
+
+    config_template::write_default(std::path::Path::new(path), force)?;
+    display::log_info(&format!("Configurația implicită a fost scrisă în '{}'", path));
+    Ok(())
+}
+
+// ---------------------------------------------------------------------------
+// Future care se finalizează la Ctrl+C sau (pe Unix) la SIGTERM - semnalul
+// trimis de systemd la `systemctl stop`.
+// ---------------------------------------------------------------------------
+async fn shutdown_signal() {
+    #[cfg(unix)]
+    {
+        use tokio::signal::unix::{signal, SignalKind};
+        match signal(SignalKind::terminate()) {
+            Ok(mut sigterm) => {
+                tokio::select! {
+                    _ = tokio::signal::ctrl_c() => {}
+                    _ = sigterm.recv() => {}
+                }
+            }
+            Err(_) => {
+                let _ = tokio::signal::ctrl_c().await;
+            }
+        }
+    }
+
+    #[cfg(not(unix))]
+    {
+        let _ = tokio::signal::ctrl_c().await;
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Setează SO_RCVBUF pe socket-ul tokio
+//
+// `socket2::SockRef` împrumută descriptorul socket-ului fără a prelua
+// ownership-ul - socket-ul rămâne gestionat de tokio.
+//
+// Linux dublează valoarea cerută (spațiu pentru metadate) și o plafonează
+// la `net.core.rmem_max`, deci raportăm valoarea efectivă citită înapoi.
+// ---------------------------------------------------------------------------
+fn apply_recv_buffer_size(socket: &UdpSocket, requested: usize) {
+    let sock_ref = socket2::SockRef::from(socket);
+
+    if let Err(e) = sock_ref.set_recv_buffer_size(requested) {
+        display::log_error(&format!("Nu s-a putut seta SO_RCVBUF la {} bytes: {}", requested, e));
+        return;
+    }
+
+    match sock_ref.recv_buffer_size() {
+        Ok(effective) if effective < requested => display::log_warn(&format!(
+            "SO_RCVBUF efectiv este {} bytes (cerut {}). Măriți net.core.rmem_max.",
+            effective, requested
+        )),
+        Ok(effective) => display::log_info(&format!("Buffer recepție UDP: {} bytes", effective)),
+        Err(e) => display::log_warn(&format!("Nu s-a putut citi SO_RCVBUF: {}", e)),
+    }
 }
 
 // ---------------------------------------------------------------------------
@@ -182,26 +1000,115 @@ async fn main() -> Result<()> {
 //
 // NOTĂ despre "buffer coalescing":
 // Firewall-urile pot trimite multiple log-uri într-un singur pachet UDP
-// (pentru eficiență). Le separăm prin newline.
+// (pentru eficiență). Le separăm prin `[listener] record_delimiter`.
+//
+// Detecția propriu-zisă este în `Engine`; aici rămân doar efectele:
+// diagnosticele de parsare, consola și trimiterea alertelor decise.
+//
+// Span-ul `packet` poartă adresa exporterului (`src_addr`): fiecare
+// eveniment structurat emis în timpul procesării o moștenește.
+//
+// Efectele alertelor (SIEM, email, blocare) și alerta de parse health
+// rulează în task-uri separate: apelantul (ex: `POST /ingest`) primește
+// sumarul fără să aștepte livrarea.
 // ---------------------------------------------------------------------------
 async fn process_packet(
     raw_data: &str,
-    src_addr: &str,
+    src_addr: SocketAddr,
     config:   &Arc<Config>,
-    parser:   &Arc<Box<dyn LogParser>>,
-    state:    &SharedState,
-) {
-    // Split pe newline-uri - gestionăm "buffer coalescing"
-    // Un pachet poate conține 1 sau mai multe log-uri concatenate
-    for line in raw_data.lines() {
-        let line = line.trim();
-        if line.is_empty() {
-            continue;
-        }
+    input:    &Input,
+    engine:   &Arc<Engine>,
+    alerts:   &Arc<AlertDispatcher>,
+    #[cfg(feature = "checkpoint")]
+    responder: Option<&Arc<response::checkpoint::CheckpointResponder>>,
+) -> PacketSummary {
+    // Contoarele pentru heartbeat / detectarea tăcerii fluxului de intrare
+    engine.state().activity().record_packet();
+    input.stats.record_datagram();
+
+    process_records(
+        raw_data,
+        src_addr,
+        config,
+        input,
+        engine,
+        alerts,
+        #[cfg(feature = "checkpoint")]
+        responder,
+    )
+    .await
+}
+
+/// Liniile unui pachet (sau ale unei cereri `POST /ingest`), fără contoarele
+/// per datagramă - apelantul înregistrează o singură dată pachetul / cererea
+#[tracing::instrument(name = "packet", skip_all, fields(src_addr = %src_addr))]
+async fn process_records(
+    raw_data: &str,
+    src_addr: SocketAddr,
+    config:   &Arc<Config>,
+    input:    &Input,
+    engine:   &Arc<Engine>,
+    alerts:   &Arc<AlertDispatcher>,
+    #[cfg(feature = "checkpoint")]
+    responder: Option<&Arc<response::checkpoint::CheckpointResponder>>,
+) -> PacketSummary {
+    let state = engine.state();
+    let parser = &input.parser;
+    let mut summary = PacketSummary::default();
+
+    // Split pe separatorul configurat (implicit newline) - gestionăm
+    // "buffer coalescing": un pachet poate conține 1 sau mai multe log-uri
+    // concatenate. Segmentele goale sunt deja sărite de `records`.
+    let separator = config.listener.record_separator();
+    let oversized = |line: &str| warn_oversized_line(state, src_addr, line, config.listener.max_line_bytes);
+    for line in datagram::records_within(raw_data, separator, config.listener.max_line_bytes, oversized) {
 
         // Parsăm linia cu parser-ul activ
-        // `parse()` returnează Option<LogEntry> - None dacă linia nu e relevantă
-        let entry = match parser.parse(line) {
+        // `parse_outcome()` spune și DE CE o linie nu a produs un eveniment
+        let outcome = parser.parse_outcome(line);
+        let diagnostics = state.parse_diagnostics();
+        diagnostics.parser_stats.record(&outcome);
+        input.stats.record_line(&outcome);
+        summary.record(&outcome);
+
+        match &outcome {
+            parser::ParseOutcome::Unrecognized => {
+                tracing::debug!(target: EVENTS_TARGET, outcome = "unrecognized", "parse");
+            }
+            parser::ParseOutcome::IgnoredAction(action) => {
+                tracing::debug!(target: EVENTS_TARGET, outcome = "ignored_action", action = %action, "parse");
+            }
+            _ => {}
+        }
+
+        let unrecognized = matches!(outcome, parser::ParseOutcome::Unrecognized);
+        if unrecognized && diagnostics.should_log_unrecognized() {
+            display::log_debug(&format!(
+                "Linie nerecunoscută de '{}' de la {}: \"{}\"",
+                parser.name(),
+                src_addr,
+                diagnostics::sanitize_sample(line)
+            ));
+        }
+
+        // Diagnostic: un exporter ale cărui linii nu se parsează aproape
+        // niciodată are, cel mai probabil, formatul greșit.
+        // O acțiune ignorată (ex: accept) NU este un eșec de format.
+        if let Some(warning) = diagnostics.record(src_addr.ip(), !unrecognized, line) {
+            display::log_warn(&format!(
+                "{:.1}% din ultimele {} linii de la {} nu au putut fi parsate de '{}'. \
+                 Verificați [listener] parser. Exemplu: \"{}\"",
+                warning.failure_percent,
+                diagnostics::BLOCK_LINES,
+                warning.source,
+                parser.name(),
+                warning.sample
+            ));
+            let alerts = Arc::clone(alerts);
+            tokio::spawn(async move { alerts.send_parse_health(&warning).await }.in_current_span());
+        }
+
+        let entry = match outcome.into_entry() {
             Some(e) => e,
             None => {
                 // Linia nu e un log valid sau nu e de tip "drop" - ignorăm
@@ -209,43 +1116,317 @@ async fn process_packet(
             }
         };
 
-        // Logăm evenimentul de drop (nivel debug pentru a nu polua consola)
-        display::log_drop_event(&entry.source_ip, entry.dest_port);
+        tracing::debug!(
+            target: EVENTS_TARGET,
+            outcome = "matched",
+            ip = %entry.source_ip,
+            port = entry.dest_port(),
+            action = %entry.action,
+            "parse"
+        );
 
-        // Înregistrăm evenimentul în starea shared
-        state.record_event(entry.source_ip, entry.dest_port);
+        // Evenimentul normalizat intră în `[event_log]` (fără să aștepte discul)
+        if let Some(events) = &input.events {
+            events.record(&entry);
+        }
 
-        // Evaluăm dacă pragurile de detecție sunt depășite
-        let detection = evaluate(&entry.source_ip, state, &config.detection);
+        // Linia brută intră în buffer-ul de context (no-op dacă e dezactivat)
+        state.context().push(entry.source_ip, line);
 
-        // Dacă s-a detectat o amenințare ȘI IP-ul nu e în cooldown
-        if detection.is_threat() && !state.is_in_cooldown(&entry.source_ip, config.detection.alert_cooldown_secs) {
-            // Marcăm IP-ul ca alertat (intrăm în cooldown)
-            state.mark_alerted(entry.source_ip);
+        // Logăm evenimentul de drop (agregat/oprit conform [display] drop_events)
+        if !entry.accepted {
+            display::log_drop_event(&entry.source_ip, &entry.kind);
+        }
 
-            // Afișăm alerta vizuală în consolă
-            match &detection {
-                detector::DetectionResult::FastScan { ports, window_secs } => {
-                    display::log_fast_scan_alert(&entry.source_ip, *ports, *window_secs);
+        // Motorul decide; o sursă evaluată curată recent nu se re-evaluează
+        // la fiecare pachet - evaluarea amânată rulează într-un task separat
+        let ip = entry.source_ip;
+        let ingested = engine.ingest_throttled(entry);
+        if let Some(delay) = ingested.deferred {
+            summary.deferred += 1;
+            let config = Arc::clone(config);
+            let engine = Arc::clone(engine);
+            let alerts = Arc::clone(alerts);
+            #[cfg(feature = "checkpoint")]
+            let responder = responder.cloned();
+            // Task-ul amânat rămâne în span-ul pachetului (același `src_addr`)
+            tokio::spawn(
+                async move {
+                    tokio::time::sleep(delay).await;
+                    if let Some(alert) = engine.evaluate_deferred(&ip) {
+                        dispatch_alert(
+                            &alert,
+                            &config,
+                            engine.state(),
+                            &alerts,
+                            #[cfg(feature = "checkpoint")]
+                            responder.as_ref(),
+                        )
+                        .await;
+                    }
                 }
-                detector::DetectionResult::SlowScan { ports, window_mins } => {
-                    display::log_slow_scan_alert(&entry.source_ip, *ports, *window_mins);
-                }
-                detector::DetectionResult::BothScans { fast_ports, .. } => {
-                    // Prioritizăm afișarea Fast Scan pentru BothScans
-                    display::log_fast_scan_alert(&entry.source_ip, *fast_ports, config.detection.fast_scan_window_secs);
+                .in_current_span(),
+            );
+        }
+
+        if let Some(alert) = ingested.alert {
+            summary.alerted += 1;
+            let config = Arc::clone(config);
+            let engine = Arc::clone(engine);
+            let alerts = Arc::clone(alerts);
+            #[cfg(feature = "checkpoint")]
+            let responder = responder.cloned();
+            tokio::spawn(
+                async move {
+                    dispatch_alert(
+                        &alert,
+                        &config,
+                        engine.state(),
+                        &alerts,
+                        #[cfg(feature = "checkpoint")]
+                        responder.as_ref(),
+                    )
+                    .await;
                 }
-                detector::DetectionResult::Clean => unreachable!(),
+                .in_current_span(),
+            );
+        }
+    }
+
+    summary
+}
+
+/// Ce a produs un pachet: linii văzute, recunoscute / respinse, alerte decise
+/// imediat și evaluări amânate (alertele lor, dacă apar, vin ulterior)
+#[derive(Debug, Default, Clone, Copy)]
+struct PacketSummary {
+    lines:    u64,
+    parsed:   u64,
+    rejected: u64,
+    alerted:  u64,
+    deferred: u64,
+}
+
+impl PacketSummary {
+    fn record(&mut self, outcome: &parser::ParseOutcome) {
+        self.lines += 1;
+        match outcome {
+            parser::ParseOutcome::Unrecognized => self.rejected += 1,
+            _ => self.parsed += 1,
+        }
+    }
+
+    fn add(&mut self, other: PacketSummary) {
+        self.lines += other.lines;
+        self.parsed += other.parsed;
+        self.rejected += other.rejected;
+        self.alerted += other.alerted;
+        self.deferred += other.deferred;
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Efectele unei alerte decise de `Engine`: consola, SIEM + email (cu
+// contextul liniilor brute) și, dacă e cazul, răspunsul activ.
+//
+// Blocarea pe firewall vine după gating-ul de cooldown și doar pentru
+// alertele efectiv trimise - nu în warmup.
+// ---------------------------------------------------------------------------
+async fn dispatch_alert(
+    alert:     &Alert,
+    config:    &Config,
+    state:     &SharedState,
+    alerts:    &AlertDispatcher,
+    #[cfg(feature = "checkpoint")]
+    responder: Option<&Arc<response::checkpoint::CheckpointResponder>>,
+) {
+    display_alert(alert, config);
+
+    // Scanările distribuite nu au o singură sursă: fără context
+    let context = match alert.result {
+        DetectionResult::DistributedScan { .. } => Vec::new(),
+        _ => state.context().recent_for(&alert.ip, config.alert.context_lines),
+    };
+    let alert_payload = AlertPayload {
+        ip:       &alert.ip,
+        result:   &alert.result,
+        targets:  &alert.targets,
+        rules:    &alert.rules,
+        context:  &context,
+        labels:   &alert.labels,
+        evidence: alert.evidence.as_ref(),
+    };
+    let dispatched = alerts.send_alerts(&alert_payload).await;
+
+    #[cfg(feature = "checkpoint")]
+    if let (true, true, Some(responder)) = (dispatched, alert.blocks(), responder) {
+        use response::checkpoint::BlockOutcome;
+        match responder.block(&alert.ip, &alert.result, state).await {
+            BlockOutcome::Skipped => {}
+            BlockOutcome::DryRun { endpoint, rule } => {
+                display::log_info(&format!("[DRY-RUN] POST {} {}", endpoint, rule));
             }
+            BlockOutcome::Blocked => display::log_alert_sent(responder.management_url(), "Check Point SAM"),
+            BlockOutcome::Failed(e) => {
+                display::log_warn(&format!("Blocarea SAM pentru {} a eșuat: {:#}", alert.ip, e));
+            }
+        }
+    }
+    #[cfg(not(feature = "checkpoint"))]
+    let _ = dispatched;
+}
+
+/// Alerta vizuală în consolă, după tipul detecției
+fn display_alert(alert: &Alert, config: &Config) {
+    let ip = &alert.ip;
+    if alert.escalated {
+        display::log_info(&format!(
+            "Escaladare pentru {}: severitate {} > ultima alertă, cooldown ignorat",
+            ip, alert.severity
+        ));
+    }
+
+    match &alert.result {
+        DetectionResult::DistributedScan { dest, sources, total_unique_ports, window_secs, sample } => {
+            display::log_distributed_scan_alert(dest.as_ref(), *sources, *total_unique_ports, *window_secs, sample);
+        }
+        DetectionResult::IcmpSweep { events, destinations, window_secs } => {
+            display::log_icmp_sweep_alert(ip, *events, *destinations, *window_secs);
+        }
+        DetectionResult::PostScanAccess { port } => {
+            display::log_post_scan_access_alert(ip, alert.dest.as_ref(), *port);
+        }
+        DetectionResult::KnownOffenderReturned { alerts, since_alert_secs } => {
+            display::log_known_offender_alert(ip, *alerts, *since_alert_secs);
+        }
+        // Scanările de porturi: tier-ul principal (cel mai sever)
+        result => {
+            if let Some(hit) = result.primary() {
+                display::log_scan_alert(ip, &hit.tier, hit.ports, hit.window_secs, result.severity());
+            }
+        }
+    }
+
+    if !alert.labels.is_empty() {
+        display::log_info(&format!("Etichete pentru {}: {}", ip, alert.labels.join(", ")));
+    }
+    if let Some(evidence) = &alert.evidence {
+        display::log_info(&format!("Dovezi pentru {}: {}", ip, evidence.compact()));
+    }
+
+    let base = Duration::from_secs(config.detection.alert_cooldown_secs);
+    if let Some(cooldown) = alert.cooldown.filter(|cooldown| *cooldown > base) {
+        display::log_info(&format!(
+            "Re-alertare pentru {}: următoarea alertă cel mai devreme peste {}s",
+            ip,
+            cooldown.as_secs()
+        ));
+    }
+}
 
-            // Trimitem alertele externe (SIEM + email)
-            let alert_payload = AlertPayload {
-                ip:     &entry.source_ip,
-                result: &detection,
+// ---------------------------------------------------------------------------
+// `POST /inject`: fiecare eveniment sintetic trece prin `Engine::ingest_at`,
+// exact ca un log real (cooldown-urile și alertele sunt cele reale).
+// Fără throttling: numărul de alerte din răspuns trebuie să fie complet.
+// ---------------------------------------------------------------------------
+async fn handle_inject(
+    request:   http::Request,
+    config:    &Arc<Config>,
+    engine:    &Engine,
+    alerts:    &Arc<AlertDispatcher>,
+    #[cfg(feature = "checkpoint")]
+    responder: Option<&Arc<response::checkpoint::CheckpointResponder>>,
+) -> http::Response {
+    let specs = match control::parse_inject(&request, config.control.token.as_deref()) {
+        Ok(specs) => specs,
+        Err(response) => return response,
+    };
+
+    let now = engine.state().now();
+    let (mut events, mut alerted) = (0u64, 0u64);
+    for spec in &specs {
+        for injected in control::expand(spec, now) {
+            if injected.entry.dest_port().is_none() {
+                continue;
+            }
+            events += 1;
+            let Some(alert) = engine.ingest_at(injected.entry, injected.seen_at) else {
+                continue;
             };
-            send_alerts(&alert_payload, config).await;
+            alerted += 1;
+            dispatch_alert(
+                &alert,
+                config,
+                engine.state(),
+                alerts,
+                #[cfg(feature = "checkpoint")]
+                responder,
+            )
+            .await;
         }
+    }
+
+    display::log_info(&format!(
+        "[CONTROL] {} evenimente injectate de {} ({} alerte)",
+        events, request.peer, alerted
+    ));
+    http::Response::json(200, serde_json::json!({ "events": events, "alerts": alerted }).to_string())
+}
+
+// ---------------------------------------------------------------------------
+// `POST /ingest`: fiecare linie din corp trece prin `process_records`, ca o
+// linie TCP. Cererea contează o singură dată (ca o datagramă) în contoarele
+// de intrare. Răspunsul este sumarul lotului; alertele pleacă în fundal.
+// ---------------------------------------------------------------------------
+async fn handle_ingest(
+    request:   http::Request,
+    config:    &Arc<Config>,
+    input:     &Input,
+    engine:    &Arc<Engine>,
+    alerts:    &Arc<AlertDispatcher>,
+    #[cfg(feature = "checkpoint")]
+    responder: Option<&Arc<response::checkpoint::CheckpointResponder>>,
+) -> http::Response {
+    let lines = match ingest::parse_ingest(&request, config.http_ingest.token.as_deref()) {
+        Ok(lines) => lines,
+        Err(response) => return response,
+    };
 
-        let _ = src_addr; // Suprima warning "unused" - poate fi folosit pentru logging extins
+    engine.state().activity().record_packet();
+    input.stats.record_datagram();
+
+    let mut summary = PacketSummary::default();
+    for line in &lines {
+        summary.add(
+            process_records(
+                line,
+                request.peer,
+                config,
+                input,
+                engine,
+                alerts,
+                #[cfg(feature = "checkpoint")]
+                responder,
+            )
+            .await,
+        );
     }
+
+    http::Response::json(
+        200,
+        serde_json::json!({
+            "lines": summary.lines,
+            "parsed": summary.parsed,
+            "rejected": summary.rejected,
+            "alerted": summary.alerted,
+            "deferred": summary.deferred,
+        })
+        .to_string(),
+    )
+}
+
+/// Fluxul de intrare tace de peste `[listener] input_silence_warn_secs`?
+fn input_is_silent(config: &Config, state: &SharedState) -> bool {
+    let threshold = config.listener.input_silence_warn_secs;
+    threshold > 0 && state.activity().silent_for() >= Duration::from_secs(threshold)
 }
@@ -0,0 +1,65 @@
+// ============================================================
+//  netstat.rs - Statistici de drop UDP raportate de kernel
+// ============================================================
+//
+//  Când un burst de log-uri depășește buffer-ul de recepție al
+//  socket-ului (SO_RCVBUF), kernel-ul aruncă datagramele fără ca
+//  aplicația să afle vreodată. Pe Linux, contorul este expus în
+//  ultima coloană ("drops") din /proc/net/udp și /proc/net/udp6.
+//
+//  Format linie (câmpuri separate prin spații):
+//    sl local_address rem_address st tx:rx tr:tm retrnsmt uid timeout inode ref pointer drops
+//     0: 00000000:15B3 00000000:0000 07 ...                                          42
+//
+//  Concepte Rust demonstrate:
+//  - `u16::from_str_radix` : parsare numere hexazecimale
+//  - Iteratori înlănțuiți: `lines().skip(1).filter_map(...)`
+// ============================================================
+
+use std::fs;
+
+/// Fișierele procfs care conțin tabelele socket-urilor UDP (IPv4 și IPv6)
+const PROC_NET_UDP: [&str; 2] = ["/proc/net/udp", "/proc/net/udp6"];
+
+/// Returnează numărul total de datagrame aruncate de kernel pentru
+/// socket-urile UDP legate pe `local_port`.
+///
+/// Returnează `None` dacă statisticile nu sunt disponibile (ex: sistem non-Linux)
+/// sau dacă nu s-a găsit niciun socket pe portul respectiv.
+pub fn read_udp_drops(local_port: u16) -> Option<u64> {
+    let mut total: Option<u64> = None;
+
+    for path in PROC_NET_UDP {
+        // udp6 poate lipsi dacă IPv6 e dezactivat - nu e o eroare
+        let Ok(content) = fs::read_to_string(path) else {
+            continue;
+        };
+
+        for drops in content
+            .lines()
+            .skip(1) // Prima linie este header-ul tabelului
+            .filter_map(|line| parse_line(line, local_port))
+        {
+            *total.get_or_insert(0) += drops;
+        }
+    }
+
+    total
+}
+
+// ---------------------------------------------------------------------------
+// Parsează o linie din /proc/net/udp și returnează contorul de drops
+// dacă socket-ul este legat pe portul căutat.
+// ---------------------------------------------------------------------------
+fn parse_line(line: &str, local_port: u16) -> Option<u64> {
+    let fields: Vec<&str> = line.split_whitespace().collect();
+
+    // Adresa locală are forma "ADRESA_HEX:PORT_HEX"
+    let (_, port_hex) = fields.get(1)?.rsplit_once(':')?;
+    let port = u16::from_str_radix(port_hex, 16).ok()?;
+    if port != local_port {
+        return None;
+    }
+
+    fields.last()?.parse().ok()
+}
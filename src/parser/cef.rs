@@ -12,8 +12,12 @@
 //
 //  Câmpuri relevante din Extension:
 //    src  = IP sursă
-//    dpt  = destination port
+//    dst  = IP destinație (opțional)
+//    dpt  = destination port (lipsește pentru ICMP)
+//    proto = protocolul (ex: TCP, ICMP)
 //    act  = acțiunea (Drop, Allow, etc.)
+//    csN  = regula, când eticheta ei este "Rule" / "Rule Name" (opțional):
+//           cs1Label=Rule cs1=Block_Inbound
 //
 //  Concepte Rust demonstrate:
 //  - Implementare parțială a unui trait (schelet pentru extensie viitoare)
@@ -21,7 +25,9 @@
 //  - Documentație inline cu `///` (rustdoc)
 // ============================================================
 
-use super::{LogEntry, LogParser};
+use super::{
+    is_accept_action, normalize_protocol, EventKind, LogEntry, LogParser, ParseOutcome, ParserOptions, PortResolver,
+};
 use chrono::Utc;
 use once_cell::sync::Lazy;
 use regex::Regex;
@@ -32,11 +38,25 @@ use std::net::IpAddr;
 static CEF_SRC_REGEX: Lazy<Regex> =
     Lazy::new(|| Regex::new(r"src=([\d.]+)").expect("CEF_SRC_REGEX invalid"));
 
+static CEF_DST_REGEX: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r"\bdst=([\d.]+)").expect("CEF_DST_REGEX invalid"));
+
 static CEF_DPT_REGEX: Lazy<Regex> =
-    Lazy::new(|| Regex::new(r"dpt=(\d+)").expect("CEF_DPT_REGEX invalid"));
+    Lazy::new(|| Regex::new(r"dpt=([\w-]+)").expect("CEF_DPT_REGEX invalid"));
 
 static CEF_ACT_REGEX: Lazy<Regex> =
     Lazy::new(|| Regex::new(r"act=(\w+)").expect("CEF_ACT_REGEX invalid"));
+static CEF_PROTO_REGEX: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r"\bproto=(\w+)").expect("CEF_PROTO_REGEX invalid"));
+
+// Regula nu are o cheie standard: vendor-ii o pun într-un câmp custom
+// etichetat (`cs1Label=Rule cs1=Block_Inbound`), deci căutăm întâi slotul
+static CEF_RULE_LABEL_REGEX: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r"\b(cs[1-6])Label=(?i:rule(?:[ _]?name)?)(?:\s|$)").expect("CEF_RULE_LABEL_REGEX invalid")
+});
+// Începutul următoarei perechi `cheie=` (sfârșitul unei valori cu spații)
+static CEF_NEXT_KEY_REGEX: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r"\s+[\w.]+=").expect("CEF_NEXT_KEY_REGEX invalid"));
 
 /// Parser pentru formatul Common Event Format (CEF) utilizat de ArcSight.
 ///
@@ -47,11 +67,27 @@ static CEF_ACT_REGEX: Lazy<Regex> =
 /// Log-urile reale ce vin din syslog / firewall au întotdeauna prefix de
 /// timestamp + hostname înaintea payload-ului CEF. Parser-ul detectează
 /// și extrage portul CEF din linie indiferent de prefix.
-pub struct CefParser;
+#[derive(Default)]
+pub struct CefParser {
+    /// Rezolvarea `dpt=` nenumeric (hex sau nume de serviciu)
+    ports:        PortResolver,
+    /// Emite și conexiunile permise (accept/allow), marcate `accepted`
+    emit_accepts: bool,
+}
 
 impl CefParser {
     pub fn new() -> Self {
-        CefParser
+        Self::with_ports(PortResolver::default())
+    }
+
+    /// Parser cu tabelul de servicii extins din configurație
+    pub fn with_ports(ports: PortResolver) -> Self {
+        Self::with_options(ParserOptions { ports, ..ParserOptions::default() })
+    }
+
+    /// Parser cu opțiunile comune (porturi + emiterea accept-urilor)
+    pub fn with_options(options: ParserOptions) -> Self {
+        CefParser { ports: options.ports, emit_accepts: options.emit_accepts }
     }
 
     // -----------------------------------------------------------------------
@@ -91,6 +127,23 @@ impl CefParser {
         // (ultimul câmp = extensia, poate conține orice)
         cef_portion.splitn(8, '|').count() >= 7
     }
+
+    // -----------------------------------------------------------------------
+    // Valoarea unei chei din extensie. Spre deosebire de `src=` / `dpt=`,
+    // valorile custom pot conține spații ("Block Inbound SSH"), deci valoarea
+    // se termină abia la următoarea pereche `cheie=` (un `\=` escapat nu
+    // încheie valoarea: cheia trebuie să fie formată doar din litere/cifre).
+    // -----------------------------------------------------------------------
+    fn extension_value(line: &str, key: &str) -> Option<String> {
+        let needle = format!("{}=", key);
+        let (at, _) = line
+            .match_indices(&needle)
+            .find(|(at, _)| line[..*at].ends_with([' ', '|']))?;
+        let rest = &line[at + needle.len()..];
+        let end = CEF_NEXT_KEY_REGEX.find(rest).map_or(rest.len(), |m| m.start());
+        let value = rest[..end].trim().replace("\\=", "=").replace("\\\\", "\\");
+        (!value.is_empty()).then_some(value)
+    }
 }
 
 impl LogParser for CefParser {
@@ -99,39 +152,24 @@ impl LogParser for CefParser {
     }
 
     fn parse(&self, line: &str) -> Option<LogEntry> {
+        self.parse_outcome(line).into_entry()
+    }
+
+    fn parse_outcome(&self, line: &str) -> ParseOutcome {
         let line = line.trim();
 
         // Pasul 1: verificăm că linia conține un payload CEF (oriunde în linie)
         if !Self::is_cef(line) {
-            return None;
+            return ParseOutcome::Unrecognized;
         }
 
         // Pasul 2: extragem DOAR porțiunea CEF (fără prefix syslog)
-        let cef_portion = Self::extract_cef_portion(line)?;
-
         // Pasul 3: validăm structura minimă a header-ului CEF
-        if !Self::validate_header(cef_portion) {
-            return None;
+        match Self::extract_cef_portion(line) {
+            Some(cef_portion) if Self::validate_header(cef_portion) => {}
+            _ => return ParseOutcome::Unrecognized,
         }
 
-        // Pasul 4: extragem câmpurile din extensia CEF cu regex-uri key=value.
-        // Regex-urile caută în întreaga linie (nu doar în cef_portion) pentru că
-        // `src=`, `dpt=`, `act=` se află în extension, după ultimul `|`.
-        // Căutăm în `line` original pentru a beneficia de indexarea suplimentară.
-        let source_ip: IpAddr = CEF_SRC_REGEX
-            .captures(line)?
-            .get(1)?
-            .as_str()
-            .parse()
-            .ok()?;
-
-        let dest_port: u16 = CEF_DPT_REGEX
-            .captures(line)?
-            .get(1)?
-            .as_str()
-            .parse()
-            .ok()?;
-
         // Extragem acțiunea (case-insensitive: "Drop", "DROP", "drop" sunt toate valide)
         let action = CEF_ACT_REGEX
             .captures(line)
@@ -139,16 +177,60 @@ impl LogParser for CefParser {
             .map(|m| m.as_str().to_lowercase())
             .unwrap_or_else(|| "unknown".to_string());
 
-        // Filtrăm: ne interesează doar acțiuni de blocare
+        // Filtrăm: ne interesează acțiunile de blocare și, opțional, accept-urile
         // Checkpoint CEF folosește "drop", alte vendor-uri pot folosi "deny"
-        if action != "drop" && action != "deny" {
-            return None;
+        let accepted = self.emit_accepts && is_accept_action(&action);
+        if action != "drop" && action != "deny" && !accepted {
+            return ParseOutcome::IgnoredAction(action);
         }
 
-        Some(LogEntry {
+        // Pasul 4: extragem câmpurile din extensia CEF cu regex-uri key=value.
+        // Regex-urile caută în întreaga linie (nu doar în cef_portion) pentru că
+        // `src=`, `dpt=`, `act=` se află în extension, după ultimul `|`.
+        // Căutăm în `line` original pentru a beneficia de indexarea suplimentară.
+        let source_ip: Option<IpAddr> = CEF_SRC_REGEX
+            .captures(line)
+            .and_then(|c| c[1].parse().ok());
+
+        let dest_port: Option<u16> = CEF_DPT_REGEX
+            .captures(line)
+            .and_then(|c| self.ports.resolve(&c[1]));
+        let protocol = CEF_PROTO_REGEX.captures(line).and_then(|c| normalize_protocol(&c[1]));
+        let is_icmp = protocol.as_deref() == Some("icmp");
+
+        // Un eveniment de blocare fără sursă (sau fără port, în afară de ICMP)
+        // nu poate fi folosit de detector. CEF nu are un câmp standard pentru
+        // tipul ICMP, deci îl lăsăm necunoscut.
+        let kind = match (is_icmp, dest_port) {
+            // Un accept ICMP nu are port de corelat cu scanarea
+            (true, _) if accepted => return ParseOutcome::IgnoredAction(action),
+            (true, _) => EventKind::Icmp { icmp_type: None },
+            (false, Some(port)) => EventKind::Port(port),
+            (false, None) => return ParseOutcome::Unrecognized,
+        };
+        let Some(source_ip) = source_ip else {
+            return ParseOutcome::Unrecognized;
+        };
+
+        // IP-ul destinație este opțional în CEF
+        let dest_ip: Option<IpAddr> = CEF_DST_REGEX
+            .captures(line)
+            .and_then(|c| c.get(1))
+            .and_then(|m| m.as_str().parse().ok());
+
+        // Regula (opțională), din câmpul custom etichetat "Rule"
+        let rule = CEF_RULE_LABEL_REGEX
+            .captures(line)
+            .and_then(|c| Self::extension_value(line, &c[1]));
+
+        ParseOutcome::Matched(LogEntry {
             source_ip,
-            dest_port,
+            dest_ip,
+            kind,
+            protocol,
             action,
+            accepted,
+            rule,
             timestamp: Utc::now(),
         })
     }
@@ -5,10 +5,16 @@
 //  Format log exemplu:
 //  Sep 3 15:12:20 192.168.99.1 Checkpoint: drop 192.168.11.7 proto: tcp; service: 22; s_port: 1352
 //
+//  Format ICMP (fără port):
+//  Sep 3 15:12:20 192.168.99.1 Checkpoint: drop 192.168.11.7 dst: 10.0.0.1; proto: icmp; icmp-type: 8; icmp-code: 0
+//
 //  Câmpuri extrase:
-//    - Acțiunea : "drop" (ignorăm tot ce nu este "drop")
+//    - Acțiunea : "drop" (plus accept/allow cu `emit_accepts`; restul ignorat)
 //    - IP sursă : 192.168.11.7
-//    - Port dest.: 22 (câmpul "service:")
+//    - Port dest.: 22 (câmpul "service:"), obligatoriu dacă proto nu e icmp;
+//                 acceptă și hex ("0x16") sau nume de serviciu ("ssh")
+//    - Tip ICMP : 8 (câmpul "icmp-type:", opțional)
+//    - Regula   : "rule_name: Block_Inbound;" sau "rule: 42;" (opțional)
 //
 //  Concepte Rust demonstrate:
 //  - `once_cell::sync::Lazy` : inițializare leneșă a regex-ului (compilat o singură dată)
@@ -17,7 +23,9 @@
 //  - Conversii de tip: `.parse::<IpAddr>()`, `.parse::<u16>()`
 // ============================================================
 
-use super::{LogEntry, LogParser};
+use super::{
+    is_accept_action, normalize_protocol, EventKind, LogEntry, LogParser, ParseOutcome, ParserOptions, PortResolver,
+};
 use chrono::Utc;
 use once_cell::sync::Lazy;
 use regex::Regex;
@@ -40,29 +48,62 @@ static GAIA_REGEX: Lazy<Regex> = Lazy::new(|| {
     //   (\w+)            -> capturează acțiunea (drop, accept, reject...)
     //   \s+              -> spații
     //   ([\d.]+)         -> capturează IP-ul sursă (cifre și puncte)
-    //   .*?service:\s*   -> orice caractere, ne-lacom, până la "service:"
-    //   (\d+)            -> capturează portul destinație
+    //   (?:...)?         -> grup opțional (ICMP nu are "service:"):
+    //     .*?service:\s* -> orice caractere, ne-lacom, până la "service:"
+    //     ([\w-]+)       -> capturează portul destinație (număr, hex sau nume)
     Regex::new(
-        r"Checkpoint:\s+(\w+)\s+([\d.]+).*?service:\s*(\d+)"
+        r"Checkpoint:\s+(\w+)\s+([\d.]+)(?:.*?service:\s*([\w-]+))?"
     ).expect("GAIA_REGEX: pattern invalid - eroare de programare!")
     // `.expect()` e acceptabil pentru erori de programare (bug, nu eroare de runtime)
     // Dacă regex-ul e invalid, e un bug în cod, nu o eroare de utilizator.
 });
 
+// Destinația apare doar în unele variante de export ("dst: 10.0.0.1;"),
+// deci o căutăm separat și o tratăm ca opțională.
+static GAIA_DST_REGEX: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r"\bdst:\s*([\d.]+)").expect("GAIA_DST_REGEX invalid"));
+
+// Protocolul și tipul ICMP - tot câmpuri opționale ale exportului
+static GAIA_PROTO_REGEX: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r"\bproto:\s*(\w+)").expect("GAIA_PROTO_REGEX invalid"));
+static GAIA_ICMP_TYPE_REGEX: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r"\bicmp-type:\s*(\d+)").expect("GAIA_ICMP_TYPE_REGEX invalid"));
+
+// Regula care a tratat pachetul: numele (poate conține spații, până la `;`)
+// are prioritate față de numărul regulii din politică
+static GAIA_RULE_NAME_REGEX: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r"\brule_name:\s*([^;]+)").expect("GAIA_RULE_NAME_REGEX invalid"));
+static GAIA_RULE_REGEX: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r"\brule:\s*([^;\s]+)").expect("GAIA_RULE_REGEX invalid"));
+
 // ---------------------------------------------------------------------------
 // Structura concretă a parser-ului Gaia.
 //
-// Deocamdată nu are câmpuri (este un "unit struct" efectiv),
-// dar o structurăm explicit pentru extensibilitate viitoare
-// (ex: ar putea stoca configurații specifice parser-ului).
+// Singura configurație specifică parser-ului: rezolvarea numelor de
+// serviciu (`service: ssh`) în porturi.
 // ---------------------------------------------------------------------------
-pub struct GaiaParser;
+#[derive(Default)]
+pub struct GaiaParser {
+    ports:        PortResolver,
+    /// Emite și conexiunile permise (accept/allow), marcate `accepted`
+    emit_accepts: bool,
+}
 
 impl GaiaParser {
     /// Constructor convențional în Rust.
     /// `new()` este convenție, nu keyword; returnează instanță owned.
     pub fn new() -> Self {
-        GaiaParser
+        Self::with_ports(PortResolver::default())
+    }
+
+    /// Parser cu tabelul de servicii extins din configurație
+    pub fn with_ports(ports: PortResolver) -> Self {
+        Self::with_options(ParserOptions { ports, ..ParserOptions::default() })
+    }
+
+    /// Parser cu opțiunile comune (porturi + emiterea accept-urilor)
+    pub fn with_options(options: ParserOptions) -> Self {
+        GaiaParser { ports: options.ports, emit_accepts: options.emit_accepts }
     }
 }
 
@@ -80,43 +121,84 @@ impl LogParser for GaiaParser {
     }
 
     fn parse(&self, line: &str) -> Option<LogEntry> {
-        // Ignorăm linii goale sau comentarii - early return cu None
+        self.parse_outcome(line).into_entry()
+    }
+
+    fn parse_outcome(&self, line: &str) -> ParseOutcome {
+        // Ignorăm linii goale sau comentarii - early return
         let line = line.trim();
         if line.is_empty() {
-            return None;
+            return ParseOutcome::Unrecognized;
         }
 
         // `captures()` returnează Option<Captures>
-        // Dacă pattern-ul nu se potrivește, returnăm None (linia nu este un log Gaia valid)
-        let caps = GAIA_REGEX.captures(line)?;
-        // Nota: `?` pe Option funcționează ca un early return cu None
+        // Dacă pattern-ul nu se potrivește, linia nu este un log Gaia valid
+        let Some(caps) = GAIA_REGEX.captures(line) else {
+            return ParseOutcome::Unrecognized;
+        };
 
         // Grupele de captură sunt indexate de la 1 (0 = întregul match)
-        // `.get(n)` returnează Option<Match>, `.as_str()` returnează &str
+        // Grupele 1 și 2 sunt obligatorii în pattern, deci `[n]` nu poate eșua;
+        // grupa 3 (portul) este opțională -> `caps.get(3)`
 
         // Extragere acțiune (câmpul 1)
-        let action = caps.get(1)?.as_str().to_lowercase();
+        let action = caps[1].to_lowercase();
 
-        // Filtrăm: ne interesează DOAR acțiunile "drop"
-        // Logica de business: alte acțiuni (accept, log) nu sunt relevante pentru IDS
-        if action != "drop" {
-            return None;
+        // Filtrăm: ne interesează acțiunile "drop" și, opțional, accept-urile
+        // (pentru acces-după-scan). Alte acțiuni (log, reject...) sunt ignorate.
+        let accepted = self.emit_accepts && is_accept_action(&action);
+        if action != "drop" && !accepted {
+            return ParseOutcome::IgnoredAction(action);
         }
 
         // Extragere IP sursă (câmpul 2)
         // `.parse::<IpAddr>()` returnează Result<IpAddr, _>
-        // `.ok()` convertește Result în Option (Err devine None)
-        let source_ip: IpAddr = caps.get(2)?.as_str().parse().ok()?;
+        // Un IP invalid înseamnă că linia doar seamănă cu formatul Gaia
+        let Ok(source_ip) = caps[2].parse::<IpAddr>() else {
+            return ParseOutcome::Unrecognized;
+        };
+
+        // ICMP: fără port; altfel portul destinație (câmpul 3) este obligatoriu
+        let protocol = GAIA_PROTO_REGEX.captures(line).and_then(|c| normalize_protocol(&c[1]));
+        let is_icmp = protocol.as_deref() == Some("icmp");
+        // Un accept ICMP nu are port de corelat cu scanarea
+        if accepted && is_icmp {
+            return ParseOutcome::IgnoredAction(action);
+        }
+        let kind = if is_icmp {
+            EventKind::Icmp {
+                icmp_type: GAIA_ICMP_TYPE_REGEX.captures(line).and_then(|c| c[1].parse().ok()),
+            }
+        } else {
+            match caps.get(3).and_then(|m| self.ports.resolve(m.as_str())) {
+                Some(port) => EventKind::Port(port),
+                None => return ParseOutcome::Unrecognized,
+            }
+        };
+
+        // IP destinație (opțional) - lipsa lui nu invalidează log-ul
+        let dest_ip: Option<IpAddr> = GAIA_DST_REGEX
+            .captures(line)
+            .and_then(|c| c.get(1))
+            .and_then(|m| m.as_str().parse().ok());
 
-        // Extragere port destinație (câmpul 3)
-        let dest_port: u16 = caps.get(3)?.as_str().parse().ok()?;
+        // Regula (opțională) - exporturile fără ea rămân valide
+        let rule = GAIA_RULE_NAME_REGEX
+            .captures(line)
+            .or_else(|| GAIA_RULE_REGEX.captures(line))
+            .map(|c| c[1].trim().to_string())
+            .filter(|rule| !rule.is_empty());
 
         // Construim LogEntry. Rust garantează că dacă ajungem aici,
         // toate câmpurile sunt valide (compilatorul nu permite valori lipsă/null).
-        Some(LogEntry {
+        ParseOutcome::Matched(LogEntry {
             source_ip,
-            dest_port,
+            dest_ip,
+            kind,
+            protocol,
             action,
+            accepted,
+            rule,
             timestamp: Utc::now(), // Folosim timestamps UTC pentru consistență
         })
     }
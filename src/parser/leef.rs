@@ -0,0 +1,196 @@
+// ============================================================
+//  parser/leef.rs - Parser LEEF (Log Event Extended Format) - QRadar
+// ============================================================
+//
+//  Formatul LEEF urmează schema:
+//  LEEF:1.0|Vendor|Product|Version|EventID|Atribute
+//  LEEF:2.0|Vendor|Product|Version|EventID|[Delimitator|]Atribute
+//
+//  Exemplu log LEEF (cu prefix syslog):
+//  Nov 20 15:30:00 fw01 LEEF:2.0|PaloAlto|PA|9.1|DENY|src=192.168.1.10<TAB>dstPort=22<TAB>action=deny
+//
+//  Atributele sunt perechi cheie=valoare separate prin TAB. LEEF 2.0 poate
+//  declara alt delimitator în header: un caracter (`^`) sau forma
+//  hexazecimală (`x5E` / `0x5E`).
+//
+//  Câmpuri relevante:
+//    src / srcAddr               = IP sursă
+//    dst / dstAddr               = IP destinație (opțional)
+//    dstPort / dstPostNATPort    = portul destinație (lipsește pentru ICMP)
+//    proto                       = protocolul (ex: TCP, ICMP, 1)
+//    action / devAction          = acțiunea (deny, drop, block, allow...)
+//    ruleName / rule             = regula firewall-ului (opțional)
+//
+//  Concepte Rust demonstrate:
+//  - `HashMap<&str, &str>` : atributele împrumută direct din linie (fără copii)
+//  - `split_once` / `splitn` : parsare structurată fără regex
+//  - `u8::from_str_radix` : delimitatorul declarat în hexazecimal
+// ============================================================
+
+use super::{
+    is_accept_action, normalize_protocol, EventKind, LogEntry, LogParser, ParseOutcome, ParserOptions, PortResolver,
+};
+use chrono::Utc;
+use std::collections::HashMap;
+use std::net::IpAddr;
+
+/// Delimitatorul implicit al atributelor (LEEF 1.0 și LEEF 2.0 fără declarație)
+const DEFAULT_DELIMITER: char = '\t';
+
+/// Acțiunile de blocare raportate de diverși vendori
+const BLOCK_ACTIONS: &[&str] = &["deny", "drop", "block"];
+
+/// Parser pentru formatul LEEF (IBM QRadar), versiunile 1.0 și 2.0.
+///
+/// Ca la CEF, token-ul `LEEF:` este căutat oriunde în linie: log-urile
+/// venite prin syslog au timestamp + hostname înaintea payload-ului.
+#[derive(Default)]
+pub struct LeefParser {
+    /// Rezolvarea `dstPort=` nenumeric (hex sau nume de serviciu)
+    ports:        PortResolver,
+    /// Emite și conexiunile permise (accept/allow), marcate `accepted`
+    emit_accepts: bool,
+}
+
+impl LeefParser {
+    pub fn new() -> Self {
+        Self::with_ports(PortResolver::default())
+    }
+
+    /// Parser cu tabelul de servicii extins din configurație
+    pub fn with_ports(ports: PortResolver) -> Self {
+        Self::with_options(ParserOptions { ports, ..ParserOptions::default() })
+    }
+
+    /// Parser cu opțiunile comune (porturi + emiterea accept-urilor)
+    pub fn with_options(options: ParserOptions) -> Self {
+        LeefParser { ports: options.ports, emit_accepts: options.emit_accepts }
+    }
+
+    // -----------------------------------------------------------------------
+    // Separă header-ul de atribute și află delimitatorul.
+    //
+    // Header-ul are 5 câmpuri fixe (`LEEF:V|Vendor|Product|Version|EventID|`).
+    // În LEEF 2.0 urmează opțional delimitatorul și încă un `|`; îl
+    // recunoaștem după formă (un caracter sau `xHH`), nu după poziție,
+    // pentru că multe exportere 2.0 îl omit.
+    // -----------------------------------------------------------------------
+    fn split_header(leef: &str) -> Option<(char, &str)> {
+        let mut fields = leef.splitn(6, '|');
+        let version = fields.next()?.strip_prefix("LEEF:")?;
+        for _ in 0..4 {
+            fields.next()?; // vendor, product, version, event ID
+        }
+        let rest = fields.next()?;
+
+        if version.trim() == "1.0" {
+            return Some((DEFAULT_DELIMITER, rest));
+        }
+        match rest.split_once('|') {
+            Some((spec, attributes)) => match Self::parse_delimiter(spec) {
+                Some(delimiter) => Some((delimiter, attributes)),
+                None => Some((DEFAULT_DELIMITER, rest)),
+            },
+            None => Some((DEFAULT_DELIMITER, rest)),
+        }
+    }
+
+    /// Delimitatorul declarat în LEEF 2.0: `^`, `x5E` sau `0x5E` (gol = TAB)
+    fn parse_delimiter(spec: &str) -> Option<char> {
+        if spec.is_empty() {
+            return Some(DEFAULT_DELIMITER);
+        }
+        let hex = spec
+            .strip_prefix("0x")
+            .or_else(|| spec.strip_prefix("0X"))
+            .or_else(|| spec.strip_prefix('x'))
+            .or_else(|| spec.strip_prefix('X'));
+        if let Some(hex) = hex.filter(|h| !h.is_empty() && h.len() <= 2) {
+            return u8::from_str_radix(hex, 16).ok().map(char::from);
+        }
+
+        let mut chars = spec.chars();
+        match (chars.next(), chars.next()) {
+            (Some(c), None) if c != '=' => Some(c),
+            _ => None,
+        }
+    }
+
+    /// Atributele `cheie=valoare`; perechile fără `=` sunt ignorate
+    fn attributes(attributes: &str, delimiter: char) -> HashMap<&str, &str> {
+        attributes
+            .split(delimiter)
+            .filter_map(|pair| pair.split_once('='))
+            .map(|(key, value)| (key.trim(), value.trim()))
+            .collect()
+    }
+}
+
+impl LogParser for LeefParser {
+    fn name(&self) -> &str {
+        "IBM LEEF"
+    }
+
+    fn parse(&self, line: &str) -> Option<LogEntry> {
+        self.parse_outcome(line).into_entry()
+    }
+
+    fn parse_outcome(&self, line: &str) -> ParseOutcome {
+        let line = line.trim();
+
+        // Pasul 1: porțiunea LEEF, oriunde în linie (prefixul syslog e ignorat)
+        let Some(start) = line.find("LEEF:") else {
+            return ParseOutcome::Unrecognized;
+        };
+
+        // Pasul 2: header-ul (versiune + delimitator) și atributele
+        let Some((delimiter, attributes)) = Self::split_header(&line[start..]) else {
+            return ParseOutcome::Unrecognized;
+        };
+        let attrs = Self::attributes(attributes, delimiter);
+        let get = |keys: &[&str]| keys.iter().find_map(|key| attrs.get(key).copied());
+
+        // Pasul 3: acțiunea - doar blocările (și, opțional, accept-urile)
+        let action = get(&["action", "devAction"])
+            .map(str::to_lowercase)
+            .unwrap_or_else(|| "unknown".to_string());
+        let accepted = self.emit_accepts && is_accept_action(&action);
+        if !BLOCK_ACTIONS.contains(&action.as_str()) && !accepted {
+            return ParseOutcome::IgnoredAction(action);
+        }
+
+        // Pasul 4: sursa, portul (sau ICMP) și destinația opțională
+        let Some(source_ip) = get(&["src", "srcAddr"]).and_then(|v| v.parse::<IpAddr>().ok()) else {
+            return ParseOutcome::Unrecognized;
+        };
+        let protocol = get(&["proto"]).and_then(normalize_protocol);
+        let is_icmp = protocol.as_deref() == Some("icmp");
+        let kind = if is_icmp {
+            // Un accept ICMP nu are port de corelat cu scanarea
+            if accepted {
+                return ParseOutcome::IgnoredAction(action);
+            }
+            EventKind::Icmp {
+                icmp_type: get(&["icmpType"]).and_then(|v| v.parse().ok()),
+            }
+        } else {
+            match get(&["dstPort", "dstPostNATPort"]).and_then(|v| self.ports.resolve(v)) {
+                Some(port) => EventKind::Port(port),
+                None => return ParseOutcome::Unrecognized,
+            }
+        };
+        let dest_ip: Option<IpAddr> = get(&["dst", "dstAddr"]).and_then(|v| v.parse().ok());
+        let rule = get(&["ruleName", "rule"]).filter(|v| !v.is_empty()).map(str::to_string);
+
+        ParseOutcome::Matched(LogEntry {
+            source_ip,
+            dest_ip,
+            kind,
+            protocol,
+            action,
+            accepted,
+            rule,
+            timestamp: Utc::now(),
+        })
+    }
+}
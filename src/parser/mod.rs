@@ -8,12 +8,23 @@
 //  - `Send + Sync` : marker traits pentru thread safety
 //  - `Option<T>` : tipul Rust pentru valori care pot fi absente (fără null!)
 //  - Vizibilitate module: `pub mod`, `pub use`
+//
+//  Formatele noi se adaugă fără a modifica crate-ul, prin `register_parser`
+//  (vezi `registry.rs`).
 // ============================================================
 
 pub mod cef;
 pub mod gaia;
+pub mod leef;
+pub mod ports;
+pub mod registry;
+pub mod windows_fw;
+
+pub use ports::PortResolver;
+pub use registry::{register_parser, registered_parsers, ParserConstructor, ParserRegistry};
 
 use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
 use std::net::IpAddr;
 
 // ---------------------------------------------------------------------------
@@ -22,22 +33,128 @@ use std::net::IpAddr;
 // Aceasta reprezintă "contractul de date" intern al IDS-ului.
 // Indiferent de formatul sursă (Gaia, CEF, etc.), odată parsat,
 // orice log este reprezentat ca un `LogEntry`.
+//
+// `[event_log]` îl scrie ca linie JSON; `rust-ids report` îl citește înapoi.
 // ---------------------------------------------------------------------------
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct LogEntry {
     /// IP-ul sursă al pachetului suspicios
     pub source_ip: IpAddr,
 
-    /// Portul destinație scanat (ex: 22 pentru SSH)
-    pub dest_port: u16,
+    /// IP-ul destinație (ținta scanării), dacă formatul de log îl raportează
+    pub dest_ip: Option<IpAddr>,
+
+    /// Tipul evenimentului: port destinație (TCP/UDP) sau ICMP (fără port)
+    pub kind: EventKind,
+
+    /// Protocolul raportat de firewall, normalizat ("tcp", "udp", "icmp"...),
+    /// dacă log-ul îl raportează (vezi `normalize_protocol`)
+    pub protocol: Option<String>,
 
     /// Acțiunea raportată de firewall (ex: "drop", "accept", "reject")
     pub action: String,
 
+    /// Conexiune PERMISĂ (accept/allow) - emisă doar cu `emit_accepts`;
+    /// nu intră în numărătoarea scanărilor, doar în corelarea acces-după-scan
+    pub accepted: bool,
+
+    /// Regula / politica firewall-ului care a tratat pachetul (nume sau
+    /// număr, ex: "42", "Block_Inbound"), dacă log-ul o raportează
+    pub rule: Option<String>,
+
     /// Timestamp-ul evenimentului (UTC pentru consistență)
     pub timestamp: DateTime<Utc>,
 }
 
+impl LogEntry {
+    /// Portul destinație scanat (ex: 22 pentru SSH); `None` pentru ICMP
+    pub fn dest_port(&self) -> Option<u16> {
+        match self.kind {
+            EventKind::Port(port) => Some(port),
+            EventKind::Icmp { .. } => None,
+        }
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Tipul unui eveniment de drop
+//
+// ICMP nu are porturi: un ping sweep (recunoașterea clasică dinaintea unui
+// port scan) trebuie numărat separat, altfel ar polua pragurile de porturi.
+// ---------------------------------------------------------------------------
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[non_exhaustive]
+pub enum EventKind {
+    /// Conexiune TCP/UDP către portul destinație dat
+    Port(u16),
+
+    /// Pachet ICMP; tipul (ex: 8 = echo request) dacă log-ul îl raportează
+    Icmp { icmp_type: Option<u8> },
+}
+
+// ---------------------------------------------------------------------------
+// Rezultatul detaliat al parsării unei linii
+//
+// `Option<LogEntry>` spune doar "da/nu". Pentru diagnosticarea unui
+// exporter nou trebuie să știm DE CE o linie nu a produs un eveniment:
+// formatul a fost recunoscut dar acțiunea nu ne interesează (ex: accept),
+// sau linia nu seamănă deloc cu formatul așteptat.
+// ---------------------------------------------------------------------------
+#[derive(Debug, Clone)]
+#[non_exhaustive]
+pub enum ParseOutcome {
+    /// Linie validă cu acțiune de blocare -> eveniment pentru detector
+    Matched(LogEntry),
+
+    /// Format recunoscut, dar acțiunea nu este relevantă (ex: "accept" fără `emit_accepts`)
+    IgnoredAction(String),
+
+    /// Linia nu corespunde formatului parser-ului
+    Unrecognized,
+}
+
+impl ParseOutcome {
+    /// Convertește rezultatul detaliat în forma simplă `Option<LogEntry>`
+    pub fn into_entry(self) -> Option<LogEntry> {
+        match self {
+            ParseOutcome::Matched(entry) => Some(entry),
+            _ => None,
+        }
+    }
+}
+
+/// Acțiunile care înseamnă "conexiune permisă" (diverși vendori)
+pub const ACCEPT_ACTIONS: &[&str] = &["accept", "allow", "permit"];
+
+/// `action` (deja în litere mici) este una de tip accept/allow?
+pub fn is_accept_action(action: &str) -> bool {
+    ACCEPT_ACTIONS.contains(&action)
+}
+
+/// Numele normalizat al protocolului raportat: litere mici, iar numerele
+/// IANA cunoscute devin nume (`TCP` -> "tcp", `17` -> "udp"). Gol = `None`.
+pub fn normalize_protocol(raw: &str) -> Option<String> {
+    let raw = raw.trim();
+    if raw.is_empty() {
+        return None;
+    }
+    let name = windows_fw::protocol_name(raw).map_or_else(|| raw.to_ascii_lowercase(), str::to_string);
+    Some(name)
+}
+
+// ---------------------------------------------------------------------------
+// Opțiunile comune tuturor parserelor
+//
+// `emit_accepts`: pe lângă drop-uri, parserele emit și conexiunile
+// permise (marcate `accepted`), pentru detecția acces-după-scan. Crește
+// volumul de evenimente, deci e oprit implicit.
+// ---------------------------------------------------------------------------
+#[derive(Debug, Clone, Default)]
+pub struct ParserOptions {
+    pub ports:        PortResolver,
+    pub emit_accepts: bool,
+}
+
 // ---------------------------------------------------------------------------
 // Trăsătura (trait) LogParser - "interfața" pe care orice parser trebuie
 // să o implementeze.
@@ -66,6 +183,20 @@ pub trait LogParser: Send + Sync {
 
     /// Numele parser-ului (pentru logging și diagnostice)
     fn name(&self) -> &str;
+
+    // -----------------------------------------------------------------------
+    // Varianta detaliată a `parse()`, folosită pentru statistici.
+    //
+    // Implementare implicită (metodă "provided"): un parser care
+    // implementează doar `parse()` raportează orice `None` ca `Unrecognized`.
+    // Parserele care pot distinge "acțiune ignorată" o suprascriu.
+    // -----------------------------------------------------------------------
+    fn parse_outcome(&self, line: &str) -> ParseOutcome {
+        match self.parse(line) {
+            Some(entry) => ParseOutcome::Matched(entry),
+            None => ParseOutcome::Unrecognized,
+        }
+    }
 }
 
 // ---------------------------------------------------------------------------
@@ -78,16 +209,26 @@ pub trait LogParser: Send + Sync {
 // trebuie să returneze ownership-ul. O referință ar expira imediat.
 // ---------------------------------------------------------------------------
 pub fn create_parser(parser_type: &str) -> Box<dyn LogParser> {
-    match parser_type.to_lowercase().as_str() {
-        "gaia" => Box::new(gaia::GaiaParser::new()),
-        "cef" => Box::new(cef::CefParser::new()),
-        unknown => {
-            // Logging la stderr pentru erori de configurare
-            eprintln!(
-                "[CONFIG] Tip parser necunoscut '{}'. Se folosește 'gaia' implicit.",
-                unknown
-            );
-            Box::new(gaia::GaiaParser::new())
-        }
+    create_parser_with_ports(parser_type, PortResolver::default())
+}
+
+/// Ca `create_parser`, cu numele de serviciu suplimentare (`[listener.services]`)
+pub fn create_parser_with_ports(parser_type: &str, ports: PortResolver) -> Box<dyn LogParser> {
+    create_parser_with_options(parser_type, ParserOptions { ports, ..ParserOptions::default() })
+}
+
+/// Ca `create_parser`, cu toate opțiunile comune (porturi, emiterea accept-urilor)
+///
+/// Numele se caută în registrul global (`register_parser`), care conține
+/// formatele încorporate plus cele înregistrate de aplicație.
+pub fn create_parser_with_options(parser_type: &str, options: ParserOptions) -> Box<dyn LogParser> {
+    if let Some(parser) = registry::create_registered(parser_type, options.clone()) {
+        return parser;
     }
+    // Logging la stderr pentru erori de configurare
+    eprintln!(
+        "[CONFIG] Tip parser necunoscut '{}'. Se folosește 'gaia' implicit.",
+        parser_type.to_lowercase()
+    );
+    Box::new(gaia::GaiaParser::with_options(options))
 }
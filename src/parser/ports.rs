@@ -0,0 +1,101 @@
+// ============================================================
+//  parser/ports.rs - Rezolvarea portului destinație din text
+// ============================================================
+//
+//  Nu toate exporterele raportează portul ca număr zecimal:
+//    service: 22      -> zecimal (cazul obișnuit)
+//    service: 0x1bb   -> hexazecimal (443)
+//    service: ssh     -> nume de serviciu (22)
+//
+//  Numele cunoscute vin dintr-un tabel încorporat (serviciile uzuale),
+//  extensibil din `[listener.services]`. Un nume necunoscut -> `None`,
+//  iar parser-ul tratează linia ca pe una fără port.
+//
+//  Concepte Rust demonstrate:
+//  - `u16::from_str_radix` : parsare în altă bază decât 10
+//  - Slice static de tupluri ca tabel constant (fără alocare la runtime)
+// ============================================================
+
+use std::collections::HashMap;
+
+/// Serviciile uzuale (nume IANA + aliasuri frecvente în log-urile de firewall)
+const WELL_KNOWN_SERVICES: &[(&str, u16)] = &[
+    ("ftp-data", 20),
+    ("ftp", 21),
+    ("ssh", 22),
+    ("telnet", 23),
+    ("smtp", 25),
+    ("domain", 53),
+    ("dns", 53),
+    ("http", 80),
+    ("www", 80),
+    ("kerberos", 88),
+    ("pop3", 110),
+    ("ntp", 123),
+    ("msrpc", 135),
+    ("netbios-ns", 137),
+    ("netbios-ssn", 139),
+    ("imap", 143),
+    ("snmp", 161),
+    ("ldap", 389),
+    ("https", 443),
+    ("microsoft-ds", 445),
+    ("smb", 445),
+    ("smtps", 465),
+    ("syslog", 514),
+    ("submission", 587),
+    ("ldaps", 636),
+    ("imaps", 993),
+    ("pop3s", 995),
+    ("mssql", 1433),
+    ("oracle", 1521),
+    ("nfs", 2049),
+    ("mysql", 3306),
+    ("rdp", 3389),
+    ("ms-wbt-server", 3389),
+    ("postgresql", 5432),
+    ("vnc", 5900),
+    ("redis", 6379),
+    ("http-alt", 8080),
+    ("https-alt", 8443),
+];
+
+// ---------------------------------------------------------------------------
+// Rezolvitorul de porturi al unui parser
+//
+// Numele din configurație au prioritate față de tabelul încorporat
+// (ex: o organizație care rulează "http-alt" pe 8000).
+// ---------------------------------------------------------------------------
+#[derive(Debug, Clone, Default)]
+pub struct PortResolver {
+    /// Numele suplimentare din configurație, cu cheile în litere mici
+    custom: HashMap<String, u16>,
+}
+
+impl PortResolver {
+    pub fn new(custom: &HashMap<String, u16>) -> Self {
+        PortResolver {
+            custom: custom.iter().map(|(name, port)| (name.to_ascii_lowercase(), *port)).collect(),
+        }
+    }
+
+    /// Portul pentru `raw`: zecimal, hexazecimal (`0x..`) sau nume de serviciu
+    pub fn resolve(&self, raw: &str) -> Option<u16> {
+        let raw = raw.trim();
+
+        if let Ok(port) = raw.parse::<u16>() {
+            return Some(port);
+        }
+        if let Some(hex) = raw.strip_prefix("0x").or_else(|| raw.strip_prefix("0X")) {
+            return u16::from_str_radix(hex, 16).ok();
+        }
+
+        let name = raw.to_ascii_lowercase();
+        self.custom.get(&name).copied().or_else(|| {
+            WELL_KNOWN_SERVICES
+                .iter()
+                .find(|(known, _)| *known == name)
+                .map(|(_, port)| *port)
+        })
+    }
+}
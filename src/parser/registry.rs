@@ -0,0 +1,105 @@
+// ============================================================
+//  parser/registry.rs - Registrul de parsere: nume de format -> constructor
+// ============================================================
+//
+//  `[listener] parser = "..."` alege parser-ul după nume. Formatele
+//  încorporate (gaia, cef, leef, windows) sunt înregistrate implicit; o
+//  aplicație care încorporează biblioteca își poate adăuga propriul format
+//  înainte de pornire, fără să modifice crate-ul:
+//
+//    rust_ids::parser::register_parser("acme", |options| Box::new(AcmeParser::new(options)));
+//
+//  Constructorul primește `ParserOptions` (porturi suplimentare, emiterea
+//  accept-urilor), ca parserele proprii să respecte aceeași configurație
+//  ca cele încorporate.
+//
+//  Concepte Rust demonstrate:
+//  - `Box<dyn Fn(..) -> .. + Send + Sync>` : closure-uri stocate într-o colecție
+//  - `Lazy<RwLock<..>>` : stare globală inițializată la prima folosire
+//  - `'static` : closure-urile înregistrate nu pot împrumuta date locale
+// ============================================================
+
+use super::{cef, gaia, leef, windows_fw, LogParser, ParserOptions};
+use once_cell::sync::Lazy;
+use std::collections::HashMap;
+use std::sync::{PoisonError, RwLock};
+
+/// Constructorul unui parser: din opțiunile comune, un parser gata de folosit
+pub type ParserConstructor = Box<dyn Fn(ParserOptions) -> Box<dyn LogParser> + Send + Sync>;
+
+/// Registrul folosit de `create_parser`; inițial conține doar formatele încorporate
+static GLOBAL_REGISTRY: Lazy<RwLock<ParserRegistry>> = Lazy::new(|| RwLock::new(ParserRegistry::new()));
+
+/// Formatele de log cunoscute, după nume (comparat fără majuscule)
+pub struct ParserRegistry {
+    constructors: HashMap<String, ParserConstructor>,
+}
+
+impl ParserRegistry {
+    /// Registru cu formatele încorporate: gaia, cef, leef, windows
+    pub fn new() -> Self {
+        let mut registry = ParserRegistry { constructors: HashMap::new() };
+        registry.register("gaia", |options| Box::new(gaia::GaiaParser::with_options(options)));
+        registry.register("cef", |options| Box::new(cef::CefParser::with_options(options)));
+        registry.register("leef", |options| Box::new(leef::LeefParser::with_options(options)));
+        registry.register("windows", |options| Box::new(windows_fw::WindowsFwParser::with_options(options)));
+        registry
+    }
+
+    // -----------------------------------------------------------------------
+    // Adaugă (sau înlocuiește) formatul `name`. Returnează `true` dacă
+    // numele era deja înregistrat - un format încorporat poate fi astfel
+    // suprascris intenționat.
+    // -----------------------------------------------------------------------
+    pub fn register<F>(&mut self, name: &str, constructor: F) -> bool
+    where
+        F: Fn(ParserOptions) -> Box<dyn LogParser> + Send + Sync + 'static,
+    {
+        self.constructors.insert(name.to_lowercase(), Box::new(constructor)).is_some()
+    }
+
+    pub fn contains(&self, name: &str) -> bool {
+        self.constructors.contains_key(&name.to_lowercase())
+    }
+
+    /// Parser-ul formatului `name`, sau `None` dacă formatul nu e înregistrat
+    pub fn create(&self, name: &str, options: ParserOptions) -> Option<Box<dyn LogParser>> {
+        self.constructors.get(&name.to_lowercase()).map(|constructor| constructor(options))
+    }
+
+    /// Numele formatelor înregistrate, sortate
+    pub fn names(&self) -> Vec<String> {
+        let mut names: Vec<String> = self.constructors.keys().cloned().collect();
+        names.sort();
+        names
+    }
+}
+
+impl Default for ParserRegistry {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Înregistrează un format în registrul global folosit de `create_parser`.
+//
+// Se apelează înainte de pornirea listener-elor: parserele deja create nu
+// se schimbă. Returnează `true` dacă numele exista deja (și a fost înlocuit).
+// ---------------------------------------------------------------------------
+pub fn register_parser<F>(name: &str, constructor: F) -> bool
+where
+    F: Fn(ParserOptions) -> Box<dyn LogParser> + Send + Sync + 'static,
+{
+    GLOBAL_REGISTRY.write().unwrap_or_else(PoisonError::into_inner).register(name, constructor)
+}
+
+/// Numele formatelor din registrul global, sortate
+pub fn registered_parsers() -> Vec<String> {
+    GLOBAL_REGISTRY.read().unwrap_or_else(PoisonError::into_inner).names()
+}
+
+/// Parser-ul formatului `name` din registrul global
+pub(super) fn create_registered(name: &str, options: ParserOptions) -> Option<Box<dyn LogParser>> {
+    GLOBAL_REGISTRY.read().unwrap_or_else(PoisonError::into_inner).create(name, options)
+}
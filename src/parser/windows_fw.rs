@@ -0,0 +1,183 @@
+// ============================================================
+//  parser/windows_fw.rs - Parser Windows Filtering Platform (Security log)
+// ============================================================
+//
+//  Firewall-ul Windows raportează pachetele blocate în jurnalul Security:
+//    5152 = pachet blocat de Windows Filtering Platform
+//    5157 = conexiune blocată de Windows Filtering Platform
+//    5156 = conexiune permisă (folosit doar cu `emit_accepts`)
+//
+//  Agentul syslog (NXLog, Snare, Winlogbeat...) aplatizează evenimentul
+//  într-un text `cheie=valoare`:
+//  Oct 18 10:00:00 WIN-SRV01 MSWinEventLog EventID=5152 Application="\device\harddiskvolume2\windows\system32\svchost.exe" SourceAddress=10.0.0.5 SourcePort=51234 DestAddress=10.0.0.1 DestPort=445 Protocol=6 FilterRTID=67890
+//
+//  Agenții diferă prin ordinea câmpurilor și prin ghilimele în jurul
+//  valorilor, așa că parsăm perechile `cheie=valoare`, nu pozițiile.
+//
+//  Câmpuri relevante:
+//    EventID / EventCode  = 5152 / 5157 (drop), 5156 (accept)
+//    SourceAddress        = IP sursă (IPv4 sau IPv6)
+//    DestAddress          = IP destinație (opțional)
+//    DestPort             = portul destinație
+//    Protocol             = numărul IANA (6 = tcp, 17 = udp, 1 / 58 = icmp)
+//    FilterRTID           = filtrul WFP care a decis (folosit ca regulă)
+//
+//  Concepte Rust demonstrate:
+//  - Tokenizare manuală cu `char_indices` (valori între ghilimele cu spații)
+//  - `eq_ignore_ascii_case` : chei comparate fără alocare
+// ============================================================
+
+use super::{EventKind, LogEntry, LogParser, ParseOutcome, ParserOptions, PortResolver};
+use chrono::Utc;
+use std::net::IpAddr;
+
+/// Evenimentele de blocare (pachet / conexiune)
+const DROP_EVENTS: &[&str] = &["5152", "5157"];
+
+/// Conexiune permisă de WFP (emisă doar cu `emit_accepts`)
+const ACCEPT_EVENT: &str = "5156";
+
+/// Numele protocolului pentru numărul IANA raportat în `Protocol`
+pub fn protocol_name(number: &str) -> Option<&'static str> {
+    match number.trim() {
+        "6" => Some("tcp"),
+        "17" => Some("udp"),
+        "1" => Some("icmp"),
+        "58" => Some("ipv6-icmp"),
+        _ => None,
+    }
+}
+
+/// Parser pentru evenimentele Windows Firewall 5152 / 5157 aplatizate.
+///
+/// Ca la CEF și LEEF, prefixul syslog (timestamp, hostname, numele
+/// agentului) este ignorat: contează doar perechile `cheie=valoare`.
+#[derive(Default)]
+pub struct WindowsFwParser {
+    /// Rezolvarea `DestPort=` nenumeric (hex sau nume de serviciu)
+    ports:        PortResolver,
+    /// Emite și conexiunile permise (5156), marcate `accepted`
+    emit_accepts: bool,
+}
+
+impl WindowsFwParser {
+    pub fn new() -> Self {
+        Self::with_options(ParserOptions::default())
+    }
+
+    /// Parser cu opțiunile comune (porturi + emiterea accept-urilor)
+    pub fn with_options(options: ParserOptions) -> Self {
+        WindowsFwParser { ports: options.ports, emit_accepts: options.emit_accepts }
+    }
+
+    // -----------------------------------------------------------------------
+    // Perechile `cheie=valoare` din linie, în ordine.
+    //
+    // Separatorii sunt spațiile, TAB-urile, `,` și `;`. O valoare între
+    // ghilimele poate conține separatori (căi cu "Program Files"); fără
+    // ghilimele, valoarea se termină la primul separator. Cuvintele fără
+    // `=` (prefixul syslog) sunt sărite.
+    // -----------------------------------------------------------------------
+    fn pairs(line: &str) -> Vec<(&str, &str)> {
+        let is_separator = |c: char| c.is_whitespace() || c == ',' || c == ';';
+        let mut pairs = Vec::new();
+        let mut rest = line;
+
+        loop {
+            rest = rest.trim_start_matches(is_separator);
+            if rest.is_empty() {
+                break;
+            }
+            let word_end = rest.find(is_separator).unwrap_or(rest.len());
+            let Some(eq) = rest[..word_end].find('=') else {
+                rest = &rest[word_end..];
+                continue;
+            };
+
+            let key = &rest[..eq];
+            let after = &rest[eq + 1..];
+            let (value, consumed) = match after.strip_prefix('"') {
+                Some(quoted) => match quoted.find('"') {
+                    Some(close) => (&quoted[..close], close + 2),
+                    // Ghilimea de închidere lipsește: restul liniei e valoarea
+                    None => (quoted, after.len()),
+                },
+                None => {
+                    let end = after.find(is_separator).unwrap_or(after.len());
+                    (&after[..end], end)
+                }
+            };
+            pairs.push((key, value.trim()));
+            rest = &after[consumed..];
+        }
+        pairs
+    }
+}
+
+impl LogParser for WindowsFwParser {
+    fn name(&self) -> &str {
+        "Windows Firewall"
+    }
+
+    fn parse(&self, line: &str) -> Option<LogEntry> {
+        self.parse_outcome(line).into_entry()
+    }
+
+    fn parse_outcome(&self, line: &str) -> ParseOutcome {
+        let pairs = Self::pairs(line);
+        let get = |keys: &[&str]| {
+            pairs
+                .iter()
+                .find(|(key, _)| keys.iter().any(|k| key.eq_ignore_ascii_case(k)))
+                .map(|&(_, value)| value)
+        };
+
+        // Pasul 1: marker-ul evenimentului - fără el linia nu e un eveniment Windows
+        let Some(event_id) = get(&["EventID", "EventCode", "event_id"]) else {
+            return ParseOutcome::Unrecognized;
+        };
+
+        // Pasul 2: doar blocările (și, opțional, conexiunile permise)
+        let accepted = self.emit_accepts && event_id == ACCEPT_EVENT;
+        if !DROP_EVENTS.contains(&event_id) && !accepted {
+            return ParseOutcome::IgnoredAction(format!("event {}", event_id));
+        }
+        let action = if accepted { "accept" } else { "drop" }.to_string();
+
+        // Pasul 3: sursa (zona IPv6 `%5` și forma IPv4-mapped sunt normalizate)
+        let parse_ip = |value: &str| {
+            let address = value.split('%').next().unwrap_or(value);
+            address.parse::<IpAddr>().ok().map(|ip| ip.to_canonical())
+        };
+        let Some(source_ip) = get(&["SourceAddress"]).and_then(parse_ip) else {
+            return ParseOutcome::Unrecognized;
+        };
+
+        // Pasul 4: protocolul decide între port și ICMP
+        let protocol = get(&["Protocol"]).and_then(protocol_name);
+        let kind = match protocol {
+            Some("tcp" | "udp") => match get(&["DestPort"]).and_then(|v| self.ports.resolve(v)) {
+                Some(port) => EventKind::Port(port),
+                None => return ParseOutcome::Unrecognized,
+            },
+            // Un accept ICMP nu are port de corelat cu scanarea
+            Some(_) if accepted => return ParseOutcome::IgnoredAction(action),
+            Some(_) => EventKind::Icmp { icmp_type: None },
+            // Alt protocol (GRE, ESP...): fără porturi de numărat
+            None => return ParseOutcome::IgnoredAction(format!("event {} proto", event_id)),
+        };
+        let dest_ip = get(&["DestAddress"]).and_then(parse_ip);
+        let rule = get(&["FilterRTID"]).filter(|v| !v.is_empty() && *v != "0").map(str::to_string);
+
+        ParseOutcome::Matched(LogEntry {
+            source_ip,
+            dest_ip,
+            kind,
+            protocol: protocol.map(str::to_string),
+            action,
+            accepted,
+            rule,
+            timestamp: Utc::now(),
+        })
+    }
+}
@@ -0,0 +1,579 @@
+// ============================================================
+//  report.rs - Raportul periodic al detecțiilor (`rust-ids report`)
+// ============================================================
+//
+//  Responsabilul de securitate vrea un sumar, nu alerte brute: totalul
+//  evenimentelor, atacatorii distincți, alertele pe tip, top 10 atacatori
+//  cu porturile lor și comparația cu perioada anterioară de aceeași lungime.
+//
+//  Raportul se construiește din jurnalele JSONL scrise de daemon (fișierul
+//  curent plus cele rotite):
+//
+//    [alert_log]  obligatoriu - alertele trimise: tipuri, atacatori
+//    [event_log]  opțional    - totalul evenimentelor, porturile exacte
+//
+//  Fără `[alert_log]` raportul nu are sursă de date și se oprește cu o
+//  eroare care spune ce trebuie activat. Fără `[event_log]` secțiunile de
+//  evenimente apar ca "n/a". Țările atacatorilor ar cere GeoIP, pe care
+//  IDS-ul nu îl are - coloana lipsește.
+//
+//  Două moduri de rulare, același `Report`:
+//    rust-ids report --since 7d --format html > raport.html
+//    [report] schedule = "daily@07:00"  ->  email către [report] to
+//
+//  Concepte Rust demonstrate:
+//  - `serde::de::DeserializeOwned` : o singură funcție de citire pentru ambele jurnale
+//  - `BufRead::read_until` : fișiere mari citite în flux, fără a le încărca în memorie
+//  - `DateTime<Tz: TimeZone>` : următoarea rulare programată, în orice fus orar
+// ============================================================
+
+use crate::alert_log::AlertRecord;
+use crate::config::Config;
+use crate::parser::LogEntry;
+use anyhow::{bail, ensure, Context, Result};
+use chrono::{Datelike, DateTime, Days, Duration, NaiveTime, TimeZone, Utc, Weekday};
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, HashSet};
+use std::fmt::{self, Write as _};
+use std::fs::{self, File};
+use std::io::{BufRead, BufReader};
+use std::net::IpAddr;
+use std::path::{Path, PathBuf};
+use std::time::SystemTime;
+
+/// Atacatorii afișați în raport
+pub const TOP_ATTACKERS: usize = 10;
+
+/// Cea mai lungă perioadă acceptată de `--since` (un an, în ore)
+const MAX_SINCE_HOURS: u32 = 366 * 24;
+
+// ---------------------------------------------------------------------------
+// `--since`: "24h" sau "7d". `strip_suffix` taie un caracter întreg - un
+// sufix multi-byte (ex: "5ș") este o eroare, nu o tăietură în mijlocul lui.
+// ---------------------------------------------------------------------------
+pub fn parse_since(text: &str) -> Result<Duration> {
+    let parsed = if let Some(count) = text.strip_suffix('h') {
+        count.parse::<u32>().ok()
+    } else if let Some(count) = text.strip_suffix('d') {
+        count.parse::<u32>().ok().and_then(|days| days.checked_mul(24))
+    } else {
+        None
+    };
+    match parsed {
+        Some(hours) if hours > 0 && hours <= MAX_SINCE_HOURS => Ok(Duration::hours(i64::from(hours))),
+        _ => bail!("--since '{}' invalid: se așteaptă ore sau zile, cel mult 366d (ex: 24h, 7d)", text),
+    }
+}
+
+/// Formatul raportului: fișier HTML autonom sau text simplu
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ReportFormat {
+    #[default]
+    Html,
+    Text,
+}
+
+// ---------------------------------------------------------------------------
+// Programarea raportului, ora locală: "daily@07:00" (ultimele 24h) sau
+// "weekly@mon@07:00" (ultimele 7 zile)
+// ---------------------------------------------------------------------------
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(try_from = "String", into = "String")]
+pub struct ReportSchedule {
+    /// `None` = zilnic
+    pub weekday: Option<Weekday>,
+    pub at:      NaiveTime,
+}
+
+impl ReportSchedule {
+    /// Perioada acoperită de fiecare raport
+    pub fn period(&self) -> Duration {
+        match self.weekday {
+            Some(_) => Duration::days(7),
+            None => Duration::days(1),
+        }
+    }
+
+    // -----------------------------------------------------------------------
+    // Următorul moment programat, strict după `now`. O oră care nu există
+    // în ziua respectivă (trecerea la ora de vară) sare la ziua următoare.
+    // -----------------------------------------------------------------------
+    pub fn next_after<Tz: TimeZone>(&self, now: &DateTime<Tz>) -> DateTime<Tz> {
+        let today = now.date_naive();
+        (0..=8)
+            .filter_map(|offset| today.checked_add_days(Days::new(offset)))
+            .filter(|day| self.weekday.is_none_or(|weekday| day.weekday() == weekday))
+            .filter_map(|day| now.timezone().from_local_datetime(&day.and_time(self.at)).earliest())
+            .find(|at| at > now)
+            .unwrap_or_else(|| now.clone() + self.period())
+    }
+}
+
+impl TryFrom<String> for ReportSchedule {
+    type Error = String;
+
+    fn try_from(text: String) -> Result<Self, Self::Error> {
+        let invalid = || format!("schedule '{}' invalid: se așteaptă \"daily@HH:MM\" sau \"weekly@mon@HH:MM\"", text);
+        let time = |part: &str| NaiveTime::parse_from_str(part, "%H:%M").ok();
+        let parts: Vec<&str> = text.split('@').map(str::trim).collect();
+        match parts.as_slice() {
+            ["daily", at] => Ok(ReportSchedule { weekday: None, at: time(at).ok_or_else(invalid)? }),
+            ["weekly", day, at] => Ok(ReportSchedule {
+                weekday: Some(day.parse().map_err(|_| invalid())?),
+                at:      time(at).ok_or_else(invalid)?,
+            }),
+            _ => Err(invalid()),
+        }
+    }
+}
+
+impl From<ReportSchedule> for String {
+    fn from(schedule: ReportSchedule) -> Self {
+        schedule.to_string()
+    }
+}
+
+impl fmt::Display for ReportSchedule {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.weekday {
+            Some(weekday) => write!(f, "weekly@{}@{}", weekday.to_string().to_lowercase(), self.at.format("%H:%M")),
+            None => write!(f, "daily@{}", self.at.format("%H:%M")),
+        }
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Raportul calculat
+// ---------------------------------------------------------------------------
+
+/// Totalurile unei perioade; cele de evenimente sunt `None` fără `[event_log]`
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct Totals {
+    pub alerts:    u64,
+    /// IP-uri sursă distincte care au declanșat alerte
+    pub attackers: usize,
+    pub events:    Option<u64>,
+    /// IP-uri sursă distincte din jurnalul de evenimente
+    pub sources:   Option<usize>,
+}
+
+/// Un rând din "top atacatori"
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AttackerRow {
+    pub ip:     IpAddr,
+    pub alerts: u64,
+    /// Porturi unice: maximul dintre detecții și porturile blocate din `[event_log]`
+    pub ports:  usize,
+    pub events: Option<u64>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Report {
+    pub from:           DateTime<Utc>,
+    pub to:             DateTime<Utc>,
+    pub current:        Totals,
+    /// Perioada de aceeași lungime dinaintea lui `from`
+    pub previous:       Totals,
+    /// Tip de detecție -> alerte, descrescător
+    pub alerts_by_type: Vec<(String, u64)>,
+    pub top_attackers:  Vec<AttackerRow>,
+    /// Linii ilizibile din jurnale (ex: ultima linie, încă în scriere)
+    pub skipped_lines:  u64,
+}
+
+// ---------------------------------------------------------------------------
+// Acumularea înregistrărilor, una câte una: jurnalele pot avea milioane de
+// linii, deci nu se țin în memorie decât contoarele și mulțimile de IP-uri.
+// ---------------------------------------------------------------------------
+#[derive(Default)]
+struct PeriodCounts {
+    alerts:    u64,
+    attackers: HashSet<IpAddr>,
+    events:    u64,
+    sources:   HashSet<IpAddr>,
+}
+
+#[derive(Default)]
+struct AttackerCounts {
+    alerts:      u64,
+    alert_ports: usize,
+    events:      u64,
+    ports:       HashSet<u16>,
+}
+
+pub struct ReportBuilder {
+    from:           DateTime<Utc>,
+    to:             DateTime<Utc>,
+    previous_from:  DateTime<Utc>,
+    current:        PeriodCounts,
+    previous:       PeriodCounts,
+    alerts_by_type: HashMap<String, u64>,
+    attackers:      HashMap<IpAddr, AttackerCounts>,
+    events_read:    bool,
+}
+
+impl ReportBuilder {
+    /// Perioada `(to - span, to]`, comparată cu cea de dinainte
+    pub fn new(to: DateTime<Utc>, span: Duration) -> Self {
+        ReportBuilder {
+            from:           to - span,
+            to,
+            previous_from:  to - span - span,
+            current:        PeriodCounts::default(),
+            previous:       PeriodCounts::default(),
+            alerts_by_type: HashMap::new(),
+            attackers:      HashMap::new(),
+            events_read:    false,
+        }
+    }
+
+    /// Începutul perioadei anterioare: înregistrările mai vechi nu contează
+    pub fn previous_from(&self) -> DateTime<Utc> {
+        self.previous_from
+    }
+
+    /// `Some(true)` = perioada curentă, `Some(false)` = cea anterioară
+    fn is_current(&self, at: DateTime<Utc>) -> Option<bool> {
+        if at > self.from && at <= self.to {
+            Some(true)
+        } else if at > self.previous_from && at <= self.from {
+            Some(false)
+        } else {
+            None
+        }
+    }
+
+    pub fn add_alert(&mut self, alert: &AlertRecord) {
+        let Some(current) = self.is_current(alert.timestamp) else {
+            return;
+        };
+        let counts = if current { &mut self.current } else { &mut self.previous };
+        counts.alerts += 1;
+        counts.attackers.insert(alert.ip);
+        if current {
+            *self.alerts_by_type.entry(alert.scan_type.clone()).or_default() += 1;
+            let attacker = self.attackers.entry(alert.ip).or_default();
+            attacker.alerts += 1;
+            attacker.alert_ports = attacker.alert_ports.max(alert.unique_ports);
+        }
+    }
+
+    /// Evenimentele se adaugă DUPĂ alerte: porturile se țin doar pentru
+    /// sursele deja alertate, nu pentru fiecare IP din jurnal
+    pub fn add_event(&mut self, entry: &LogEntry) {
+        self.events_read = true;
+        let Some(current) = self.is_current(entry.timestamp) else {
+            return;
+        };
+        let counts = if current { &mut self.current } else { &mut self.previous };
+        counts.events += 1;
+        counts.sources.insert(entry.source_ip);
+        if current && !entry.accepted {
+            if let Some(attacker) = self.attackers.get_mut(&entry.source_ip) {
+                attacker.events += 1;
+                attacker.ports.extend(entry.dest_port());
+            }
+        }
+    }
+
+    /// Marchează jurnalul de evenimente ca citit, chiar dacă era gol
+    pub fn events_read(&mut self) {
+        self.events_read = true;
+    }
+
+    pub fn finish(self) -> Report {
+        let events_read = self.events_read;
+        let totals = |counts: &PeriodCounts| Totals {
+            alerts:    counts.alerts,
+            attackers: counts.attackers.len(),
+            events:    events_read.then_some(counts.events),
+            sources:   events_read.then_some(counts.sources.len()),
+        };
+
+        let mut alerts_by_type: Vec<(String, u64)> = self.alerts_by_type.into_iter().collect();
+        alerts_by_type.sort_by(|a, b| b.1.cmp(&a.1).then_with(|| a.0.cmp(&b.0)));
+
+        let mut top_attackers: Vec<AttackerRow> = self
+            .attackers
+            .into_iter()
+            .map(|(ip, counts)| AttackerRow {
+                ip,
+                alerts: counts.alerts,
+                ports:  counts.alert_ports.max(counts.ports.len()),
+                events: events_read.then_some(counts.events),
+            })
+            .collect();
+        top_attackers.sort_by(|a, b| {
+            b.alerts.cmp(&a.alerts).then_with(|| b.ports.cmp(&a.ports)).then_with(|| a.ip.cmp(&b.ip))
+        });
+        top_attackers.truncate(TOP_ATTACKERS);
+
+        Report {
+            from: self.from,
+            to: self.to,
+            current: totals(&self.current),
+            previous: totals(&self.previous),
+            alerts_by_type,
+            top_attackers,
+            skipped_lines: 0,
+        }
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Construiește raportul ultimei perioade `span` din jurnalele configurate
+// ---------------------------------------------------------------------------
+pub fn build(config: &Config, span: Duration, now: DateTime<Utc>) -> Result<Report> {
+    ensure!(
+        config.alert_log.enabled,
+        "Raportul nu are sursă de date: istoricul alertelor se păstrează doar cu \
+         [alert_log] enabled = true în config.toml. Activați-l și reporniți IDS-ul - \
+         raportul acoperă alertele de după activare (cu [event_log] enabled = true \
+         include și totalul evenimentelor)."
+    );
+    let alerts = Path::new(&config.alert_log.path);
+    ensure!(
+        alerts.exists(),
+        "Raportul nu are sursă de date: [alert_log] path = {} nu există - IDS-ul nu a \
+         rulat încă cu [alert_log] enabled = true din acest director",
+        alerts.display()
+    );
+
+    let mut builder = ReportBuilder::new(now, span);
+    let since = builder.previous_from();
+    let mut skipped = read_records(alerts, since, |alert: AlertRecord| builder.add_alert(&alert))?;
+
+    let events = Path::new(&config.event_log.path);
+    if config.event_log.enabled && events.exists() {
+        builder.events_read();
+        skipped += read_records(events, since, |entry: LogEntry| builder.add_event(&entry))?;
+    }
+
+    let mut report = builder.finish();
+    report.skipped_lines = skipped;
+    Ok(report)
+}
+
+// ---------------------------------------------------------------------------
+// Fișierul curent plus cele rotite (`alerts.jsonl.20261018-142501[.N]`).
+// Un fișier rotit nemodificat de la `since` conține doar înregistrări mai
+// vechi și nu mai este deschis.
+// ---------------------------------------------------------------------------
+fn log_files(path: &Path, since: DateTime<Utc>) -> Result<Vec<PathBuf>> {
+    let dir = match path.parent() {
+        Some(parent) if !parent.as_os_str().is_empty() => parent,
+        _ => Path::new("."),
+    };
+    let prefix = format!("{}.", path.file_name().and_then(|name| name.to_str()).unwrap_or_default());
+    let since = SystemTime::from(since);
+
+    let mut files = Vec::new();
+    for entry in fs::read_dir(dir).with_context(|| format!("Nu s-a putut citi directorul {}", dir.display()))? {
+        let entry = entry?;
+        let rotated = entry.file_name().to_str().is_some_and(|name| name.starts_with(&prefix));
+        let recent = entry.metadata().and_then(|m| m.modified()).is_ok_and(|modified| modified >= since);
+        if rotated && recent {
+            files.push(entry.path());
+        }
+    }
+    files.push(path.to_path_buf());
+    Ok(files)
+}
+
+/// Citește fiecare linie JSON ca `T`; returnează liniile ilizibile
+fn read_records<T: DeserializeOwned>(path: &Path, since: DateTime<Utc>, mut add: impl FnMut(T)) -> Result<u64> {
+    let mut skipped = 0;
+    for file in log_files(path, since)? {
+        let mut reader =
+            BufReader::new(File::open(&file).with_context(|| format!("Nu s-a putut deschide {}", file.display()))?);
+        let mut line = Vec::new();
+        loop {
+            line.clear();
+            let read = reader
+                .read_until(b'\n', &mut line)
+                .with_context(|| format!("Citirea {} a eșuat", file.display()))?;
+            if read == 0 {
+                break;
+            }
+            if line.trim_ascii().is_empty() {
+                continue;
+            }
+            match serde_json::from_slice(&line) {
+                Ok(record) => add(record),
+                Err(_) => skipped += 1,
+            }
+        }
+    }
+    Ok(skipped)
+}
+
+// ---------------------------------------------------------------------------
+// Randarea: text (consolă, email simplu) și HTML autonom (CSS inline,
+// tabele simple - se deschide direct în browser sau în clientul de email)
+// ---------------------------------------------------------------------------
+
+/// Diferența față de perioada anterioară: "+12 (+40%)", "-3 (-10%)", "+5 (nou)"
+fn delta(current: u64, previous: u64) -> String {
+    let diff = i128::from(current) - i128::from(previous);
+    match (previous, diff) {
+        (_, 0) => "0".to_string(),
+        (0, _) => format!("+{} (nou)", diff),
+        _ => format!("{:+} ({:+.0}%)", diff, diff as f64 * 100.0 / previous as f64),
+    }
+}
+
+fn optional(value: Option<u64>) -> String {
+    value.map_or_else(|| "n/a".to_string(), |value| value.to_string())
+}
+
+fn optional_delta(current: Option<u64>, previous: Option<u64>) -> String {
+    match (current, previous) {
+        (Some(current), Some(previous)) => delta(current, previous),
+        _ => "n/a".to_string(),
+    }
+}
+
+fn html_escape(text: &str) -> String {
+    text.replace('&', "&amp;").replace('<', "&lt;").replace('>', "&gt;").replace('"', "&quot;")
+}
+
+const HTML_STYLE: &str = "\
+body{font-family:Arial,Helvetica,sans-serif;color:#222;margin:24px}\
+h1{font-size:20px;margin-bottom:4px}h2{font-size:16px;margin-top:24px}\
+p.period{color:#555;margin-top:0}\
+table{border-collapse:collapse;min-width:420px}\
+th,td{border:1px solid #ccc;padding:4px 10px;text-align:left}\
+th{background:#f0f0f0}td.num{text-align:right;font-family:monospace}\
+p.note{color:#777;font-size:12px}";
+
+impl Report {
+    /// Rândurile comparației: (nume, curent, anterior, diferență)
+    fn comparison(&self) -> [(&'static str, String, String, String); 4] {
+        let row = |name, current: Option<u64>, previous: Option<u64>| {
+            (name, optional(current), optional(previous), optional_delta(current, previous))
+        };
+        let (current, previous) = (&self.current, &self.previous);
+        let sources = |totals: &Totals| totals.sources.map(|n| n as u64);
+        [
+            row("Evenimente", current.events, previous.events),
+            row("Surse distincte", sources(current), sources(previous)),
+            row("Alerte", Some(current.alerts), Some(previous.alerts)),
+            row("Atacatori distincți", Some(current.attackers as u64), Some(previous.attackers as u64)),
+        ]
+    }
+
+    fn period_label(&self) -> String {
+        format!("{} - {}", self.from.format("%Y-%m-%d %H:%M UTC"), self.to.format("%Y-%m-%d %H:%M UTC"))
+    }
+
+    fn notes(&self) -> Vec<String> {
+        let mut notes = Vec::new();
+        if self.current.events.is_none() {
+            notes.push("Evenimente n/a: activați [event_log] enabled = true pentru totalul evenimentelor.".to_string());
+        }
+        if self.skipped_lines > 0 {
+            notes.push(format!("{} linii ilizibile din jurnale au fost ignorate.", self.skipped_lines));
+        }
+        notes
+    }
+
+    /// Subiectul email-ului programat
+    pub fn subject(&self) -> String {
+        format!(
+            "[IDS REPORT] {}: {} alerte, {} atacatori",
+            self.to.format("%Y-%m-%d"),
+            self.current.alerts,
+            self.current.attackers
+        )
+    }
+
+    pub fn render(&self, format: ReportFormat) -> String {
+        match format {
+            ReportFormat::Html => self.render_html(),
+            ReportFormat::Text => self.render_text(),
+        }
+    }
+
+    pub fn render_text(&self) -> String {
+        let mut out = String::new();
+        let _ = writeln!(out, "RUST IDS - Raport de detecție");
+        let _ = writeln!(out, "Perioada: {}", self.period_label());
+        let _ = writeln!(out, "\n  {:<22} {:>12} {:>12}   Diferență", "", "Perioada", "Anterioară");
+        for (name, current, previous, delta) in self.comparison() {
+            let _ = writeln!(out, "  {:<22} {:>12} {:>12}   {}", name, current, previous, delta);
+        }
+
+        let _ = writeln!(out, "\nAlerte pe tip:");
+        if self.alerts_by_type.is_empty() {
+            let _ = writeln!(out, "  (nicio alertă)");
+        }
+        for (scan_type, count) in &self.alerts_by_type {
+            let _ = writeln!(out, "  {:<30} {:>8}", scan_type, count);
+        }
+
+        let _ = writeln!(out, "\nTop {} atacatori:", TOP_ATTACKERS);
+        if !self.top_attackers.is_empty() {
+            let _ = writeln!(out, "  {:<40} {:>8} {:>8} {:>12}", "IP", "Alerte", "Porturi", "Evenimente");
+        }
+        for row in &self.top_attackers {
+            let _ = writeln!(out, "  {:<40} {:>8} {:>8} {:>12}", row.ip.to_string(), row.alerts, row.ports, optional(row.events));
+        }
+
+        for note in self.notes() {
+            let _ = writeln!(out, "\n{}", note);
+        }
+        out
+    }
+
+    pub fn render_html(&self) -> String {
+        let mut out = String::new();
+        let _ = write!(
+            out,
+            "<!DOCTYPE html>\n<html><head><meta charset=\"utf-8\"><title>Raport IDS {}</title><style>{}</style></head><body>\n",
+            self.to.format("%Y-%m-%d"),
+            HTML_STYLE
+        );
+        let _ = writeln!(out, "<h1>RUST IDS - Raport de detecție</h1>\n<p class=\"period\">{}</p>", self.period_label());
+
+        let _ = writeln!(out, "<table><tr><th></th><th>Perioada</th><th>Anterioară</th><th>Diferență</th></tr>");
+        for (name, current, previous, delta) in self.comparison() {
+            let _ = writeln!(
+                out,
+                "<tr><td>{}</td><td class=\"num\">{}</td><td class=\"num\">{}</td><td class=\"num\">{}</td></tr>",
+                name, current, previous, delta
+            );
+        }
+        let _ = writeln!(out, "</table>");
+
+        let _ = writeln!(out, "<h2>Alerte pe tip</h2>\n<table><tr><th>Tip</th><th>Alerte</th></tr>");
+        for (scan_type, count) in &self.alerts_by_type {
+            let _ = writeln!(out, "<tr><td>{}</td><td class=\"num\">{}</td></tr>", html_escape(scan_type), count);
+        }
+        let _ = writeln!(out, "</table>");
+
+        let _ = writeln!(
+            out,
+            "<h2>Top {} atacatori</h2>\n<table><tr><th>IP</th><th>Alerte</th><th>Porturi</th><th>Evenimente</th></tr>",
+            TOP_ATTACKERS
+        );
+        for row in &self.top_attackers {
+            let _ = writeln!(
+                out,
+                "<tr><td>{}</td><td class=\"num\">{}</td><td class=\"num\">{}</td><td class=\"num\">{}</td></tr>",
+                row.ip,
+                row.alerts,
+                row.ports,
+                optional(row.events)
+            );
+        }
+        let _ = writeln!(out, "</table>");
+
+        for note in self.notes() {
+            let _ = writeln!(out, "<p class=\"note\">{}</p>", html_escape(&note));
+        }
+        out.push_str("</body></html>\n");
+        out
+    }
+}
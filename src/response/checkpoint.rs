@@ -0,0 +1,250 @@
+// ============================================================
+//  response/checkpoint.rs - Blocare SAM prin Check Point Management API
+// ============================================================
+//
+//  Fluxul unui apel (Management API, HTTPS + JSON):
+//    1. POST /web_api/login                          -> obține `sid`
+//    2. POST /web_api/add-suspicious-activity-rule   -> regula SAM cu expirare
+//    3. POST /web_api/publish                        -> aplică sesiunea
+//
+//  Sesiunea (`sid`) este reutilizată între blocări. Dacă serverul o
+//  respinge (expirată -> HTTP 401), ne re-autentificăm o singură dată.
+//
+//  `block` returnează ce s-a întâmplat (`BlockOutcome`), fără să scrie la
+//  consolă - afișarea rămâne în binar, ca la alertele din `Engine`.
+//
+//  Concepte Rust demonstrate:
+//  - `tokio::sync::Mutex` : lock async, poate fi ținut peste `.await`
+//  - `serde_json::json!` : construirea corpului cererii ca JSON
+//  - `reqwest::Client` : client HTTP async reutilizabil (connection pool)
+// ============================================================
+
+use crate::config::CheckpointConfig;
+use crate::detector::DetectionResult;
+use crate::state::SharedState;
+use anyhow::{bail, Context, Result};
+use reqwest::StatusCode;
+use serde_json::{json, Value};
+use std::net::IpAddr;
+use std::time::Duration;
+use tokio::sync::Mutex;
+
+/// Timeout pentru fiecare apel API - un server de management lent
+/// nu trebuie să țină task-urile de procesare blocate la nesfârșit
+const API_TIMEOUT_SECS: u64 = 15;
+
+/// Rezultatul unei cereri de blocare
+#[derive(Debug)]
+pub enum BlockOutcome {
+    /// Nimic de făcut: scan distribuit, severitate sub `min_severity` sau IP deja
+    /// blocat (ori în curs de blocare de o altă alertă)
+    Skipped,
+    /// `dry_run`: cererea care ar fi fost trimisă (IP-ul e marcat blocat oricum)
+    DryRun { endpoint: String, rule: Value },
+    /// Regula SAM a fost adăugată și publicată
+    Blocked,
+    /// Apelul API a eșuat; IP-ul NU e marcat blocat (se reîncearcă la alerta următoare)
+    Failed(anyhow::Error),
+}
+
+/// Responder care blochează IP-urile sursă prin reguli SAM pe gateway-uri Check Point
+pub struct CheckpointResponder {
+    config:  CheckpointConfig,
+    client:  reqwest::Client,
+    /// ID-ul sesiunii API curente (`None` = neautentificat)
+    session: Mutex<Option<String>>,
+}
+
+impl CheckpointResponder {
+    pub fn new(config: CheckpointConfig) -> Result<Self> {
+        if config.api_key.is_none() && (config.username.is_none() || config.password.is_none()) {
+            bail!("[response.checkpoint] necesită 'api_key' sau 'username' + 'password'");
+        }
+
+        let client = reqwest::Client::builder()
+            .timeout(Duration::from_secs(API_TIMEOUT_SECS))
+            .danger_accept_invalid_certs(!config.verify_tls)
+            .build()
+            .context("Nu s-a putut crea clientul HTTPS pentru Check Point")?;
+
+        Ok(CheckpointResponder {
+            config,
+            client,
+            session: Mutex::new(None),
+        })
+    }
+
+    pub fn dry_run(&self) -> bool {
+        self.config.dry_run
+    }
+
+    pub fn management_url(&self) -> &str {
+        &self.config.management_url
+    }
+
+    // -----------------------------------------------------------------------
+    // Punctul de intrare din fluxul de alertare.
+    //
+    // Blochează IP-ul dacă alerta are severitatea minimă și IP-ul nu este
+    // deja blocat (sau în curs de blocare). Un eșec nu oprește nimic:
+    // apelantul îl afișează.
+    // -----------------------------------------------------------------------
+    pub async fn block(&self, ip: &IpAddr, result: &DetectionResult, state: &SharedState) -> BlockOutcome {
+        // Un scan distribuit nu are o singură sursă de blocat
+        if matches!(result, DetectionResult::DistributedScan { .. }) {
+            return BlockOutcome::Skipped;
+        }
+        if result.severity() < self.config.min_severity {
+            return BlockOutcome::Skipped;
+        }
+        // Rezervarea atomică: din două alerte simultane pentru același IP,
+        // doar una ajunge la API-ul de management
+        if !state.try_mark_blocked(*ip, self.config.block_duration_secs) {
+            return BlockOutcome::Skipped;
+        }
+
+        let rule = self.build_rule(ip, result);
+
+        if self.config.dry_run {
+            return BlockOutcome::DryRun { endpoint: self.endpoint("add-suspicious-activity-rule"), rule };
+        }
+
+        match self.submit(&rule).await {
+            Ok(()) => {
+                // Blocarea curge de la publicare, nu de la rezervare
+                state.mark_blocked(*ip, self.config.block_duration_secs);
+                BlockOutcome::Blocked
+            }
+            Err(e) => {
+                state.unmark_blocked(ip);
+                BlockOutcome::Failed(e)
+            }
+        }
+    }
+
+    /// Corpul cererii `add-suspicious-activity-rule` pentru un IP
+    fn build_rule(&self, ip: &IpAddr, result: &DetectionResult) -> Value {
+        let mut rule = json!({
+            "source":      ip.to_string(),
+            "destination": "Any",
+            "service":     "Any",
+            "action":      "Drop",
+            "expiration":  self.config.block_duration_secs,
+            "comments":    format!("RustIDS: {} de la {}", result.scan_type_label(), ip),
+        });
+
+        if let Some(package) = &self.config.package {
+            rule["package"] = json!(package);
+        }
+        if !self.config.targets.is_empty() {
+            rule["targets"] = json!(self.config.targets);
+        }
+
+        rule
+    }
+
+    // -----------------------------------------------------------------------
+    // Trimite regula și publică sesiunea, cu o re-autentificare la 401
+    // -----------------------------------------------------------------------
+    async fn submit(&self, rule: &Value) -> Result<()> {
+        // Ținem lock-ul pe toată durata secvenței add -> publish, astfel încât
+        // două alerte simultane să nu publice aceeași sesiune în paralel
+        let mut session = self.session.lock().await;
+
+        let sid = match session.as_deref() {
+            Some(sid) => sid.to_string(),
+            None => self.login().await?,
+        };
+
+        let sid = match self.call("add-suspicious-activity-rule", Some(&sid), rule).await {
+            Err(e) if is_session_expired(&e) => {
+                // Sesiunea a expirat pe server - ne autentificăm din nou și reîncercăm
+                *session = None;
+                let sid = self.login().await?;
+                self.call("add-suspicious-activity-rule", Some(&sid), rule).await?;
+                sid
+            }
+            Err(e) => {
+                *session = None;
+                return Err(e);
+            }
+            Ok(_) => sid,
+        };
+
+        self.call("publish", Some(&sid), &json!({})).await?;
+        *session = Some(sid);
+
+        Ok(())
+    }
+
+    /// Autentificare: returnează un `sid` nou
+    async fn login(&self) -> Result<String> {
+        let body = match (&self.config.api_key, &self.config.username, &self.config.password) {
+            (Some(key), _, _) => json!({ "api-key": key }),
+            (None, Some(user), Some(password)) => json!({ "user": user, "password": password }),
+            _ => bail!("Credențiale Check Point lipsă"),
+        };
+
+        let response = self.call("login", None, &body).await.context("Login Check Point eșuat")?;
+
+        response["sid"]
+            .as_str()
+            .map(str::to_string)
+            .context("Răspunsul de login nu conține 'sid'")
+    }
+
+    /// Un apel generic `POST /web_api/<command>`
+    async fn call(&self, command: &str, sid: Option<&str>, body: &Value) -> Result<Value> {
+        let mut request = self.client.post(self.endpoint(command)).json(body);
+        if let Some(sid) = sid {
+            request = request.header("X-chkp-sid", sid);
+        }
+
+        let response = request
+            .send()
+            .await
+            .with_context(|| format!("Cererea '{}' nu a ajuns la serverul de management", command))?;
+
+        let status = response.status();
+        if status == StatusCode::UNAUTHORIZED {
+            bail!(SessionExpired);
+        }
+
+        let payload: Value = response.json().await.unwrap_or(Value::Null);
+        if !status.is_success() {
+            bail!(
+                "'{}' a returnat HTTP {}: {}",
+                command,
+                status,
+                payload["message"].as_str().unwrap_or("fără mesaj")
+            );
+        }
+
+        Ok(payload)
+    }
+
+    fn endpoint(&self, command: &str) -> String {
+        format!("{}/web_api/{}", self.config.management_url.trim_end_matches('/'), command)
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Marker de eroare pentru sesiune expirată/invalidă.
+//
+// Îl împachetăm în `anyhow::Error` și îl recunoaștem cu `downcast_ref`,
+// fără a defini un enum de erori complet pentru un singur caz special.
+// ---------------------------------------------------------------------------
+#[derive(Debug)]
+struct SessionExpired;
+
+impl std::fmt::Display for SessionExpired {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "sesiunea API Check Point a expirat")
+    }
+}
+
+impl std::error::Error for SessionExpired {}
+
+fn is_session_expired(e: &anyhow::Error) -> bool {
+    e.downcast_ref::<SessionExpired>().is_some()
+}
@@ -0,0 +1,17 @@
+// ============================================================
+//  response/mod.rs - Răspuns activ la scanările detectate
+// ============================================================
+//
+//  Alertele informează operatorul; responder-ii acționează direct
+//  asupra infrastructurii (ex: blocarea IP-ului pe firewall).
+//
+//  Fiecare responder este opțional atât la compilare (cargo feature),
+//  cât și la rulare (secțiune `[response.*]` în config.toml).
+//  Erorile unui responder produc doar avertismente - nu opresc IDS-ul.
+//
+//  Concepte Rust demonstrate:
+//  - `#[cfg(feature = "...")]` : compilare condiționată a modulelor
+// ============================================================
+
+#[cfg(feature = "checkpoint")]
+pub mod checkpoint;
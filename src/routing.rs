@@ -0,0 +1,120 @@
+// ============================================================
+//  routing.rs - Ce canal primește o alertă (politica per canal)
+// ============================================================
+//
+//  SIEM-ul și socket-ul UDS primesc toate alertele, 24/7: sunt consumate
+//  de mașini. Email-ul ajunge la oameni, deci are o politică proprie:
+//
+//    [email]
+//    min_severity             = 5              # sub 5: doar SIEM
+//    quiet_hours              = "22:00-07:00"  # ora locală
+//    quiet_hours_min_severity = 9              # noaptea: doar critice
+//
+//  Un slow scan de severitate 5 la 3 noaptea ajunge în SIEM, dar nu trezește
+//  pe nimeni; un fast scan de severitate 9 pleacă și pe email.
+//
+//  Concepte Rust demonstrate:
+//  - `#[serde(try_from = "String", into = "String")]` : tip validat la deserializare
+//  - `chrono::NaiveTime` : ora din zi, fără dată și fără fus orar
+// ============================================================
+
+use crate::config::EmailConfig;
+use chrono::NaiveTime;
+use serde::{Deserialize, Serialize};
+use std::fmt;
+
+/// Canalele de alertă ale dispatcher-ului
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Channel {
+    Siem,
+    Uds,
+    Email,
+}
+
+// ---------------------------------------------------------------------------
+// Intervalul orar "HH:MM-HH:MM" (ora locală), capătul de final exclus.
+// Un interval cu start > end trece peste miezul nopții ("22:00-07:00").
+// ---------------------------------------------------------------------------
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(try_from = "String", into = "String")]
+pub struct QuietHours {
+    pub start: NaiveTime,
+    pub end:   NaiveTime,
+}
+
+impl QuietHours {
+    pub fn contains(&self, time: NaiveTime) -> bool {
+        if self.start <= self.end {
+            self.start <= time && time < self.end
+        } else {
+            time >= self.start || time < self.end
+        }
+    }
+}
+
+impl TryFrom<String> for QuietHours {
+    type Error = String;
+
+    fn try_from(text: String) -> Result<Self, Self::Error> {
+        let parse = |part: &str| NaiveTime::parse_from_str(part.trim(), "%H:%M").ok();
+        let (start, end) = text
+            .split_once('-')
+            .and_then(|(start, end)| Some((parse(start)?, parse(end)?)))
+            .ok_or_else(|| format!("quiet_hours '{}' invalid: se așteaptă \"HH:MM-HH:MM\" (ex: \"22:00-07:00\")", text))?;
+        if start == end {
+            return Err(format!("quiet_hours '{}' invalid: intervalul este gol", text));
+        }
+        Ok(QuietHours { start, end })
+    }
+}
+
+impl From<QuietHours> for String {
+    fn from(hours: QuietHours) -> Self {
+        hours.to_string()
+    }
+}
+
+impl fmt::Display for QuietHours {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}-{}", self.start.format("%H:%M"), self.end.format("%H:%M"))
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Politica de rutare, derivată din configurație la pornire
+// ---------------------------------------------------------------------------
+#[derive(Debug, Clone, PartialEq)]
+pub struct RoutingPolicy {
+    email_min_severity:       u8,
+    quiet_hours:              Option<QuietHours>,
+    quiet_hours_min_severity: u8,
+}
+
+impl RoutingPolicy {
+    pub fn new(email: &EmailConfig) -> Self {
+        RoutingPolicy {
+            email_min_severity:       email.min_severity,
+            quiet_hours:              email.quiet_hours,
+            quiet_hours_min_severity: email.quiet_hours_min_severity,
+        }
+    }
+
+    // -----------------------------------------------------------------------
+    // Primește `channel` o alertă de severitate `severity` (după mapare și
+    // clamp) la ora locală `local_time`? SIEM și UDS: întotdeauna.
+    // -----------------------------------------------------------------------
+    pub fn allows(&self, channel: Channel, severity: u8, local_time: NaiveTime) -> bool {
+        match channel {
+            Channel::Siem | Channel::Uds => true,
+            Channel::Email => {
+                let quiet = self.quiet_hours.is_some_and(|hours| hours.contains(local_time));
+                let min_severity = if quiet {
+                    self.email_min_severity.max(self.quiet_hours_min_severity)
+                } else {
+                    self.email_min_severity
+                };
+                severity >= min_severity
+            }
+        }
+    }
+}
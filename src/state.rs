@@ -12,24 +12,522 @@
 //  - `Clone` derivat: clonarea unui `Arc` nu copiează datele,
 //    ci incrementează atomic contorul de referințe.
 //  - `Instant`: timp monoton (nu poate da înapoi) - ideal pentru măsurarea intervalelor
+//  - `Arc<dyn Clock>`: momentul curent vine dintr-un ceas injectabil (vezi clock.rs)
 // ============================================================
 
+use crate::clock::{Clock, SystemClock};
+use crate::context::ContextBuffer;
+use crate::diagnostics::ParseDiagnostics;
+use dashmap::mapref::entry::Entry;
 use dashmap::DashMap;
+use serde::Serialize;
+use std::collections::{HashMap, HashSet, VecDeque};
 use std::net::IpAddr;
+use std::sync::atomic::{AtomicU64, Ordering};
 use std::sync::Arc;
 use std::time::{Duration, Instant};
 
 // ---------------------------------------------------------------------------
-// Un eveniment de scan: portul văzut + momentul exact al observării
+// Istoricul porturilor unei surse: un inel de bucket-uri de timp
+//
+// Un vector cu FIECARE eveniment crește cu traficul: o sursă activă pe
+// fereastra slow (o oră) poate aduna milioane de intrări, iar numărarea
+// porturilor unice le parcurge pe toate. În schimb, timpul este împărțit
+// în bucket-uri de `SCAN_BUCKET` secunde; fiecare ține setul porturilor
+// văzute în intervalul lui, cu ultimul moment al fiecărui port:
+//
+//   memorie   = (fereastră / bucket) * porturi unice - nu evenimente
+//   numărare  = reuniunea bucket-urilor din fereastră
+//   expirare  = eliminarea bucket-urilor vechi de la începutul inelului
+//
+// Momentul per port păstrează numărătoarea EXACTĂ la marginea ferestrei:
+// un bucket tăiat de fereastră contribuie doar porturile văzute în ea.
 //
 // `Instant` NU este un timestamp absolut (nu știe data/ora).
 // Este un punct pe o linie de timp monotonă - perfect pentru calcule
 // de interval (ex: "a trecut X secunde de la eveniment?")
 // ---------------------------------------------------------------------------
+pub const SCAN_BUCKET: Duration = Duration::from_secs(5);
+
+/// Biții protocoalelor văzute pe un port (`PortSeen::protocols`)
+const PROTO_TCP: u8 = 1;
+const PROTO_UDP: u8 = 2;
+const PROTO_OTHER: u8 = 4;
+
+/// Bitul protocolului raportat; fără protocol = "alt protocol"
+fn protocol_bit(protocol: Option<&str>) -> u8 {
+    match protocol {
+        Some("tcp") => PROTO_TCP,
+        Some("udp") => PROTO_UDP,
+        _ => PROTO_OTHER,
+    }
+}
+
+/// Un port dintr-un bucket: ultima apariție + protocoalele văzute
+#[derive(Debug, Clone, Copy)]
+pub(crate) struct PortSeen {
+    at:        Instant,
+    protocols: u8,
+}
+
+/// Porturile văzute într-un interval de `SCAN_BUCKET`
+#[derive(Debug, Clone)]
+pub(crate) struct PortBucket {
+    /// Indexul bucket-ului față de `PortRing::origin` (negativ = înainte)
+    index:  i64,
+    /// Port -> ultima apariție în acest bucket
+    ports:  HashMap<u16, PortSeen>,
+    /// Cea mai recentă apariție din bucket (expirarea fără parcurgere)
+    newest: Instant,
+}
+
+/// Porturile unice ale unei surse într-o fereastră, cu detaliile lor
+/// (dovezile unei alerte - vezi `detector::evidence`)
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct PortWindow {
+    /// Porturile unice din fereastră
+    pub unique:     usize,
+    /// Primele porturi, sortate crescător (plafonat la dimensiunea cerută)
+    pub sample:     Vec<u16>,
+    /// Porturile unice per protocol
+    pub protocols:  ProtocolBreakdown,
+    /// Cea mai veche / cea mai nouă apariție din fereastră (`None` dacă
+    /// fereastra e goală). Fiecare bucket păstrează doar ultima apariție a
+    /// unui port, deci `first_seen` are rezoluția `SCAN_BUCKET`.
+    pub first_seen: Option<Instant>,
+    pub last_seen:  Option<Instant>,
+}
+
+/// Porturile unice per protocol (un port văzut pe TCP și UDP contează la ambele)
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize)]
+pub struct ProtocolBreakdown {
+    pub tcp:   usize,
+    pub udp:   usize,
+    /// Alt protocol (ex: SCTP) sau protocol neraportat de log
+    pub other: usize,
+}
+
+/// Rezultatul brut al parcurgerii unei ferestre: port -> biții de protocol,
+/// plus marginile în timp ale aparițiilor
+#[derive(Default)]
+struct WindowScan {
+    ports:      HashMap<u16, u8>,
+    first_seen: Option<Instant>,
+    last_seen:  Option<Instant>,
+}
+
+/// Bucket-urile unei surse, ordonate crescător după index
+#[derive(Debug, Clone)]
+pub(crate) struct PortRing {
+    origin:  Instant,
+    buckets: VecDeque<PortBucket>,
+}
+
+impl PortRing {
+    fn new(origin: Instant) -> Self {
+        PortRing { origin, buckets: VecDeque::new() }
+    }
+
+    /// Indexul bucket-ului momentului `at` (evenimentele pot fi datate în trecut)
+    fn index_of(&self, at: Instant) -> i64 {
+        let width = SCAN_BUCKET.as_nanos();
+        match at.checked_duration_since(self.origin) {
+            Some(after) => (after.as_nanos() / width) as i64,
+            None => -((self.origin.duration_since(at).as_nanos().div_ceil(width)) as i64),
+        }
+    }
+
+    /// Începutul bucket-ului cu indexul dat
+    fn start_of(&self, index: i64) -> Option<Instant> {
+        let offset = SCAN_BUCKET.checked_mul(index.unsigned_abs().try_into().ok()?)?;
+        if index >= 0 { self.origin.checked_add(offset) } else { self.origin.checked_sub(offset) }
+    }
+
+    fn record(&mut self, port: u16, protocol: u8, seen_at: Instant) {
+        let index = self.index_of(seen_at);
+        // Cazul obișnuit: evenimentul cade în ultimul bucket sau după el;
+        // evenimentele sintetice (datate în trecut) se inserează la locul lor
+        let position = match self.buckets.back() {
+            Some(last) if last.index <= index => self.buckets.len() - usize::from(last.index == index),
+            _ => self.buckets.partition_point(|bucket| bucket.index < index),
+        };
+        if self.buckets.get(position).is_none_or(|bucket| bucket.index != index) {
+            self.buckets.insert(position, PortBucket { index, ports: HashMap::new(), newest: seen_at });
+        }
+
+        let bucket = &mut self.buckets[position];
+        let seen = bucket.ports.entry(port).or_insert(PortSeen { at: seen_at, protocols: 0 });
+        seen.at = seen.at.max(seen_at);
+        seen.protocols |= protocol;
+        bucket.newest = bucket.newest.max(seen_at);
+    }
+
+    /// Porturile unice văzute în ultimele `window` (acceptate de `counted`)
+    fn unique_in_window(&self, now: Instant, window: Duration, counted: impl Fn(u16) -> bool) -> usize {
+        let mut unique = HashSet::new();
+        self.for_each_in_window(now, window, |port, _seen| {
+            if counted(port) {
+                unique.insert(port);
+            }
+        });
+        unique.len()
+    }
+
+    /// Porturile din ultimele `window` (acceptate de `counted`), cu
+    /// protocoalele văzute pe fiecare
+    fn protocols_in_window(&self, now: Instant, window: Duration, counted: impl Fn(u16) -> bool) -> WindowScan {
+        let mut scan = WindowScan::default();
+        self.for_each_in_window(now, window, |port, seen| {
+            if counted(port) {
+                *scan.ports.entry(port).or_default() |= seen.protocols;
+                scan.first_seen = Some(scan.first_seen.map_or(seen.at, |first| first.min(seen.at)));
+                scan.last_seen = Some(scan.last_seen.map_or(seen.at, |last| last.max(seen.at)));
+            }
+        });
+        scan
+    }
+
+    /// Fiecare apariție (port, bucket) din ultimele `window`
+    fn for_each_in_window(&self, now: Instant, window: Duration, mut visit: impl FnMut(u16, &PortSeen)) {
+        let in_window = |at: Instant| now.saturating_duration_since(at) <= window;
+
+        // De la cel mai nou bucket spre cel mai vechi; ne oprim la primul
+        // bucket ieșit complet din fereastră
+        for bucket in self.buckets.iter().rev() {
+            if !in_window(bucket.newest) {
+                break;
+            }
+            // Bucket-ul întreg în fereastră: fără verificare per port
+            let whole = self.start_of(bucket.index).is_some_and(in_window);
+            for (&port, seen) in &bucket.ports {
+                if whole || in_window(seen.at) {
+                    visit(port, seen);
+                }
+            }
+        }
+    }
+
+    /// Toate porturile din istoric
+    fn ports(&self) -> impl Iterator<Item = u16> + '_ {
+        self.buckets.iter().flat_map(|bucket| bucket.ports.keys().copied())
+    }
+
+    /// Elimină bucket-urile fără apariții în ultimele `max_age`; `false` = gol
+    fn expire(&mut self, now: Instant, max_age: Duration) -> bool {
+        self.buckets.retain(|bucket| now.saturating_duration_since(bucket.newest) <= max_age);
+        !self.buckets.is_empty()
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Limitele de memorie ale detectorului de scanări distribuite
+//
+// Un atacator controlează câte perechi (sursă, port) generează, deci
+// structura trebuie plafonată explicit:
+//   - MAX_DISTRIBUTED_PAIRS: perechi distincte per destinație. La atingerea
+//     limitei se elimină întâi perechile expirate, apoi cele mai vechi 10%.
+//   - MAX_DISTRIBUTED_BUCKETS: destinații urmărite simultan. Peste limită,
+//     evenimentele pentru destinații noi cad în bucket-ul global (`None`),
+//     astfel încât un sweep pe un /16 nu poate crea 65536 de bucket-uri.
+// ---------------------------------------------------------------------------
+pub const MAX_DISTRIBUTED_PAIRS: usize = 50_000;
+pub const MAX_DISTRIBUTED_BUCKETS: usize = 4_096;
+
+// ---------------------------------------------------------------------------
+// Profilul țintelor unui atacator: ce destinații (gazde interne) a atins
+//
+// Limitat la MAX_TARGETS_PER_SOURCE destinații per sursă, cu evacuare LRU
+// (destinația cu `last_seen` cel mai vechi iese prima), astfel încât un
+// sweep pe un /16 nu poate consuma memorie nelimitată. Porturile unice
+// per destinație sunt plafonate la MAX_PORTS_PER_TARGET (contor = minim).
+// ---------------------------------------------------------------------------
+pub const MAX_TARGETS_PER_SOURCE: usize = 256;
+pub const MAX_PORTS_PER_TARGET: usize = 1024;
+
+/// Câte destinații (cele mai atinse) se includ în alerte
+pub const TOP_TARGETS: usize = 5;
+
+/// Activitatea unui atacator către o destinație
+#[derive(Debug, Clone, Default)]
+pub(crate) struct TargetStats {
+    pub ports:     HashSet<u16>,
+    pub hits:      u64,
+    pub last_seen: Option<Instant>,
+}
+
+/// Sumarul (imutabil, fără referințe în DashMap) al unei ținte
+#[derive(Debug, Clone, PartialEq)]
+pub struct TargetSummary {
+    pub dest:         IpAddr,
+    pub unique_ports: usize,
+    pub hits:         u64,
+    pub last_seen:    Instant,
+}
+
+// ---------------------------------------------------------------------------
+// Regulile firewall-ului care au blocat un atacator (triaj + ajustarea
+// politicii: ce linie din politică "prinde" scanner-ul)
+//
+// Per sursă: max MAX_RULES_PER_SOURCE reguli, cu evacuare LRU ca la ținte.
+// Global: contoarele intervalului curent de sumar, max MAX_RULES_TOTAL
+// reguli distincte; regulile noi peste limită nu se mai numără până la
+// următorul sumar (care golește contoarele).
+// ---------------------------------------------------------------------------
+pub const MAX_RULES_PER_SOURCE: usize = 64;
+pub const MAX_RULES_TOTAL: usize = 1024;
+
+/// Câte reguli (cele mai lovite) se includ în alerte și în sumarul periodic
+pub const TOP_RULES: usize = 5;
+
+/// Lovirile unei reguli de către un atacator
+#[derive(Debug, Clone, Default)]
+pub(crate) struct RuleStats {
+    pub hits:      u64,
+    pub last_seen: Option<Instant>,
+}
+
+/// Sumarul unei reguli: numele și câte drop-uri a generat
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RuleSummary {
+    pub rule: String,
+    pub hits: u64,
+}
+
+// ---------------------------------------------------------------------------
+// Recidiviștii: IP-urile alertate în ultimele `repeat_offender_window_hours`
+//
+// Spre deosebire de istoric și cooldown-uri, memoria NU depinde de
+// `max_age` al cleanup-ului: expiră doar după fereastra proprie (ore).
+// Plafonată la MAX_REPEAT_OFFENDERS IP-uri; un IP nou peste limită
+// evacuează IP-ul cu cea mai veche alertă (LRU).
+// ---------------------------------------------------------------------------
+pub const MAX_REPEAT_OFFENDERS: usize = 10_000;
+
+/// Alertele unui IP ținut minte ca recidivist
+#[derive(Debug, Clone, Copy)]
+pub(crate) struct OffenderRecord {
+    last_alert: Instant,
+    alerts:     u32,
+}
+
+/// Un recidivist: câte alerte a avut și de cât timp a tăcut
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RepeatOffender {
+    pub alerts:           u32,
+    pub since_last_alert: Duration,
+}
+
+/// Dimensiunile stării interne (sumarul periodic)
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct StateStats {
+    /// IP-uri cu istoric de porturi
+    pub tracked_ips:      usize,
+    /// IP-uri cu istoric ICMP
+    pub icmp_ips:         usize,
+    /// IP-uri în cooldown de alertă (sau cu cooldown escaladat)
+    pub cooldowns:        usize,
+    /// IP-uri într-o stare de amenințare (histerezis)
+    pub active_scans:     usize,
+    /// IP-uri ținute minte ca recidiviști
+    pub repeat_offenders: usize,
+}
+
+// ---------------------------------------------------------------------------
+// Un drop ICMP: destinația (dacă log-ul o are) + momentul observării
+//
+// Ținut separat de `PortRing`: ICMP nu are port și nu trebuie să
+// contribuie la numărătoarea porturilor unice.
+//
+// Istoricul unei surse e o coadă plafonată la MAX_ICMP_EVENTS_PER_SOURCE
+// (cele mai vechi ies primele): un flood ICMP nu crește memoria și nici
+// costul evaluării, care parcurge doar coada evenimentelor din fereastră.
+// ---------------------------------------------------------------------------
+pub const MAX_ICMP_EVENTS_PER_SOURCE: usize = 1024;
+
 #[derive(Debug, Clone)]
-pub struct ScanEvent {
-    pub port:      u16,
-    pub seen_at:   Instant,
+pub(crate) struct IcmpEvent {
+    pub dest:    Option<IpAddr>,
+    pub seen_at: Instant,
+}
+
+/// Activitatea ICMP a unei surse într-o fereastră de timp
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct IcmpActivity {
+    pub events:       usize,
+    pub destinations: usize,
+}
+
+// ---------------------------------------------------------------------------
+// Cooldown-ul alertelor per IP, cu escaladare exponențială
+//
+// Un cooldown fix re-alertează un atacator persistent la ritm constant, la
+// nesfârșit. Aici, fiecare re-alertare în `reset_after` de la finalul
+// cooldown-ului precedent îl înmulțește cu `multiplier` (plafonat la `max`);
+// după o perioadă de liniște, IP-ul revine la `base`.
+// ---------------------------------------------------------------------------
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct CooldownPolicy {
+    pub base:        Duration,
+    pub multiplier:  f64,
+    pub max:         Duration,
+    pub reset_after: Duration,
+}
+
+impl CooldownPolicy {
+    /// Cooldown fix (fără escaladare)
+    pub fn flat(base: Duration) -> Self {
+        CooldownPolicy { base, multiplier: 1.0, max: base, reset_after: Duration::ZERO }
+    }
+
+    /// Cooldown-ul următor după `current`: înmulțit, dar între bază și plafon
+    /// (un produs care nu încape în `Duration` devine plafonul)
+    fn escalate(&self, current: Duration) -> Duration {
+        Duration::try_from_secs_f64(current.as_secs_f64() * self.multiplier)
+            .unwrap_or(self.max)
+            .min(self.max)
+            .max(self.base)
+    }
+}
+
+/// Starea de cooldown a unui IP alertat
+#[derive(Debug, Clone, Copy)]
+pub(crate) struct AlertCooldown {
+    last_alert: Instant,
+    /// Cooldown-ul curent (crește la fiecare re-alertare)
+    cooldown:   Duration,
+    /// După acest moment, următoarea alertă pornește iar de la bază
+    resets_at:  Instant,
+    /// Severitatea ultimei alerte trimise (escaladarea prin cooldown)
+    severity:   u8,
+}
+
+// ---------------------------------------------------------------------------
+// O sursă aflată într-o stare de amenințare (histerezisul detecției)
+//
+// Intră la prima evaluare amenințătoare și iese abia când porturile
+// tier-urilor declanșate scad la `prag * clear_ratio` (vezi
+// `Engine::clear_pass`). Cât timp este activă, aceeași activitate nu mai
+// produce alerte noi - doar o escaladare a severității.
+// ---------------------------------------------------------------------------
+#[derive(Debug, Clone, PartialEq)]
+pub struct ActiveScan {
+    /// Momentul tranziției Clean -> Threat
+    pub since:         Instant,
+    /// Cea mai mare severitate evaluată în această activitate
+    pub peak_severity: u8,
+    /// Tier-urile declanșate (doar ele primesc pragul de eliberare redus)
+    pub tiers:         Vec<String>,
+}
+
+// ---------------------------------------------------------------------------
+// O sursă detectată ca scanner: porturile pe care a fost blocată
+//
+// Setul pornește din evenimentele deja înregistrate la momentul detecției
+// și crește cu fiecare drop ulterior, până la `expires_at` (prelungit la
+// fiecare nouă detecție). Un `accept` pe unul din aceste porturi =
+// acces după scan (vezi `detector::evaluate_post_scan_access`).
+// ---------------------------------------------------------------------------
+#[derive(Debug, Clone)]
+pub(crate) struct ScannerRecord {
+    probed_ports: HashSet<u16>,
+    expires_at:   Instant,
+}
+
+/// Throttling-ul evaluării unei surse: ultima evaluare "curată"
+#[derive(Debug, Clone, Copy)]
+pub(crate) struct EvalThrottle {
+    clean_at: Instant,
+    /// O evaluare amânată este deja programată pentru această sursă
+    deferred: bool,
+}
+
+/// Decizia throttling-ului pentru un pachet nou de la o sursă
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ThrottleCheck {
+    /// Evaluarea se face acum
+    Due,
+    /// Evaluată recent și curată; o evaluare amânată e deja programată
+    Skip,
+    /// Evaluată recent și curată: evaluarea se amână cu durata dată
+    /// (apelantul trebuie s-o programeze - altfel pragul poate fi ratat)
+    Defer(Duration),
+}
+
+/// Cheia unui bucket distribuit: IP-ul destinație, sau `None` pentru
+/// bucket-ul global (log-uri fără destinație sau depășirea numărului de bucket-uri)
+pub type DistributedKey = Option<IpAddr>;
+
+/// Perechile (sursă, port) observate către o destinație, cu ultima apariție
+#[derive(Debug, Default)]
+pub(crate) struct DistributedBucket {
+    pub pairs: HashMap<(IpAddr, u16), Instant>,
+}
+
+/// Agregatul unui bucket distribuit într-o fereastră de timp
+#[derive(Debug, Clone, PartialEq)]
+pub struct DistributedStats {
+    /// Sursele participante, ordonate descrescător după porturile atinse
+    /// (apoi după IP, pentru un eșantion determinist)
+    pub sources: Vec<IpAddr>,
+
+    /// Porturi unice cumulate pe sursele participante
+    pub ports: usize,
+}
+
+// ---------------------------------------------------------------------------
+// Activitatea fluxului de intrare (pentru heartbeat și detectarea tăcerii)
+//
+// Un exporter oprit sau o regulă de firewall care mănâncă syslog-ul fac
+// IDS-ul să tacă - fără nicio eroare. Bucla de recepție actualizează
+// contoarele; task-ul de heartbeat le citește.
+//
+// Atomice în loc de Mutex: un singur `fetch_add`/`store` per pachet,
+// fără contention între task-urile de procesare.
+// ---------------------------------------------------------------------------
+#[derive(Debug)]
+pub struct InputActivity {
+    /// Referința de timp pentru `last_packet_ms`
+    started:        Instant,
+
+    /// Pachete primite de la ultimul heartbeat
+    packets:        AtomicU64,
+
+    /// Momentul ultimului pachet, în ms de la `started` (0 = niciunul încă)
+    last_packet_ms: AtomicU64,
+}
+
+impl InputActivity {
+    pub fn new() -> Self {
+        InputActivity {
+            started:        Instant::now(),
+            packets:        AtomicU64::new(0),
+            last_packet_ms: AtomicU64::new(0),
+        }
+    }
+
+    /// Apelat de bucla de recepție pentru fiecare pachet (datagramă UDP / linie TCP)
+    pub fn record_packet(&self) {
+        self.packets.fetch_add(1, Ordering::Relaxed);
+        let elapsed_ms = self.started.elapsed().as_millis() as u64;
+        self.last_packet_ms.store(elapsed_ms, Ordering::Relaxed);
+    }
+
+    /// Pachetele primite de la apelul anterior (contorul se resetează)
+    pub fn take_packets(&self) -> u64 {
+        self.packets.swap(0, Ordering::Relaxed)
+    }
+
+    /// De cât timp nu a mai sosit niciun pachet (de la pornire, dacă niciunul)
+    pub fn silent_for(&self) -> Duration {
+        let last = Duration::from_millis(self.last_packet_ms.load(Ordering::Relaxed));
+        self.started.elapsed().saturating_sub(last)
+    }
+}
+
+impl Default for InputActivity {
+    fn default() -> Self {
+        Self::new()
+    }
 }
 
 // ---------------------------------------------------------------------------
@@ -45,13 +543,76 @@ pub struct ScanEvent {
 // ---------------------------------------------------------------------------
 #[derive(Clone)]
 pub struct SharedState {
-    /// Istoricul evenimentelor per IP sursă
-    /// Key: IP sursă | Value: lista evenimentelor de scan (port + timestamp)
-    pub scan_map: Arc<DashMap<IpAddr, Vec<ScanEvent>>>,
+    /// Istoricul porturilor per IP sursă
+    /// Key: IP sursă | Value: inelul de bucket-uri de timp (porturi unice per bucket)
+    scan_map: Arc<DashMap<IpAddr, PortRing>>,
 
     /// Cooldown pentru alerte: previne spam-ul de alerte pentru același IP
-    /// Key: IP sursă | Value: momentul ultimei alerte trimise
-    pub alert_cooldown: Arc<DashMap<IpAddr, Instant>>,
+    /// Key: IP sursă | Value: ultima alertă, cooldown-ul curent, momentul
+    /// revenirii la bază (`resets_at`) și severitatea ultimei alerte
+    alert_cooldown: Arc<DashMap<IpAddr, AlertCooldown>>,
+
+    /// Sursele aflate acum într-o stare de amenințare (histerezis)
+    active_scans: Arc<DashMap<IpAddr, ActiveScan>>,
+
+    /// IP-uri deja blocate pe firewall (răspuns activ)
+    /// Key: IP sursă | Value: momentul la care blocarea expiră
+    blocked_ips: Arc<DashMap<IpAddr, Instant>>,
+
+    /// Istoricul drop-urilor ICMP per IP sursă (clasă de activitate separată)
+    icmp_map: Arc<DashMap<IpAddr, VecDeque<IcmpEvent>>>,
+
+    /// Cooldown pentru alertele ICMP sweep - separat de cel al port scan-urilor,
+    /// astfel încât un sweep să nu ascundă scanarea de porturi care urmează
+    icmp_cooldown: Arc<DashMap<IpAddr, Instant>>,
+
+    /// Perechi (sursă, port) per destinație - pentru scanările distribuite
+    distributed: Arc<DashMap<DistributedKey, DistributedBucket>>,
+
+    /// Cooldown pentru alertele distribuite - per DESTINAȚIE, nu per sursă
+    distributed_cooldown: Arc<DashMap<DistributedKey, Instant>>,
+
+    /// Profilul țintelor per atacator
+    /// Key: IP sursă | Value: destinație -> activitate (max MAX_TARGETS_PER_SOURCE)
+    targets: Arc<DashMap<IpAddr, HashMap<IpAddr, TargetStats>>>,
+
+    /// Regulile care au blocat fiecare atacator
+    /// Key: IP sursă | Value: regulă -> loviri (max MAX_RULES_PER_SOURCE)
+    rules: Arc<DashMap<IpAddr, HashMap<String, RuleStats>>>,
+
+    /// Drop-urile per regulă, toate sursele, din intervalul de sumar curent
+    rule_totals: Arc<DashMap<String, u64>>,
+
+    /// Contoarele de parsare per exporter (diagnosticarea formatului greșit)
+    parse_diagnostics: Arc<ParseDiagnostics>,
+
+    /// Ultimele linii brute primite (contextul atașat alertelor)
+    context: Arc<ContextBuffer>,
+
+    /// Contoarele fluxului de intrare (heartbeat SIEM, avertizare de tăcere)
+    activity: Arc<InputActivity>,
+
+    /// Sursele detectate ca scanner și porturile lor sondate (acces după scan)
+    scanners: Arc<DashMap<IpAddr, ScannerRecord>>,
+
+    /// Cooldown pentru alertele acces-după-scan - per (sursă, port)
+    post_scan_cooldown: Arc<DashMap<(IpAddr, u16), Instant>>,
+
+    /// Ultima evaluare curată per sursă (throttling în timpul unui flood)
+    eval_throttle: Arc<DashMap<IpAddr, EvalThrottle>>,
+
+    /// Evaluările per-sursă efectuate (vs. pachete: eficiența throttling-ului)
+    evaluations: Arc<AtomicU64>,
+
+    /// IP-urile alertate recent (recidiviști), păstrate peste cleanup
+    /// Key: IP sursă | Value: ultima alertă + numărul de alerte
+    offenders: Arc<DashMap<IpAddr, OffenderRecord>>,
+
+    /// Cât timp se ține minte un recidivist (zero = memoria e dezactivată)
+    offender_window: Duration,
+
+    /// Sursa de timp a ferestrelor, cooldown-urilor și cleanup-ului
+    clock: Arc<dyn Clock>,
 }
 
 impl SharedState {
@@ -60,26 +621,362 @@ impl SharedState {
         SharedState {
             scan_map:       Arc::new(DashMap::new()),
             alert_cooldown: Arc::new(DashMap::new()),
+            active_scans:   Arc::new(DashMap::new()),
+            blocked_ips:    Arc::new(DashMap::new()),
+            icmp_map:       Arc::new(DashMap::new()),
+            icmp_cooldown:  Arc::new(DashMap::new()),
+            distributed:    Arc::new(DashMap::new()),
+            distributed_cooldown: Arc::new(DashMap::new()),
+            targets:        Arc::new(DashMap::new()),
+            rules:          Arc::new(DashMap::new()),
+            rule_totals:    Arc::new(DashMap::new()),
+            parse_diagnostics: Arc::new(ParseDiagnostics::new()),
+            context:        Arc::new(ContextBuffer::default()),
+            activity:       Arc::new(InputActivity::new()),
+            scanners:       Arc::new(DashMap::new()),
+            post_scan_cooldown: Arc::new(DashMap::new()),
+            eval_throttle:  Arc::new(DashMap::new()),
+            evaluations:    Arc::new(AtomicU64::new(0)),
+            offenders:      Arc::new(DashMap::new()),
+            offender_window: Duration::ZERO,
+            clock:          Arc::new(SystemClock),
         }
     }
 
+    /// Momentul curent, după ceasul stării (`SystemClock` în producție)
+    pub fn now(&self) -> Instant {
+        self.clock.now()
+    }
+
+    /// Contoarele de parsare per exporter
+    pub fn parse_diagnostics(&self) -> &ParseDiagnostics {
+        &self.parse_diagnostics
+    }
+
+    /// Buffer-ul cu liniile brute recente
+    pub fn context(&self) -> &ContextBuffer {
+        &self.context
+    }
+
+    /// Activitatea fluxului de intrare (pachete, ultimul pachet)
+    pub fn activity(&self) -> &InputActivity {
+        &self.activity
+    }
+
+    /// Activează buffer-ul de context cu capacitatea dată (0 = dezactivat)
+    pub fn with_context_buffer(mut self, capacity: usize) -> Self {
+        self.context = Arc::new(ContextBuffer::new(capacity));
+        self
+    }
+
+    /// Activează memoria recidiviștilor pentru `window` (zero = dezactivată)
+    pub fn with_repeat_offender_window(mut self, window: Duration) -> Self {
+        self.offender_window = window;
+        self
+    }
+
+    /// Înlocuiește ceasul sistemului (ex: `MockClock` în teste deterministe)
+    pub fn with_clock(mut self, clock: Arc<dyn Clock>) -> Self {
+        self.clock = clock;
+        self
+    }
+
     // -----------------------------------------------------------------------
     // Înregistrează un eveniment de scan pentru un IP
     //
     // `.entry(ip)` returnează un `Entry` (similar cu HashMap::entry)
-    // `.or_insert_with(Vec::new)` inserează un Vec gol dacă cheia nu există
-    // `.push(...)` adaugă evenimentul în vector
+    // `.or_insert_with(...)` creează inelul gol dacă cheia nu există
+    // `.record(...)` adaugă portul în bucket-ul momentului `seen_at`
     //
     // DashMap garantează că operația este atomică per-shard.
+    //
+    // `seen_at` este de regulă momentul curent al ceasului (vezi `record_event`);
+    // evenimentele sintetice (`[control]`) pot fi datate în trecut.
     // -----------------------------------------------------------------------
     pub fn record_event(&self, ip: IpAddr, port: u16) {
+        self.record_event_at(ip, port, self.now());
+    }
+
+    /// Ca `record_event`, cu momentul dat
+    pub fn record_event_at(&self, ip: IpAddr, port: u16, seen_at: Instant) {
+        self.record_port_event_at(ip, port, None, seen_at);
+    }
+
+    /// Ca `record_event_at`, cu protocolul raportat de log (normalizat, ex:
+    /// "tcp") - apare în dovezile alertei (`port_window_where`)
+    pub fn record_port_event_at(&self, ip: IpAddr, port: u16, protocol: Option<&str>, seen_at: Instant) {
         self.scan_map
             .entry(ip)
-            .or_insert_with(Vec::new)
-            .push(ScanEvent {
-                port,
-                seen_at: Instant::now(),
-            });
+            .or_insert_with(|| PortRing::new(seen_at))
+            .record(port, protocol_bit(protocol), seen_at);
+
+        // Un scanner deja semnalat: portul intră și în setul sondat
+        if let Some(mut scanner) = self.scanners.get_mut(&ip) {
+            scanner.probed_ports.insert(port);
+        }
+    }
+
+    /// Semnalează `ip` ca scanner pentru `window`: porturile din istoricul
+    /// lui devin "sondate" (o nouă detecție prelungește fereastra)
+    pub fn flag_scanner(&self, ip: IpAddr, window: Duration) {
+        let history: Vec<u16> = self
+            .scan_map
+            .get(&ip)
+            .map(|ring| ring.ports().collect())
+            .unwrap_or_default();
+
+        let expires_at = self.now() + window;
+        let mut scanner = self.scanners.entry(ip).or_insert_with(|| ScannerRecord {
+            probed_ports: HashSet::new(),
+            expires_at,
+        });
+        scanner.probed_ports.extend(history);
+        scanner.expires_at = expires_at;
+    }
+
+    /// `ip` este un scanner semnalat (neexpirat) care a fost blocat pe `port`?
+    pub fn was_probed_by_scanner(&self, ip: &IpAddr, port: u16) -> bool {
+        self.scanners
+            .get(ip)
+            .is_some_and(|s| s.expires_at > self.now() && s.probed_ports.contains(&port))
+    }
+
+    /// Înregistrează un drop ICMP de la `ip` către `dest`
+    pub fn record_icmp(&self, ip: IpAddr, dest: Option<IpAddr>) {
+        let mut events = self.icmp_map.entry(ip).or_default();
+        if events.len() >= MAX_ICMP_EVENTS_PER_SOURCE {
+            events.pop_front();
+        }
+        events.push_back(IcmpEvent {
+            dest,
+            seen_at: self.now(),
+        });
+    }
+
+    /// Numărul de drop-uri ICMP și de destinații distincte ale lui `ip` în fereastră
+    pub fn icmp_activity_in_window(&self, ip: &IpAddr, window_secs: u64) -> IcmpActivity {
+        let window = Duration::from_secs(window_secs);
+        let now = self.now();
+
+        let Some(events) = self.icmp_map.get(ip) else {
+            return IcmpActivity { events: 0, destinations: 0 };
+        };
+
+        // Evenimentele sunt în ordine cronologică: parcurgem de la cel mai
+        // nou și ne oprim la primul din afara ferestrei
+        let mut recent = 0;
+        let mut destinations: HashSet<IpAddr> = HashSet::new();
+        for event in events.iter().rev().take_while(|e| now.duration_since(e.seen_at) <= window) {
+            recent += 1;
+            destinations.extend(event.dest);
+        }
+
+        IcmpActivity {
+            events:       recent,
+            destinations: destinations.len(),
+        }
+    }
+
+    /// Verifică dacă un IP este în cooldown pentru alerte ICMP sweep
+    pub fn is_icmp_in_cooldown(&self, ip: &IpAddr, cooldown_secs: u64) -> bool {
+        self.icmp_cooldown
+            .get(ip)
+            .is_some_and(|last| self.now().duration_since(*last) < Duration::from_secs(cooldown_secs))
+    }
+
+    /// Marchează un IP ca alertat pentru ICMP sweep
+    pub fn mark_icmp_alerted(&self, ip: IpAddr) {
+        self.icmp_cooldown.insert(ip, self.now());
+    }
+
+    /// Verifică dacă perechea (IP, port) este în cooldown pentru alerte acces-după-scan
+    pub fn is_post_scan_in_cooldown(&self, ip: &IpAddr, port: u16, cooldown_secs: u64) -> bool {
+        self.post_scan_cooldown
+            .get(&(*ip, port))
+            .is_some_and(|last| self.now().duration_since(*last) < Duration::from_secs(cooldown_secs))
+    }
+
+    /// Marchează perechea (IP, port) ca alertată pentru acces după scan
+    pub fn mark_post_scan_alerted(&self, ip: IpAddr, port: u16) {
+        self.post_scan_cooldown.insert((ip, port), self.now());
+    }
+
+    /// Ține minte `ip` ca recidivist (apelată la fiecare alertă trimisă)
+    pub fn remember_offender(&self, ip: IpAddr) {
+        self.remember_offender_at(ip, self.now());
+    }
+
+    // -----------------------------------------------------------------------
+    // Ca `remember_offender`, cu momentul alertei dat. Când memoria e plină
+    // și IP-ul e nou, evacuăm recidivistul cu cea mai veche alertă -
+    // O(MAX_REPEAT_OFFENDERS), dar doar la alerte, nu per pachet.
+    // -----------------------------------------------------------------------
+    pub fn remember_offender_at(&self, ip: IpAddr, alerted_at: Instant) {
+        if self.offender_window.is_zero() {
+            return;
+        }
+
+        if self.offenders.len() >= MAX_REPEAT_OFFENDERS && !self.offenders.contains_key(&ip) {
+            let oldest = self
+                .offenders
+                .iter()
+                .min_by_key(|entry| entry.last_alert)
+                .map(|entry| *entry.key());
+            if let Some(oldest) = oldest {
+                self.offenders.remove(&oldest);
+            }
+        }
+
+        let mut record = self.offenders.entry(ip).or_insert(OffenderRecord { last_alert: alerted_at, alerts: 0 });
+        record.last_alert = record.last_alert.max(alerted_at);
+        record.alerts = record.alerts.saturating_add(1);
+    }
+
+    /// `ip` a fost alertat în fereastra recidiviștilor?
+    pub fn repeat_offender(&self, ip: &IpAddr) -> Option<RepeatOffender> {
+        let record = self.offenders.get(ip)?;
+        let since_last_alert = self.now().saturating_duration_since(record.last_alert);
+        (since_last_alert < self.offender_window).then_some(RepeatOffender {
+            alerts: record.alerts,
+            since_last_alert,
+        })
+    }
+
+    // -----------------------------------------------------------------------
+    // Un recidivist care REVINE: ținut minte, dar fără istoric curent (porturi
+    // sau ICMP) - a tăcut cel puțin până la cleanup. Se verifică înainte de
+    // înregistrarea evenimentului: după el, sursa are din nou istoric, deci
+    // doar primul drop de după pauză este "revenirea".
+    // -----------------------------------------------------------------------
+    pub fn returning_offender(&self, ip: &IpAddr) -> Option<RepeatOffender> {
+        if self.scan_map.contains_key(ip) || self.icmp_map.contains_key(ip) {
+            return None;
+        }
+        self.repeat_offender(ip)
+    }
+
+    /// Dimensiunile stării interne, pentru sumarul periodic
+    pub fn stats(&self) -> StateStats {
+        StateStats {
+            tracked_ips:      self.scan_map.len(),
+            icmp_ips:         self.icmp_map.len(),
+            cooldowns:        self.alert_cooldown.len(),
+            active_scans:     self.active_scans.len(),
+            repeat_offenders: self.offenders.len(),
+        }
+    }
+
+    // -----------------------------------------------------------------------
+    // Actualizează profilul țintelor atacatorului `ip` pentru destinația `dest`
+    //
+    // Când profilul e plin și destinația e nouă, evacuăm destinația cea mai
+    // veche (LRU). Căutarea minimului e O(MAX_TARGETS_PER_SOURCE) - ieftin.
+    // -----------------------------------------------------------------------
+    pub fn record_target(&self, ip: IpAddr, dest: IpAddr, port: u16) {
+        let mut profile = self.targets.entry(ip).or_default();
+
+        if profile.len() >= MAX_TARGETS_PER_SOURCE && !profile.contains_key(&dest) {
+            let oldest = profile
+                .iter()
+                .min_by_key(|(_, stats)| stats.last_seen)
+                .map(|(dest, _)| *dest);
+            if let Some(oldest) = oldest {
+                profile.remove(&oldest);
+            }
+        }
+
+        let stats = profile.entry(dest).or_default();
+        stats.hits += 1;
+        stats.last_seen = Some(self.now());
+        if stats.ports.len() < MAX_PORTS_PER_TARGET {
+            stats.ports.insert(port);
+        }
+    }
+
+    // -----------------------------------------------------------------------
+    // Destinațiile atinse de `ip`, ordonate descrescător după porturi unice,
+    // apoi după numărul de evenimente (cele mai "interesante" ținte primele)
+    // -----------------------------------------------------------------------
+    pub fn targets_for(&self, ip: &IpAddr) -> Vec<TargetSummary> {
+        let Some(profile) = self.targets.get(ip) else {
+            return Vec::new();
+        };
+
+        let mut targets: Vec<TargetSummary> = profile
+            .iter()
+            .filter_map(|(dest, stats)| {
+                Some(TargetSummary {
+                    dest:         *dest,
+                    unique_ports: stats.ports.len(),
+                    hits:         stats.hits,
+                    last_seen:    stats.last_seen?,
+                })
+            })
+            .collect();
+
+        targets.sort_by(|a, b| {
+            b.unique_ports
+                .cmp(&a.unique_ports)
+                .then(b.hits.cmp(&a.hits))
+                .then(a.dest.cmp(&b.dest))
+        });
+        targets
+    }
+
+    // -----------------------------------------------------------------------
+    // Înregistrează un drop al regulii `rule` pentru atacatorul `ip`, atât
+    // în profilul sursei (LRU, ca ținta) cât și în totalul intervalului
+    // -----------------------------------------------------------------------
+    pub fn record_rule(&self, ip: IpAddr, rule: &str) {
+        {
+            let mut profile = self.rules.entry(ip).or_default();
+            if profile.len() >= MAX_RULES_PER_SOURCE && !profile.contains_key(rule) {
+                let oldest = profile
+                    .iter()
+                    .min_by_key(|(_, stats)| stats.last_seen)
+                    .map(|(rule, _)| rule.clone());
+                if let Some(oldest) = oldest {
+                    profile.remove(&oldest);
+                }
+            }
+            let stats = profile.entry(rule.to_string()).or_default();
+            stats.hits += 1;
+            stats.last_seen = Some(self.now());
+        }
+
+        if let Some(mut hits) = self.rule_totals.get_mut(rule) {
+            *hits += 1;
+        } else if self.rule_totals.len() < MAX_RULES_TOTAL {
+            *self.rule_totals.entry(rule.to_string()).or_insert(0) += 1;
+        }
+    }
+
+    /// Regulile care au blocat `ip`, descrescător după loviri
+    pub fn rules_for(&self, ip: &IpAddr) -> Vec<RuleSummary> {
+        let Some(profile) = self.rules.get(ip) else {
+            return Vec::new();
+        };
+        let summaries = profile
+            .iter()
+            .map(|(rule, stats)| RuleSummary { rule: rule.clone(), hits: stats.hits })
+            .collect();
+        sorted_rules(summaries)
+    }
+
+    // -----------------------------------------------------------------------
+    // Primele `limit` reguli după drop-uri din intervalul curent, apoi
+    // contoarele se golesc: fiecare sumar periodic acoperă doar intervalul
+    // lui (o politică greșită apare imediat, nu diluată de istoric)
+    // -----------------------------------------------------------------------
+    pub fn take_top_rules(&self, limit: usize) -> Vec<RuleSummary> {
+        let mut summaries = Vec::with_capacity(self.rule_totals.len());
+        self.rule_totals.retain(|rule, hits| {
+            summaries.push(RuleSummary { rule: rule.clone(), hits: *hits });
+            false
+        });
+        let mut summaries = sorted_rules(summaries);
+        summaries.truncate(limit);
+        summaries
     }
 
     // -----------------------------------------------------------------------
@@ -89,35 +986,67 @@ impl SharedState {
     // `window_secs`: numărul de secunde înapoi în care ne uităm
     //
     // Algoritmul:
-    //   1. Filtrăm evenimentele mai vechi decât fereastra
-    //   2. Colectăm porturile unice folosind un set de deduplicare
+    //   1. Parcurgem bucket-urile de la cel mai nou, până la primul ieșit
+    //      complet din fereastră
+    //   2. Reunim porturile lor într-un set de deduplicare (la bucket-ul de
+    //      la marginea ferestrei, doar porturile văzute în fereastră)
     //   3. Returnăm numărul de porturi unice
+    //
+    // Costul depinde de porturile unice per bucket, nu de numărul de
+    // evenimente: un flood pe același port nu face numărarea mai scumpă.
     // -----------------------------------------------------------------------
     pub fn unique_ports_in_window(&self, ip: &IpAddr, window_secs: u64) -> usize {
+        self.unique_ports_in_window_where(ip, window_secs, |_| true)
+    }
+
+    /// Ca `unique_ports_in_window`, numărând doar porturile acceptate de
+    /// `counted` (ex: fără `[detection] ignore_ports`)
+    pub fn unique_ports_in_window_where(&self, ip: &IpAddr, window_secs: u64, counted: impl Fn(u16) -> bool) -> usize {
         let window = Duration::from_secs(window_secs);
-        let now = Instant::now();
+        let now = self.now();
 
-        // `get(ip)` returnează Option<Ref<'_, IpAddr, Vec<ScanEvent>>>
+        // `get(ip)` returnează Option<Ref<'_, IpAddr, PortRing>>
         // Dacă IP-ul nu există, returnăm 0 direct cu `?`... dar nu putem
         // folosi `?` pe Option în funcție care returnează usize.
         // Folosim `if let` sau `.map_or`:
         match self.scan_map.get(ip) {
             None => 0,
-            Some(events) => {
-                // Iterăm evenimentele, filtrăm pe fereastra de timp,
-                // colectăm porturile unice într-un HashSet
-                let unique: std::collections::HashSet<u16> = events
-                    .iter()
-                    .filter(|e| {
-                        // `now.duration_since(e.seen_at)` calculează intervalul
-                        // Dacă seen_at este în fereastra, păstrăm evenimentul
-                        now.duration_since(e.seen_at) <= window
-                    })
-                    .map(|e| e.port)
-                    .collect();
-                unique.len()
-            }
+            Some(ring) => ring.unique_in_window(now, window, counted),
+        }
+    }
+
+    // -----------------------------------------------------------------------
+    // Ca `unique_ports_in_window_where`, dar păstrează și porturile: primele
+    // `sample_size` (sortate) și împărțirea pe protocoale.
+    //
+    // Mai scump decât numărarea (sortare + per-protocol): se apelează doar
+    // pentru o alertă, nu la fiecare evaluare.
+    // -----------------------------------------------------------------------
+    pub fn port_window_where(
+        &self,
+        ip: &IpAddr,
+        window_secs: u64,
+        sample_size: usize,
+        counted: impl Fn(u16) -> bool,
+    ) -> PortWindow {
+        let Some(ring) = self.scan_map.get(ip) else {
+            return PortWindow::default();
+        };
+        let WindowScan { ports, first_seen, last_seen } =
+            ring.protocols_in_window(self.now(), Duration::from_secs(window_secs), counted);
+        drop(ring);
+
+        let mut protocols = ProtocolBreakdown::default();
+        for &bits in ports.values() {
+            protocols.tcp += usize::from(bits & PROTO_TCP != 0);
+            protocols.udp += usize::from(bits & PROTO_UDP != 0);
+            protocols.other += usize::from(bits & PROTO_OTHER != 0);
         }
+        let mut sample: Vec<u16> = ports.keys().copied().collect();
+        sample.sort_unstable();
+        sample.truncate(sample_size);
+
+        PortWindow { unique: ports.len(), sample, protocols, first_seen, last_seen }
     }
 
     // -----------------------------------------------------------------------
@@ -125,18 +1054,285 @@ impl SharedState {
     //
     // Returnează `true` dacă NU trebuie să trimitem alertă (suntem în cooldown)
     // -----------------------------------------------------------------------
-    pub fn is_in_cooldown(&self, ip: &IpAddr, cooldown_secs: u64) -> bool {
+    pub fn is_in_cooldown(&self, ip: &IpAddr) -> bool {
         match self.alert_cooldown.get(ip) {
             None => false, // Nicio alertă anterioară => putem alerta
-            Some(last_alert) => {
-                Instant::now().duration_since(*last_alert) < Duration::from_secs(cooldown_secs)
+            Some(state) => self.now().saturating_duration_since(state.last_alert) < state.cooldown,
+        }
+    }
+
+    // -----------------------------------------------------------------------
+    // Escaladare: IP-ul e în cooldown, dar `severity` depășește STRICT
+    // severitatea ultimei alerte (ex: un slow scan devenit fast scan).
+    // O astfel de alertă trece de cooldown; aceeași severitate, nu.
+    // -----------------------------------------------------------------------
+    pub fn is_escalation(&self, ip: &IpAddr, severity: u8) -> bool {
+        self.alert_cooldown
+            .get(ip)
+            .is_some_and(|state| self.now().saturating_duration_since(state.last_alert) < state.cooldown && severity > state.severity)
+    }
+
+    // -----------------------------------------------------------------------
+    // Histerezisul: tranziția Clean -> Threat și menținerea stării.
+    //
+    // Returnează severitatea maximă de până acum a activității, sau `None`
+    // dacă sursa tocmai a intrat în starea de amenințare. Tier-urile noi și
+    // severitatea mai mare se adaugă activității existente.
+    // -----------------------------------------------------------------------
+    pub fn enter_scan(&self, ip: IpAddr, severity: u8, tiers: &[&str]) -> Option<u8> {
+        let mut previous = None;
+        let mut scan = self
+            .active_scans
+            .entry(ip)
+            .and_modify(|scan| previous = Some(scan.peak_severity))
+            .or_insert_with(|| ActiveScan { since: self.now(), peak_severity: severity, tiers: Vec::new() });
+
+        scan.peak_severity = scan.peak_severity.max(severity);
+        for tier in tiers {
+            if !scan.tiers.iter().any(|t| t == tier) {
+                scan.tiers.push(tier.to_string());
             }
         }
+        previous
+    }
+
+    /// Activitatea curentă a sursei, dacă este într-o stare de amenințare
+    pub fn active_scan(&self, ip: &IpAddr) -> Option<ActiveScan> {
+        self.active_scans.get(ip).map(|scan| scan.clone())
+    }
+
+    /// Sursele aflate acum într-o stare de amenințare
+    pub fn active_scan_ips(&self) -> Vec<IpAddr> {
+        self.active_scans.iter().map(|entry| *entry.key()).collect()
+    }
+
+    /// Tranziția Threat -> Clean: sursa iese din starea de amenințare
+    pub fn leave_scan(&self, ip: &IpAddr) -> Option<ActiveScan> {
+        self.active_scans.remove(ip).map(|(_, scan)| scan)
+    }
+
+    // -----------------------------------------------------------------------
+    // Throttling-ul evaluării: în timpul unui flood, aceeași sursă ar fi
+    // re-evaluată la fiecare pachet - O(evenimente) de fiecare dată.
+    //
+    // Doar evaluările CURATE sunt throttle-uite: după una, următorul pachet
+    // din interval programează o singură evaluare amânată (`Defer`), iar
+    // restul sunt ignorate (`Skip`). Pragul depășit între timp este prins
+    // cel târziu la finalul intervalului, chiar dacă traficul se oprește.
+    // -----------------------------------------------------------------------
+    pub fn throttle_check(&self, ip: &IpAddr, min_interval: Duration) -> ThrottleCheck {
+        if min_interval.is_zero() {
+            return ThrottleCheck::Due;
+        }
+        let Some(mut entry) = self.eval_throttle.get_mut(ip) else {
+            return ThrottleCheck::Due;
+        };
+
+        let elapsed = self.now().saturating_duration_since(entry.clean_at);
+        if elapsed >= min_interval {
+            ThrottleCheck::Due
+        } else if entry.deferred {
+            ThrottleCheck::Skip
+        } else {
+            entry.deferred = true;
+            ThrottleCheck::Defer(min_interval - elapsed)
+        }
+    }
+
+    /// Înregistrează rezultatul unei evaluări; doar cele curate intră în throttling
+    pub fn record_evaluation(&self, ip: IpAddr, threat: bool) {
+        self.evaluations.fetch_add(1, Ordering::Relaxed);
+        if threat {
+            self.eval_throttle.remove(&ip);
+        } else {
+            self.eval_throttle.insert(ip, EvalThrottle { clean_at: self.now(), deferred: false });
+        }
+    }
+
+    /// Numărul total de evaluări per-sursă efectuate
+    pub fn evaluations_performed(&self) -> u64 {
+        self.evaluations.load(Ordering::Relaxed)
+    }
+
+    /// Marchează un IP ca "alertat" - pornește (sau prelungește) cooldown-ul
+    ///
+    /// O re-alertare înainte de `resets_at` escaladează cooldown-ul; altfel
+    /// (prima alertă sau după o perioadă de liniște) pornește de la bază.
+    /// `severity` (cea trimisă efectiv) se reține pentru `is_escalation`.
+    /// Returnează cooldown-ul aplicat.
+    pub fn mark_alerted(&self, ip: IpAddr, policy: &CooldownPolicy, severity: u8) -> Duration {
+        let now = self.now();
+        let mut entry = self.alert_cooldown.entry(ip).or_insert(AlertCooldown {
+            last_alert: now,
+            cooldown:   Duration::ZERO,
+            resets_at:  now,
+            severity,
+        });
+
+        entry.cooldown = if entry.cooldown.is_zero() || now >= entry.resets_at {
+            policy.base
+        } else {
+            policy.escalate(entry.cooldown)
+        };
+        entry.last_alert = now;
+        entry.resets_at = Self::instant_after(now, entry.cooldown.saturating_add(policy.reset_after));
+        entry.severity = severity;
+        entry.cooldown
     }
 
-    /// Marchează un IP ca "alertat" - resetează cooldown-ul
-    pub fn mark_alerted(&self, ip: IpAddr) {
-        self.alert_cooldown.insert(ip, Instant::now());
+    /// Marchează o alertă escaladată: noua severitate devine referința, iar
+    /// cooldown-ul repornește de la bază (o amenințare nouă, nu o repetare)
+    pub fn mark_escalated(&self, ip: IpAddr, policy: &CooldownPolicy, severity: u8) -> Duration {
+        let now = self.now();
+        let cooldown = AlertCooldown {
+            last_alert: now,
+            cooldown:   policy.base,
+            resets_at:  Self::instant_after(now, policy.base.saturating_add(policy.reset_after)),
+            severity,
+        };
+        self.alert_cooldown.insert(ip, cooldown);
+        policy.base
+    }
+
+    // -----------------------------------------------------------------------
+    // IP-urile blocate pe firewall (răspuns activ)
+    //
+    // O blocare (sau un cooldown) a cărei durată nu încape în `Instant`
+    // expiră după BLOCK_FAR_FUTURE (~100 de ani): o durată uriașă din config
+    // (ex: u64::MAX = "niciodată") nu dă panică la prima alertă.
+    // -----------------------------------------------------------------------
+    const BLOCK_FAR_FUTURE: Duration = Duration::from_secs(100 * 365 * 24 * 3600);
+
+    /// `now + after`, saturat la `BLOCK_FAR_FUTURE`
+    fn instant_after(now: Instant, after: Duration) -> Instant {
+        now.checked_add(after).unwrap_or_else(|| now + Self::BLOCK_FAR_FUTURE)
+    }
+
+    /// Returnează `true` dacă IP-ul are o blocare activă (neexpirată) pe firewall
+    pub fn is_blocked(&self, ip: &IpAddr) -> bool {
+        self.blocked_ips
+            .get(ip)
+            .is_some_and(|expires_at| *expires_at > self.now())
+    }
+
+    /// Înregistrează blocarea unui IP pentru `duration_secs` secunde (o durată
+    /// care ar depăși `Instant` devine o blocare practic permanentă)
+    pub fn mark_blocked(&self, ip: IpAddr, duration_secs: u64) {
+        let now = self.now();
+        let expires_at = Self::instant_after(now, Duration::from_secs(duration_secs));
+        self.blocked_ips.insert(ip, expires_at);
+    }
+
+    /// Rezervă atomic blocarea unui IP înainte de apelul către firewall:
+    /// `false` dacă IP-ul este deja blocat sau o altă alertă îl blochează
+    /// chiar acum. Un apel eșuat eliberează rezervarea cu `unmark_blocked`.
+    pub fn try_mark_blocked(&self, ip: IpAddr, duration_secs: u64) -> bool {
+        let now = self.now();
+        let expires_at = Self::instant_after(now, Duration::from_secs(duration_secs));
+        match self.blocked_ips.entry(ip) {
+            Entry::Occupied(entry) if *entry.get() > now => false,
+            entry => {
+                entry.insert(expires_at);
+                true
+            }
+        }
+    }
+
+    /// Anulează o blocare rezervată cu `try_mark_blocked` (apelul a eșuat)
+    pub fn unmark_blocked(&self, ip: &IpAddr) {
+        self.blocked_ips.remove(ip);
+    }
+
+    // -----------------------------------------------------------------------
+    // Înregistrează o pereche (sursă, port) pentru detectorul distribuit,
+    // văzută la momentul `seen_at`
+    //
+    // `window_secs` este folosit doar la evacuare: când bucket-ul e plin,
+    // perechile mai vechi decât fereastra sunt primele eliminate.
+    // -----------------------------------------------------------------------
+    pub fn record_distributed_at(
+        &self,
+        dest: DistributedKey,
+        source: IpAddr,
+        port: u16,
+        window_secs: u64,
+        seen_at: Instant,
+    ) {
+        // Destinație nouă peste limita de bucket-uri -> bucket-ul global
+        let key = if dest.is_some()
+            && self.distributed.len() >= MAX_DISTRIBUTED_BUCKETS
+            && !self.distributed.contains_key(&dest)
+        {
+            None
+        } else {
+            dest
+        };
+
+        let mut bucket = self.distributed.entry(key).or_default();
+
+        if bucket.pairs.len() >= MAX_DISTRIBUTED_PAIRS && !bucket.pairs.contains_key(&(source, port)) {
+            bucket.evict(self.now(), Duration::from_secs(window_secs));
+        }
+
+        bucket.pairs.insert((source, port), seen_at);
+    }
+
+    /// Destinațiile (bucket-urile) urmărite în acest moment
+    pub fn distributed_keys(&self) -> Vec<DistributedKey> {
+        self.distributed.iter().map(|e| *e.key()).collect()
+    }
+
+    // -----------------------------------------------------------------------
+    // Agregă sursele și porturile unice ale unei destinații în fereastra dată
+    //
+    // Doar sursele cu cel mult `max_ports_per_source` porturi fac parte din
+    // cluster: un scanner "zgomotos" e deja prins de detecția per-IP și nu
+    // trebuie să transforme în scan distribuit câțiva vizitatori legitimi.
+    // -----------------------------------------------------------------------
+    pub fn distributed_stats(&self, dest: &DistributedKey, window_secs: u64, max_ports_per_source: usize) -> DistributedStats {
+        let window = Duration::from_secs(window_secs);
+        let now = self.now();
+
+        let Some(bucket) = self.distributed.get(dest) else {
+            return DistributedStats { sources: Vec::new(), ports: 0 };
+        };
+
+        // Pasul 1: porturile fiecărei surse din fereastră
+        let mut per_source: HashMap<IpAddr, HashSet<u16>> = HashMap::new();
+        for ((source, port), seen_at) in bucket.pairs.iter() {
+            if now.duration_since(*seen_at) <= window {
+                per_source.entry(*source).or_default().insert(*port);
+            }
+        }
+        drop(bucket);
+
+        // Pasul 2: reuniunea porturilor surselor discrete
+        let mut ports = HashSet::new();
+        let mut sources: Vec<(IpAddr, usize)> = Vec::new();
+        for (source, source_ports) in per_source {
+            if source_ports.len() <= max_ports_per_source {
+                sources.push((source, source_ports.len()));
+                ports.extend(source_ports);
+            }
+        }
+        sources.sort_by(|a, b| b.1.cmp(&a.1).then(a.0.cmp(&b.0)));
+
+        DistributedStats {
+            sources: sources.into_iter().map(|(ip, _)| ip).collect(),
+            ports:   ports.len(),
+        }
+    }
+
+    /// Verifică dacă o destinație este în cooldown pentru alerte distribuite
+    pub fn is_distributed_in_cooldown(&self, dest: &DistributedKey, cooldown_secs: u64) -> bool {
+        self.distributed_cooldown
+            .get(dest)
+            .is_some_and(|last| self.now().duration_since(*last) < Duration::from_secs(cooldown_secs))
+    }
+
+    /// Marchează o destinație ca alertată pentru scan distribuit
+    pub fn mark_distributed_alerted(&self, dest: DistributedKey) {
+        self.distributed_cooldown.insert(dest, self.now());
     }
 
     // -----------------------------------------------------------------------
@@ -149,16 +1345,14 @@ impl SharedState {
     // -----------------------------------------------------------------------
     pub fn cleanup_old_entries(&self, max_age_secs: u64) -> usize {
         let max_age = Duration::from_secs(max_age_secs);
-        let now = Instant::now();
+        let now = self.now();
         let mut removed = 0;
 
         // `retain` parcurge DashMap și păstrează doar intrările pentru care
         // closure-ul returnează `true`. Aceasta este o operație de cleanup in-place.
-        self.scan_map.retain(|_ip, events| {
-            // Dacă cel mai recent eveniment e mai vechi decât max_age, eliminăm IP-ul
-            let is_fresh = events
-                .iter()
-                .any(|e| now.duration_since(e.seen_at) <= max_age);
+        self.scan_map.retain(|_ip, ring| {
+            // Bucket-urile vechi dispar; un IP fără bucket-uri recente dispare complet
+            let is_fresh = ring.expire(now, max_age);
 
             if !is_fresh {
                 removed += 1;
@@ -166,17 +1360,93 @@ impl SharedState {
             is_fresh
         });
 
+        // Evenimentele ICMP: eliminăm cele vechi, apoi sursele rămase fără evenimente
+        self.icmp_map.retain(|_ip, events| {
+            events.retain(|e| now.duration_since(e.seen_at) <= max_age);
+            !events.is_empty()
+        });
+        self.icmp_cooldown.retain(|_ip, last_alert| {
+            now.duration_since(*last_alert) < max_age
+        });
+
+        // Exporterele care nu mai trimit nimic (sau adrese falsificate)
+        self.parse_diagnostics.prune_idle(now, max_age);
+
+        // O activitate fără istoric nu mai are ce menține; în mod normal
+        // `Engine::clear_pass` a eliberat-o (și a notificat) mult înainte
+        self.active_scans.retain(|ip, _scan| self.scan_map.contains_key(ip));
+
+        // Profilul țintelor dispare odată cu istoricul atacatorului
+        self.targets.retain(|ip, _profile| self.scan_map.contains_key(ip));
+        self.rules.retain(|ip, _profile| self.scan_map.contains_key(ip) || self.icmp_map.contains_key(ip));
+
         // Cleanup și cooldown-uri expirate
-        self.alert_cooldown.retain(|_ip, last_alert| {
+        // Un cooldown escaladat se păstrează până la reset, nu doar `max_age`:
+        // altfel un atacator persistent ar reveni prematur la cooldown-ul de bază
+        self.alert_cooldown.retain(|_ip, cooldown| {
+            now.duration_since(cooldown.last_alert) < max_age || now < cooldown.resets_at
+        });
+
+        // Perechile distribuite vechi; bucket-urile rămase goale dispar complet
+        self.distributed.retain(|_dest, bucket| {
+            bucket.pairs.retain(|_pair, seen_at| now.duration_since(*seen_at) <= max_age);
+            !bucket.pairs.is_empty()
+        });
+        self.distributed_cooldown.retain(|_dest, last_alert| {
             now.duration_since(*last_alert) < max_age
         });
 
+        // Blocările expirate nu mai trebuie ținute minte
+        self.blocked_ips.retain(|_ip, expires_at| *expires_at > now);
+
+        // Scannerii își păstrează porturile sondate pe toată fereastra
+        // accept-după-scan, independent de `max_age`
+        self.scanners.retain(|_ip, scanner| scanner.expires_at > now);
+        self.post_scan_cooldown.retain(|_key, last_alert| now.duration_since(*last_alert) < max_age);
+
+        // Throttling-ul contează doar câteva sute de ms după evaluare
+        self.eval_throttle.retain(|_ip, throttle| now.duration_since(throttle.clean_at) < max_age);
+
+        // Recidiviștii expiră după fereastra lor (ore), nu după `max_age`
+        self.offenders.retain(|_ip, record| now.duration_since(record.last_alert) < self.offender_window);
+
         removed
     }
 }
 
+impl DistributedBucket {
+    // -----------------------------------------------------------------------
+    // Eliberează loc într-un bucket plin.
+    //
+    // Pasul 1: perechile ieșite din fereastră nu mai contează pentru detecție.
+    // Pasul 2: dacă bucket-ul e tot plin (flood în fereastră), eliminăm cele
+    // mai vechi 10% - evacuare în lot, ca costul O(n) să nu se plătească la
+    // fiecare eveniment nou.
+    // -----------------------------------------------------------------------
+    fn evict(&mut self, now: Instant, window: Duration) {
+        self.pairs.retain(|_pair, seen_at| now.duration_since(*seen_at) <= window);
+
+        if self.pairs.len() < MAX_DISTRIBUTED_PAIRS {
+            return;
+        }
+
+        let mut ages: Vec<Instant> = self.pairs.values().copied().collect();
+        let cut = ages.len() / 10;
+        // `select_nth_unstable` găsește pragul în O(n), fără sortare completă
+        let (_, threshold, _) = ages.select_nth_unstable(cut);
+        let threshold = *threshold;
+        self.pairs.retain(|_pair, seen_at| *seen_at > threshold);
+    }
+}
+
 impl Default for SharedState {
     fn default() -> Self {
         Self::new()
     }
 }
+
+/// Descrescător după loviri; la egalitate, alfabetic (ordine stabilă)
+fn sorted_rules(mut rules: Vec<RuleSummary>) -> Vec<RuleSummary> {
+    rules.sort_by(|a, b| b.hits.cmp(&a.hits).then_with(|| a.rule.cmp(&b.rule)));
+    rules
+}
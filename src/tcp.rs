@@ -0,0 +1,124 @@
+// ============================================================
+//  tcp.rs - Listener TCP pentru syslog (opțional)
+// ============================================================
+//
+//  Unele exportere trimit syslog peste TCP (RFC 6587, o linie per
+//  mesaj). Spre deosebire de UDP, fiecare conexiune ține un task și un
+//  descriptor de fișier, deci un sender defect sau rău-intenționat poate
+//  epuiza resursele. Două limite protejează calea TCP:
+//
+//    - `read_timeout_secs`: o conexiune fără date în acest interval
+//      (ex: half-open după căderea rețelei) este închisă
+//    - `max_connections`: peste acest număr de conexiuni simultane,
+//      conexiunile noi sunt respinse imediat
+//
+//  Concepte Rust demonstrate:
+//  - `tokio::time::timeout` : limită de timp pentru orice future
+//  - `tokio::sync::Semaphore` : plafonarea resurselor concurente; permisul
+//    `OwnedSemaphorePermit` se eliberează automat la `drop` (RAII)
+//  - `AsyncBufReadExt::read_until` : citire linie cu linie din stream
+//
+//  Închiderile de conexiuni se loghează prin `tracing::debug!` - vizibile
+//  doar cu `RUST_LOG=rust_ids=debug`, altfel ar inunda consola. Modulul e în
+//  bibliotecă (doar transport, fără alertare): limitele se testează din tests/.
+// ============================================================
+
+use std::net::SocketAddr;
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::io::{AsyncBufReadExt, AsyncReadExt, BufReader};
+use tokio::net::{TcpListener, TcpStream};
+use tokio::sync::Semaphore;
+
+/// Lungimea maximă a unei linii syslog pe TCP - o conexiune care trimite
+/// date fără newline nu trebuie să poată crește buffer-ul la nesfârșit
+const MAX_LINE_BYTES: u64 = 64 * 1024;
+
+/// Limitele aplicate conexiunilor TCP
+#[derive(Debug, Clone, Copy)]
+pub struct TcpLimits {
+    pub read_timeout:    Duration,
+    pub max_connections: usize,
+}
+
+// ---------------------------------------------------------------------------
+// Bucla de accept: rulează până la oprirea procesului.
+//
+// `on_line` este apelat pentru fiecare linie primită, cu adresa peer-ului
+// (exporter-ul) - echivalentul `src_addr` de la UDP.
+// ---------------------------------------------------------------------------
+pub async fn serve<F>(listener: TcpListener, limits: TcpLimits, on_line: F)
+where
+    F: Fn(String, SocketAddr) + Send + Sync + 'static,
+{
+    let slots = Arc::new(Semaphore::new(limits.max_connections));
+    let on_line = Arc::new(on_line);
+
+    loop {
+        let (stream, peer) = match listener.accept().await {
+            Ok(accepted) => accepted,
+            Err(e) => {
+                // Ex: EMFILE (prea multe fișiere deschise) - nu oprim listener-ul
+                tracing::warn!("Eroare la accept TCP: {}", e);
+                tokio::time::sleep(Duration::from_millis(100)).await;
+                continue;
+            }
+        };
+
+        // `try_acquire_owned` nu așteaptă: fără slot liber, respingem imediat
+        let Ok(permit) = Arc::clone(&slots).try_acquire_owned() else {
+            tracing::warn!(
+                "Conexiune TCP respinsă de la {}: limita de {} conexiuni atinsă",
+                peer,
+                limits.max_connections
+            );
+            continue; // `stream` iese din scope -> conexiunea se închide
+        };
+
+        let on_line = Arc::clone(&on_line);
+        tokio::spawn(async move {
+            handle_connection(stream, peer, limits.read_timeout, on_line.as_ref()).await;
+            // Permisul este eliberat aici, odată cu task-ul
+            drop(permit);
+        });
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Citește linii de pe o conexiune până la EOF, eroare sau inactivitate
+// ---------------------------------------------------------------------------
+async fn handle_connection<F>(stream: TcpStream, peer: SocketAddr, read_timeout: Duration, on_line: &F)
+where
+    F: Fn(String, SocketAddr),
+{
+    let mut reader = BufReader::new(stream);
+    let mut buf = Vec::new();
+
+    loop {
+        buf.clear();
+        let mut limited = (&mut reader).take(MAX_LINE_BYTES);
+        let read = limited.read_until(b'\n', &mut buf);
+
+        match tokio::time::timeout(read_timeout, read).await {
+            Err(_) => {
+                tracing::debug!(%peer, "Conexiune TCP închisă: inactivă peste {}s", read_timeout.as_secs());
+                return;
+            }
+            Ok(Err(e)) => {
+                tracing::debug!(%peer, "Conexiune TCP închisă: {}", e);
+                return;
+            }
+            Ok(Ok(0)) => {
+                tracing::debug!(%peer, "Conexiune TCP închisă de peer");
+                return;
+            }
+            Ok(Ok(_)) => {
+                let line = String::from_utf8_lossy(&buf);
+                let line = line.trim_end_matches(['\r', '\n']);
+                if !line.is_empty() {
+                    on_line(line.to_string(), peer);
+                }
+            }
+        }
+    }
+}
@@ -11,6 +11,8 @@ Utilizare:
   python3 tester.py --mode fast_scan --format cef        # CEF fast scan
   python3 tester.py --mode slow_scan --format gaia
   python3 tester.py --mode cef_normal                    # CEF Allow - nu alertă
+  python3 tester.py --mode icmp_sweep                    # Ping sweep (ICMP, fără port)
+  python3 tester.py --mode inject                        # Slow scan via [control] /inject
   python3 tester.py --mode all
   python3 tester.py --mode fast_scan --coalesce --verbose
 
@@ -20,7 +22,10 @@ IMPORTANT: --format TREBUIE să corespundă cu parser din config.toml!
 """
 
 import argparse
+import json
 import socket
+import urllib.error
+import urllib.request
 import time
 import sys
 import random
@@ -33,6 +38,7 @@ from datetime import datetime
 DEFAULT_HOST   = "127.0.0.1"
 DEFAULT_PORT   = 5555
 DEFAULT_SRC_IP = "192.168.11.7"
+DEFAULT_CONTROL_PORT = 5580
 FW_IP          = "192.168.99.1"
 
 COMMON_PORTS = [
@@ -107,6 +113,32 @@ def make_cef_allow_log(src_ip: str, dst_port: int) -> str:
     )
 
 
+def make_gaia_icmp_log(src_ip: str, dst_ip: str, fw_ip: str = FW_IP) -> str:
+    """
+    Log Gaia 'drop' ICMP (echo request) - fără port destinație.
+
+    Exemplu:
+      Sep  3 15:12:20 192.168.99.1 Checkpoint: drop 192.168.11.7 dst: 10.0.0.1; proto: icmp; icmp-type: 8; icmp-code: 0
+    """
+    ts = datetime.now().strftime("%b %d %H:%M:%S")
+    return (
+        f"{ts} {fw_ip} Checkpoint: drop {src_ip} "
+        f"dst: {dst_ip}; proto: icmp; icmp-type: 8; icmp-code: 0"
+    )
+
+
+def make_cef_icmp_log(src_ip: str, dst_ip: str) -> str:
+    """Log CEF 'drop' ICMP - fără dpt=."""
+    ts = datetime.now().strftime("%b %d %H:%M:%S")
+    return (
+        f"{ts} firewall "
+        f"CEF:0|Checkpoint|VPN-1 & FireWall-1|NGX R65|firewall|"
+        f"Connection Blocked|5|"
+        f"src={src_ip} dst={dst_ip} act=drop "
+        f"proto=ICMP rt={int(time.time() * 1000)}"
+    )
+
+
 def make_log(src_ip: str, dst_port: int, fmt: str, drop: bool = True) -> str:
     """
     Factory: alege generatorul corect pe baza formatului.
@@ -233,6 +265,76 @@ def test_slow_scan(host, port, src_ip, log_format, verbose):
     print()
 
 
+def test_icmp_sweep(host, port, src_ip, log_format, verbose):
+    """
+    Simulare ICMP (ping) sweep: echo request către 15 destinații distincte.
+
+    NOTA: Setati icmp_sweep_events=10 in config.toml (implicit 0 = dezactivat).
+    Evenimentele ICMP nu trebuie sa declanseze FAST/SLOW SCAN.
+    """
+    print()
+    print("=" * 65)
+    print(f"  TEST: ICMP SWEEP  |  Format log: {log_format.upper()}")
+    print(f"  IP Sursa: {src_ip}  |  IDS: {host}:{port}")
+    print(f"  Destinatii: 15 distincte (10.0.0.1 - 10.0.0.15)")
+    print()
+    print("  !! Setati icmp_sweep_events=10 in config.toml !!")
+    print("=" * 65)
+
+    make = make_cef_icmp_log if log_format == "cef" else make_gaia_icmp_log
+    sent = 0
+    for i in range(1, 16):
+        if send_udp(host, port, make(src_ip, f"10.0.0.{i}"), verbose):
+            sent += 1
+        time.sleep(0.02)
+
+    print(f"\n  Trimis: {sent}/15 pachete")
+    print(f"  ASTEPTAT: Alerta ICMP SWEEP pentru IP {src_ip} (fara FAST SCAN)")
+    print()
+
+
+def test_inject(host, control_port, src_ip, token, verbose):
+    """
+    Slow scan comprimat in timp prin endpoint-ul de control (POST /inject):
+    40 de porturi datate uniform in ultimele 50 de minute, injectate instant.
+
+    NOTA: Setati [control] enabled = true in config.toml.
+    """
+    print()
+    print("=" * 65)
+    print("  TEST: INJECT (slow scan comprimat in timp)")
+    print(f"  IP Sursa: {src_ip}  |  Control: http://{host}:{control_port}/inject")
+    print()
+    print("  !! Setati [control] enabled = true in config.toml !!")
+    print("=" * 65)
+
+    specs = [{"src_ip": src_ip, "dst_port": 1000, "count": 40, "spread_secs": 3000}]
+    request = urllib.request.Request(
+        f"http://{host}:{control_port}/inject",
+        data=json.dumps(specs).encode(),
+        method="POST",
+        headers={"Content-Type": "application/json"},
+    )
+    if token:
+        request.add_header("Authorization", f"Bearer {token}")
+    if verbose:
+        print(f"  [POST] {json.dumps(specs)}")
+
+    try:
+        with urllib.request.urlopen(request, timeout=10) as response:
+            result = json.loads(response.read())
+    except urllib.error.HTTPError as e:
+        print(f"  [EROARE] HTTP {e.code}: {e.read().decode().strip()}")
+        return
+    except OSError as e:
+        print(f"  [EROARE] Endpoint-ul de control nu raspunde: {e}")
+        return
+
+    print(f"\n  Injectat: {result['events']} evenimente, {result['alerts']} alerte")
+    print(f"  ASTEPTAT: Alerta SLOW SCAN pentru IP {src_ip} (fara FAST SCAN)")
+    print()
+
+
 def test_cef_normal(host, port, src_ip, verbose):
     """
     Trimite log-uri CEF cu actiunea 'Allow' - IDS-ul NU trebuie sa alerteze.
@@ -305,13 +407,19 @@ Exemple rapide:
   # Verifica ca CEF Allow nu declanseaza alerta
   python3 tester.py --mode cef_normal
 
+  # Ping sweep (config.toml: icmp_sweep_events = 10)
+  python3 tester.py --mode icmp_sweep
+
+  # Slow scan instant prin endpoint-ul de control ([control] enabled = true)
+  python3 tester.py --mode inject
+
   # Toate testele (schimbati parserul in config.toml dupa nevoie)
   python3 tester.py --mode all --format cef
         """
     )
 
     parser.add_argument("--mode",
-        choices=["fast_scan", "slow_scan", "cef_normal", "normal", "all"],
+        choices=["fast_scan", "slow_scan", "cef_normal", "normal", "icmp_sweep", "inject", "all"],
         default="fast_scan",
         help="Tipul de test (default: fast_scan)")
     parser.add_argument("--format",
@@ -329,6 +437,10 @@ Exemple rapide:
                         help="Delay intre pachete (fast_scan, default: 0.05s)")
     parser.add_argument("--coalesce",  action="store_true",
                         help="Trimite toate log-urile intr-un singur pachet UDP")
+    parser.add_argument("--control-port", type=int, default=DEFAULT_CONTROL_PORT,
+                        help=f"Port HTTP [control] (inject, default: {DEFAULT_CONTROL_PORT})")
+    parser.add_argument("--token",     default=None,
+                        help="Token [control] (inject, optional)")
     parser.add_argument("--verbose",   action="store_true",
                         help="Afiseaza fiecare pachet trimis")
 
@@ -355,6 +467,12 @@ Exemple rapide:
     elif args.mode == "normal":
         test_normal_traffic(args.host, args.port, args.ip, args.format, args.verbose)
 
+    elif args.mode == "icmp_sweep":
+        test_icmp_sweep(args.host, args.port, args.ip, args.format, args.verbose)
+
+    elif args.mode == "inject":
+        test_inject(args.host, args.control_port, args.ip, args.token, args.verbose)
+
     elif args.mode == "all":
         test_normal_traffic(args.host, args.port, "192.168.1.1",
                             args.format, args.verbose)
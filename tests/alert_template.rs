@@ -0,0 +1,65 @@
+// ============================================================
+//  tests/alert_template.rs - Șabloanele [alert] email_template / siem_template
+// ============================================================
+
+use rust_ids::alert_template::{render, validate, TemplateValues};
+use rust_ids::config::Config;
+use rust_ids::detector::{DetectionResult, TierHit};
+use std::net::IpAddr;
+
+fn hit(tier: &str, ports: usize, window_secs: u64) -> TierHit {
+    TierHit { tier: tier.to_string(), ports, window_secs, severity: 8, sig_id: "IDS001".to_string() }
+}
+
+fn config_with(key: &str, template: &str) -> anyhow::Result<Config> {
+    let toml = include_str!("../config.toml")
+        .replacen("[alert]\n", &format!("[alert]\n{} = {:?}\n", key, template), 1);
+    Config::from_toml_str(&toml)
+}
+
+#[test]
+fn placeholders_are_substituted() {
+    let ip: IpAddr = "10.11.0.1".parse().unwrap();
+    let result = DetectionResult::MultiScan(vec![hit("fast", 23, 10), hit("slow", 41, 3600)]);
+    let mut values = TemplateValues::new(&ip, &result);
+    values.timestamp = "2026-01-01 00:00:00 UTC".to_string();
+
+    let rendered = render(
+        "{{IDS}} {scan_type} {ip} fast={fast_ports} slow={slow_ports} w={window}s @{timestamp} on {hostname}",
+        &values,
+    );
+    assert_eq!(
+        rendered,
+        format!(
+            "{{IDS}} {} 10.11.0.1 fast=23 slow=41 w=10s @2026-01-01 00:00:00 UTC on rust-ids",
+            result.scan_type_label()
+        )
+    );
+}
+
+#[test]
+fn tiers_that_did_not_fire_render_as_zero() {
+    let ip: IpAddr = "10.11.0.2".parse().unwrap();
+    let values = TemplateValues::new(&ip, &DetectionResult::Scan(hit("slow", 31, 3600)));
+    assert_eq!(render("{fast_ports}/{slow_ports}/{window}", &values), "0/31/3600");
+}
+
+#[test]
+fn unknown_or_malformed_templates_are_rejected() {
+    assert!(validate("ok {ip} {{literal}}").is_ok());
+    assert!(validate("{ip").is_err());
+    assert!(validate("ip}").is_err());
+
+    let err = validate("{ip} {source}").unwrap_err().to_string();
+    assert!(err.contains("{source}") && err.contains("scan_type"), "mesaj: {}", err);
+}
+
+#[test]
+fn templates_are_validated_at_load() {
+    let config = config_with("siem_template", "scan {scan_type} from {ip}").unwrap();
+    assert_eq!(config.alert.siem_template.as_deref(), Some("scan {scan_type} from {ip}"));
+    assert_eq!(config.alert.email_template, None);
+
+    let err = config_with("email_template", "Alert {attacker}").unwrap_err();
+    assert!(format!("{:#}", err).contains("[alert] email_template"), "{:#}", err);
+}
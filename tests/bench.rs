@@ -0,0 +1,65 @@
+// ============================================================
+//  tests/bench.rs - Subcomanda `bench`: încărcare sintetică prin Engine
+// ============================================================
+
+mod common;
+
+use common::config;
+use rust_ids::bench::{run, BenchOptions, LatencyHistogram};
+use std::time::Duration;
+
+#[test]
+fn short_run_reports_throughput_latency_and_state() {
+    let options = BenchOptions { sources: 20, events_per_source: 50, duration: Duration::from_millis(300), workers: 4 };
+    let report = run(&config(), &options).expect("bench rulează");
+
+    assert_eq!(report.workers, 4);
+    assert!(report.events >= 20 * 50, "cel puțin o trecere completă: {}", report.events);
+    assert!(report.events_per_sec() > 0.0);
+    assert!(report.elapsed >= options.duration);
+
+    // Porturi aleatoare: fiecare sursă depășește pragul fast (15 porturi / 10s)
+    assert!(report.alerts >= 20, "{} alerte", report.alerts);
+    assert_eq!(report.peak_tracked_ips, 20);
+    assert!(report.latency_p50 <= report.latency_p99 && report.latency_p99 <= report.latency_max);
+    if cfg!(target_os = "linux") {
+        assert!(report.rss_peak_bytes.is_some_and(|peak| peak >= report.rss_start_bytes.unwrap()));
+    }
+}
+
+#[test]
+fn workers_are_capped_at_the_number_of_sources() {
+    let options = BenchOptions { sources: 2, events_per_source: 1, duration: Duration::from_millis(50), workers: 8 };
+    assert_eq!(run(&config(), &options).unwrap().workers, 2);
+}
+
+#[test]
+fn empty_load_is_rejected() {
+    for options in [
+        BenchOptions { sources: 0, ..BenchOptions::default() },
+        BenchOptions { events_per_source: 0, ..BenchOptions::default() },
+        BenchOptions { duration: Duration::ZERO, ..BenchOptions::default() },
+    ] {
+        assert!(run(&config(), &options).is_err(), "{:?}", options);
+    }
+}
+
+#[test]
+fn histogram_percentiles_are_within_bucket_precision() {
+    let mut histogram = LatencyHistogram::default();
+    for micros in 1..=1000 {
+        histogram.record(Duration::from_micros(micros));
+    }
+    assert_eq!(histogram.count(), 1000);
+    assert_eq!(histogram.max(), Duration::from_micros(1000));
+
+    for (p, expected) in [(50.0, 500_000.0), (99.0, 990_000.0)] {
+        let got = histogram.percentile(p).as_nanos() as f64;
+        assert!(got <= expected && got >= expected * 0.968, "p{}: {} ns", p, got);
+    }
+
+    let mut merged = LatencyHistogram::default();
+    merged.record(Duration::from_nanos(10));
+    merged.merge(&histogram);
+    assert_eq!(merged.percentile(0.0), Duration::from_nanos(10));
+}
@@ -0,0 +1,217 @@
+// ============================================================
+//  tests/checkpoint.rs - Responder-ul SAM Check Point (feature "checkpoint")
+// ============================================================
+//
+//  Serverul de management este simulat local: un listener TCP care
+//  răspunde cu JSON și înregistrează comenzile primite.
+
+#![cfg(feature = "checkpoint")]
+
+mod common;
+
+use common::ip;
+use rust_ids::clock::MockClock;
+use rust_ids::config::Config;
+use rust_ids::detector::{DetectionResult, TierHit};
+use rust_ids::response::checkpoint::{BlockOutcome, CheckpointResponder};
+use rust_ids::state::SharedState;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+use tokio::io::{AsyncBufReadExt, AsyncReadExt, AsyncWriteExt, BufReader};
+use tokio::net::TcpListener;
+
+fn responder(management_url: &str, dry_run: bool) -> CheckpointResponder {
+    let text = format!(
+        "{}\n[response.checkpoint]\nmanagement_url = \"{}\"\nusername = \"ids\"\npassword = \"secret\"\n\
+         block_duration_secs = 600\nmin_severity = 8\ndry_run = {}\n",
+        include_str!("../config.toml"),
+        management_url,
+        dry_run
+    );
+    let config = Config::from_toml_str(&text).expect("config cu [response.checkpoint] valid");
+    CheckpointResponder::new(config.response.checkpoint.expect("secțiunea checkpoint")).unwrap()
+}
+
+fn fast_scan(severity: u8) -> DetectionResult {
+    DetectionResult::Scan(TierHit {
+        tier:        "fast".to_string(),
+        ports:       20,
+        window_secs: 10,
+        severity,
+        sig_id:      "IDS001".to_string(),
+    })
+}
+
+// ---------------------------------------------------------------------------
+// Server de management simulat: `add-suspicious-activity-rule` răspunde 401
+// de `expire_first_adds` ori (sesiune expirată), apoi 200
+// ---------------------------------------------------------------------------
+async fn management_server(expire_first_adds: usize) -> (String, Arc<Mutex<Vec<String>>>) {
+    let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let url = format!("http://{}", listener.local_addr().unwrap());
+    let calls = Arc::new(Mutex::new(Vec::new()));
+    let log = Arc::clone(&calls);
+
+    tokio::spawn(async move {
+        let mut logins = 0;
+        let mut expired = 0;
+        loop {
+            let (stream, _) = listener.accept().await.unwrap();
+            let mut reader = BufReader::new(stream);
+            let mut request_line = String::new();
+            reader.read_line(&mut request_line).await.unwrap();
+            let mut content_length = 0;
+            let mut sid = String::new();
+            loop {
+                let mut header = String::new();
+                reader.read_line(&mut header).await.unwrap();
+                let header = header.trim_end();
+                if header.is_empty() {
+                    break;
+                }
+                let (name, value) = header.split_once(':').unwrap();
+                match name.to_ascii_lowercase().as_str() {
+                    "content-length" => content_length = value.trim().parse().unwrap(),
+                    "x-chkp-sid" => sid = value.trim().to_string(),
+                    _ => {}
+                }
+            }
+            let mut body = vec![0; content_length];
+            reader.read_exact(&mut body).await.unwrap();
+
+            let command = request_line.split_whitespace().nth(1).unwrap().trim_start_matches("/web_api/").to_string();
+            let (status, payload) = match command.as_str() {
+                "login" => {
+                    logins += 1;
+                    ("200 OK", format!("{{\"sid\":\"sid-{}\"}}", logins))
+                }
+                "add-suspicious-activity-rule" if expired < expire_first_adds => {
+                    expired += 1;
+                    ("401 Unauthorized", "{}".to_string())
+                }
+                _ => ("200 OK", "{}".to_string()),
+            };
+            log.lock().unwrap().push(if sid.is_empty() { command } else { format!("{} [{}]", command, sid) });
+
+            let response = format!(
+                "HTTP/1.1 {}\r\nContent-Type: application/json\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+                status,
+                payload.len(),
+                payload
+            );
+            reader.get_mut().write_all(response.as_bytes()).await.unwrap();
+        }
+    });
+    (url, calls)
+}
+
+#[tokio::test]
+async fn dry_run_marks_blocked_without_calling_the_api() {
+    let responder = responder("https://mgmt.invalid", true);
+    let state = SharedState::new();
+    let src = ip("10.70.0.1");
+
+    match responder.block(&src, &fast_scan(9), &state).await {
+        BlockOutcome::DryRun { endpoint, rule } => {
+            assert_eq!(endpoint, "https://mgmt.invalid/web_api/add-suspicious-activity-rule");
+            assert_eq!(rule["source"], "10.70.0.1");
+            assert_eq!(rule["expiration"], 600);
+        }
+        other => panic!("dry-run așteptat, nu {:?}", other),
+    }
+    assert!(state.is_blocked(&src));
+    assert!(matches!(responder.block(&src, &fast_scan(9), &state).await, BlockOutcome::Skipped), "deja blocat");
+}
+
+#[tokio::test]
+async fn low_severity_is_not_blocked() {
+    let responder = responder("https://mgmt.invalid", true);
+    let state = SharedState::new();
+    assert!(matches!(responder.block(&ip("10.70.0.2"), &fast_scan(7), &state).await, BlockOutcome::Skipped));
+    assert!(!state.is_blocked(&ip("10.70.0.2")));
+}
+
+#[tokio::test]
+async fn login_add_publish_and_reuse_the_session() {
+    let (url, calls) = management_server(0).await;
+    let responder = responder(&url, false);
+    let state = SharedState::new();
+
+    assert!(matches!(responder.block(&ip("10.70.0.3"), &fast_scan(9), &state).await, BlockOutcome::Blocked));
+    assert!(matches!(responder.block(&ip("10.70.0.4"), &fast_scan(9), &state).await, BlockOutcome::Blocked));
+    assert!(state.is_blocked(&ip("10.70.0.3")) && state.is_blocked(&ip("10.70.0.4")));
+    assert_eq!(
+        *calls.lock().unwrap(),
+        [
+            "login",
+            "add-suspicious-activity-rule [sid-1]",
+            "publish [sid-1]",
+            "add-suspicious-activity-rule [sid-1]",
+            "publish [sid-1]",
+        ]
+    );
+}
+
+#[tokio::test]
+async fn expired_session_is_renewed_once() {
+    let (url, calls) = management_server(1).await;
+    let responder = responder(&url, false);
+    let state = SharedState::new();
+
+    assert!(matches!(responder.block(&ip("10.70.0.5"), &fast_scan(9), &state).await, BlockOutcome::Blocked));
+    assert_eq!(
+        *calls.lock().unwrap(),
+        [
+            "login",
+            "add-suspicious-activity-rule [sid-1]",
+            "login",
+            "add-suspicious-activity-rule [sid-2]",
+            "publish [sid-2]",
+        ]
+    );
+}
+
+#[tokio::test]
+async fn concurrent_alerts_submit_a_single_rule() {
+    let (url, calls) = management_server(0).await;
+    let responder = responder(&url, false);
+    let state = SharedState::new();
+    let src = ip("10.70.0.9");
+    let scan = fast_scan(9);
+
+    let (first, second) = tokio::join!(responder.block(&src, &scan, &state), responder.block(&src, &scan, &state));
+    assert!(matches!(first, BlockOutcome::Blocked), "{:?}", first);
+    assert!(matches!(second, BlockOutcome::Skipped), "IP-ul e deja în curs de blocare: {:?}", second);
+    assert_eq!(*calls.lock().unwrap(), ["login", "add-suspicious-activity-rule [sid-1]", "publish [sid-1]"]);
+}
+
+#[tokio::test]
+async fn api_failure_leaves_the_ip_unblocked() {
+    let (url, _calls) = management_server(2).await;
+    let responder = responder(&url, false);
+    let state = SharedState::new();
+
+    assert!(matches!(responder.block(&ip("10.70.0.6"), &fast_scan(9), &state).await, BlockOutcome::Failed(_)));
+    assert!(!state.is_blocked(&ip("10.70.0.6")), "se reîncearcă la alerta următoare");
+    assert!(state.try_mark_blocked(ip("10.70.0.6"), 600), "rezervarea a fost eliberată");
+}
+
+#[test]
+fn block_expires_exactly_after_its_duration() {
+    let clock = Arc::new(MockClock::new());
+    let state = SharedState::new().with_clock(clock.clone());
+    let src = ip("10.70.0.7");
+
+    state.mark_blocked(src, 600);
+    clock.advance(Duration::from_secs(600) - Duration::from_nanos(1));
+    assert!(state.is_blocked(&src));
+    clock.advance(Duration::from_nanos(1));
+    assert!(!state.is_blocked(&src));
+}
+
+#[test]
+fn huge_block_duration_does_not_overflow() {
+    let state = SharedState::new();
+    state.mark_blocked(ip("10.70.0.8"), u64::MAX);
+    assert!(state.is_blocked(&ip("10.70.0.8")));
+}
@@ -0,0 +1,95 @@
+// ============================================================
+//  tests/clock.rs - Ferestre, cooldown și cleanup cu `MockClock`
+// ============================================================
+
+mod common;
+
+use common::{config, drop_from, ip};
+use rust_ids::clock::{Clock, MockClock};
+use rust_ids::engine::Engine;
+use rust_ids::state::SharedState;
+use std::sync::Arc;
+use std::time::Duration;
+
+const NANO: Duration = Duration::from_nanos(1);
+
+#[test]
+fn mock_clock_moves_only_when_told() {
+    let clock = MockClock::new();
+    let start = clock.now();
+    assert_eq!(clock.now(), start);
+
+    clock.advance(Duration::from_secs(5));
+    assert_eq!(clock.now(), start + Duration::from_secs(5));
+
+    clock.set(start + Duration::from_secs(2));
+    assert_eq!(clock.now(), start + Duration::from_secs(5), "ceasul nu dă înapoi");
+    clock.set(start + Duration::from_secs(60));
+    assert_eq!(clock.now() - start, Duration::from_secs(60));
+}
+
+#[test]
+fn an_event_exactly_at_the_window_edge_is_counted() {
+    let clock = Arc::new(MockClock::new());
+    let state = SharedState::new().with_clock(clock.clone());
+    let src = ip("10.60.0.1");
+    for port in 1..=5 {
+        state.record_event(src, port);
+    }
+
+    clock.advance(Duration::from_secs(10));
+    assert_eq!(state.unique_ports_in_window(&src, 10), 5, "vârsta = fereastra: încă în fereastră");
+    clock.advance(NANO);
+    assert_eq!(state.unique_ports_in_window(&src, 10), 0);
+    assert_eq!(state.unique_ports_in_window(&src, 60), 5);
+}
+
+#[test]
+fn fast_scan_threshold_depends_on_the_window_edge() {
+    for (gap, alerts) in [(Duration::from_secs(10), true), (Duration::from_secs(10) + NANO, false)] {
+        let clock = Arc::new(MockClock::new());
+        let engine = Engine::with_clock(&config(), clock.clone());
+
+        // 15 porturi acum, al 16-lea după `gap`: pragul fast este >15 în 10s
+        for port in 1..=15 {
+            assert!(engine.ingest(drop_from("10.60.0.2", port)).is_none());
+        }
+        clock.advance(gap);
+        let alert = engine.ingest(drop_from("10.60.0.2", 16));
+        assert_eq!(alert.is_some(), alerts, "al 16-lea port după {:?}", gap);
+    }
+}
+
+#[test]
+fn cooldown_expires_exactly_at_its_duration() {
+    let clock = Arc::new(MockClock::new());
+    let engine = Engine::with_clock(&config(), clock.clone());
+    let src = ip("10.60.0.3");
+
+    let alert = (1..=16).filter_map(|port| engine.ingest(drop_from("10.60.0.3", port))).next().expect("fast scan");
+    let cooldown = alert.cooldown.expect("scanările de porturi pornesc cooldown-ul");
+
+    clock.advance(cooldown - NANO);
+    assert!(engine.state().is_in_cooldown(&src));
+    clock.advance(NANO);
+    assert!(!engine.state().is_in_cooldown(&src));
+}
+
+#[test]
+fn cleanup_removes_idle_sources_deterministically() {
+    let clock = Arc::new(MockClock::new());
+    let state = SharedState::new().with_clock(clock.clone());
+    state.record_event(ip("10.60.0.4"), 22);
+    clock.advance(Duration::from_secs(30));
+    state.record_event(ip("10.60.0.5"), 22);
+
+    clock.advance(Duration::from_secs(90));
+    assert_eq!(state.cleanup_old_entries(120), 0, "vârsta 120s = limita: păstrat");
+    clock.advance(NANO);
+    assert_eq!(state.cleanup_old_entries(120), 1);
+    assert_eq!(state.stats().tracked_ips, 1);
+
+    clock.advance(Duration::from_secs(30));
+    assert_eq!(state.cleanup_old_entries(120), 1);
+    assert_eq!(state.stats().tracked_ips, 0);
+}
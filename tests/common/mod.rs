@@ -0,0 +1,84 @@
+// ============================================================
+//  tests/common/mod.rs - Ajutoare comune testelor de integrare
+// ============================================================
+//
+//  Fiecare fișier din tests/ se compilează ca un crate separat; cele care
+//  au nevoie de ajutoarele de aici declară `mod common;`. Directorul (nu
+//  `tests/common.rs`) împiedică Cargo să-l ruleze ca test propriu.
+//
+//  Un fișier de test folosește doar o parte din funcții - de aici
+//  `allow(dead_code)`.
+// ============================================================
+
+#![allow(dead_code)]
+
+use rust_ids::clock::MockClock;
+use rust_ids::config::Config;
+use rust_ids::parser::{create_parser, LogEntry};
+use std::net::IpAddr;
+use std::sync::Arc;
+use std::time::Duration;
+
+/// config.toml din repo (trebuie să fie mereu valid)
+pub fn config() -> Config {
+    Config::from_toml_str(include_str!("../../config.toml")).expect("config.toml din repo trebuie să fie valid")
+}
+
+/// config.toml cu înlocuiri textuale `(din, în)`, fiecare aplicată o dată
+pub fn config_with(replacements: &[(&str, &str)]) -> Config {
+    let text = replacements
+        .iter()
+        .fold(include_str!("../../config.toml").to_string(), |text, (from, to)| {
+            assert!(text.contains(from), "config.toml nu conține {:?}", from);
+            text.replacen(from, to, 1)
+        });
+    Config::from_toml_str(&text).expect("config de test valid")
+}
+
+/// Ceas de test avansat deja cu `history`: `clock.now() - history` e un
+/// moment valid și imediat după boot (`Instant::now() - 2h` poate da panică
+/// pe o mașină pornită de mai puțin de 2 ore)
+pub fn clock_with_history(history: Duration) -> Arc<MockClock> {
+    let clock = Arc::new(MockClock::new());
+    clock.advance(history);
+    clock
+}
+
+pub fn ip(s: &str) -> IpAddr {
+    s.parse().unwrap()
+}
+
+/// Linie Gaia: drop TCP de la `src` pe `port`
+pub fn gaia_drop(src: &str, port: u16) -> String {
+    format!("Sep 3 15:12:20 192.168.99.1 Checkpoint: drop {} proto: tcp; service: {}; s_port: 1352", src, port)
+}
+
+fn parse_gaia(line: &str) -> LogEntry {
+    create_parser("gaia").parse(line).expect("linie Gaia validă")
+}
+
+/// Drop TCP de la `src` pe `port`, fără destinație
+pub fn drop_from(src: &str, port: u16) -> LogEntry {
+    parse_gaia(&gaia_drop(src, port))
+}
+
+/// Drop de la `src` pe `port`, cu protocolul dat ("tcp", "udp"...)
+pub fn drop_with_proto(src: &str, proto: &str, port: u16) -> LogEntry {
+    parse_gaia(&format!(
+        "Sep 3 15:12:20 192.168.99.1 Checkpoint: drop {} proto: {}; service: {}; s_port: 1352",
+        src, proto, port
+    ))
+}
+
+/// Drop TCP de la `src` către `dst:port`
+pub fn drop_to(src: &str, dst: &str, port: u16) -> LogEntry {
+    parse_gaia(&format!(
+        "Sep 3 15:12:20 192.168.99.1 Checkpoint: drop {} dst: {}; proto: tcp; service: {}; s_port: 1352",
+        src, dst, port
+    ))
+}
+
+/// Drop ICMP echo de la `src` către `dst`
+pub fn icmp(src: &str, dst: &str) -> LogEntry {
+    parse_gaia(&format!("Sep 3 15:12:20 192.168.99.1 Checkpoint: drop {} dst: {}; proto: icmp; icmp-type: 8", src, dst))
+}
@@ -0,0 +1,63 @@
+// ============================================================
+//  tests/config_load.rs - config.toml lipsă vs. invalid
+// ============================================================
+
+use rust_ids::config::Config;
+use std::path::PathBuf;
+
+/// Fișier temporar unic per test (șters la început, dacă a rămas de la o rulare anterioară)
+fn temp_path(name: &str) -> PathBuf {
+    let path = std::env::temp_dir().join(format!("rust-ids-{}-{}.toml", name, std::process::id()));
+    let _ = std::fs::remove_file(&path);
+    path
+}
+
+#[test]
+fn missing_file_is_not_an_error_for_load_optional() {
+    let path = temp_path("missing");
+    let path = path.to_str().unwrap();
+
+    assert!(Config::load_optional(path).unwrap().is_none());
+    // `load` (modul --strict-config) rămâne fatal
+    let err = Config::load(path).expect_err("fișier lipsă");
+    assert!(format!("{:#}", err).contains("Nu s-a putut citi"), "{:#}", err);
+}
+
+#[test]
+fn malformed_file_is_still_a_hard_error() {
+    let path = temp_path("malformed");
+    std::fs::write(&path, "[listener\nport = 5555\n").unwrap();
+
+    let err = Config::load_optional(path.to_str().unwrap()).expect_err("TOML invalid");
+    assert!(format!("{:#}", err).contains("Configurație invalidă"), "{:#}", err);
+    let _ = std::fs::remove_file(&path);
+}
+
+#[test]
+fn existing_file_is_loaded() {
+    let path = temp_path("present");
+    let text = include_str!("../config.toml").replacen("port         = 5555", "port         = 6000", 1);
+    std::fs::write(&path, text).unwrap();
+
+    let config = Config::load_optional(path.to_str().unwrap()).unwrap().expect("fișier existent");
+    assert_eq!(config.listener.port, 6000);
+    let _ = std::fs::remove_file(&path);
+}
+
+#[test]
+fn defaults_are_runnable_without_a_file() {
+    let config = Config::default();
+
+    assert_eq!(config.listener_addr(), "0.0.0.0:5555");
+    assert_eq!(config.listener.parser, "gaia");
+    assert_eq!(config.siem_addr(), "127.0.0.1:514");
+    assert!(!config.email.enabled);
+    assert!(!config.detection.tiers.is_empty());
+}
+
+#[test]
+fn zero_siem_aggregate_window_is_rejected() {
+    let text = include_str!("../config.toml").replacen("aggregate_window_secs = 60", "aggregate_window_secs = 0", 1);
+    let err = Config::from_toml_str(&text).expect_err("fereastră 0 invalidă");
+    assert!(err.to_string().contains("aggregate_window_secs"), "{}", err);
+}
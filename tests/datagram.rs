@@ -0,0 +1,144 @@
+// ============================================================
+//  tests/datagram.rs - UTF-8 invalid, datagrame trunchiate, linii uriașe
+// ============================================================
+
+use rust_ids::datagram::{decode_into, records_within, BufferPool, DecodeReport};
+use rust_ids::diagnostics::{DatagramIssue, DatagramStats};
+use rust_ids::parser::{create_parser, LogEntry, LogParser};
+use std::net::IpAddr;
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+const CEF_LINE: &str = "CEF:0|Checkpoint|FW|R81|firewall|Drop|5|act=Drop src=192.168.1.10 dst=10.0.0.1 dpt=4433";
+
+fn decode(bytes: &[u8], capacity: usize) -> (String, DecodeReport) {
+    let mut out = String::new();
+    let report = decode_into(bytes, capacity, "\n", &mut out);
+    (out, report)
+}
+
+#[test]
+fn complete_datagram_is_unchanged() {
+    let (text, report) = decode(CEF_LINE.as_bytes(), 65535);
+    assert_eq!(text, CEF_LINE);
+    assert_eq!(report, DecodeReport::default());
+}
+
+#[test]
+fn truncated_cef_line_is_rejected_not_misparsed() {
+    // Buffer-ul s-a umplut exact la "dpt=44": fără protecție, portul ar fi 44
+    let cut = CEF_LINE.find("dpt=44").unwrap() + "dpt=44".len();
+    let bytes = &CEF_LINE.as_bytes()[..cut];
+    assert_eq!(create_parser("cef").parse(std::str::from_utf8(bytes).unwrap()).and_then(|e| e.dest_port()), Some(44));
+
+    let (text, report) = decode(bytes, bytes.len());
+    assert!(report.truncated);
+    assert!(text.is_empty());
+    assert!(create_parser("cef").parse(&text).is_none());
+}
+
+#[test]
+fn truncation_keeps_the_complete_lines() {
+    let packet = format!("{}\n{}", CEF_LINE, CEF_LINE);
+    let capacity = packet.len() - 10;
+    let (text, report) = decode(&packet.as_bytes()[..capacity], capacity);
+
+    assert!(report.truncated);
+    let lines: Vec<&str> = text.lines().collect();
+    assert_eq!(lines, [CEF_LINE]);
+    assert_eq!(create_parser("cef").parse(lines[0]).and_then(|e| e.dest_port()), Some(4433));
+}
+
+#[test]
+fn invalid_utf8_is_replaced_and_reported() {
+    let mut bytes = CEF_LINE.as_bytes().to_vec();
+    bytes.splice(0..0, [0xff, 0xfe, b' ']);
+    let (text, report) = decode(&bytes, 65535);
+
+    assert!(report.lossy);
+    assert!(!report.truncated);
+    assert!(text.starts_with('\u{FFFD}'));
+    // Restul liniei rămâne parsabil
+    assert_eq!(create_parser("cef").parse(&text).and_then(|e| e.dest_port()), Some(4433));
+}
+
+#[test]
+fn issues_are_counted_always_but_warned_once_per_source() {
+    let stats = DatagramStats::default();
+    let a: IpAddr = "192.0.2.1".parse().unwrap();
+    let b: IpAddr = "192.0.2.2".parse().unwrap();
+    let both = DecodeReport { lossy: true, truncated: true };
+
+    assert_eq!(stats.record(a, both), [DatagramIssue::Lossy, DatagramIssue::Truncated]);
+    assert!(stats.record(a, both).is_empty());
+    assert_eq!(stats.record(b, DecodeReport { lossy: false, truncated: true }), [DatagramIssue::Truncated]);
+    assert!(stats.record(b, DecodeReport::default()).is_empty());
+    assert_eq!(stats.snapshot(), (2, 3));
+}
+
+#[test]
+fn buffer_pool_reuses_and_caps_buffers() {
+    let pool = BufferPool::new(1);
+    let mut first = pool.take();
+    first.push_str(CEF_LINE);
+    let capacity = first.capacity();
+    pool.give(first);
+    pool.give(String::from("peste limită"));
+    assert_eq!(pool.available(), 1);
+
+    let reused = pool.take();
+    assert!(reused.is_empty());
+    assert_eq!(reused.capacity(), capacity);
+    assert_eq!(pool.available(), 0);
+}
+
+/// Parser-ul Gaia, cu numărarea apelurilor (a văzut sau nu linia?)
+struct CountingParser {
+    inner: Box<dyn LogParser>,
+    calls: AtomicUsize,
+}
+
+impl LogParser for CountingParser {
+    fn parse(&self, line: &str) -> Option<LogEntry> {
+        self.calls.fetch_add(1, Ordering::Relaxed);
+        self.inner.parse(line)
+    }
+
+    fn name(&self) -> &str {
+        "counting"
+    }
+}
+
+#[test]
+fn oversized_line_is_skipped_before_the_parser() {
+    let parser = CountingParser { inner: create_parser("gaia"), calls: AtomicUsize::new(0) };
+    let normal = "Sep 3 15:12:20 192.168.99.1 Checkpoint: drop 192.168.11.7 proto: tcp; service: 22; s_port: 1352";
+    // ~60KB care se potrivesc parțial cu `service:` - costisitor pentru regex
+    let giant = format!("Sep 3 15:12:20 192.168.99.1 Checkpoint: drop 192.168.11.7 {}", "service: x ".repeat(5_500));
+    let packet = format!("{}\n{}\n{}", normal, giant, normal);
+
+    let mut skipped = Vec::new();
+    let entries: Vec<LogEntry> = records_within(&packet, "\n", 4096, |line| skipped.push(line.len()))
+        .filter_map(|line| parser.parse(line))
+        .collect();
+
+    assert_eq!(entries.len(), 2, "liniile normale din același pachet se parsează");
+    assert_eq!(parser.calls.load(Ordering::Relaxed), 2, "linia uriașă nu ajunge la parser");
+    assert_eq!(skipped, [giant.trim_end().len()]);
+
+    let stats = DatagramStats::default();
+    let source: IpAddr = "192.168.99.1".parse().unwrap();
+    assert!(stats.record_oversized(source), "primul avertisment pentru sursă");
+    assert!(!stats.record_oversized(source), "apoi doar numărat");
+    assert_eq!(stats.oversized(), 2);
+}
+
+#[test]
+fn zero_max_line_bytes_disables_the_limit() {
+    let giant = "x".repeat(10_000);
+    let mut skipped = 0;
+    assert_eq!(records_within(&giant, "\n", 0, |_| skipped += 1).count(), 1);
+    assert_eq!(skipped, 0);
+
+    // La limită exactă linia trece
+    assert_eq!(records_within(&giant, "\n", 10_000, |_| panic!("în limită")).count(), 1);
+}
@@ -0,0 +1,71 @@
+// ============================================================
+//  tests/diagnostics.rs - Diagnosticul per exporter: plafon, cleanup, top-N
+// ============================================================
+
+use rust_ids::clock::MockClock;
+use rust_ids::diagnostics::{ParseDiagnostics, MAX_PARSE_SOURCES};
+use rust_ids::state::SharedState;
+use std::net::{IpAddr, Ipv4Addr};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+fn source(n: u32) -> IpAddr {
+    IpAddr::V4(Ipv4Addr::from(0x0a00_0000 + n))
+}
+
+#[test]
+fn spoofed_sources_are_capped() {
+    let diagnostics = ParseDiagnostics::new();
+    for n in 0..(MAX_PARSE_SOURCES as u32 + 500) {
+        diagnostics.record(source(n), false, "gunoi");
+    }
+    assert_eq!(diagnostics.source_count(), MAX_PARSE_SOURCES);
+    assert_eq!(diagnostics.untracked_lines(), 500);
+
+    // O sursă deja urmărită se numără în continuare
+    diagnostics.record(source(0), true, "");
+    let first = diagnostics.snapshot().into_iter().find(|row| row.0 == source(0)).unwrap();
+    assert_eq!((first.1, first.2), (2, 1));
+}
+
+#[test]
+fn summary_shows_the_sources_with_most_failures() {
+    let diagnostics = ParseDiagnostics::new();
+    for n in 0..20u32 {
+        for i in 0..=n {
+            diagnostics.record(source(n), i % 2 == 1, "gunoi");
+        }
+    }
+
+    let top = diagnostics.top_sources(3);
+    assert_eq!(top.len(), 3);
+    // Sursa n are n + 1 linii, dintre care n / 2 + 1 eșuate
+    assert_eq!(top[0], (source(19), 20, 10));
+    assert_eq!(top[1], (source(18), 19, 10));
+    assert_eq!(top[2], (source(17), 18, 9));
+}
+
+#[test]
+fn idle_sources_are_pruned() {
+    let diagnostics = ParseDiagnostics::new();
+    diagnostics.record(source(1), false, "gunoi");
+    let max_age = Duration::from_secs(60);
+
+    assert_eq!(diagnostics.prune_idle(Instant::now() + Duration::from_secs(30), max_age), 0);
+    assert_eq!(diagnostics.prune_idle(Instant::now() + Duration::from_secs(120), max_age), 1);
+    assert_eq!(diagnostics.source_count(), 0);
+}
+
+#[test]
+fn state_cleanup_prunes_idle_exporters() {
+    let clock = Arc::new(MockClock::new());
+    let state = SharedState::new().with_clock(clock.clone());
+    state.parse_diagnostics().record(source(7), true, "");
+
+    state.cleanup_old_entries(60);
+    assert_eq!(state.parse_diagnostics().source_count(), 1, "încă activă");
+
+    clock.advance(Duration::from_secs(61));
+    state.cleanup_old_entries(60);
+    assert_eq!(state.parse_diagnostics().source_count(), 0);
+}
@@ -0,0 +1,81 @@
+// ============================================================
+//  tests/email_routing.rs - SIEM mereu, email după severitate și oră
+// ============================================================
+
+mod common;
+
+use chrono::NaiveTime;
+use common::drop_from;
+use rust_ids::clock::MockClock;
+use rust_ids::config::Config;
+use rust_ids::engine::{Alert, Engine};
+use rust_ids::routing::{Channel, QuietHours, RoutingPolicy};
+use std::sync::Arc;
+use std::time::Duration;
+
+fn config(routing: &str) -> Config {
+    common::config_with(&[("min_severity             = 0\n# quiet_hours            = \"22:00-07:00\"", routing)])
+}
+
+fn at(hour: u32, minute: u32) -> NaiveTime {
+    NaiveTime::from_hms_opt(hour, minute, 0).unwrap()
+}
+
+/// Un port pe minut (ceasul avansează între evenimente): niciodată peste
+/// pragul fast, dar peste cel slow
+fn slow_scan(engine: &Engine, clock: &MockClock, src: &str) -> Alert {
+    (0..40u16)
+        .filter_map(|i| {
+            clock.advance(Duration::from_secs(60));
+            engine.ingest(drop_from(src, 1000 + i))
+        })
+        .next()
+        .expect("alertă slow scan")
+}
+
+#[test]
+fn low_severity_slow_scan_goes_to_siem_but_not_email() {
+    let config = config("min_severity             = 7");
+    let clock = Arc::new(MockClock::new());
+    let engine = Engine::with_clock(&config, clock.clone());
+    let alert = slow_scan(&engine, &clock, "10.40.0.1");
+    let severity = config.alert.alert_severity(&alert.result);
+    assert!(severity < 7, "slow scan sub pragul email ({})", severity);
+
+    let routing = RoutingPolicy::new(&config.email);
+    assert!(routing.allows(Channel::Siem, severity, at(14, 0)));
+    assert!(routing.allows(Channel::Uds, severity, at(14, 0)));
+    assert!(!routing.allows(Channel::Email, severity, at(14, 0)));
+
+    // Un fast scan (severitate mai mare) trece și pe email
+    let fast = (1..=16).filter_map(|port| engine.ingest(drop_from("10.40.0.2", port))).next().expect("fast scan");
+    assert!(routing.allows(Channel::Email, config.alert.alert_severity(&fast.result), at(14, 0)));
+}
+
+#[test]
+fn quiet_hours_raise_the_email_threshold_across_midnight() {
+    let config = config("min_severity             = 0\nquiet_hours              = \"22:00-07:00\"");
+    assert_eq!(config.email.quiet_hours.map(|h| h.to_string()).as_deref(), Some("22:00-07:00"));
+    let routing = RoutingPolicy::new(&config.email);
+
+    assert!(routing.allows(Channel::Email, 6, at(21, 59)));
+    assert!(!routing.allows(Channel::Email, 6, at(22, 0)));
+    assert!(!routing.allows(Channel::Email, 8, at(3, 30)));
+    assert!(routing.allows(Channel::Email, 9, at(3, 30)), "criticele trezesc oricum");
+    assert!(routing.allows(Channel::Email, 6, at(7, 0)), "capătul de final e exclus");
+    assert!(routing.allows(Channel::Siem, 0, at(3, 30)));
+}
+
+#[test]
+fn invalid_quiet_hours_are_rejected_at_load() {
+    for bad in ["\"22-07\"", "\"22:00-22:00\"", "\"25:00-07:00\""] {
+        let text = include_str!("../config.toml")
+            .replacen("# quiet_hours            = \"22:00-07:00\"", &format!("quiet_hours = {}", bad), 1);
+        let err = Config::from_toml_str(&text).expect_err("quiet_hours invalid");
+        assert!(format!("{:#}", err).contains("quiet_hours"), "{:#}", err);
+    }
+    assert!("09:00-17:30".to_string().try_into().is_ok_and(|h: QuietHours| h.contains(at(17, 29))));
+
+    let text = include_str!("../config.toml").replacen("min_severity             = 0", "min_severity             = 11", 1);
+    assert!(Config::from_toml_str(&text).is_err());
+}
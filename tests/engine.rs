@@ -0,0 +1,119 @@
+// ============================================================
+//  tests/engine.rs - `Engine::ingest`: decizia de alertă fără side-effects
+// ============================================================
+
+mod common;
+
+use common::{clock_with_history, config, drop_to, icmp};
+use rust_ids::detector::DetectionResult;
+use rust_ids::engine::Engine;
+use std::net::IpAddr;
+use std::time::Duration;
+
+#[test]
+fn fast_scan_is_decided_once_then_held_by_cooldown() {
+    let config = config();
+    let engine = Engine::new(&config);
+    let threshold = config.detection.fast_scan_ports as u16;
+
+    for port in 1..=threshold {
+        assert!(engine.ingest(drop_to("10.6.0.1", "10.0.0.5", port)).is_none());
+    }
+    let alert = engine.ingest(drop_to("10.6.0.1", "10.0.0.5", threshold + 1)).expect("alertă peste prag");
+
+    assert_eq!(alert.ip, "10.6.0.1".parse::<IpAddr>().unwrap());
+    assert_eq!(alert.result.primary().map(|hit| hit.tier.as_str()), Some("fast"));
+    assert_eq!(alert.severity, 8);
+    assert_eq!(alert.cooldown, Some(Duration::from_secs(config.detection.alert_cooldown_secs)));
+    assert!(!alert.escalated);
+    assert!(alert.blocks());
+    assert_eq!(alert.targets.len(), 1);
+    assert_eq!(alert.targets[0].unique_ports, usize::from(threshold) + 1);
+
+    // Aceeași sursă, încă în cooldown: nicio decizie nouă
+    assert!(engine.ingest(drop_to("10.6.0.1", "10.0.0.5", threshold + 2)).is_none());
+}
+
+#[test]
+fn more_severe_detection_escalates_through_cooldown() {
+    let config = config();
+    let clock = clock_with_history(Duration::from_secs(300));
+    let engine = Engine::with_clock(&config, clock.clone());
+
+    // Slow scan datat acum 5 minute: doar tier-ul slow (severitate 6)
+    let earlier = engine.state().now() - Duration::from_secs(300);
+    let slow = (1..=31)
+        .filter_map(|port| engine.ingest_at(drop_to("10.6.0.2", "10.0.0.5", port), earlier))
+        .next()
+        .expect("alertă slow scan");
+    assert_eq!(slow.severity, 6);
+
+    // Apoi un fast scan: multi-tier, mai sever, trece de cooldown
+    let fast = (100..=120)
+        .filter_map(|port| engine.ingest(drop_to("10.6.0.2", "10.0.0.5", port)))
+        .next()
+        .expect("alertă escaladată");
+    assert!(matches!(fast.result, DetectionResult::MultiScan(_)));
+    assert!(fast.escalated);
+    assert!(fast.severity > slow.severity);
+}
+
+#[test]
+fn icmp_sweep_is_decided_without_active_response() {
+    let mut config = config();
+    config.detection.icmp_sweep_events = 3;
+    config.detection.icmp_sweep_destinations = 3;
+    let engine = Engine::new(&config);
+
+    assert!(engine.ingest(icmp("10.6.0.3", "10.0.0.1")).is_none());
+    assert!(engine.ingest(icmp("10.6.0.3", "10.0.0.2")).is_none());
+    let alert = engine.ingest(icmp("10.6.0.3", "10.0.0.3")).expect("ICMP sweep");
+
+    assert!(matches!(alert.result, DetectionResult::IcmpSweep { events: 3, destinations: 3, .. }));
+    assert!(!alert.blocks());
+    assert_eq!(alert.cooldown, None);
+    assert!(engine.ingest(icmp("10.6.0.3", "10.0.0.4")).is_none(), "cooldown ICMP");
+}
+
+#[test]
+fn distributed_pass_returns_one_alert_per_destination() {
+    let mut config = config();
+    config.detection.distributed_scan_ports = 10;
+    config.detection.distributed_scan_min_sources = 3;
+    let engine = Engine::new(&config);
+
+    let mut port = 1;
+    for source in 1..=4 {
+        for _ in 0..3 {
+            assert!(engine.ingest(drop_to(&format!("10.6.1.{}", source), "10.0.0.9", port)).is_none());
+            port += 1;
+        }
+    }
+
+    let alerts = engine.distributed_pass();
+    assert_eq!(alerts.len(), 1);
+    assert!(matches!(alerts[0].result, DetectionResult::DistributedScan { sources: 4, total_unique_ports: 12, .. }));
+    assert_eq!(alerts[0].dest, Some("10.0.0.9".parse().unwrap()));
+    assert!(!alerts[0].blocks());
+    assert!(engine.distributed_pass().is_empty(), "cooldown per destinație");
+}
+
+#[test]
+fn throttled_ingest_defers_and_the_deferred_evaluation_alerts() {
+    let config = config();
+    let engine = Engine::new(&config);
+    let ip: IpAddr = "10.6.0.4".parse().unwrap();
+
+    // Prima evaluare e curată; următorul pachet din interval programează o
+    // singură evaluare amânată, restul sunt sărite
+    assert_eq!(engine.ingest_throttled(drop_to("10.6.0.4", "10.0.0.5", 1)).deferred, None);
+    assert!(engine.ingest_throttled(drop_to("10.6.0.4", "10.0.0.5", 2)).deferred.is_some());
+    for port in 3..=20 {
+        let ingested = engine.ingest_throttled(drop_to("10.6.0.4", "10.0.0.5", port));
+        assert_eq!((ingested.alert, ingested.deferred), (None, None));
+    }
+
+    // Pragul depășit între timp este prins de evaluarea amânată
+    let alert = engine.evaluate_deferred(&ip).expect("fast scan prins la evaluarea amânată");
+    assert_eq!(alert.result.primary().map(|hit| hit.ports), Some(20));
+}
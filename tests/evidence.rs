@@ -0,0 +1,112 @@
+// ============================================================
+//  tests/evidence.rs - Dovezile unei alerte de scanare de porturi
+// ============================================================
+
+mod common;
+
+use common::{clock_with_history, config, drop_with_proto};
+use rust_ids::alert_fields::detection_fields;
+use rust_ids::config::Config;
+use rust_ids::detector::EVIDENCE_SAMPLE_SIZE;
+use rust_ids::engine::{Alert, Engine};
+use std::time::{Duration, Instant};
+
+/// Prima alertă pentru porturile date (în ordinea dată), cu protocolul ales per port
+fn first_alert(engine: &Engine, src: &str, ports: &[u16], proto: impl Fn(u16) -> &'static str) -> Alert {
+    ports
+        .iter()
+        .filter_map(|&port| engine.ingest(drop_with_proto(src, proto(port), port)))
+        .next()
+        .expect("alertă fast scan")
+}
+
+#[test]
+fn port_scan_alert_carries_sorted_capped_sample_and_threshold() {
+    let config = config();
+    let engine = Engine::new(&config);
+    let threshold = config.detection.fast_scan_ports;
+
+    // Porturile sosesc în ordine descrescătoare: eșantionul rămâne sortat
+    let ports: Vec<u16> = (1..=60).rev().map(|p| p * 10).collect();
+    let alert = first_alert(&engine, "10.20.0.1", &ports, |_| "tcp");
+    let evidence = alert.evidence.expect("dovezi pentru scanare de porturi");
+
+    assert_eq!(evidence.tier, "fast");
+    assert_eq!(evidence.threshold, threshold);
+    assert_eq!(evidence.unique_ports, threshold + 1);
+    assert_eq!(evidence.window_secs, config.detection.fast_scan_window_secs);
+    assert!(evidence.sample_ports.len() <= EVIDENCE_SAMPLE_SIZE);
+    assert!(evidence.sample_ports.windows(2).all(|w| w[0] < w[1]), "{:?}", evidence.sample_ports);
+    assert_eq!(evidence.sample_ports.first(), Some(&ports[threshold]));
+    assert_eq!(evidence.protocols.tcp, threshold + 1);
+    assert_eq!(evidence.protocols.udp, 0);
+}
+
+#[test]
+fn sample_is_capped_and_compact_form_counts_the_rest() {
+    let text = include_str!("../config.toml").replacen("fast_scan_ports       = 15", "fast_scan_ports       = 30", 1);
+    let config = Config::from_toml_str(&text).expect("config de test valid");
+    let engine = Engine::new(&config);
+
+    let ports: Vec<u16> = (1..=40).collect();
+    let alert = first_alert(&engine, "10.20.0.2", &ports, |port| if port % 3 == 0 { "udp" } else { "tcp" });
+    let evidence = alert.evidence.expect("dovezi");
+
+    assert_eq!(evidence.unique_ports, 31);
+    assert_eq!(evidence.sample_ports, (1..=EVIDENCE_SAMPLE_SIZE as u16).collect::<Vec<_>>());
+    assert_eq!((evidence.protocols.tcp, evidence.protocols.udp, evidence.protocols.other), (21, 10, 0));
+
+    let compact = evidence.compact();
+    assert!(compact.starts_with("fast: 31 > 30 ports in 10s; sample: 1,2,3,"), "{}", compact);
+    assert!(compact.contains(",20,...(+11); tcp: 21, udp: 10, other: 0"), "{}", compact);
+}
+
+#[test]
+fn evidence_goes_into_cef_reason_and_threshold() {
+    let config = config();
+    let engine = Engine::new(&config);
+    let ports: Vec<u16> = (1..=20).collect();
+    let alert = first_alert(&engine, "10.20.0.3", &ports, |_| "tcp");
+    let evidence = alert.evidence.as_ref().expect("dovezi");
+
+    let mut fields = detection_fields(&alert.ip, &alert.result, &[], &[], alert.severity).expect("câmpuri");
+    fields.push_evidence(Some(evidence));
+    let cef = fields.to_cef();
+    assert!(cef.contains(" cn3Label=Threshold cn3=15 cfp1Label=PortsPerSec cfp1="), "{}", cef);
+    assert!(cef.ends_with(&format!(" reason={}", evidence.compact())), "{}", cef);
+    assert!(fields.to_leef('\t').contains("\tthreshold=15\t"), "{}", fields.to_leef('\t'));
+
+    // Fără dovezi (alte detecții): niciun câmp în plus
+    let mut plain = detection_fields(&alert.ip, &alert.result, &[], &[], alert.severity).expect("câmpuri");
+    plain.push_evidence(None);
+    assert!(!plain.to_cef().contains("reason="));
+}
+
+#[test]
+fn velocity_separates_a_burst_from_a_slow_probe() {
+    let config = config();
+
+    // 16 porturi la 250 ms distanță: 3.75s între primul și ultimul
+    let engine = Engine::with_clock(&config, clock_with_history(Duration::from_secs(5)));
+    let start = engine.state().now() - Duration::from_secs(5);
+    let alert = (0..16u16)
+        .filter_map(|i| {
+            let seen_at = start + Duration::from_millis(250 * u64::from(i));
+            engine.ingest_at(drop_with_proto("10.20.0.4", "tcp", 100 + i), seen_at)
+        })
+        .next()
+        .expect("alertă fast scan");
+    let evidence = alert.evidence.expect("dovezi");
+    assert!((evidence.span_secs - 3.75).abs() < 0.01, "{}", evidence.span_secs);
+    assert!((evidence.ports_per_sec - 16.0 / 3.75).abs() < 0.01, "{}", evidence.ports_per_sec);
+    assert!(evidence.compact().ends_with("; rate: 4.27 ports/s over 3.8s"), "{}", evidence.compact());
+
+    // Aceeași rafală, sosită în același moment: intervalul minim de 1s
+    let engine = Engine::new(&config);
+    let now = Instant::now();
+    let alert = (0..16u16)
+        .filter_map(|i| engine.ingest_at(drop_with_proto("10.20.0.5", "tcp", 100 + i), now))
+        .next()
+        .expect("alertă fast scan");
+    assert_eq!(alert.evidence.expect("dovezi").ports_per_sec, 16.0);
+}
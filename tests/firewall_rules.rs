@@ -0,0 +1,90 @@
+// ============================================================
+//  tests/firewall_rules.rs - Regula firewall-ului: parsare și agregare
+// ============================================================
+
+use rust_ids::parser::{create_parser, LogEntry};
+use rust_ids::state::{RuleSummary, SharedState, MAX_RULES_PER_SOURCE};
+use std::net::IpAddr;
+
+fn parse(format: &str, line: &str) -> LogEntry {
+    create_parser(format).parse(line).unwrap_or_else(|| panic!("linie validă: {}", line))
+}
+
+fn summary(rule: &str, hits: u64) -> RuleSummary {
+    RuleSummary { rule: rule.to_string(), hits }
+}
+
+#[test]
+fn gaia_rule_number_and_name() {
+    let number = "Sep 3 15:12:20 192.168.99.1 Checkpoint: drop 192.168.11.7 proto: tcp; service: 22; rule: 42; s_port: 1352";
+    let named = "Sep 3 15:12:20 192.168.99.1 Checkpoint: drop 192.168.11.7 proto: tcp; service: 22; rule: 42; \
+                 rule_name: Block Inbound SSH; s_port: 1352";
+
+    assert_eq!(parse("gaia", number).rule.as_deref(), Some("42"));
+    // Numele regulii are prioritate față de numărul ei
+    assert_eq!(parse("gaia", named).rule.as_deref(), Some("Block Inbound SSH"));
+}
+
+#[test]
+fn cef_rule_from_labelled_custom_string() {
+    let line = "Nov 20 15:30:00 fw CEF:0|Checkpoint|VPN-1 & FireWall-1|R81|firewall|Drop|5|\
+                src=192.168.1.10 dst=10.0.0.1 dpt=80 cs2Label=Rule Name cs2=Block Inbound act=Drop";
+    assert_eq!(parse("cef", line).rule.as_deref(), Some("Block Inbound"));
+
+    let first = "CEF:0|Checkpoint|FW|R81|firewall|Drop|5|cs1Label=Rule cs1=Block_Inbound src=192.168.1.10 dpt=22 act=Drop";
+    assert_eq!(parse("cef", first).rule.as_deref(), Some("Block_Inbound"));
+}
+
+#[test]
+fn leef_rule_attribute() {
+    let line = "LEEF:2.0|PaloAlto|PAN-OS|9.1|DENY|src=192.168.1.10\tdstPort=22\taction=deny\truleName=deny-all";
+    assert_eq!(parse("leef", line).rule.as_deref(), Some("deny-all"));
+}
+
+#[test]
+fn logs_without_rule_still_parse() {
+    let gaia = "Sep 3 15:12:20 192.168.99.1 Checkpoint: drop 192.168.11.7 proto: tcp; service: 22; s_port: 1352";
+    let cef = "CEF:0|Checkpoint|FW|R81|firewall|Drop|5|src=192.168.1.10 dpt=22 cs1Label=Zone cs1=External act=Drop";
+    let leef = "LEEF:1.0|PaloAlto|PAN-OS|9.1|TRAFFIC|src=192.168.1.10\tdstPort=22\taction=deny";
+
+    assert_eq!(parse("gaia", gaia).rule, None);
+    assert_eq!(parse("cef", cef).rule, None);
+    assert_eq!(parse("leef", leef).rule, None);
+}
+
+#[test]
+fn per_source_rules_are_sorted_by_hits() {
+    let state = SharedState::new();
+    let src: IpAddr = "10.4.0.1".parse().unwrap();
+    for _ in 0..3 {
+        state.record_rule(src, "Block_Inbound");
+    }
+    state.record_rule(src, "42");
+
+    assert_eq!(state.rules_for(&src), vec![summary("Block_Inbound", 3), summary("42", 1)]);
+    assert!(state.rules_for(&"10.4.0.2".parse().unwrap()).is_empty());
+}
+
+#[test]
+fn per_source_rules_are_bounded() {
+    let state = SharedState::new();
+    let src: IpAddr = "10.4.0.3".parse().unwrap();
+    for rule in 0..MAX_RULES_PER_SOURCE + 10 {
+        state.record_rule(src, &format!("rule-{}", rule));
+    }
+    assert_eq!(state.rules_for(&src).len(), MAX_RULES_PER_SOURCE);
+}
+
+#[test]
+fn top_rules_cover_all_sources_and_reset_each_summary() {
+    let state = SharedState::new();
+    for (src, rule, hits) in [("10.4.0.4", "flood", 5), ("10.4.0.5", "flood", 4), ("10.4.0.5", "42", 2)] {
+        for _ in 0..hits {
+            state.record_rule(src.parse().unwrap(), rule);
+        }
+    }
+
+    assert_eq!(state.take_top_rules(1), vec![summary("flood", 9)]);
+    // Contoarele globale pornesc de la zero după fiecare sumar
+    assert!(state.take_top_rules(5).is_empty());
+}
@@ -0,0 +1,132 @@
+// ============================================================
+//  tests/http_ingest.rs - `POST /ingest`: validarea cererii și limitele serverului
+// ============================================================
+
+use rust_ids::config::Config;
+use rust_ids::http::{self, Request, Response};
+use rust_ids::ingest::parse_ingest;
+use std::net::SocketAddr;
+use std::time::Duration;
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use tokio::net::{TcpListener, TcpStream};
+
+const DROP: &str = "Sep 3 15:12:20 192.168.99.1 Checkpoint: drop 192.168.11.7 proto: tcp; service: 22; s_port: 1352";
+
+fn request(method: &str, path: &str, headers: &[(&str, &str)], body: &str) -> Request {
+    Request {
+        method:  method.to_string(),
+        path:    path.to_string(),
+        headers: headers.iter().map(|(n, v)| (n.to_string(), v.to_string())).collect(),
+        body:    body.as_bytes().to_vec(),
+        peer:    "127.0.0.1:40000".parse().unwrap(),
+    }
+}
+
+fn status(result: Result<Vec<String>, Response>) -> u16 {
+    result.expect_err("cererea trebuie respinsă").status
+}
+
+#[test]
+fn text_body_is_split_into_lines() {
+    let body = format!("{}\n\n{}\r\n", DROP, DROP);
+    let lines = parse_ingest(&request("POST", "/ingest", &[], &body), None).unwrap();
+    assert_eq!(lines, [DROP, "", DROP]);
+}
+
+#[test]
+fn json_array_is_accepted_by_content_type_or_shape() {
+    let body = serde_json::to_string(&[DROP, DROP]).unwrap();
+    let typed = request("POST", "/ingest", &[("content-type", "application/json; charset=utf-8")], &body);
+    assert_eq!(parse_ingest(&typed, None).unwrap().len(), 2);
+
+    // Fără Content-Type: corpul care începe cu `[` e tratat ca JSON
+    let untyped = request("POST", "/ingest", &[], &format!("  {}", body));
+    assert_eq!(parse_ingest(&untyped, None).unwrap(), [DROP, DROP]);
+
+    let not_lines = request("POST", "/ingest", &[("content-type", "application/json")], r#"{"line": 1}"#);
+    assert_eq!(status(parse_ingest(&not_lines, None)), 400);
+}
+
+#[test]
+fn missing_or_wrong_token_is_401() {
+    let token = Some("s3cret");
+    assert_eq!(status(parse_ingest(&request("POST", "/ingest", &[], DROP), token)), 401);
+    let wrong = request("POST", "/ingest", &[("authorization", "Bearer altul")], DROP);
+    assert_eq!(status(parse_ingest(&wrong, token)), 401);
+
+    let good = request("POST", "/ingest", &[("authorization", "Bearer s3cret")], DROP);
+    assert_eq!(parse_ingest(&good, token).unwrap(), [DROP]);
+}
+
+#[test]
+fn wrong_method_is_405_and_wrong_path_is_404() {
+    assert_eq!(status(parse_ingest(&request("GET", "/ingest", &[], ""), None)), 405);
+    assert_eq!(status(parse_ingest(&request("POST", "/inject", &[], DROP), None)), 404);
+}
+
+#[test]
+fn http_ingest_connection_limit_must_be_positive() {
+    assert_eq!(Config::default().http_ingest.max_connections, 64);
+    let text = include_str!("../config.toml").replacen("max_connections = 64", "max_connections = 0", 1);
+    let err = Config::from_toml_str(&text).expect_err("plafon 0 invalid");
+    assert!(format!("{:#}", err).contains("[http_ingest] max_connections"), "{:#}", err);
+}
+
+// ---------------------------------------------------------------------------
+// Serverul: plafonul de conexiuni și corpul citit doar cât sosește
+// ---------------------------------------------------------------------------
+
+/// Pornește `http::serve` cu un handler care răspunde cu lungimea corpului
+async fn start(max_connections: usize) -> SocketAddr {
+    let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let addr = listener.local_addr().unwrap();
+    tokio::spawn(http::serve(listener, max_connections, 1024, |request: Request| async move {
+        Response::text(200, &request.body.len().to_string())
+    }));
+    addr
+}
+
+async fn read_all(stream: &mut TcpStream, within: Duration) -> String {
+    let mut buf = Vec::new();
+    let _ = tokio::time::timeout(within, stream.read_to_end(&mut buf)).await;
+    String::from_utf8_lossy(&buf).into_owned()
+}
+
+#[tokio::test]
+async fn connections_over_the_limit_are_closed_without_response() {
+    let addr = start(1).await;
+
+    // Prima conexiune ține singurul slot (antete incomplete)
+    let mut first = TcpStream::connect(addr).await.unwrap();
+    first.write_all(b"POST /ingest HTTP/1.1\r\n").await.unwrap();
+    tokio::time::sleep(Duration::from_millis(100)).await;
+
+    let mut second = TcpStream::connect(addr).await.unwrap();
+    assert_eq!(read_all(&mut second, Duration::from_secs(2)).await, "", "închisă fără răspuns");
+
+    // Prima cerere se termină normal și eliberează slotul
+    first.write_all(b"Content-Length: 3\r\n\r\nabc").await.unwrap();
+    assert!(read_all(&mut first, Duration::from_secs(2)).await.starts_with("HTTP/1.1 200 OK"));
+    tokio::time::sleep(Duration::from_millis(100)).await;
+
+    let mut third = TcpStream::connect(addr).await.unwrap();
+    third.write_all(b"POST /ingest HTTP/1.1\r\nContent-Length: 2\r\n\r\nok").await.unwrap();
+    let response = read_all(&mut third, Duration::from_secs(2)).await;
+    assert!(response.starts_with("HTTP/1.1 200 OK") && response.ends_with("\r\n\r\n2\n"), "{:?}", response);
+}
+
+#[tokio::test]
+async fn body_limit_and_truncated_body() {
+    let addr = start(4).await;
+
+    // Peste `max_body_bytes`: 413 fără a citi (sau aloca) corpul
+    let mut large = TcpStream::connect(addr).await.unwrap();
+    large.write_all(b"POST /ingest HTTP/1.1\r\nContent-Length: 4096\r\n\r\n").await.unwrap();
+    assert!(read_all(&mut large, Duration::from_secs(2)).await.starts_with("HTTP/1.1 413"));
+
+    // Corp anunțat mai lung decât cel trimis: conexiunea se închide fără 200
+    let mut short = TcpStream::connect(addr).await.unwrap();
+    short.write_all(b"POST /ingest HTTP/1.1\r\nContent-Length: 1000\r\n\r\nabc").await.unwrap();
+    short.shutdown().await.unwrap();
+    assert_eq!(read_all(&mut short, Duration::from_secs(2)).await, "");
+}
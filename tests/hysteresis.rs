@@ -0,0 +1,110 @@
+// ============================================================
+//  tests/hysteresis.rs - Histerezisul detecției (`clear_ratio`)
+// ============================================================
+//
+//  Testele folosesc o fereastră fast de 2s și un `MockClock`: porturile
+//  ies din fereastră când testul avansează ceasul, fără așteptare reală.
+
+mod common;
+
+use common::drop_from;
+use rust_ids::clock::MockClock;
+use rust_ids::config::Config;
+use rust_ids::engine::{Alert, Engine};
+use std::net::IpAddr;
+use std::sync::Arc;
+use std::time::Duration;
+
+/// Tier fast: >20 porturi în 2s (eliberare la <=10), fără cooldown - fără
+/// histerezis, fiecare evaluare peste prag ar fi o alertă nouă
+fn config(slow_scan_ports: usize) -> Config {
+    let config = common::config_with(&[
+        ("fast_scan_ports       = 15", "fast_scan_ports       = 20"),
+        ("fast_scan_window_secs = 10", "fast_scan_window_secs = 2"),
+        ("slow_scan_ports       = 30", &format!("slow_scan_ports       = {}", slow_scan_ports)),
+        ("alert_cooldown_secs = 60", "alert_cooldown_secs = 0"),
+        ("evaluation_min_interval_ms = 100", "evaluation_min_interval_ms = 0"),
+    ]);
+    assert_eq!(config.detection.clear_ratio, 0.5);
+    config
+}
+
+/// Motorul cu ceasul de test
+fn mock_engine(config: &Config) -> (Engine, Arc<MockClock>) {
+    let clock = Arc::new(MockClock::new());
+    (Engine::with_clock(config, clock.clone()), clock)
+}
+
+/// Alertele decise pentru porturile date, toate văzute la momentul curent al ceasului
+fn scan(engine: &Engine, src: &str, ports: impl IntoIterator<Item = u16>) -> Vec<Alert> {
+    ports
+        .into_iter()
+        .filter_map(|port| engine.ingest(drop_from(src, port)))
+        .collect()
+}
+
+#[test]
+fn hovering_around_the_threshold_alerts_once_and_clears_once() {
+    let (engine, clock) = mock_engine(&config(1000));
+    let ip: IpAddr = "10.9.0.1".parse().unwrap();
+    let mut alerts = Vec::new();
+    let mut cleared = Vec::new();
+
+    // Clean -> Threat: 21 porturi; peste 1.5s încă 12
+    alerts.extend(scan(&engine, "10.9.0.1", 1..=21));
+    assert_eq!(alerts.len(), 1, "alertă la depășirea pragului");
+    clock.advance(Duration::from_millis(1500));
+    alerts.extend(scan(&engine, "10.9.0.1", 101..=112));
+    cleared.extend(engine.clear_pass());
+
+    // Primele 21 au ieșit din fereastră: 12 porturi, sub prag dar peste 10
+    clock.advance(Duration::from_millis(700));
+    cleared.extend(engine.clear_pass());
+    assert!(engine.state().active_scan(&ip).is_some(), "sursa rămâne activă în banda de histerezis");
+
+    // Înapoi peste prag (21 porturi): aceeași activitate, fără alertă nouă
+    alerts.extend(scan(&engine, "10.9.0.1", 201..=209));
+    cleared.extend(engine.clear_pass());
+
+    // Activitatea încetează: toate porturile ies din fereastră
+    clock.advance(Duration::from_millis(2100));
+    cleared.extend(engine.clear_pass());
+    cleared.extend(engine.clear_pass());
+
+    assert_eq!(alerts.len(), 1, "o singură alertă pentru o activitate continuă");
+    assert_eq!(cleared.len(), 1, "o singură notificare de încetare");
+    assert_eq!(cleared[0].ip, ip);
+    assert_eq!(cleared[0].peak_severity, alerts[0].severity);
+    assert_eq!(cleared[0].active_for, Duration::from_millis(4300));
+    assert!(engine.state().active_scan(&ip).is_none());
+
+    // După revenirea la Clean, o scanare nouă este o activitate nouă
+    let again = scan(&engine, "10.9.0.1", 301..=321);
+    assert_eq!(again.len(), 1);
+}
+
+#[test]
+fn only_triggered_tiers_use_the_lower_clear_threshold() {
+    // Tier-ul slow (>30 porturi în 60 min, eliberare la <=15) nu a declanșat:
+    // cele 21 de porturi din fereastra lui nu țin sursa activă
+    let (engine, clock) = mock_engine(&config(30));
+    assert_eq!(scan(&engine, "10.9.0.2", 1..=21).len(), 1);
+
+    let ip: IpAddr = "10.9.0.2".parse().unwrap();
+    let scan_state = engine.state().active_scan(&ip).expect("sursă activă");
+    assert_eq!(scan_state.tiers, ["fast"]);
+
+    clock.advance(Duration::from_millis(2200));
+    let cleared = engine.clear_pass();
+    assert_eq!(cleared.len(), 1);
+    assert_eq!(cleared[0].ip, ip);
+}
+
+#[test]
+fn clear_ratio_must_be_in_unit_interval() {
+    for bad in ["0.0", "1.5", "-0.5"] {
+        let text = include_str!("../config.toml").replacen("clear_ratio  = 0.5", &format!("clear_ratio  = {}", bad), 1);
+        let err = Config::from_toml_str(&text).expect_err("clear_ratio invalid");
+        assert!(err.to_string().contains("clear_ratio"), "{}", err);
+    }
+}
@@ -0,0 +1,138 @@
+// ============================================================
+//  tests/icmp.rs - Drop-uri ICMP: parsare (Gaia / CEF) și detecția ICMP Sweep
+// ============================================================
+
+mod common;
+
+use common::ip;
+use rust_ids::clock::MockClock;
+use rust_ids::config::Config;
+use rust_ids::detector::{evaluate_icmp, DetectionResult};
+use rust_ids::parser::{create_parser, EventKind};
+use rust_ids::state::{SharedState, MAX_ICMP_EVENTS_PER_SOURCE};
+use std::net::IpAddr;
+use std::sync::Arc;
+use std::time::Duration;
+
+/// Config cu ICMP sweep activ: 5 drop-uri către 3 destinații în 60s
+fn sweep_config() -> Config {
+    let mut config = Config::default();
+    config.detection.icmp_sweep_events = 5;
+    config.detection.icmp_sweep_destinations = 3;
+    config.detection.icmp_sweep_window_secs = 60;
+    config
+}
+
+#[test]
+fn gaia_icmp_drop_has_type_and_destination() {
+    let line = "Sep 3 15:12:20 192.168.99.1 Checkpoint: drop 192.168.11.7 dst: 10.0.0.1; proto: icmp; icmp-type: 8; icmp-code: 0";
+    let entry = create_parser("gaia").parse(line).expect("ICMP Gaia valid");
+    assert_eq!(entry.source_ip, ip("192.168.11.7"));
+    assert_eq!(entry.dest_ip, Some(ip("10.0.0.1")));
+    assert_eq!(entry.kind, EventKind::Icmp { icmp_type: Some(8) });
+    assert_eq!(entry.protocol.as_deref(), Some("icmp"));
+    assert_eq!(entry.dest_port(), None);
+
+    // Fără `icmp-type`: tot ICMP, tip necunoscut
+    let line = "Sep 3 15:12:20 192.168.99.1 Checkpoint: drop 192.168.11.7 proto: ICMP";
+    let entry = create_parser("gaia").parse(line).expect("ICMP Gaia fără tip");
+    assert_eq!(entry.kind, EventKind::Icmp { icmp_type: None });
+    assert_eq!(entry.dest_ip, None);
+}
+
+#[test]
+fn cef_icmp_drop_needs_no_port() {
+    let line = "CEF:0|Checkpoint|FW|R81|firewall|Drop|5|act=Drop src=192.168.1.10 dst=10.0.0.9 proto=ICMP";
+    let entry = create_parser("cef").parse(line).expect("ICMP CEF valid");
+    assert_eq!(entry.source_ip, ip("192.168.1.10"));
+    assert_eq!(entry.dest_ip, Some(ip("10.0.0.9")));
+    assert_eq!(entry.kind, EventKind::Icmp { icmp_type: None });
+    assert_eq!(entry.protocol.as_deref(), Some("icmp"));
+
+    // TCP fără `dpt` rămâne nerecunoscut
+    let tcp = "CEF:0|Checkpoint|FW|R81|firewall|Drop|5|act=Drop src=192.168.1.10 dst=10.0.0.9 proto=TCP";
+    assert!(create_parser("cef").parse(tcp).is_none());
+}
+
+#[test]
+fn sweep_fires_on_events_and_distinct_destinations() {
+    let config = sweep_config();
+    let state = SharedState::new();
+    let src = ip("10.7.0.1");
+
+    // 5 drop-uri, dar doar 2 destinații: sub pragul de destinații
+    for i in 0..5 {
+        state.record_icmp(src, Some(ip(&format!("10.0.0.{}", i % 2 + 1))));
+    }
+    assert_eq!(evaluate_icmp(&src, &state, &config.detection), DetectionResult::Clean);
+
+    state.record_icmp(src, Some(ip("10.0.0.3")));
+    assert_eq!(
+        evaluate_icmp(&src, &state, &config.detection),
+        DetectionResult::IcmpSweep { events: 6, destinations: 3, window_secs: 60 }
+    );
+
+    // Detector dezactivat (icmp_sweep_events = 0): niciodată
+    let mut disabled = config.clone();
+    disabled.detection.icmp_sweep_events = 0;
+    assert_eq!(evaluate_icmp(&src, &state, &disabled.detection), DetectionResult::Clean);
+}
+
+#[test]
+fn sweep_window_slides_with_the_clock() {
+    let config = sweep_config();
+    let clock = Arc::new(MockClock::new());
+    let state = SharedState::new().with_clock(clock.clone());
+    let src = ip("10.7.0.2");
+
+    for i in 1..=3 {
+        state.record_icmp(src, Some(ip(&format!("10.0.0.{}", i))));
+    }
+    clock.advance(Duration::from_secs(61));
+    for i in 4..=5 {
+        state.record_icmp(src, Some(ip(&format!("10.0.0.{}", i))));
+    }
+    // Primele 3 au ieșit din fereastră: 2 evenimente, 2 destinații
+    assert_eq!(evaluate_icmp(&src, &state, &config.detection), DetectionResult::Clean);
+
+    for i in 6..=8 {
+        state.record_icmp(src, Some(ip(&format!("10.0.0.{}", i))));
+    }
+    assert!(matches!(
+        evaluate_icmp(&src, &state, &config.detection),
+        DetectionResult::IcmpSweep { events: 5, destinations: 5, .. }
+    ));
+
+    // Cleanup-ul elimină sursa odată ce tot istoricul e vechi
+    clock.advance(Duration::from_secs(120));
+    state.cleanup_old_entries(60);
+    assert_eq!(state.stats().icmp_ips, 0);
+}
+
+#[test]
+fn icmp_flood_history_is_capped_per_source() {
+    let mut config = sweep_config();
+    config.detection.icmp_sweep_events = MAX_ICMP_EVENTS_PER_SOURCE;
+    let state = SharedState::new();
+    let src = ip("10.7.0.3");
+
+    for i in 0..5 * MAX_ICMP_EVENTS_PER_SOURCE as u32 {
+        state.record_icmp(src, Some(IpAddr::from([10, 1, (i >> 8) as u8, i as u8])));
+    }
+
+    // Doar cele mai recente evenimente rămân - pragul maxim e încă atins
+    match evaluate_icmp(&src, &state, &config.detection) {
+        DetectionResult::IcmpSweep { events, destinations, .. } => {
+            assert_eq!(events, MAX_ICMP_EVENTS_PER_SOURCE);
+            assert_eq!(destinations, MAX_ICMP_EVENTS_PER_SOURCE);
+        }
+        other => panic!("se aștepta IcmpSweep, nu {:?}", other),
+    }
+}
+
+#[test]
+fn unreachable_icmp_thresholds_are_rejected() {
+    let text = include_str!("../config.toml").replacen("icmp_sweep_events       = 0", "icmp_sweep_events       = 5000", 1);
+    let err = Config::from_toml_str(&text).expect_err("prag peste istoricul reținut");
+    assert!(err.to_string().contains("icmp_sweep_events"), "{}", err);
+}
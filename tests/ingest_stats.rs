@@ -0,0 +1,65 @@
+// ============================================================
+//  tests/ingest_stats.rs - Contoarele de ingestie per listener și parser
+// ============================================================
+
+mod common;
+
+use common::gaia_drop;
+use rust_ids::datagram::records;
+use rust_ids::diagnostics::{IngestHandle, ParseDiagnostics};
+use rust_ids::parser::{create_parser, LogParser};
+
+/// Ce face `process_packet` pentru o datagramă: o numără, apoi fiecare linie
+fn ingest(handle: &IngestHandle, parser: &dyn LogParser, packet: &str) {
+    handle.record_datagram();
+    for line in records(packet, "\n") {
+        handle.record_line(&parser.parse_outcome(line));
+    }
+}
+
+#[test]
+fn parsed_and_rejected_lines_are_counted_exactly() {
+    let diagnostics = ParseDiagnostics::new();
+    let parser = create_parser("gaia");
+    let udp = diagnostics.ingest.handle("udp", parser.name());
+
+    let packet = format!(
+        "{}\nnu este un log\n{}\n\u{1b}[31mgunoi\n{}\n",
+        gaia_drop("10.10.0.1", 22),
+        gaia_drop("10.10.0.1", 23),
+        gaia_drop("10.10.0.2", 80)
+    );
+    ingest(&udp, parser.as_ref(), &packet);
+    ingest(&udp, parser.as_ref(), "încă o linie invalidă");
+
+    let listeners = diagnostics.ingest.listeners();
+    assert_eq!(listeners.len(), 1);
+    let stats = &listeners[0];
+    assert_eq!(stats.name, "udp");
+    assert_eq!((stats.datagrams, stats.lines, stats.parsed, stats.rejected), (2, 6, 3, 3));
+    assert!(stats.last_event.is_some());
+}
+
+#[test]
+fn listeners_are_counted_separately_and_parsers_together() {
+    let diagnostics = ParseDiagnostics::new();
+    let parser = create_parser("gaia");
+    let udp = diagnostics.ingest.handle("udp", parser.name());
+    let tcp = diagnostics.ingest.handle("tcp", parser.name());
+
+    ingest(&udp, parser.as_ref(), &gaia_drop("10.10.0.3", 22));
+    ingest(&tcp, parser.as_ref(), "gunoi");
+    ingest(&tcp, parser.as_ref(), "gunoi");
+
+    let listeners = diagnostics.ingest.listeners();
+    let summary: Vec<(&str, u64, u64, u64)> =
+        listeners.iter().map(|s| (s.name.as_str(), s.datagrams, s.parsed, s.rejected)).collect();
+    assert_eq!(summary, [("tcp", 2, 0, 2), ("udp", 1, 1, 0)]);
+
+    // Un listener care nu a produs niciun eveniment nu are "ultimul eveniment"
+    assert!(listeners[0].last_event.is_none());
+
+    let parsers = diagnostics.ingest.parsers();
+    assert_eq!(parsers.len(), 1);
+    assert_eq!((parsers[0].datagrams, parsers[0].lines, parsers[0].parsed, parsers[0].rejected), (3, 3, 1, 2));
+}
@@ -0,0 +1,74 @@
+// ============================================================
+//  tests/init_config.rs - `rust-ids init-config`: config.toml implicit
+// ============================================================
+
+use rust_ids::config::Config;
+use rust_ids::config_template::{render_default, write_default};
+use std::path::PathBuf;
+
+const REPO_CONFIG: &str = include_str!("../config.toml");
+
+fn as_toml(config: Config) -> toml::Value {
+    toml::Value::try_from(config).expect("configurația se serializează")
+}
+
+/// Fișier temporar unic per test (șters la început, dacă a rămas de la o rulare anterioară)
+fn temp_path(name: &str) -> PathBuf {
+    let path = std::env::temp_dir().join(format!("rust-ids-{}-{}.toml", name, std::process::id()));
+    let _ = std::fs::remove_file(&path);
+    path
+}
+
+#[test]
+fn generated_config_loads_back_to_the_defaults() {
+    let text = render_default().unwrap();
+    let loaded = Config::from_toml_str(&text).expect("config.toml generat trebuie să fie valid");
+
+    assert_eq!(as_toml(loaded), as_toml(Config::default()));
+    assert!(!Config::default().detection.tiers.is_empty(), "tier-urile fast/slow sunt derivate și în Default");
+}
+
+#[test]
+fn generated_config_matches_the_repo_config_below_the_header() {
+    // `config.toml` din repo și `Config::default()` descriu aceleași valori:
+    // o valoare implicită schimbată doar într-o parte rupe acest test
+    let text = render_default().unwrap();
+    let body = |text: &str| text.lines().skip_while(|line| line.starts_with('#')).collect::<Vec<_>>().join("\n");
+
+    assert!(text.starts_with("# ===="));
+    assert!(text.contains("init-config"));
+    assert_eq!(body(&text), body(REPO_CONFIG));
+}
+
+#[test]
+fn every_section_and_field_is_commented() {
+    let text = render_default().unwrap();
+    for section in ["[listener]", "[detection]", "[siem]", "[email]"] {
+        assert!(text.contains(section), "lipsește {}", section);
+    }
+    assert!(text.contains("# Tipul de parser activ"));
+    assert!(text.contains("fast_scan_ports       = 15   # X: număr porturi unice"));
+    assert!(text.contains("# recv_buffer_bytes = 8388608"), "exemplele comentate sunt păstrate");
+}
+
+#[test]
+fn existing_file_is_kept_unless_forced() {
+    let path = temp_path("init-config");
+    std::fs::write(&path, "# configurația operatorului\n").unwrap();
+
+    let error = write_default(&path, false).expect_err("fișierul existent nu se suprascrie");
+    assert!(format!("{:#}", error).contains("--force"));
+    assert_eq!(std::fs::read_to_string(&path).unwrap(), "# configurația operatorului\n");
+
+    write_default(&path, true).expect("--force suprascrie");
+    assert_eq!(std::fs::read_to_string(&path).unwrap(), render_default().unwrap());
+    std::fs::remove_file(&path).unwrap();
+}
+
+#[test]
+fn missing_file_is_created() {
+    let path = temp_path("init-config-new");
+    write_default(&path, false).unwrap();
+    assert!(Config::load(path.to_str().unwrap()).is_ok());
+    std::fs::remove_file(&path).unwrap();
+}
@@ -0,0 +1,99 @@
+// ============================================================
+//  tests/json_logging.rs - Evenimentele tracing ca linii JSON
+// ============================================================
+
+use rust_ids::logging::{JsonLayer, EVENTS_TARGET};
+use serde_json::Value;
+use std::io::Write;
+use std::sync::{Arc, Mutex};
+use tracing_subscriber::layer::SubscriberExt;
+
+/// Destinația output-ului: un buffer partajat, inspectat după eveniment
+#[derive(Clone, Default)]
+struct Captured(Arc<Mutex<Vec<u8>>>);
+
+impl Write for Captured {
+    fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+        self.0.lock().unwrap().write(buf)
+    }
+
+    fn flush(&mut self) -> std::io::Result<()> {
+        Ok(())
+    }
+}
+
+impl Captured {
+    fn lines(&self) -> Vec<Value> {
+        let bytes = self.0.lock().unwrap().clone();
+        String::from_utf8(bytes)
+            .unwrap()
+            .lines()
+            .map(|line| serde_json::from_str(line).expect("fiecare linie este JSON valid"))
+            .collect()
+    }
+}
+
+fn capture(f: impl FnOnce()) -> Vec<Value> {
+    let captured = Captured::default();
+    let writer = captured.clone();
+    let subscriber = tracing_subscriber::registry().with(JsonLayer::new(move || writer.clone()));
+    tracing::subscriber::with_default(subscriber, f);
+    captured.lines()
+}
+
+#[test]
+fn event_carries_span_and_typed_fields() {
+    let lines = capture(|| {
+        let span = tracing::info_span!("packet", src_addr = %"192.168.99.1:514");
+        let _guard = span.enter();
+        tracing::info!(
+            target: EVENTS_TARGET,
+            ip = %"10.0.0.7",
+            scan_type = "FAST_SCAN",
+            unique_ports = 23u64,
+            severity = 8u8,
+            "alert"
+        );
+    });
+
+    assert_eq!(lines.len(), 1);
+    let line = &lines[0];
+    assert_eq!(line["message"], "alert");
+    assert_eq!(line["level"], "INFO");
+    assert_eq!(line["target"], EVENTS_TARGET);
+    assert_eq!(line["src_addr"], "192.168.99.1:514");
+    assert_eq!(line["ip"], "10.0.0.7");
+    assert_eq!(line["scan_type"], "FAST_SCAN");
+    assert_eq!(line["unique_ports"], 23, "numerele rămân numere");
+    assert_eq!(line["spans"], serde_json::json!(["packet"]));
+    assert!(line["timestamp"].is_string());
+}
+
+#[test]
+fn recorded_span_fields_and_inner_spans_override() {
+    let lines = capture(|| {
+        let outer = tracing::info_span!("packet", src_addr = %"192.168.99.1:514", lines = tracing::field::Empty);
+        let _outer = outer.enter();
+        outer.record("lines", 3u64);
+
+        let inner = tracing::info_span!("replay", src_addr = "control");
+        let _inner = inner.enter();
+        tracing::debug!(port = 22u16, "record");
+    });
+
+    let line = &lines[0];
+    assert_eq!(line["lines"], 3);
+    assert_eq!(line["src_addr"], "control", "span-ul interior are prioritate");
+    assert_eq!(line["port"], 22);
+    assert_eq!(line["level"], "DEBUG");
+    assert_eq!(line["spans"], serde_json::json!(["packet", "replay"]));
+}
+
+#[test]
+fn event_outside_any_span_has_no_span_fields() {
+    let lines = capture(|| tracing::warn!("fără span"));
+
+    assert_eq!(lines[0]["message"], "fără span");
+    assert!(lines[0].get("spans").is_none());
+    assert!(lines[0].get("src_addr").is_none());
+}
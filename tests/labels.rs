@@ -0,0 +1,79 @@
+// ============================================================
+//  tests/labels.rs - Etichetele surselor (`[[labels]]`) în alerte
+// ============================================================
+
+mod common;
+
+use common::{drop_from, ip};
+use rust_ids::alert_fields::detection_fields;
+use rust_ids::config::Config;
+use rust_ids::engine::Engine;
+use rust_ids::labels::LabelResolver;
+
+const LABELS: &str = r#"
+[[labels]]
+network = "203.0.113.0/24"
+tag     = "partner-acme"
+
+[[labels]]
+network = "203.0.113.128/25"
+tag     = "dmz"
+
+[[labels]]
+network = "2001:db8::/32"
+tag     = "partner-acme"
+"#;
+
+fn config() -> Config {
+    let text = format!("{}\n{}", include_str!("../config.toml"), LABELS);
+    Config::from_toml_str(&text).expect("config cu etichete valid")
+}
+
+#[test]
+fn matching_labels_concatenate_in_config_order() {
+    let resolver = LabelResolver::new(&config().labels);
+
+    assert_eq!(resolver.tags(&ip("203.0.113.200")), ["partner-acme", "dmz"]);
+    assert_eq!(resolver.tags(&ip("203.0.113.5")), ["partner-acme"]);
+    // Același tag din două rețele apare o singură dată; IPv4-mapped = IPv4
+    assert_eq!(resolver.tags(&ip("2001:db8::7")), ["partner-acme"]);
+    assert_eq!(resolver.tags(&ip("::ffff:203.0.113.5")), ["partner-acme"]);
+    assert!(resolver.tags(&ip("198.51.100.1")).is_empty());
+
+    // A doua rezolvare vine din cache, cu același rezultat
+    assert_eq!(resolver.tags(&ip("203.0.113.200")), ["partner-acme", "dmz"]);
+}
+
+#[test]
+fn alert_carries_the_source_tags_into_cef_and_leef() {
+    let config = config();
+    let engine = Engine::new(&config);
+    let threshold = config.detection.fast_scan_ports as u16;
+
+    let alert = (1..=threshold + 1)
+        .filter_map(|port| engine.ingest(drop_from("203.0.113.200", port)))
+        .next()
+        .expect("alertă fast scan");
+    assert_eq!(alert.labels, ["partner-acme", "dmz"]);
+
+    let mut fields = detection_fields(&alert.ip, &alert.result, &[], &[], alert.severity).expect("câmpuri");
+    fields.push_labels(&alert.labels);
+    assert!(fields.to_cef().ends_with(" cs6Label=SourceTag cs6=partner-acme,dmz"), "{}", fields.to_cef());
+    assert!(fields.to_leef('\t').contains("\tsourceTag=partner-acme,dmz\t"), "{}", fields.to_leef('\t'));
+
+    // Fără etichete: niciun câmp cs6
+    let mut plain = detection_fields(&alert.ip, &alert.result, &[], &[], alert.severity).expect("câmpuri");
+    plain.push_labels(&[]);
+    assert!(!plain.to_cef().contains("cs6"));
+}
+
+#[test]
+fn invalid_labels_are_rejected_at_load() {
+    let base = include_str!("../config.toml");
+    let bad_network = format!("{}\n[[labels]]\nnetwork = \"203.0.113.0/33\"\ntag = \"x\"\n", base);
+    assert!(Config::from_toml_str(&bad_network).is_err());
+
+    let bad_tag = format!("{}\n[[labels]]\nnetwork = \"203.0.113.0/24\"\ntag = \"a,b\"\n", base);
+    let err = Config::from_toml_str(&bad_tag).expect_err("tag cu virgulă");
+    assert!(err.to_string().contains("[[labels]]"), "{}", err);
+}
@@ -0,0 +1,58 @@
+// ============================================================
+//  tests/liveness.rs - Starea buclei de recepție pentru `/healthz`
+// ============================================================
+
+use rust_ids::config::Config;
+use rust_ids::liveness::Liveness;
+use std::time::{Duration, Instant};
+
+const TIMEOUT: Duration = Duration::from_secs(30);
+
+#[test]
+fn idle_ticks_keep_a_quiet_system_live() {
+    let start = Instant::now();
+    let liveness = Liveness::starting_at(start);
+    assert!(liveness.report_at(start, TIMEOUT).live, "pornirea contează ca activitate");
+
+    // Fără pachete, dar bucla ticăie la fiecare 10s
+    for tick in 1..=6 {
+        liveness.touch_at(start + Duration::from_secs(10 * tick));
+    }
+    let report = liveness.report_at(start + Duration::from_secs(65), TIMEOUT);
+    assert!(report.live);
+    assert_eq!(report.packets, 0);
+    assert_eq!(report.idle_secs, 5.0);
+}
+
+#[test]
+fn a_wedged_loop_goes_unhealthy_after_the_timeout() {
+    let start = Instant::now();
+    let liveness = Liveness::starting_at(start);
+    liveness.touch_at(start + Duration::from_secs(5));
+
+    assert!(liveness.report_at(start + Duration::from_secs(35), TIMEOUT).live);
+    let report = liveness.report_at(start + Duration::from_secs(36), TIMEOUT);
+    assert!(!report.live);
+    assert_eq!(report.timeout_secs, 30);
+
+    // Un tic întârziat, cu un moment mai vechi, nu dă ceasul înapoi
+    liveness.touch_at(start + Duration::from_secs(2));
+    assert_eq!(liveness.idle_at(start + Duration::from_secs(6)), Duration::from_secs(1));
+}
+
+#[test]
+fn packets_count_as_activity() {
+    let liveness = Liveness::new();
+    liveness.record_packet();
+    liveness.record_packet();
+    let report = liveness.report(TIMEOUT);
+    assert!(report.live);
+    assert_eq!(report.packets, 2);
+}
+
+#[test]
+fn liveness_timeout_must_be_positive() {
+    let text = include_str!("../config.toml").replacen("liveness_timeout_secs = 30", "liveness_timeout_secs = 0", 1);
+    let err = Config::from_toml_str(&text).expect_err("timeout 0 invalid");
+    assert!(err.to_string().contains("liveness_timeout_secs"), "{}", err);
+}
@@ -0,0 +1,93 @@
+// ============================================================
+//  tests/parser_leef.rs - Parser LEEF 1.0 / 2.0 (QRadar)
+// ============================================================
+
+use rust_ids::parser::{create_parser, create_parser_with_options, EventKind, LogEntry, ParseOutcome, ParserOptions};
+
+const SYSLOG_PREFIX: &str = "Nov 20 15:30:00 fw01 ";
+
+fn parse(line: &str) -> Option<LogEntry> {
+    create_parser("leef").parse(line)
+}
+
+#[test]
+fn leef_1_0_tab_delimited() {
+    let line = "LEEF:1.0|PaloAlto|PAN-OS|9.1|TRAFFIC|src=192.168.1.10\tdst=10.0.0.5\tdstPort=22\tproto=TCP\taction=deny";
+    let entry = parse(line).expect("LEEF 1.0 valid");
+
+    assert_eq!(entry.source_ip.to_string(), "192.168.1.10");
+    assert_eq!(entry.dest_ip.map(|ip| ip.to_string()).as_deref(), Some("10.0.0.5"));
+    assert_eq!(entry.dest_port(), Some(22));
+    assert_eq!(entry.action, "deny");
+    assert!(!entry.accepted);
+}
+
+#[test]
+fn leef_2_0_without_declared_delimiter_defaults_to_tab() {
+    let line = "LEEF:2.0|Fortinet|FortiGate|7.0|DROP|src=192.168.1.11\tdstPort=443\taction=drop";
+    assert_eq!(parse(line).and_then(|e| e.dest_port()), Some(443));
+}
+
+#[test]
+fn leef_2_0_declared_delimiter_as_char_and_hex() {
+    let caret = "LEEF:2.0|Lancope|StealthWatch|1.0|41|^|src=192.168.1.12^dstPort=3389^action=block";
+    let hex = "LEEF:2.0|Lancope|StealthWatch|1.0|41|x5E|src=192.168.1.12^dstPort=3389^action=block";
+    let hex0 = "LEEF:2.0|Lancope|StealthWatch|1.0|41|0x7C|src=192.168.1.12|dstPort=3389|action=block";
+
+    for line in [caret, hex, hex0] {
+        let entry = parse(line).unwrap_or_else(|| panic!("delimitator nerecunoscut: {}", line));
+        assert_eq!(entry.dest_port(), Some(3389));
+        assert_eq!(entry.action, "block");
+    }
+}
+
+#[test]
+fn syslog_prefix_before_the_leef_token_is_ignored() {
+    let line = format!("{}LEEF:2.0|PaloAlto|PAN-OS|9.1|TRAFFIC|src=192.168.1.13\tdstPort=8080\taction=deny", SYSLOG_PREFIX);
+    assert_eq!(parse(&line).and_then(|e| e.dest_port()), Some(8080));
+}
+
+#[test]
+fn only_blocking_actions_are_emitted() {
+    let allow = "LEEF:1.0|PaloAlto|PAN-OS|9.1|TRAFFIC|src=192.168.1.14\tdstPort=22\taction=allow";
+    assert!(matches!(
+        create_parser("leef").parse_outcome(allow),
+        ParseOutcome::IgnoredAction(ref action) if action == "allow"
+    ));
+
+    // Cu emit_accepts (post-scan access), accept-ul este emis și marcat
+    let parser = create_parser_with_options("leef", ParserOptions { emit_accepts: true, ..ParserOptions::default() });
+    let entry = parser.parse(allow).expect("allow emis cu emit_accepts");
+    assert!(entry.accepted);
+}
+
+#[test]
+fn alternate_attribute_names_are_used_as_fallback() {
+    let line = "LEEF:2.0|Vendor|Product|1.0|EV|src-less\tsrcAddr=192.168.1.15\tdstPostNATPort=ssh\tdevAction=Deny";
+    let entry = parse(line).expect("srcAddr / dstPostNATPort / devAction");
+
+    assert_eq!(entry.source_ip.to_string(), "192.168.1.15");
+    assert_eq!(entry.dest_port(), Some(22), "numele de serviciu este rezolvat");
+    assert_eq!(entry.action, "deny");
+}
+
+#[test]
+fn icmp_events_have_no_port() {
+    let line = "LEEF:1.0|PaloAlto|PAN-OS|9.1|TRAFFIC|src=192.168.1.16\tdst=10.0.0.9\tproto=ICMP\ticmpType=8\taction=drop";
+    let entry = parse(line).expect("ICMP drop");
+    assert_eq!(entry.kind, EventKind::Icmp { icmp_type: Some(8) });
+    assert_eq!(entry.dest_port(), None);
+}
+
+#[test]
+fn malformed_lines_are_unrecognized() {
+    let parser = create_parser("leef");
+    for line in [
+        "just some text",
+        "LEEF:1.0|PaloAlto|PAN-OS",
+        "LEEF:1.0|PaloAlto|PAN-OS|9.1|TRAFFIC|dstPort=22\taction=deny",
+        "LEEF:1.0|PaloAlto|PAN-OS|9.1|TRAFFIC|src=192.168.1.17\taction=deny",
+    ] {
+        assert!(matches!(parser.parse_outcome(line), ParseOutcome::Unrecognized), "{}", line);
+    }
+}
@@ -0,0 +1,66 @@
+// ============================================================
+//  tests/parser_ports.rs - Porturi raportate ca nume de serviciu sau hex
+// ============================================================
+
+use rust_ids::parser::{create_parser, create_parser_with_ports, PortResolver};
+use std::collections::HashMap;
+
+fn gaia_line(service: &str) -> String {
+    format!("Sep 3 15:12:20 192.168.99.1 Checkpoint: drop 192.168.11.7 proto: tcp; service: {}; s_port: 1352", service)
+}
+
+fn gaia_port(service: &str) -> Option<u16> {
+    create_parser("gaia").parse(&gaia_line(service)).and_then(|e| e.dest_port())
+}
+
+#[test]
+fn gaia_resolves_well_known_service_names() {
+    assert_eq!(gaia_port("ssh"), Some(22));
+    assert_eq!(gaia_port("https"), Some(443));
+    assert_eq!(gaia_port("HTTPS"), Some(443));
+}
+
+#[test]
+fn gaia_accepts_hex_ports() {
+    assert_eq!(gaia_port("0x1bb"), Some(443));
+    assert_eq!(gaia_port("0X16"), Some(22));
+    assert_eq!(gaia_port("0x10000"), None, "în afara intervalului u16");
+}
+
+#[test]
+fn gaia_decimal_ports_are_unchanged() {
+    assert_eq!(gaia_port("22"), Some(22));
+    assert_eq!(gaia_port("65535"), Some(65535));
+}
+
+#[test]
+fn unknown_service_names_yield_no_event() {
+    assert_eq!(gaia_port("no-such-service"), None);
+}
+
+#[test]
+fn configured_service_names_extend_the_table() {
+    let custom = HashMap::from([("MyApp".to_string(), 9000), ("http-alt".to_string(), 8000)]);
+    let parser = create_parser_with_ports("gaia", PortResolver::new(&custom));
+
+    let port = |service: &str| parser.parse(&gaia_line(service)).and_then(|e| e.dest_port());
+    assert_eq!(port("myapp"), Some(9000));
+    assert_eq!(port("http-alt"), Some(8000), "configurația are prioritate");
+    assert_eq!(port("ssh"), Some(22));
+}
+
+#[test]
+fn cef_dpt_accepts_service_names_and_hex() {
+    let parser = create_parser("cef");
+    let line = |dpt: &str| {
+        format!(
+            "Nov 20 15:30:00 firewall CEF:0|Checkpoint|VPN-1|NGX R65|firewall|Blocked|7|\
+             src=10.0.0.5 dst=10.0.0.1 dpt={} act=drop proto=TCP",
+            dpt
+        )
+    };
+
+    assert_eq!(parser.parse(&line("ssh")).and_then(|e| e.dest_port()), Some(22));
+    assert_eq!(parser.parse(&line("0x50")).and_then(|e| e.dest_port()), Some(80));
+    assert!(parser.parse(&line("bogus")).is_none());
+}
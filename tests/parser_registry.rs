@@ -0,0 +1,74 @@
+// ============================================================
+//  tests/parser_registry.rs - Parsere proprii prin `register_parser`
+// ============================================================
+
+use chrono::Utc;
+use rust_ids::parser::{
+    create_parser, register_parser, registered_parsers, EventKind, LogEntry, LogParser, ParserOptions, ParserRegistry,
+};
+
+/// Format intern de test: `ACME|<ip sursă>|<port>`
+struct AcmeParser {
+    options: ParserOptions,
+}
+
+impl LogParser for AcmeParser {
+    fn parse(&self, line: &str) -> Option<LogEntry> {
+        let mut parts = line.trim().split('|');
+        if parts.next()? != "ACME" {
+            return None;
+        }
+        let source_ip = parts.next()?.parse().ok()?;
+        let port = self.options.ports.resolve(parts.next()?)?;
+        Some(LogEntry {
+            source_ip,
+            dest_ip: None,
+            kind: EventKind::Port(port),
+            protocol: Some("tcp".to_string()),
+            action: "drop".to_string(),
+            accepted: false,
+            rule: None,
+            timestamp: Utc::now(),
+        })
+    }
+
+    fn name(&self) -> &str {
+        "ACME"
+    }
+}
+
+#[test]
+fn registered_parser_is_created_by_name() {
+    let replaced = register_parser("acme", |options| Box::new(AcmeParser { options }));
+    assert!(!replaced);
+    assert!(registered_parsers().contains(&"acme".to_string()));
+
+    // Numele se compară fără majuscule, ca la formatele încorporate
+    let parser = create_parser("Acme");
+    assert_eq!(parser.name(), "ACME");
+    let entry = parser.parse("ACME|10.30.0.1|ssh").expect("linie ACME validă");
+    assert_eq!(entry.dest_port(), Some(22));
+    assert!(parser.parse("Sep 3 15:12:20 192.168.99.1 Checkpoint: drop 10.30.0.1 proto: tcp; service: 22").is_none());
+}
+
+#[test]
+fn builtin_formats_are_unchanged_without_registrations() {
+    let registry = ParserRegistry::new();
+    assert_eq!(registry.names(), ["cef", "gaia", "leef", "windows"]);
+
+    assert_eq!(create_parser("gaia").name(), create_parser("GAIA").name());
+    assert_eq!(create_parser("cef").name(), "ArcSight CEF");
+    // Un nume necunoscut revine la Gaia, ca înainte
+    assert_eq!(create_parser("necunoscut").name(), create_parser("gaia").name());
+}
+
+#[test]
+fn local_registry_can_override_a_builtin() {
+    let mut registry = ParserRegistry::new();
+    assert!(registry.register("GAIA", |options| Box::new(AcmeParser { options })));
+    assert!(registry.contains("gaia"));
+
+    let parser = registry.create("gaia", ParserOptions::default()).expect("format înregistrat");
+    assert_eq!(parser.name(), "ACME");
+    assert!(registry.create("acme-local", ParserOptions::default()).is_none());
+}
@@ -0,0 +1,81 @@
+// ============================================================
+//  tests/parser_windows.rs - Parser Windows Firewall (Event ID 5152 / 5157)
+// ============================================================
+
+use rust_ids::parser::{create_parser, create_parser_with_options, EventKind, LogEntry, ParseOutcome, ParserOptions};
+
+const SYSLOG_PREFIX: &str = "Oct 18 10:00:00 WIN-SRV01 MSWinEventLog ";
+
+fn parse(line: &str) -> Option<LogEntry> {
+    create_parser("windows").parse(line)
+}
+
+#[test]
+fn packet_drop_5152() {
+    let line = format!(
+        "{}EventID=5152 Application=\\device\\harddiskvolume2\\windows\\system32\\svchost.exe \
+         SourceAddress=10.0.0.5 SourcePort=51234 DestAddress=10.0.0.1 DestPort=445 Protocol=6 FilterRTID=67890",
+        SYSLOG_PREFIX
+    );
+    let entry = parse(&line).expect("5152 valid");
+
+    assert_eq!(entry.source_ip.to_string(), "10.0.0.5");
+    assert_eq!(entry.dest_ip.map(|ip| ip.to_string()).as_deref(), Some("10.0.0.1"));
+    assert_eq!(entry.dest_port(), Some(445));
+    assert_eq!(entry.action, "drop");
+    assert_eq!(entry.rule.as_deref(), Some("67890"));
+    assert!(!entry.accepted);
+}
+
+#[test]
+fn connection_drop_5157_with_quotes_other_order_and_ipv6() {
+    let line = format!(
+        "{}Protocol=\"17\"\tDestPort=\"53\"\tFilterRTID=\"0\"\t\
+         Application=\"C:\\Program Files\\App\\app.exe\"\tSourceAddress=\"fe80::1%12\"\tEventCode=\"5157\"",
+        SYSLOG_PREFIX
+    );
+    let entry = parse(&line).expect("5157 valid");
+
+    assert_eq!(entry.source_ip.to_string(), "fe80::1");
+    assert_eq!(entry.dest_ip, None);
+    assert_eq!(entry.dest_port(), Some(53));
+    assert_eq!(entry.rule, None, "FilterRTID=0 nu este un filtru");
+}
+
+#[test]
+fn ipv4_mapped_source_and_icmp() {
+    let line = "EventID=5152, SourceAddress=::ffff:192.168.5.9, DestAddress=10.0.0.1, DestPort=0, Protocol=1";
+    let entry = parse(line).expect("5152 ICMP valid");
+    assert_eq!(entry.source_ip.to_string(), "192.168.5.9");
+    assert_eq!(entry.kind, EventKind::Icmp { icmp_type: None });
+}
+
+#[test]
+fn unrelated_windows_event_is_not_an_event() {
+    let logon = format!(
+        "{}EventID=4624 TargetUserName=admin LogonType=3 SourceAddress=10.0.0.7 SourcePort=0",
+        SYSLOG_PREFIX
+    );
+    assert!(parse(&logon).is_none());
+    assert!(matches!(
+        create_parser("windows").parse_outcome(&logon),
+        ParseOutcome::IgnoredAction(action) if action == "event 4624"
+    ));
+
+    // Fără marker de eveniment linia nu este în formatul Windows
+    assert!(matches!(
+        create_parser("windows").parse_outcome("SourceAddress=10.0.0.7 DestPort=22 Protocol=6"),
+        ParseOutcome::Unrecognized
+    ));
+}
+
+#[test]
+fn allowed_connection_5156_only_with_emit_accepts() {
+    let line = "EventID=5156 SourceAddress=10.0.0.8 DestAddress=10.0.0.1 DestPort=22 Protocol=6";
+    assert!(parse(line).is_none());
+
+    let options = ParserOptions { emit_accepts: true, ..ParserOptions::default() };
+    let entry = create_parser_with_options("windows", options).parse(line).expect("5156 cu emit_accepts");
+    assert!(entry.accepted);
+    assert_eq!(entry.action, "accept");
+}
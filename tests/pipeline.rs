@@ -0,0 +1,249 @@
+// ============================================================
+//  tests/pipeline.rs - Fluxul parsare -> stare -> detecție prin API-ul public
+// ============================================================
+//
+//  Testele de integrare văd crate-ul `rust_ids` exact ca un consumator
+//  extern: doar itemii `pub`, fără socket-uri și fără binar.
+// ============================================================
+
+mod common;
+
+use common::{clock_with_history, config, config_with, gaia_drop};
+use rust_ids::clock::MockClock;
+use rust_ids::config::Config;
+use rust_ids::detector::{evaluate, evaluate_icmp, DetectionResult};
+use rust_ids::parser::{create_parser, LogParser, ParseOutcome};
+use rust_ids::state::{CooldownPolicy, SharedState, MAX_TARGETS_PER_SOURCE};
+use std::net::{IpAddr, Ipv4Addr};
+use std::sync::Arc;
+use std::time::Duration;
+
+/// Parsează și înregistrează o linie, apoi evaluează sursa
+fn ingest(parser: &dyn LogParser, state: &SharedState, config: &Config, line: &str) -> DetectionResult {
+    let entry = parser.parse(line).expect("linie validă");
+    state.record_event(entry.source_ip, entry.dest_port().expect("eveniment cu port"));
+    evaluate(&entry.source_ip, state, &config.detection)
+}
+
+#[test]
+fn fast_scan_fires_just_above_threshold() {
+    let config = config();
+    let parser = create_parser("gaia");
+    let state = SharedState::new();
+    let threshold = config.detection.fast_scan_ports as u16;
+
+    for port in 1..=threshold {
+        let result = ingest(parser.as_ref(), &state, &config, &gaia_drop("10.1.1.1", port));
+        assert!(!result.is_threat(), "{} porturi nu depășesc pragul", port);
+    }
+
+    let result = ingest(parser.as_ref(), &state, &config, &gaia_drop("10.1.1.1", threshold + 1));
+    let hit = result.primary().expect("alertă peste prag");
+    assert_eq!(hit.tier, "fast");
+    assert_eq!(hit.ports, usize::from(threshold) + 1);
+}
+
+#[test]
+fn repeated_port_does_not_count_twice() {
+    let config = config();
+    let parser = create_parser("gaia");
+    let state = SharedState::new();
+
+    for _ in 0..100 {
+        let result = ingest(parser.as_ref(), &state, &config, &gaia_drop("10.1.1.2", 22));
+        assert_eq!(result, DetectionResult::Clean);
+    }
+}
+
+#[test]
+fn accept_lines_are_ignored_not_unrecognized() {
+    let parser = create_parser("gaia");
+    let line = "Sep 3 15:12:20 192.168.99.1 Checkpoint: accept 10.1.1.3 proto: tcp; service: 22; s_port: 1352";
+
+    assert!(matches!(parser.parse_outcome(line), ParseOutcome::IgnoredAction(action) if action == "accept"));
+    assert!(matches!(parser.parse_outcome("nu este un log"), ParseOutcome::Unrecognized));
+}
+
+#[test]
+fn cef_drop_line_yields_source_and_port() {
+    let parser = create_parser("cef");
+    let line = "Nov 20 15:30:00 firewall CEF:0|Checkpoint|VPN-1 & FireWall-1|NGX R65|firewall|\
+                Connection Blocked|7|src=10.0.0.5 dst=10.0.0.1 dpt=22 act=drop proto=TCP";
+
+    let entry = parser.parse(line).expect("CEF drop valid");
+    assert_eq!(entry.source_ip, "10.0.0.5".parse::<IpAddr>().unwrap());
+    assert_eq!(entry.dest_port(), Some(22));
+}
+
+#[test]
+fn icmp_drops_stay_out_of_port_counting() {
+    let config = config();
+    let parser = create_parser("gaia");
+    let state = SharedState::new();
+    let src: IpAddr = "10.1.1.4".parse().unwrap();
+
+    for i in 1..=50 {
+        let line = format!(
+            "Sep 3 15:12:20 192.168.99.1 Checkpoint: drop {} dst: 10.0.0.{}; proto: icmp; icmp-type: 8",
+            src, i
+        );
+        let entry = parser.parse(&line).expect("ICMP valid");
+        assert_eq!(entry.dest_port(), None);
+        state.record_icmp(entry.source_ip, entry.dest_ip);
+    }
+
+    assert_eq!(evaluate(&src, &state, &config.detection), DetectionResult::Clean);
+    // config.toml lasă ICMP sweep dezactivat (icmp_sweep_events = 0)
+    assert_eq!(evaluate_icmp(&src, &state, &config.detection), DetectionResult::Clean);
+}
+
+#[test]
+fn cooldown_is_tracked_per_source() {
+    let state = SharedState::new();
+    let a: IpAddr = "10.1.1.5".parse().unwrap();
+    let b: IpAddr = "10.1.1.6".parse().unwrap();
+
+    state.mark_alerted(a, &CooldownPolicy::flat(Duration::from_secs(300)), 8);
+    assert!(state.is_in_cooldown(&a));
+    assert!(!state.is_in_cooldown(&b));
+}
+
+#[test]
+fn cooldown_escalates_on_realert_up_to_the_cap() {
+    let state = SharedState::new();
+    let ip: IpAddr = "10.1.1.8".parse().unwrap();
+    let policy = config().detection.cooldown_policy();
+    assert_eq!(policy.base, Duration::from_secs(60));
+
+    let applied: Vec<u64> = (0..8).map(|_| state.mark_alerted(ip, &policy, 8).as_secs()).collect();
+    assert_eq!(applied, [60, 120, 240, 480, 960, 1920, 3600, 3600]);
+}
+
+#[test]
+fn cooldown_returns_to_base_after_a_quiet_period() {
+    let clock = Arc::new(MockClock::new());
+    let state = SharedState::new().with_clock(clock.clone());
+    let ip: IpAddr = "10.1.1.9".parse().unwrap();
+    let policy = CooldownPolicy {
+        base:        Duration::from_millis(20),
+        multiplier:  2.0,
+        max:         Duration::from_secs(1),
+        reset_after: Duration::from_millis(30),
+    };
+
+    assert_eq!(state.mark_alerted(ip, &policy, 8), Duration::from_millis(20));
+    assert_eq!(state.mark_alerted(ip, &policy, 8), Duration::from_millis(40));
+    assert!(state.is_in_cooldown(&ip));
+
+    // 40ms cooldown + 30ms de liniște -> înapoi la bază
+    clock.advance(Duration::from_millis(70));
+    assert!(!state.is_in_cooldown(&ip));
+    assert_eq!(state.mark_alerted(ip, &policy, 8), Duration::from_millis(20));
+}
+
+#[test]
+fn extreme_cooldown_settings_saturate_instead_of_panicking() {
+    let state = SharedState::new();
+    let ip: IpAddr = "10.1.1.11".parse().unwrap();
+
+    // Config validă: multiplicator finit >= 1, plafon >= bază
+    let config = config_with(&[
+        ("alert_cooldown_multiplier = 2.0", "alert_cooldown_multiplier = 1e20"),
+        ("alert_cooldown_max_secs   = 3600", "alert_cooldown_max_secs   = 9223372036854775807"),
+        ("alert_cooldown_reset_secs = 3600", "alert_cooldown_reset_secs = 9223372036854775807"),
+    ]);
+    let policy = config.detection.cooldown_policy();
+    assert_eq!(state.mark_alerted(ip, &policy, 8), policy.base);
+    assert_eq!(state.mark_alerted(ip, &policy, 8), policy.max, "produsul care depășește `Duration` devine plafonul");
+    assert_eq!(state.mark_alerted(ip, &policy, 8), policy.max);
+    assert_eq!(state.mark_escalated(ip, &policy, 9), policy.base);
+    assert!(state.is_in_cooldown(&ip));
+}
+
+#[test]
+fn slow_to_fast_escalation_breaks_through_cooldown() {
+    let config = config();
+    let state = SharedState::new().with_clock(clock_with_history(Duration::from_secs(30)));
+    let src: IpAddr = "10.1.1.10".parse().unwrap();
+    let policy = config.detection.cooldown_policy();
+
+    // Slow scan: porturi vechi de 30s (în afara ferestrei fast de 10s)
+    let earlier = state.now() - Duration::from_secs(30);
+    for port in 1..=config.detection.slow_scan_ports as u16 + 1 {
+        state.record_event_at(src, port, earlier);
+    }
+    let slow = evaluate(&src, &state, &config.detection);
+    assert_eq!(slow.primary().map(|hit| hit.tier.as_str()), Some("slow"));
+    state.mark_alerted(src, &policy, slow.severity());
+    assert!(state.is_in_cooldown(&src));
+    assert!(!state.is_escalation(&src, slow.severity()));
+
+    // Aceeași sursă trece și pragul fast, încă în cooldown
+    for port in 1000..=1000 + config.detection.fast_scan_ports as u16 {
+        state.record_event(src, port);
+    }
+    let fast = evaluate(&src, &state, &config.detection);
+    assert!(fast.severity() > slow.severity());
+    assert!(state.is_escalation(&src, fast.severity()));
+
+    // După alerta escaladată: cooldown repornit, noua severitate e referința
+    assert_eq!(state.mark_escalated(src, &policy, fast.severity()), policy.base);
+    assert!(state.is_in_cooldown(&src));
+    assert!(!state.is_escalation(&src, fast.severity()));
+}
+
+#[test]
+fn detection_result_serializes_for_downstream_consumers() {
+    let config = config();
+    let parser = create_parser("gaia");
+    let state = SharedState::new();
+
+    // Peste pragul fast, sub cel slow -> un singur tier (`Scan`)
+    let mut result = DetectionResult::Clean;
+    for port in 1..=20 {
+        result = ingest(parser.as_ref(), &state, &config, &gaia_drop("10.1.1.7", port));
+    }
+
+    let json = serde_json::to_value(&result).expect("serializare JSON");
+    assert_eq!(json["Scan"]["tier"], "fast");
+    assert_eq!(json["Scan"]["ports"], 20);
+}
+
+#[test]
+fn target_profile_is_capped_and_ordered_by_ports_then_hits() {
+    // Ceasul avansează 1 ms per eveniment: ordinea LRU e deterministă
+    let clock = Arc::new(MockClock::new());
+    let state = SharedState::new().with_clock(clock.clone());
+    let src: IpAddr = "10.1.1.8".parse().unwrap();
+    let dest = |n: u32| IpAddr::V4(Ipv4Addr::from(0xac10_0000 + n));
+
+    for n in 0..500 {
+        state.record_target(src, dest(n), 22);
+        clock.advance(Duration::from_millis(1));
+    }
+
+    // Doar cele mai recente 256 destinații rămân (LRU)
+    let targets = state.targets_for(&src);
+    assert_eq!(targets.len(), MAX_TARGETS_PER_SOURCE);
+    let oldest_kept = 500 - MAX_TARGETS_PER_SOURCE as u32;
+    assert!(targets.iter().all(|t| t.dest >= dest(oldest_kept)));
+    assert!(!targets.iter().any(|t| t.dest == dest(oldest_kept - 1)));
+
+    // Porturi unice primele, apoi evenimentele, apoi adresa
+    for port in [80, 443] {
+        state.record_target(src, dest(300), port);
+    }
+    for _ in 0..9 {
+        state.record_target(src, dest(400), 80);
+    }
+    for _ in 0..3 {
+        state.record_target(src, dest(450), 80);
+    }
+    let targets = state.targets_for(&src);
+    let top: Vec<(IpAddr, usize, u64)> = targets.iter().take(4).map(|t| (t.dest, t.unique_ports, t.hits)).collect();
+    assert_eq!(
+        top,
+        [(dest(300), 3, 3), (dest(400), 2, 10), (dest(450), 2, 4), (dest(oldest_kept), 1, 1)]
+    );
+    assert_eq!(targets.len(), MAX_TARGETS_PER_SOURCE, "destinațiile existente nu evacuează nimic");
+}
@@ -0,0 +1,101 @@
+// ============================================================
+//  tests/port_filter.rs - `ignore_ports` / `port_range` la detecție
+// ============================================================
+//
+//  Porturile excluse sunt înregistrate (istoric complet pentru analiză),
+//  dar nu contează la numărătoarea porturilor unice a tier-urilor.
+// ============================================================
+
+use rust_ids::config::{Config, PortRange};
+use rust_ids::detector::{evaluate, DetectionResult};
+use rust_ids::state::SharedState;
+use std::net::IpAddr;
+
+const REPO_CONFIG: &str = include_str!("../config.toml");
+
+fn config(ignore_ports: Vec<u16>, port_range: Option<PortRange>) -> Config {
+    let mut config = Config::from_toml_str(REPO_CONFIG).expect("config.toml din repo trebuie să fie valid");
+    config.detection.ignore_ports = ignore_ports;
+    config.detection.port_range = port_range;
+    config
+}
+
+/// Configurația repo-ului cu filtrul de porturi din `[detection]` înlocuit
+fn config_with(filter: &str) -> anyhow::Result<Config> {
+    let toml = REPO_CONFIG
+        .replacen("ignore_ports = []\n", "", 1)
+        .replacen("[detection]\n", &format!("[detection]\n{}\n", filter), 1);
+    Config::from_toml_str(&toml)
+}
+
+#[test]
+fn scan_made_only_of_ignored_ports_never_alerts() {
+    let ephemeral: Vec<u16> = (40000..40050).collect();
+    let config = config(ephemeral.clone(), None);
+    let state = SharedState::new();
+    let src: IpAddr = "10.4.0.1".parse().unwrap();
+
+    for port in &ephemeral {
+        state.record_event(src, *port);
+        assert_eq!(evaluate(&src, &state, &config.detection), DetectionResult::Clean);
+    }
+    // Evenimentele sunt păstrate, doar nu sunt numărate
+    assert_eq!(state.unique_ports_in_window(&src, 60), ephemeral.len());
+}
+
+#[test]
+fn mixed_scan_counts_only_the_non_ignored_ports() {
+    let config = config((40000..40010).collect(), None);
+    let threshold = config.detection.fast_scan_ports;
+    let state = SharedState::new();
+    let src: IpAddr = "10.4.0.2".parse().unwrap();
+
+    // 10 porturi ignorate + exact pragul de porturi numărate: încă curat
+    for port in 40000..40010 {
+        state.record_event(src, port);
+    }
+    for port in 1..=threshold as u16 {
+        state.record_event(src, port);
+    }
+    assert_eq!(evaluate(&src, &state, &config.detection), DetectionResult::Clean);
+
+    // Un singur port numărat în plus depășește pragul
+    state.record_event(src, threshold as u16 + 1);
+    match evaluate(&src, &state, &config.detection) {
+        DetectionResult::Scan(hit) => assert_eq!(hit.ports, threshold + 1),
+        other => panic!("se aștepta Fast Scan, nu {:?}", other),
+    }
+}
+
+#[test]
+fn ports_outside_the_range_are_not_counted() {
+    let config = config(vec![], Some(PortRange { min: 1, max: 1024 }));
+    let state = SharedState::new();
+    let src: IpAddr = "10.4.0.3".parse().unwrap();
+
+    for port in 50000..50100 {
+        state.record_event(src, port);
+    }
+    assert_eq!(evaluate(&src, &state, &config.detection), DetectionResult::Clean);
+
+    for port in 1..=config.detection.fast_scan_ports as u16 + 1 {
+        state.record_event(src, port);
+    }
+    assert!(evaluate(&src, &state, &config.detection).is_threat());
+}
+
+#[test]
+fn contradictory_port_filters_are_rejected() {
+    assert!(config_with("port_range = { min = 1, max = 1024 }\nignore_ports = [22, 80]").is_ok());
+    assert!(config_with("port_range = { max = 1024 }").is_ok(), "min implicit 0");
+
+    let errors = [
+        config_with("port_range = { min = 2000, max = 1024 }"),
+        config_with("port_range = { min = 1, max = 1024 }\nignore_ports = [8080]"),
+        config_with("port_range = { min = 22, max = 23 }\nignore_ports = [22, 23]"),
+    ];
+    for result in errors {
+        let error = format!("{:#}", result.expect_err("filtru contradictoriu acceptat"));
+        assert!(error.contains("[detection]"), "{}", error);
+    }
+}
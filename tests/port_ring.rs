@@ -0,0 +1,110 @@
+// ============================================================
+//  tests/port_ring.rs - Bucket-urile de porturi vs. numărarea naivă
+// ============================================================
+
+mod common;
+
+use common::clock_with_history;
+use rust_ids::clock::Clock;
+use rust_ids::state::{SharedState, SCAN_BUCKET};
+use std::collections::HashSet;
+use std::net::IpAddr;
+use std::time::{Duration, Instant};
+
+/// Generator pseudo-aleator determinist (xorshift) - fără dependențe
+struct XorShift(u64);
+
+impl XorShift {
+    fn next(&mut self) -> u64 {
+        self.0 ^= self.0 << 13;
+        self.0 ^= self.0 >> 7;
+        self.0 ^= self.0 << 17;
+        self.0
+    }
+
+    fn below(&mut self, n: u64) -> u64 {
+        self.next() % n
+    }
+}
+
+/// Implementarea de referință: toate evenimentele, filtrate pe fereastră
+fn naive(events: &[(u16, Instant)], now: Instant, window_secs: u64) -> usize {
+    let window = Duration::from_secs(window_secs);
+    events
+        .iter()
+        .filter(|(_, seen_at)| now.saturating_duration_since(*seen_at) <= window)
+        .map(|(port, _)| *port)
+        .collect::<HashSet<u16>>()
+        .len()
+}
+
+#[test]
+fn bucketed_counts_match_naive_for_random_streams() {
+    let mut rng = XorShift(0x9E37_79B9_7F4A_7C15);
+    let clock = clock_with_history(Duration::from_secs(7200));
+    let now = clock.now();
+
+    for round in 0..20 {
+        let state = SharedState::new().with_clock(clock.clone());
+        let ip = IpAddr::from([10, 12, 0, round as u8]);
+        let mut events = Vec::new();
+
+        // Evenimente în ultimele 2 ore, în ordine aleatoare (ca injectarea
+        // sintetică), cu porturi dintr-un interval mic (multe repetiții)
+        let port_range = [20, 200, 5000][round % 3];
+        for _ in 0..2000 {
+            let age = Duration::from_millis(rng.below(7_200_000));
+            let port = 1 + rng.below(port_range) as u16;
+            let seen_at = now - age;
+            state.record_event_at(ip, port, seen_at);
+            events.push((port, seen_at));
+        }
+
+        // Ceasul stă pe loc: referința și bucket-urile văd același "acum"
+        for window_secs in [1, 5, 10, 12, 60, 333, 3600, 7200] {
+            assert_eq!(
+                state.unique_ports_in_window(&ip, window_secs),
+                naive(&events, now, window_secs),
+                "runda {}, fereastra {}s",
+                round,
+                window_secs
+            );
+        }
+    }
+}
+
+#[test]
+fn window_edge_inside_a_bucket_is_exact() {
+    let clock = clock_with_history(Duration::from_secs(60));
+    let state = SharedState::new().with_clock(clock.clone());
+    let ip: IpAddr = "10.12.1.1".parse().unwrap();
+    let now = clock.now();
+
+    // Două porturi în același bucket (primul eveniment îl începe), de o
+    // parte și de alta a marginii de 10s
+    assert!(SCAN_BUCKET > Duration::from_secs(2));
+    state.record_event_at(ip, 23, now - Duration::from_secs(11));
+    state.record_event_at(ip, 22, now - Duration::from_secs(9));
+
+    assert_eq!(state.unique_ports_in_window(&ip, 10), 1);
+    assert_eq!(state.unique_ports_in_window(&ip, 12), 2);
+}
+
+#[test]
+fn repeated_port_floods_do_not_grow_the_count_and_cleanup_drops_old_buckets() {
+    let clock = clock_with_history(Duration::from_secs(600));
+    let state = SharedState::new().with_clock(clock.clone());
+    let ip: IpAddr = "10.12.1.2".parse().unwrap();
+    let old = clock.now() - Duration::from_secs(600);
+
+    for _ in 0..10_000 {
+        state.record_event(ip, 80);
+    }
+    state.record_event_at(ip, 443, old);
+    assert_eq!(state.unique_ports_in_window(&ip, 10), 1);
+    assert_eq!(state.unique_ports_in_window(&ip, 3600), 2);
+
+    // Cleanup-ul elimină doar bucket-ul vechi; IP-ul rămâne urmărit
+    assert_eq!(state.cleanup_old_entries(60), 0);
+    assert_eq!(state.unique_ports_in_window(&ip, 3600), 1);
+}
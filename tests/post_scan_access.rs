@@ -0,0 +1,108 @@
+// ============================================================
+//  tests/post_scan_access.rs - Accept după scan (posibilă intruziune reușită)
+// ============================================================
+
+mod common;
+
+use rust_ids::config::Config;
+use rust_ids::detector::{evaluate_and_record, evaluate_post_scan_access, DetectionResult};
+use rust_ids::parser::{create_parser, create_parser_with_options, LogParser, ParseOutcome, ParserOptions};
+use rust_ids::state::SharedState;
+use std::net::IpAddr;
+
+fn config() -> Config {
+    let mut config = common::config();
+    config.detection.post_scan_access_window_secs = 3600;
+    config
+}
+
+fn accepting_parser() -> Box<dyn LogParser> {
+    create_parser_with_options("gaia", ParserOptions { emit_accepts: true, ..ParserOptions::default() })
+}
+
+fn gaia(action: &str, src: &str, port: u16) -> String {
+    format!("Sep 3 15:12:20 192.168.99.1 Checkpoint: {} {} proto: tcp; service: {}; s_port: 1352", action, src, port)
+}
+
+/// Drop-uri pe porturile 1..=count de la `src`, cu evaluare după fiecare
+fn scan(parser: &dyn LogParser, state: &SharedState, config: &Config, src: &str, count: u16) -> DetectionResult {
+    let mut result = DetectionResult::Clean;
+    for port in 1..=count {
+        let entry = parser.parse(&gaia("drop", src, port)).expect("drop valid");
+        assert!(!entry.accepted);
+        state.record_event(entry.source_ip, port);
+        result = evaluate_and_record(&entry.source_ip, state, &config.detection);
+    }
+    result
+}
+
+#[test]
+fn accepts_are_emitted_only_when_enabled() {
+    let line = gaia("accept", "10.3.0.1", 22);
+
+    assert!(matches!(create_parser("gaia").parse_outcome(&line), ParseOutcome::IgnoredAction(_)));
+
+    let entry = accepting_parser().parse(&line).expect("accept emis cu emit_accepts");
+    assert!(entry.accepted);
+    assert_eq!(entry.action, "accept");
+    assert_eq!(entry.dest_port(), Some(22));
+}
+
+#[test]
+fn accept_on_a_probed_port_after_a_scan_alerts() {
+    let config = config();
+    let parser = accepting_parser();
+    let state = SharedState::new();
+    let src: IpAddr = "10.3.0.2".parse().unwrap();
+
+    assert!(scan(parser.as_ref(), &state, &config, "10.3.0.2", 20).is_threat());
+
+    let accept = parser.parse(&gaia("accept", "10.3.0.2", 7)).unwrap();
+    let result = evaluate_post_scan_access(&src, 7, &state, &config.detection);
+    assert_eq!(result, DetectionResult::PostScanAccess { port: 7 });
+    assert_eq!(result.severity(), 10);
+    assert_eq!(result.sig_id(), "IDS006");
+    assert!(accept.accepted);
+}
+
+#[test]
+fn accept_on_a_port_dropped_after_detection_also_alerts() {
+    let config = config();
+    let parser = accepting_parser();
+    let state = SharedState::new();
+    let src: IpAddr = "10.3.0.3".parse().unwrap();
+
+    assert!(scan(parser.as_ref(), &state, &config, "10.3.0.3", 20).is_threat());
+    state.record_event(src, 8443);
+
+    assert!(evaluate_post_scan_access(&src, 8443, &state, &config.detection).is_threat());
+}
+
+#[test]
+fn accept_without_prior_scan_or_probe_is_clean() {
+    let config = config();
+    let parser = accepting_parser();
+    let state = SharedState::new();
+    let scanner: IpAddr = "10.3.0.4".parse().unwrap();
+    let quiet: IpAddr = "10.3.0.5".parse().unwrap();
+
+    // Câteva drop-uri sub prag: nu e scanner, deci accept-ul e normal
+    assert!(!scan(parser.as_ref(), &state, &config, "10.3.0.5", 3).is_threat());
+    assert_eq!(evaluate_post_scan_access(&quiet, 2, &state, &config.detection), DetectionResult::Clean);
+
+    // Scanner, dar portul accept-ului nu a fost sondat
+    assert!(scan(parser.as_ref(), &state, &config, "10.3.0.4", 20).is_threat());
+    assert_eq!(evaluate_post_scan_access(&scanner, 443, &state, &config.detection), DetectionResult::Clean);
+}
+
+#[test]
+fn disabled_window_never_alerts() {
+    let mut config = config();
+    config.detection.post_scan_access_window_secs = 0;
+    let parser = accepting_parser();
+    let state = SharedState::new();
+    let src: IpAddr = "10.3.0.6".parse().unwrap();
+
+    assert!(scan(parser.as_ref(), &state, &config, "10.3.0.6", 20).is_threat());
+    assert_eq!(evaluate_post_scan_access(&src, 7, &state, &config.detection), DetectionResult::Clean);
+}
@@ -0,0 +1,78 @@
+// ============================================================
+//  tests/record_delimiter.rs - Pachete cu mai multe înregistrări (NUL, text)
+// ============================================================
+
+mod common;
+
+use common::gaia_drop;
+use rust_ids::config::Config;
+use rust_ids::datagram::{decode_into, records};
+use rust_ids::parser::create_parser;
+
+fn config_with(delimiter: &str) -> anyhow::Result<Config> {
+    let base = include_str!("../config.toml").replace("record_delimiter = '\\n'\n", "");
+    let toml = base.replacen("[listener]\n", &format!("[listener]\nrecord_delimiter = {}\n", delimiter), 1);
+    Config::from_toml_str(&toml)
+}
+
+#[test]
+fn default_separator_is_newline() {
+    let config = Config::from_toml_str(include_str!("../config.toml")).unwrap();
+    assert_eq!(config.listener.record_separator(), "\n");
+    assert_eq!(Config::default().listener.record_separator(), "\n");
+}
+
+#[test]
+fn nul_delimited_packet_parses_every_record() {
+    let config = config_with("'\\0'").expect("'\\0' este un separator valid");
+    let separator = config.listener.record_separator();
+    assert_eq!(separator, "\0");
+
+    // Separator și la început, și la sfârșit, și dublat: segmentele goale se sar
+    let packet = format!(
+        "\0{}\0{}\0\0{}\0",
+        gaia_drop("10.5.0.1", 22),
+        gaia_drop("10.5.0.1", 23),
+        gaia_drop("10.5.0.1", 80)
+    );
+    let parser = create_parser("gaia");
+    let ports: Vec<u16> = records(&packet, separator)
+        .map(|record| parser.parse(record).expect("înregistrare validă").dest_port().unwrap())
+        .collect();
+    assert_eq!(ports, [22, 23, 80]);
+}
+
+#[test]
+fn toml_unicode_escape_and_custom_text_separators() {
+    assert_eq!(config_with("\"\\u0000\"").unwrap().listener.record_separator(), "\0");
+
+    let config = config_with("\"<EOR>\"").unwrap();
+    let packet = format!("{}<EOR>{}<EOR>", gaia_drop("10.5.0.2", 22), gaia_drop("10.5.0.2", 443));
+    assert_eq!(records(&packet, config.listener.record_separator()).count(), 2);
+}
+
+#[test]
+fn newline_separator_handles_crlf() {
+    let packet = format!("{}\r\n{}\r\n", gaia_drop("10.5.0.3", 22), gaia_drop("10.5.0.3", 25));
+    let parsed: Vec<u16> = records(&packet, "\n")
+        .filter_map(|record| create_parser("gaia").parse(record).and_then(|e| e.dest_port()))
+        .collect();
+    assert_eq!(parsed, [22, 25]);
+}
+
+#[test]
+fn truncation_cuts_after_the_last_custom_separator() {
+    let packet = format!("{}\0{}", gaia_drop("10.5.0.4", 22), gaia_drop("10.5.0.4", 4433));
+    let capacity = packet.len() - 2;
+    let mut text = String::new();
+    let report = decode_into(&packet.as_bytes()[..capacity], capacity, "\0", &mut text);
+
+    assert!(report.truncated);
+    assert_eq!(records(&text, "\0").collect::<Vec<_>>(), [gaia_drop("10.5.0.4", 22)]);
+}
+
+#[test]
+fn empty_or_unknown_escape_is_rejected() {
+    assert!(config_with("''").is_err());
+    assert!(config_with("'\\q'").is_err());
+}
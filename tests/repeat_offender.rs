@@ -0,0 +1,150 @@
+// ============================================================
+//  tests/repeat_offender.rs - Recidiviști: memoria alertelor peste cleanup
+// ============================================================
+
+mod common;
+
+use common::clock_with_history;
+use rust_ids::config::{Config, RepeatOffenderAction};
+use rust_ids::detector::{evaluate, evaluate_and_record, evaluate_returning_offender, DetectionResult};
+use rust_ids::state::{SharedState, MAX_REPEAT_OFFENDERS};
+use std::net::IpAddr;
+use std::time::Duration;
+
+/// `max_age` al cleanup-ului în teste: istoricul de acum 5 minute expiră
+const CLEANUP_MAX_AGE_SECS: u64 = 60;
+
+fn config(action: RepeatOffenderAction) -> Config {
+    let mut config = common::config();
+    config.detection.repeat_offender_window_hours = 24;
+    config.detection.repeat_offender_action = action;
+    config
+}
+
+/// Starea cu un ceas de test care are deja o oră de "trecut" disponibilă
+fn state(config: &Config) -> SharedState {
+    SharedState::new()
+        .with_clock(clock_with_history(Duration::from_secs(3600)))
+        .with_repeat_offender_window(config.detection.repeat_offender_window())
+}
+
+// ---------------------------------------------------------------------------
+// Alertă -> perioadă de liniște: un slow scan de acum 5 minute (alertat, ca
+// în `raise_port_alert`), apoi cleanup-ul care îi șterge istoricul
+// ---------------------------------------------------------------------------
+fn alert_then_go_quiet(state: &SharedState, config: &Config, ip: IpAddr) {
+    let five_minutes_ago = state.now() - Duration::from_secs(300);
+    for port in 1..=40 {
+        state.record_event_at(ip, port, five_minutes_ago);
+    }
+    assert!(evaluate_and_record(&ip, state, &config.detection).is_threat());
+    state.remember_offender(ip);
+
+    assert_eq!(state.cleanup_old_entries(CLEANUP_MAX_AGE_SECS), 1);
+    assert_eq!(state.unique_ports_in_window(&ip, 3600), 0, "istoricul a fost șters de cleanup");
+}
+
+#[test]
+fn returning_offender_is_notified_on_its_first_drop() {
+    let config = config(RepeatOffenderAction::Notify);
+    let state = state(&config);
+    let ip: IpAddr = "10.7.0.1".parse().unwrap();
+    alert_then_go_quiet(&state, &config, ip);
+
+    // Un singur drop nou: notificarea se evaluează înainte de înregistrare
+    let result = evaluate_returning_offender(&ip, &state, &config.detection);
+    assert!(matches!(result, DetectionResult::KnownOffenderReturned { alerts: 1, since_alert_secs: 0 }));
+    assert_eq!(result.sig_id(), "IDS007");
+    assert_eq!(result.severity(), 3);
+    state.record_event(ip, 22);
+
+    // Următoarele drop-uri nu mai sunt o "revenire", iar pragurile sunt cele normale
+    assert_eq!(evaluate_returning_offender(&ip, &state, &config.detection), DetectionResult::Clean);
+    assert_eq!(evaluate(&ip, &state, &config.detection), DetectionResult::Clean);
+}
+
+#[test]
+fn returning_offender_gets_lowered_thresholds() {
+    let config = config(RepeatOffenderAction::LowerThresholds);
+    let state = state(&config);
+    let offender: IpAddr = "10.7.0.2".parse().unwrap();
+    let newcomer: IpAddr = "10.7.0.3".parse().unwrap();
+    alert_then_go_quiet(&state, &config, offender);
+
+    // Fără notificare în acest mod
+    assert_eq!(evaluate_returning_offender(&offender, &state, &config.detection), DetectionResult::Clean);
+
+    // Fast scan: prag 15 porturi, redus la 8 (factor 0.5); 9 porturi ajung doar pentru recidivist
+    for port in 1..=9 {
+        state.record_event(offender, port);
+        state.record_event(newcomer, port);
+    }
+    match evaluate(&offender, &state, &config.detection) {
+        DetectionResult::Scan(hit) => assert_eq!((hit.tier.as_str(), hit.ports), ("fast", 9)),
+        other => panic!("fast scan așteptat pentru recidivist, nu {:?}", other),
+    }
+    assert_eq!(evaluate(&newcomer, &state, &config.detection), DetectionResult::Clean);
+}
+
+#[test]
+fn memory_survives_cleanup_but_expires_after_its_window() {
+    let config = config(RepeatOffenderAction::Notify);
+    let state = SharedState::new()
+        .with_clock(clock_with_history(Duration::from_secs(900)))
+        .with_repeat_offender_window(Duration::from_secs(600));
+    let recent: IpAddr = "10.7.0.4".parse().unwrap();
+    let expired: IpAddr = "10.7.0.5".parse().unwrap();
+
+    state.remember_offender(recent);
+    state.remember_offender_at(expired, state.now() - Duration::from_secs(900));
+    assert!(state.repeat_offender(&expired).is_none());
+
+    state.cleanup_old_entries(0);
+    assert_eq!(state.stats().repeat_offenders, 1);
+    assert!(state.repeat_offender(&recent).is_some());
+    assert!(evaluate_returning_offender(&recent, &state, &config.detection).is_threat());
+}
+
+#[test]
+fn memory_is_disabled_without_a_window() {
+    let config = common::config();
+    assert!(!config.detection.repeat_offender_enabled());
+
+    let state = SharedState::new();
+    let ip: IpAddr = "10.7.0.6".parse().unwrap();
+    state.remember_offender(ip);
+    assert_eq!(state.stats().repeat_offenders, 0);
+    assert_eq!(evaluate_returning_offender(&ip, &state, &config.detection), DetectionResult::Clean);
+}
+
+#[test]
+fn memory_is_capped_with_lru_eviction() {
+    let state = SharedState::new()
+        .with_clock(clock_with_history(Duration::from_secs(60)))
+        .with_repeat_offender_window(Duration::from_secs(3600));
+    let oldest: IpAddr = "10.8.0.0".parse().unwrap();
+    state.remember_offender_at(oldest, state.now() - Duration::from_secs(60));
+
+    for n in 1..=MAX_REPEAT_OFFENDERS as u32 {
+        state.remember_offender(IpAddr::from([10, 9, (n >> 8) as u8, n as u8]));
+    }
+
+    assert_eq!(state.stats().repeat_offenders, MAX_REPEAT_OFFENDERS);
+    assert!(state.repeat_offender(&oldest).is_none(), "cea mai veche alertă este evacuată prima");
+}
+
+#[test]
+fn threshold_factor_must_be_in_unit_interval() {
+    for factor in ["0.0", "1.5", "-0.5"] {
+        let toml = include_str!("../config.toml").replace(
+            "repeat_offender_threshold_factor = 0.5",
+            &format!("repeat_offender_threshold_factor = {}", factor),
+        );
+        assert!(Config::from_toml_str(&toml).is_err(), "factor {} trebuie respins", factor);
+    }
+
+    let toml = include_str!("../config.toml")
+        .replace("repeat_offender_action           = \"notify\"", "repeat_offender_action = \"lower_thresholds\"");
+    let config = Config::from_toml_str(&toml).unwrap();
+    assert_eq!(config.detection.repeat_offender_action, RepeatOffenderAction::LowerThresholds);
+}
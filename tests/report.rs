@@ -0,0 +1,238 @@
+// ============================================================
+//  tests/report.rs - `rust-ids report`: jurnale -> sumarul perioadei
+// ============================================================
+
+mod common;
+
+use chrono::{DateTime, Duration, TimeZone, Utc, Weekday};
+use common::{config_with, drop_from, ip};
+use rust_ids::alert_log::{AlertLog, AlertRecord};
+use rust_ids::config::{AlertLogConfig, Config};
+use rust_ids::detector::{DetectionResult, TierHit};
+use rust_ids::event_log::EventLogWriter;
+use rust_ids::parser::LogEntry;
+use rust_ids::report::{self, parse_since, AttackerRow, ReportBuilder, ReportFormat, ReportSchedule};
+use std::fs;
+use std::path::PathBuf;
+
+/// Un director gol, propriu testului
+fn scratch_dir(name: &str) -> PathBuf {
+    let dir = std::env::temp_dir().join(format!("rust-ids-report-{}-{}", name, std::process::id()));
+    let _ = fs::remove_dir_all(&dir);
+    fs::create_dir_all(&dir).unwrap();
+    dir
+}
+
+fn at(day: u32, hour: u32) -> DateTime<Utc> {
+    Utc.with_ymd_and_hms(2026, 10, day, hour, 0, 0).unwrap()
+}
+
+fn alert(src: &str, scan_type: &str, ports: usize, timestamp: DateTime<Utc>) -> AlertRecord {
+    AlertRecord {
+        timestamp,
+        ip: ip(src),
+        scan_type: scan_type.to_string(),
+        severity: 7,
+        unique_ports: ports,
+        labels: Vec::new(),
+    }
+}
+
+fn event(src: &str, port: u16, timestamp: DateTime<Utc>) -> LogEntry {
+    LogEntry { timestamp, ..drop_from(src, port) }
+}
+
+#[test]
+fn since_accepts_hours_and_days_only() {
+    assert_eq!(parse_since("24h").unwrap(), Duration::hours(24));
+    assert_eq!(parse_since("7d").unwrap(), Duration::days(7));
+
+    // Sufix multi-byte: eroare, nu panică la tăierea șirului
+    for bad in ["5ș", "ș", "", "d", "0d", "7w", "-1d", "367d", "4294967295d"] {
+        let err = parse_since(bad).expect_err(bad);
+        assert!(err.to_string().contains("--since"), "{}", err);
+    }
+}
+
+#[test]
+fn schedule_parses_and_finds_the_next_run() {
+    let daily = ReportSchedule::try_from("daily@07:00".to_string()).unwrap();
+    assert_eq!(daily.weekday, None);
+    assert_eq!(daily.to_string(), "daily@07:00");
+    assert_eq!(daily.period(), Duration::days(1));
+    assert_eq!(daily.next_after(&at(18, 6)), at(18, 7));
+    assert_eq!(daily.next_after(&at(18, 7)), at(19, 7), "strict după momentul curent");
+
+    // 2026-10-18 este duminică
+    let weekly = ReportSchedule::try_from("weekly@mon@07:00".to_string()).unwrap();
+    assert_eq!(weekly.weekday, Some(Weekday::Mon));
+    assert_eq!(weekly.to_string(), "weekly@mon@07:00");
+    assert_eq!(weekly.period(), Duration::days(7));
+    assert_eq!(weekly.next_after(&at(18, 12)), at(19, 7));
+    assert_eq!(weekly.next_after(&at(19, 8)), at(26, 7));
+
+    for bad in ["daily", "daily@25:00", "hourly@07:00", "weekly@07:00", "weekly@someday@07:00"] {
+        assert!(ReportSchedule::try_from(bad.to_string()).is_err(), "{}", bad);
+    }
+}
+
+#[test]
+fn builder_splits_current_and_previous_periods() {
+    // Perioada: (11 oct 07:00, 18 oct 07:00]; anterioara: (4 oct, 11 oct]
+    let mut builder = ReportBuilder::new(at(18, 7), Duration::days(7));
+    builder.add_alert(&alert("10.0.0.1", "FAST_SCAN", 20, at(17, 10)));
+    builder.add_alert(&alert("10.0.0.1", "FAST+SLOW_SCAN", 45, at(17, 11)));
+    builder.add_alert(&alert("10.0.0.2", "FAST_SCAN", 18, at(16, 9)));
+    builder.add_alert(&alert("10.0.0.3", "ICMP_SWEEP", 0, at(12, 9)));
+    builder.add_alert(&alert("10.0.0.9", "FAST_SCAN", 30, at(8, 9)));
+    builder.add_alert(&alert("10.0.0.9", "FAST_SCAN", 30, at(1, 9)));
+
+    // Evenimentele sosesc după alerte: porturile blocate ale atacatorilor
+    for port in 1000..1050 {
+        builder.add_event(&event("10.0.0.2", port, at(16, 8)));
+    }
+    builder.add_event(&event("10.0.0.5", 22, at(15, 8)));
+    builder.add_event(&event("10.0.0.9", 22, at(9, 8)));
+    let report = builder.finish();
+
+    assert_eq!(report.current.alerts, 4);
+    assert_eq!(report.current.attackers, 3);
+    assert_eq!(report.current.events, Some(51));
+    assert_eq!(report.current.sources, Some(2));
+    assert_eq!(report.previous.alerts, 1, "alerta de pe 1 oct e în afara ambelor perioade");
+    assert_eq!(report.previous.events, Some(1));
+
+    assert_eq!(
+        report.alerts_by_type,
+        [("FAST_SCAN".to_string(), 2), ("FAST+SLOW_SCAN".to_string(), 1), ("ICMP_SWEEP".to_string(), 1)]
+    );
+    assert_eq!(
+        report.top_attackers,
+        [
+            AttackerRow { ip: ip("10.0.0.1"), alerts: 2, ports: 45, events: Some(0) },
+            AttackerRow { ip: ip("10.0.0.2"), alerts: 1, ports: 50, events: Some(50) },
+            AttackerRow { ip: ip("10.0.0.3"), alerts: 1, ports: 0, events: Some(0) },
+        ]
+    );
+
+    let text = report.render_text();
+    assert!(text.contains("Perioada: 2026-10-11 07:00 UTC - 2026-10-18 07:00 UTC"), "{}", text);
+    assert!(text.contains("+3 (+300%)"), "alerte 4 vs 1:\n{}", text);
+    assert!(text.contains("+50 (+5000%)"), "evenimente 51 vs 1:\n{}", text);
+}
+
+#[test]
+fn top_attackers_are_capped_and_events_are_optional() {
+    let mut builder = ReportBuilder::new(at(18, 7), Duration::days(1));
+    for i in 0..25u8 {
+        builder.add_alert(&alert(&format!("10.1.0.{}", i), "SLOW_SCAN", usize::from(i), at(18, 1)));
+    }
+    let report = builder.finish();
+
+    assert_eq!(report.top_attackers.len(), report::TOP_ATTACKERS);
+    assert_eq!(report.top_attackers[0].ip, ip("10.1.0.24"), "la alerte egale: mai multe porturi primele");
+    assert_eq!(report.current.events, None);
+    assert_eq!(report.previous.attackers, 0);
+
+    let text = report.render_text();
+    assert!(text.contains("+25 (nou)"), "{}", text);
+    assert!(text.contains("activați [event_log] enabled = true"), "{}", text);
+}
+
+#[test]
+fn html_is_a_single_self_contained_file() {
+    let mut builder = ReportBuilder::new(at(18, 7), Duration::days(1));
+    builder.add_alert(&alert("10.2.0.1", "<script>", 20, at(18, 1)));
+    let html = builder.finish().render(ReportFormat::Html);
+
+    assert!(html.starts_with("<!DOCTYPE html>") && html.ends_with("</html>\n"));
+    assert!(html.contains("<style>") && html.contains("<table>"));
+    for external in ["<link", "<script", "src=", "href=", "http"] {
+        assert!(!html.contains(external), "fără resurse externe: {}", external);
+    }
+    assert!(html.contains("&lt;script&gt;"), "textul din jurnal este escapat");
+}
+
+#[test]
+fn missing_alert_log_names_the_feature_to_enable() {
+    let err = report::build(&Config::default(), Duration::days(7), Utc::now()).expect_err("fără [alert_log]");
+    assert!(err.to_string().contains("[alert_log] enabled = true"), "{}", err);
+
+    let dir = scratch_dir("missing");
+    let mut config = common::config();
+    config.alert_log.enabled = true;
+    config.alert_log.path = dir.join("alerts.jsonl").display().to_string();
+    let err = report::build(&config, Duration::days(7), Utc::now()).expect_err("fișier inexistent");
+    assert!(err.to_string().contains("nu există"), "{}", err);
+    let _ = fs::remove_dir_all(&dir);
+}
+
+#[test]
+fn build_reads_current_and_rotated_logs() {
+    let dir = scratch_dir("build");
+    let mut config = common::config();
+    config.alert_log = AlertLogConfig {
+        enabled:           true,
+        path:              dir.join("alerts.jsonl").display().to_string(),
+        rotate_size_bytes: 0,
+    };
+    config.event_log.enabled = true;
+    config.event_log.path = dir.join("events.jsonl").display().to_string();
+    let now = Utc::now();
+
+    // Alertele vechi au ajuns într-un fișier rotit
+    let rotated = format!("{}.20261018-070000", config.alert_log.path);
+    let mut writer = EventLogWriter::open(&rotated, 0).unwrap();
+    writer.append(&alert("10.3.0.1", "FAST_SCAN", 20, now - Duration::hours(30))).unwrap();
+    writer.flush().unwrap();
+
+    let hit = TierHit { tier: "fast".to_string(), ports: 16, window_secs: 10, severity: 9, sig_id: "IDS001".to_string() };
+    let alert_log = AlertLog::open(&config.alert_log).unwrap();
+    alert_log.record(&AlertRecord::new(ip("10.3.0.1"), &DetectionResult::Scan(hit), 9, &[], now - Duration::hours(2))).unwrap();
+    drop(alert_log);
+    // O linie pe jumătate scrisă (daemon-ul încă scrie) este ignorată
+    let mut text = fs::read_to_string(&config.alert_log.path).unwrap();
+    text.push_str("{\"timestamp\":");
+    fs::write(&config.alert_log.path, text).unwrap();
+
+    let mut events = EventLogWriter::open(&config.event_log.path, 0).unwrap();
+    for port in [22, 80, 443] {
+        events.append(&event("10.3.0.1", port, now - Duration::hours(3))).unwrap();
+    }
+    events.flush().unwrap();
+
+    let report = report::build(&config, Duration::days(1), now).unwrap();
+    assert_eq!(report.current.alerts, 1);
+    assert_eq!(report.previous.alerts, 1, "alerta din fișierul rotit");
+    assert_eq!(report.current.events, Some(3));
+    assert_eq!(report.alerts_by_type, [("FAST_SCAN".to_string(), 1)]);
+    assert_eq!(report.top_attackers, [AttackerRow { ip: ip("10.3.0.1"), alerts: 1, ports: 16, events: Some(3) }]);
+    assert_eq!(report.skipped_lines, 1);
+    let _ = fs::remove_dir_all(&dir);
+}
+
+#[test]
+fn scheduled_report_needs_the_alert_log_and_recipients() {
+    let schedule = [("# schedule = \"daily@07:00\"", "schedule = \"daily@07:00\"")];
+    let text = include_str!("../config.toml").replacen(schedule[0].0, schedule[0].1, 1);
+    let err = Config::from_toml_str(&text).expect_err("fără [alert_log]");
+    assert!(err.to_string().contains("[alert_log] enabled = true"), "{}", err);
+
+    let alert_log = ("enabled           = false\npath              = \"alerts.jsonl\"", "enabled           = true\npath              = \"alerts.jsonl\"");
+    let text = text.replacen(alert_log.0, alert_log.1, 1);
+    let err = Config::from_toml_str(&text).expect_err("fără destinatari");
+    assert!(err.to_string().contains("[report] to"), "{}", err);
+
+    let err = Config::from_toml_str(&text.replacen("to     = []", "to     = [\"nu e adresă\"]", 1)).expect_err("adresă invalidă");
+    assert!(err.to_string().contains("nu e adresă"), "{}", err);
+
+    let config = config_with(&[
+        schedule[0],
+        alert_log,
+        ("to     = []", "to     = [\"soc@company.com\", \"CISO <ciso@company.com>\"]"),
+        ("format = \"html\"", "format = \"text\""),
+    ]);
+    assert_eq!(config.report.schedule.map(|s| s.to_string()).as_deref(), Some("daily@07:00"));
+    assert_eq!(config.report.to.len(), 2);
+    assert_eq!(config.report.format, ReportFormat::Text);
+}
@@ -0,0 +1,182 @@
+// ============================================================
+//  tests/siem_format.rs - Mesajele CEF / LEEF pentru fiecare tip de detecție
+// ============================================================
+//
+//  Snapshot-uri exacte ale payload-ului (fără header-ul Syslog, care
+//  conține ora curentă). Ambele formate pleacă din același `AlertFields`,
+//  deci valorile trebuie să coincidă câmp cu câmp.
+// ============================================================
+
+mod common;
+
+use common::ip;
+use rust_ids::alert_fields::{detection_fields, AlertFields};
+use rust_ids::config::SiemFormat;
+use rust_ids::detector::{DetectionResult, TierHit};
+use rust_ids::state::{RuleSummary, TargetSummary};
+use std::time::Instant;
+
+fn hit(tier: &str, ports: usize, window_secs: u64, severity: u8, sig_id: &str) -> TierHit {
+    TierHit { tier: tier.to_string(), ports, window_secs, severity, sig_id: sig_id.to_string() }
+}
+
+fn fields(result: &DetectionResult, targets: &[TargetSummary]) -> AlertFields {
+    detection_fields(&ip("1.2.3.4"), result, targets, &[], result.severity()).expect("rezultat de alertă")
+}
+
+#[test]
+fn scan_snapshots() {
+    let result = DetectionResult::Scan(hit("fast", 23, 60, 8, "IDS001"));
+    let alert = fields(&result, &[]);
+
+    assert_eq!(
+        alert.to_cef(),
+        "CEF:0|RustIDS|NetworkScanner|0.1.0|IDS001|Fast Port Scan Detected|8|\
+         src=1.2.3.4 cs1Label=ScanType cs1=FastScan cs2Label=UniquePorts cs2=23 cs3Label=WindowSecs cs3=60"
+    );
+    assert_eq!(
+        alert.to_leef('\t'),
+        "LEEF:2.0|RustIDS|NetworkScanner|0.1.0|IDS001|cat=FastScan\tsrc=1.2.3.4\tuniquePorts=23\twindowSecs=60\tsev=8"
+    );
+}
+
+#[test]
+fn slow_scan_keeps_the_window_in_minutes() {
+    // Eticheta dinaintea tier-urilor configurabile: regulile SIEM existente o citesc
+    let result = DetectionResult::Scan(hit("slow", 41, 3600, 6, "IDS002"));
+    let alert = fields(&result, &[]);
+
+    assert_eq!(
+        alert.to_cef(),
+        "CEF:0|RustIDS|NetworkScanner|0.1.0|IDS002|Slow Port Scan Detected|6|\
+         src=1.2.3.4 cs1Label=ScanType cs1=SlowScan cs2Label=UniquePorts cs2=41 cs3Label=WindowMins cs3=60"
+    );
+    assert_eq!(
+        alert.to_leef('\t'),
+        "LEEF:2.0|RustIDS|NetworkScanner|0.1.0|IDS002|cat=SlowScan\tsrc=1.2.3.4\tuniquePorts=41\twindowMins=60\tsev=6"
+    );
+}
+
+#[test]
+fn multi_scan_snapshots() {
+    let result = DetectionResult::MultiScan(vec![hit("fast", 23, 10, 8, "IDS001"), hit("slow", 41, 3600, 6, "IDS002")]);
+    let alert = fields(&result, &[]);
+
+    // Alertă multi-tier: semnătură proprie, severitatea maximă + 1
+    assert_eq!(
+        alert.to_cef(),
+        "CEF:0|RustIDS|NetworkScanner|0.1.0|IDS003|Combined Fast+Slow Port Scan Detected|9|\
+         src=1.2.3.4 cs1Label=ScanType cs1=FastAndSlowScan cs2Label=UniquePorts cs2=41 \
+         cs3Label=Tiers cs3=fast\\=23/10s,slow\\=41/3600s"
+    );
+    assert_eq!(
+        alert.to_leef('\t'),
+        "LEEF:2.0|RustIDS|NetworkScanner|0.1.0|IDS003|cat=FastAndSlowScan\tsrc=1.2.3.4\tuniquePorts=41\t\
+         tiers=fast=23/10s,slow=41/3600s\tsev=9"
+    );
+}
+
+#[test]
+fn distributed_scan_snapshots() {
+    let result = DetectionResult::DistributedScan {
+        dest:               Some(ip("10.0.0.1")),
+        sources:            6,
+        total_unique_ports: 48,
+        window_secs:        300,
+        sample:             vec![ip("192.0.2.1"), ip("192.0.2.2")],
+    };
+    let alert = fields(&result, &[]);
+
+    assert_eq!(
+        alert.to_cef(),
+        "CEF:0|RustIDS|NetworkScanner|0.1.0|IDS004|Distributed Port Scan Detected|8|\
+         dst=10.0.0.1 cs1Label=ScanType cs1=DistributedScan cs2Label=UniquePorts cs2=48 \
+         cs3Label=WindowSecs cs3=300 cn1Label=SourceCount cn1=6 cs4Label=SampleSources cs4=192.0.2.1,192.0.2.2"
+    );
+    assert_eq!(
+        alert.to_leef('\t'),
+        "LEEF:2.0|RustIDS|NetworkScanner|0.1.0|IDS004|cat=DistributedScan\tdst=10.0.0.1\tuniquePorts=48\t\
+         windowSecs=300\tsourceCount=6\tsampleSources=192.0.2.1,192.0.2.2\tsev=8"
+    );
+}
+
+#[test]
+fn icmp_sweep_snapshots() {
+    let result = DetectionResult::IcmpSweep { events: 30, destinations: 25, window_secs: 60 };
+    let alert = fields(&result, &[]);
+
+    assert_eq!(
+        alert.to_cef(),
+        "CEF:0|RustIDS|NetworkScanner|0.1.0|IDS005|ICMP Sweep Detected|5|\
+         src=1.2.3.4 proto=ICMP cs1Label=ScanType cs1=IcmpSweep cn1Label=IcmpEvents cn1=30 \
+         cn2Label=Destinations cn2=25 cs3Label=WindowSecs cs3=60"
+    );
+    assert_eq!(
+        alert.to_leef('\t'),
+        "LEEF:2.0|RustIDS|NetworkScanner|0.1.0|IDS005|cat=IcmpSweep\tsrc=1.2.3.4\tproto=ICMP\ticmpEvents=30\t\
+         destinations=25\twindowSecs=60\tsev=5"
+    );
+}
+
+#[test]
+fn post_scan_access_snapshots() {
+    let result = DetectionResult::PostScanAccess { port: 22 };
+    let alert = fields(&result, &[]);
+
+    assert_eq!(
+        alert.to_cef(),
+        "CEF:0|RustIDS|NetworkScanner|0.1.0|IDS006|Access After Port Scan Detected|10|\
+         src=1.2.3.4 dpt=22 cs1Label=ScanType cs1=PostScanAccess"
+    );
+    assert_eq!(
+        alert.to_leef('\t'),
+        "LEEF:2.0|RustIDS|NetworkScanner|0.1.0|IDS006|cat=PostScanAccess\tsrc=1.2.3.4\tdstPort=22\tsev=10"
+    );
+}
+
+#[test]
+fn clean_has_no_alert_fields() {
+    assert!(detection_fields(&ip("1.2.3.4"), &DetectionResult::Clean, &[], &[], 0).is_none());
+}
+
+#[test]
+fn top_targets_and_custom_leef_delimiter() {
+    let result = DetectionResult::Scan(hit("fast", 23, 60, 8, "IDS001"));
+    let targets = [TargetSummary { dest: ip("10.0.0.5"), unique_ports: 23, hits: 40, last_seen: Instant::now() }];
+    let alert = fields(&result, &targets);
+
+    assert!(alert.to_cef().ends_with(" cs4Label=TopTargets cs4=10.0.0.5[23p/40h]"));
+    // Un delimitator diferit de TAB se declară în header
+    assert_eq!(
+        alert.to_leef('^'),
+        "LEEF:2.0|RustIDS|NetworkScanner|0.1.0|IDS001|^|cat=FastScan^src=1.2.3.4^uniquePorts=23^windowSecs=60^\
+         topTargets=10.0.0.5[23p/40h]^sev=8"
+    );
+}
+
+#[test]
+fn top_rules_follow_top_targets() {
+    let result = DetectionResult::Scan(hit("fast", 23, 60, 8, "IDS001"));
+    let targets = [TargetSummary { dest: ip("10.0.0.5"), unique_ports: 23, hits: 40, last_seen: Instant::now() }];
+    let rules = [
+        RuleSummary { rule: "Block_Inbound".to_string(), hits: 38 },
+        RuleSummary { rule: "42".to_string(), hits: 2 },
+    ];
+    let alert = detection_fields(&ip("1.2.3.4"), &result, &targets, &rules, 8).expect("rezultat de alertă");
+
+    assert!(alert
+        .to_cef()
+        .ends_with(" cs4Label=TopTargets cs4=10.0.0.5[23p/40h] cs5Label=TopRules cs5=Block_Inbound[38h],42[2h]"));
+    assert!(alert.to_leef('\t').ends_with("\ttopRules=Block_Inbound[38h],42[2h]\tsev=8"));
+}
+
+#[test]
+fn syslog_header_precedes_the_chosen_format() {
+    let result = DetectionResult::Scan(hit("fast", 23, 60, 8, "IDS001"));
+    let alert = fields(&result, &[]);
+
+    let cef = alert.to_syslog(SiemFormat::Cef, '\t');
+    let leef = alert.to_syslog(SiemFormat::Leef, '\t');
+    assert!(cef.ends_with(&format!(" rust-ids {}", alert.to_cef())));
+    assert!(leef.ends_with(&format!(" rust-ids {}", alert.to_leef('\t'))));
+}
@@ -0,0 +1,72 @@
+// ============================================================
+//  tests/tcp_limits.rs - Listener-ul TCP: timeout de citire și limita de conexiuni
+// ============================================================
+
+use rust_ids::config::Config;
+use rust_ids::tcp::{serve, TcpLimits};
+use std::net::SocketAddr;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use tokio::net::{TcpListener, TcpStream};
+
+/// Pornește `serve` pe un port liber; liniile primite ajung în vectorul returnat
+async fn start(limits: TcpLimits) -> (SocketAddr, Arc<Mutex<Vec<String>>>) {
+    let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let addr = listener.local_addr().unwrap();
+    let lines = Arc::new(Mutex::new(Vec::new()));
+    let sink = Arc::clone(&lines);
+    tokio::spawn(serve(listener, limits, move |line, _peer| sink.lock().unwrap().push(line)));
+    (addr, lines)
+}
+
+/// `true` dacă serverul a închis conexiunea (EOF) în `within`
+async fn closed_within(stream: &mut TcpStream, within: Duration) -> bool {
+    let mut buf = [0u8; 16];
+    matches!(tokio::time::timeout(within, stream.read(&mut buf)).await, Ok(Ok(0) | Err(_)))
+}
+
+#[tokio::test]
+async fn connections_over_the_limit_are_rejected() {
+    let (addr, lines) = start(TcpLimits { read_timeout: Duration::from_secs(30), max_connections: 1 }).await;
+
+    let mut first = TcpStream::connect(addr).await.unwrap();
+    first.write_all(b"linia 1\n").await.unwrap();
+    let mut second = TcpStream::connect(addr).await.unwrap();
+    assert!(closed_within(&mut second, Duration::from_secs(2)).await, "a doua conexiune e respinsă");
+
+    // Prima conexiune rămâne deschisă și funcțională
+    first.write_all(b"linia 2\r\n").await.unwrap();
+    assert!(!closed_within(&mut first, Duration::from_millis(200)).await);
+    assert_eq!(*lines.lock().unwrap(), ["linia 1", "linia 2"]);
+
+    // Slotul se eliberează la închidere
+    drop(first);
+    tokio::time::sleep(Duration::from_millis(100)).await;
+    let mut third = TcpStream::connect(addr).await.unwrap();
+    third.write_all(b"linia 3\n").await.unwrap();
+    assert!(!closed_within(&mut third, Duration::from_millis(200)).await);
+    assert_eq!(lines.lock().unwrap().last().map(String::as_str), Some("linia 3"));
+}
+
+#[tokio::test]
+async fn idle_connection_is_closed_after_the_read_timeout() {
+    let (addr, _lines) = start(TcpLimits { read_timeout: Duration::from_millis(300), max_connections: 4 }).await;
+
+    let mut idle = TcpStream::connect(addr).await.unwrap();
+    assert!(!closed_within(&mut idle, Duration::from_millis(100)).await, "încă deschisă înainte de timeout");
+    assert!(closed_within(&mut idle, Duration::from_secs(2)).await, "închisă după timeout");
+}
+
+#[test]
+fn zero_tcp_limits_are_rejected_at_load() {
+    for (from, to) in [
+        ("read_timeout_secs = 300", "read_timeout_secs = 0"),
+        ("max_connections   = 256", "max_connections   = 0"),
+    ] {
+        let text = include_str!("../config.toml").replacen(from, to, 1);
+        let err = Config::from_toml_str(&text).expect_err("limită 0 invalidă");
+        let field = to.split_whitespace().next().unwrap();
+        assert!(err.to_string().contains(field), "{}", err);
+    }
+}
@@ -0,0 +1,118 @@
+// ============================================================
+//  tests/throttle.rs - Throttling-ul evaluării per sursă
+// ============================================================
+//
+//  Un flood de la aceeași sursă nu trebuie să declanșeze o evaluare
+//  completă la fiecare pachet, dar trecerea la amenințare nu are voie să
+//  întârzie mai mult de `evaluation_min_interval_ms`.
+// ============================================================
+
+use rust_ids::clock::MockClock;
+use rust_ids::config::Config;
+use rust_ids::detector::{evaluate_and_record, evaluate_throttled, Throttled};
+use rust_ids::state::SharedState;
+use std::net::IpAddr;
+use std::sync::Arc;
+use std::time::Duration;
+
+fn config(min_interval_ms: u64) -> Config {
+    let mut config =
+        Config::from_toml_str(include_str!("../config.toml")).expect("config.toml din repo trebuie să fie valid");
+    config.detection.evaluation_min_interval_ms = min_interval_ms;
+    config
+}
+
+#[test]
+fn flood_from_one_source_is_evaluated_far_less_than_per_packet() {
+    let config = config(100);
+    let state = SharedState::new();
+    let src: IpAddr = "10.2.0.1".parse().unwrap();
+    let packets = 10_000u64;
+
+    // Aceleași câteva porturi, repetat: sub orice prag, deci mereu "curat"
+    let mut deferred = 0;
+    for i in 0..packets {
+        state.record_event(src, 1000 + (i % 5) as u16);
+        match evaluate_throttled(&src, &state, &config.detection) {
+            Throttled::Evaluated(result) => assert!(!result.is_threat()),
+            Throttled::Deferred(_) => deferred += 1,
+            Throttled::Skipped => {}
+        }
+    }
+
+    let evaluations = state.evaluations_performed();
+    println!("{} pachete -> {} evaluări ({} amânate)", packets, evaluations, deferred);
+    assert!(evaluations >= 1);
+    assert!(evaluations * 100 < packets, "{} evaluări pentru {} pachete", evaluations, packets);
+    // Cel mult o evaluare amânată programată per interval curat
+    assert!(deferred <= evaluations);
+}
+
+#[test]
+fn threshold_crossed_within_interval_is_caught_by_deferred_evaluation() {
+    let config = config(50);
+    let clock = Arc::new(MockClock::new());
+    let state = SharedState::new().with_clock(clock.clone());
+    let src: IpAddr = "10.2.0.2".parse().unwrap();
+    let threshold = config.detection.fast_scan_ports as u16;
+
+    // Prima evaluare: curată, pornește intervalul
+    state.record_event(src, 1);
+    assert!(matches!(
+        evaluate_throttled(&src, &state, &config.detection),
+        Throttled::Evaluated(ref r) if !r.is_threat()
+    ));
+
+    // Pragul este depășit în interval: evaluarea este amânată, nu pierdută
+    let mut delay = None;
+    for port in 2..=threshold + 1 {
+        state.record_event(src, port);
+        match evaluate_throttled(&src, &state, &config.detection) {
+            Throttled::Deferred(d) => delay = Some(d),
+            Throttled::Skipped => {}
+            Throttled::Evaluated(result) => panic!("evaluare neașteptată în interval: {:?}", result),
+        }
+    }
+    let delay = delay.expect("prima evaluare din interval trebuie amânată");
+    assert!(delay <= Duration::from_millis(50));
+
+    // Evaluarea amânată (ce programează apelantul după `delay`) vede pragul
+    clock.advance(delay);
+    assert!(evaluate_and_record(&src, &state, &config.detection).is_threat());
+
+    // O amenințare nu intră în throttling: următorul pachet e evaluat imediat
+    state.record_event(src, threshold + 2);
+    assert!(matches!(
+        evaluate_throttled(&src, &state, &config.detection),
+        Throttled::Evaluated(ref r) if r.is_threat()
+    ));
+}
+
+#[test]
+fn zero_interval_evaluates_every_packet() {
+    let config = config(0);
+    let state = SharedState::new();
+    let src: IpAddr = "10.2.0.3".parse().unwrap();
+
+    for _ in 0..200 {
+        state.record_event(src, 22);
+        assert!(matches!(evaluate_throttled(&src, &state, &config.detection), Throttled::Evaluated(_)));
+    }
+    assert_eq!(state.evaluations_performed(), 200);
+}
+
+#[test]
+fn throttling_is_tracked_per_source() {
+    let config = config(1000);
+    let state = SharedState::new();
+    let a: IpAddr = "10.2.0.4".parse().unwrap();
+    let b: IpAddr = "10.2.0.5".parse().unwrap();
+
+    state.record_event(a, 22);
+    assert!(matches!(evaluate_throttled(&a, &state, &config.detection), Throttled::Evaluated(_)));
+    assert!(matches!(evaluate_throttled(&a, &state, &config.detection), Throttled::Deferred(_)));
+    assert!(matches!(evaluate_throttled(&a, &state, &config.detection), Throttled::Skipped));
+
+    state.record_event(b, 22);
+    assert!(matches!(evaluate_throttled(&b, &state, &config.detection), Throttled::Evaluated(_)));
+}
@@ -0,0 +1,91 @@
+// ============================================================
+//  tests/tiers.rs - Tier-uri de detecție configurabile ([[detection.tier]])
+// ============================================================
+
+mod common;
+
+use rust_ids::config::Config;
+use rust_ids::detector::{evaluate, DetectionResult};
+use rust_ids::state::SharedState;
+use std::net::IpAddr;
+
+/// config.toml din repo, cu blocurile `[[detection.tier]]` date adăugate
+/// la finalul secțiunii [detection]
+fn config_with_tiers(tiers: &str) -> Config {
+    common::config_with(&[("\n[siem]\n", &format!("\n{}\n\n[siem]\n", tiers))])
+}
+
+const MEDIUM: &str = "[[detection.tier]]\nname = \"medium\"\nports = 5\nwindow_secs = 60\n";
+
+fn scan(state: &SharedState, src: IpAddr, ports: u16) {
+    for port in 1..=ports {
+        state.record_event(src, port);
+    }
+}
+
+#[test]
+fn custom_tiers_resolve_next_to_the_builtin_ones() {
+    let config = config_with_tiers(MEDIUM);
+    let tiers: Vec<(&str, usize, u64, u8, String)> = config
+        .detection
+        .tiers
+        .iter()
+        .map(|t| (t.name.as_str(), t.ports, t.window_secs, t.severity, t.sig_id()))
+        .collect();
+
+    // Ordonate după fereastră; valorile implicite ale unui tier propriu
+    assert_eq!(
+        tiers,
+        [
+            ("fast", 15, 10, 8, "IDS001".to_string()),
+            ("medium", 5, 60, 7, "IDS-MEDIUM".to_string()),
+            ("slow", 30, 3600, 6, "IDS002".to_string()),
+        ]
+    );
+}
+
+#[test]
+fn explicit_tier_overrides_the_derived_one() {
+    let config = config_with_tiers(
+        "[[detection.tier]]\nname = \"slow\"\nports = 100\nwindow_secs = 7200\nseverity = 5\nsig_id = \"IDS020\"\n",
+    );
+    let slow: Vec<_> = config.detection.tiers.iter().filter(|t| t.name == "slow").collect();
+    assert_eq!(slow.len(), 1, "un singur tier slow");
+    assert_eq!((slow[0].ports, slow[0].window_secs, slow[0].severity), (100, 7200, 5));
+    assert_eq!(slow[0].sig_id(), "IDS020");
+}
+
+#[test]
+fn single_custom_tier_hit_is_a_scan() {
+    let config = config_with_tiers(MEDIUM);
+    let state = SharedState::new();
+    let src: IpAddr = "10.8.0.1".parse().unwrap();
+
+    // 10 porturi: peste medium (5), sub fast (15)
+    scan(&state, src, 10);
+    match evaluate(&src, &state, &config.detection) {
+        DetectionResult::Scan(hit) => {
+            assert_eq!((hit.tier.as_str(), hit.ports, hit.severity), ("medium", 10, 7));
+            assert_eq!(hit.sig_id, "IDS-MEDIUM");
+        }
+        other => panic!("se aștepta Scan(medium), nu {:?}", other),
+    }
+}
+
+#[test]
+fn two_tier_hit_is_a_multi_scan_ordered_by_severity() {
+    let config = config_with_tiers(MEDIUM);
+    let state = SharedState::new();
+    let src: IpAddr = "10.8.0.2".parse().unwrap();
+
+    // 20 porturi: peste fast (15) și medium (5), sub slow (30)
+    scan(&state, src, 20);
+    let result = evaluate(&src, &state, &config.detection);
+    let DetectionResult::MultiScan(hits) = &result else {
+        panic!("se aștepta MultiScan, nu {:?}", result);
+    };
+    let tiers: Vec<&str> = hits.iter().map(|h| h.tier.as_str()).collect();
+    assert_eq!(tiers, ["fast", "medium"]);
+    assert_eq!(result.sig_id(), "IDS003");
+    assert!(result.severity() > 8, "multi-tier e mai sever decât oricare tier");
+}